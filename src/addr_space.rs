@@ -0,0 +1,114 @@
+use malachite::Integer;
+use typed_builder::TypedBuilder;
+
+#[derive(TypedBuilder, Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// The attributes of an address space. They are parsed as a group, so they may appear in
+/// any order. The fields are visible to the grammar, which builds the space from them.
+///
+/// As in Ghidra, only `name`, `index`, and `size` are required: a missing `bigendian` or
+/// `physical` is `false`, and a missing `delay` is zero.
+pub struct AddrSpace {
+    pub(crate) name: String,
+    #[builder(setter(transform = |v: impl Into<Integer>| {
+        v.into()
+    }))]
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_integer"))]
+    pub(crate) index: Integer,
+    #[builder(default)]
+    pub(crate) bigendian: bool,
+    #[builder(default, setter(transform = |v: impl Into<Integer>| {
+        v.into()
+    }))]
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_integer"))]
+    pub(crate) delay: Integer,
+    #[builder(default, setter(transform = |v: impl Into<Integer>| {
+        Some(v.into())
+    }))]
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_integer::option"))]
+    pub(crate) deadcodedelay: Option<Integer>,
+    #[builder(setter(transform = |v: impl Into<Integer>| {
+        v.into()
+    }))]
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_integer"))]
+    pub(crate) size: Integer,
+    #[builder(default, setter(transform = |v: impl Into<Integer>| {
+        Some(v.into())
+    }))]
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_integer::option"))]
+    pub(crate) wordsize: Option<Integer>,
+    #[builder(default)]
+    pub(crate) physical: bool,
+}
+
+impl AddrSpace {
+    /// The space name
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The space index
+    pub fn index(&self) -> &Integer {
+        &self.index
+    }
+
+    /// Whether values in this space are big endian
+    pub fn bigendian(&self) -> bool {
+        self.bigendian
+    }
+
+    /// The number of passes before the decompiler heritages this space
+    pub fn delay(&self) -> &Integer {
+        &self.delay
+    }
+
+    /// The number of passes before dead code in this space is removed, if declared
+    pub fn deadcodedelay(&self) -> Option<&Integer> {
+        self.deadcodedelay.as_ref()
+    }
+
+    /// The size of an address in this space, in bytes
+    pub fn size(&self) -> &Integer {
+        &self.size
+    }
+
+    /// The number of bytes in an addressable unit, if declared
+    pub fn wordsize(&self) -> Option<&Integer> {
+        self.wordsize.as_ref()
+    }
+
+    /// Whether this space is backed by physical storage
+    pub fn physical(&self) -> bool {
+        self.physical
+    }
+
+    /// The dead-code delay of the space. When the `deadcodedelay` attribute is absent,
+    /// Ghidra uses the space's `delay` value, *not* zero.
+    pub fn deadcodedelay_or_default(&self) -> Integer {
+        self.deadcodedelay
+            .clone()
+            .unwrap_or_else(|| self.delay.clone())
+    }
+}
+
+#[derive(TypedBuilder, Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// The attributes of an overlay space: those of any space, plus the name of the space it
+/// overlays
+pub struct OverlaySpace {
+    pub(crate) space: AddrSpace,
+    #[builder(setter(into))]
+    pub(crate) base: String,
+}
+
+impl OverlaySpace {
+    /// The attributes the overlay shares with every space
+    pub fn space(&self) -> &AddrSpace {
+        &self.space
+    }
+
+    /// The name of the space this space overlays, e.g. `ram`
+    pub fn base(&self) -> &str {
+        &self.base
+    }
+}
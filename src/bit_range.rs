@@ -0,0 +1,54 @@
+use malachite::{num::conversion::traits::SaturatingFrom, Integer};
+
+use crate::grammar::{ContextField, TokenField};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+/// The location of a token or context field, with the attribute names of [`TokenField`] and
+/// [`ContextField`] mapped onto one representation. Values outside the range of a `u32`
+/// saturate.
+pub struct BitRange {
+    /// The least significant bit of the field, `bitstart` or `startbit`
+    pub start: u32,
+    /// The most significant bit of the field, `bitend` or `endbit`
+    pub end: u32,
+    /// The first byte covered by the field, `bytestart` or `startbyte`
+    pub byte_start: u32,
+    /// The last byte covered by the field, `byteend` or `endbyte`
+    pub byte_end: u32,
+    /// The right shift applied after extracting the covered bytes
+    pub shift: u32,
+    /// Whether the field is sign extended, from `signbit`
+    pub signed: bool,
+}
+
+impl TokenField {
+    /// The location of the field within its token
+    pub fn bit_range(&self) -> BitRange {
+        BitRange {
+            start: saturate(self.bitstart()),
+            end: saturate(self.bitend()),
+            byte_start: saturate(self.bytestart()),
+            byte_end: saturate(self.byteend()),
+            shift: saturate(self.shift()),
+            signed: self.signbit(),
+        }
+    }
+}
+
+impl ContextField {
+    /// The location of the field within the context
+    pub fn bit_range(&self) -> BitRange {
+        BitRange {
+            start: saturate(self.startbit()),
+            end: saturate(self.endbit()),
+            byte_start: saturate(self.startbyte()),
+            byte_end: saturate(self.endbyte()),
+            shift: saturate(self.shift()),
+            signed: self.signbit(),
+        }
+    }
+}
+
+fn saturate(value: &Integer) -> u32 {
+    u32::saturating_from(value)
+}
@@ -0,0 +1,376 @@
+use std::{borrow::Cow, collections::HashMap, sync::LazyLock};
+
+use rust_sitter::tree_sitter::{Node, Parser};
+
+use crate::{
+    error::{check_root, check_syntax, with_leaf_errors},
+    grammar::{
+        self, addr_space_from_attributes, overlay_space_from_attributes,
+        sleigh_attributes_from_attributes, LeafValue, XmlUnescape,
+    },
+    HexInteger, SleighParseError,
+};
+
+/// The kind of the source file name leaf
+const SOURCE_FILE_NAME: &str = "SourceFile_name";
+/// The kind of a symbol, which precedes its header
+const SYMBOL: &str = "SleighSymbolType";
+/// The kind of a symbol's header
+const SYMBOL_HEADER: &str = "SymbolHeader";
+/// The kind of the symbol name leaf
+const SYMBOL_HEADER_NAME: &str = "SymbolHeader_name";
+/// The kind of the symbol id leaf
+const SYMBOL_HEADER_ID: &str = "SymbolHeader_id";
+/// The kind of a constructor
+const CONSTRUCTOR: &str = "Constructor";
+/// The kind of the print piece leaf
+const PRINT_PIECE: &str = "Print_piece";
+
+/// The node kinds [`collect`] matches on. These are generated from the grammar's type and
+/// field names, so they are checked against the grammar by the tests.
+#[cfg(test)]
+pub(crate) const NODE_KINDS: [&str; 7] = [
+    SOURCE_FILE_NAME,
+    SYMBOL,
+    SYMBOL_HEADER,
+    SYMBOL_HEADER_NAME,
+    SYMBOL_HEADER_ID,
+    CONSTRUCTOR,
+    PRINT_PIECE,
+];
+
+/// The fields whose integer leaves are checked by [`collect`], as the grammar's leaf
+/// transforms check them. The grammar only accepts values that look like numbers, but e.g.
+/// `1.0` passes the grammar and not the transform. Each field's node kind is its name with
+/// `::` replaced by `_`. Fields the grammar fully checks, such as hexadecimal ids, are not
+/// listed.
+pub(crate) const INTEGER_FIELDS: [&str; 49] = [
+    "BitRangeSymbol::bitoffset",
+    "BitRangeSymbol::numbits",
+    "Commit::num",
+    "ConstantTemplateType::Handle::val",
+    "ConstantTemplateType::Unknown::val",
+    "ConstantValue::val",
+    "Constructor::first",
+    "Constructor::length",
+    "ConstructorTemplate::delay",
+    "ConstructorTemplate::numlabels",
+    "ConstructorTemplate::section",
+    "ContextField::endbit",
+    "ContextField::endbyte",
+    "ContextField::shift",
+    "ContextField::startbit",
+    "ContextField::startbyte",
+    "ContextSymbol::high",
+    "ContextSymbol::low",
+    "DecisionNode::bitsize",
+    "DecisionNode::number",
+    "DecisionNode::start",
+    "DecisionNodePair::id",
+    "LabelSymbol::index",
+    "LabelSymbol::refcount",
+    "MacroSymbol::index",
+    "OperandPrint::id",
+    "OperandSymbol::base",
+    "OperandSymbol::index",
+    "OperandSymbol::minlen",
+    "OperandSymbol::off",
+    "OperandValue::index",
+    "Operation::i",
+    "Operation::shift",
+    "PatternBlock::nonzero",
+    "PatternBlock::offset",
+    "SectionSymbol::index",
+    "SourceFile::index",
+    "SubtableSymbol::numct",
+    "SymbolTable::scopesize",
+    "SymbolTable::symbolsize",
+    "TokenField::bitend",
+    "TokenField::bitstart",
+    "TokenField::byteend",
+    "TokenField::bytestart",
+    "TokenField::shift",
+    "TokenSymbol::size",
+    "UserOpSymbol::index",
+    "ValueTableValue::val",
+    "VarNodeSymbol::size",
+];
+
+/// The fields whose boolean leaves are checked by [`collect`], as [`INTEGER_FIELDS`] are
+pub(crate) const BOOL_FIELDS: [&str; 9] = [
+    "Commit::flow",
+    "ContextField::signbit",
+    "ContextSymbol::flow",
+    "DecisionNode::context",
+    "LabelSymbol::placed",
+    "OperandSymbol::code",
+    "TokenField::bigendian",
+    "TokenField::signbit",
+    "TokenSymbol::bigendian",
+];
+
+/// How [`collect`] checks the value of a leaf
+#[derive(Debug, Clone, Copy)]
+enum LeafCheck {
+    Integer(&'static str),
+    Bool(&'static str),
+    /// The attributes of the root element, parsed as a group
+    SleighAttributes,
+    /// The attributes of a space element, parsed as a group
+    AddrSpace,
+    /// The attributes of an overlay space element, parsed as a group
+    OverlaySpace,
+}
+
+/// The checked leaves by node kind id
+static LEAF_CHECKS: LazyLock<HashMap<u16, LeafCheck>> = LazyLock::new(|| {
+    let language = grammar::language();
+    let fields = INTEGER_FIELDS
+        .iter()
+        .map(|field| (field.replace("::", "_"), LeafCheck::Integer(field)))
+        .chain(
+            BOOL_FIELDS
+                .iter()
+                .map(|field| (field.replace("::", "_"), LeafCheck::Bool(field))),
+        );
+    let groups = GROUP_KINDS
+        .iter()
+        .map(|(kind, check)| (kind.to_string(), *check));
+    fields
+        .chain(groups)
+        .map(|(kind, check)| (language.id_for_node_kind(&kind, true), check))
+        .collect()
+});
+
+/// The kinds of the leaves holding a group of attributes, and how they are checked
+const GROUP_KINDS: [(&str, LeafCheck); 6] = [
+    ("Sleigh_attributes", LeafCheck::SleighAttributes),
+    ("AddrSpaceType_Base_space", LeafCheck::AddrSpace),
+    ("AddrSpaceType_Unique_space", LeafCheck::AddrSpace),
+    ("AddrSpaceType_Other_space", LeafCheck::AddrSpace),
+    ("AddrSpaceType_Space_space", LeafCheck::AddrSpace),
+    ("AddrSpaceType_Overlay_overlay", LeafCheck::OverlaySpace),
+];
+
+/// The kinds of every leaf [`collect`] checks, to check them against the grammar
+#[cfg(test)]
+pub(crate) fn checked_kinds() -> impl Iterator<Item = String> {
+    INTEGER_FIELDS
+        .iter()
+        .chain(&BOOL_FIELDS)
+        .map(|field| field.replace("::", "_"))
+        .chain(GROUP_KINDS.iter().map(|(kind, _)| kind.to_string()))
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+/// An index of the names and display text of a specification, borrowed from the text it was
+/// parsed from, as returned by [`parse_borrowed`]. Only source file names, symbol names and
+/// ids, and constructor print pieces are kept; use [`parse`](crate::parse) for the rest of
+/// the specification. Strings are only allocated when they contain XML escapes.
+pub struct SleighBorrowed<'a> {
+    source_files: Vec<Cow<'a, str>>,
+    symbols: Vec<BorrowedSymbol<'a>>,
+    constructors: Vec<BorrowedConstructor<'a>>,
+}
+
+impl<'a> SleighBorrowed<'a> {
+    /// The source file names, in [`SourceFiles::source_files`](grammar::SourceFiles::source_files)
+    /// order
+    pub fn source_files(&self) -> &[Cow<'a, str>] {
+        &self.source_files
+    }
+
+    /// The symbols, in [`SymbolTable::symbols`](grammar::SymbolTable::symbols) order
+    pub fn symbols(&self) -> &[BorrowedSymbol<'a>] {
+        &self.symbols
+    }
+
+    /// The constructors, in [`Sleigh::constructors`](grammar::Sleigh::constructors) order
+    pub fn constructors(&self) -> &[BorrowedConstructor<'a>] {
+        &self.constructors
+    }
+
+    /// Copy the borrowed strings, so the index no longer borrows the input
+    pub fn into_owned(self) -> SleighBorrowed<'static> {
+        SleighBorrowed {
+            source_files: self.source_files.into_iter().map(into_owned).collect(),
+            symbols: self
+                .symbols
+                .into_iter()
+                .map(|symbol| BorrowedSymbol {
+                    name: into_owned(symbol.name),
+                    id: symbol.id,
+                })
+                .collect(),
+            constructors: self
+                .constructors
+                .into_iter()
+                .map(|constructor| BorrowedConstructor {
+                    pieces: constructor.pieces.into_iter().map(into_owned).collect(),
+                })
+                .collect(),
+        }
+    }
+}
+
+fn into_owned(text: Cow<'_, str>) -> Cow<'static, str> {
+    Cow::Owned(text.into_owned())
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// The name and id of a symbol in a [`SleighBorrowed`]
+pub struct BorrowedSymbol<'a> {
+    name: Cow<'a, str>,
+    id: HexInteger,
+}
+
+impl<'a> BorrowedSymbol<'a> {
+    /// The name of the symbol
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The id of the symbol
+    pub fn id(&self) -> &HexInteger {
+        &self.id
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+/// The display text of a constructor in a [`SleighBorrowed`]
+pub struct BorrowedConstructor<'a> {
+    pieces: Vec<Cow<'a, str>>,
+}
+
+impl<'a> BorrowedConstructor<'a> {
+    /// The literal text of the constructor's print pieces, in order. Operands are not
+    /// included.
+    pub fn pieces(&self) -> &[Cow<'a, str>] {
+        &self.pieces
+    }
+}
+
+/// Parse a `.sla` specification into an index of its names and display text, without
+/// copying them out of `input`, for keeping many specifications in memory where the full
+/// tree of [`parse`](crate::parse) is not needed.
+///
+/// The input is validated as [`parse`](crate::parse) validates it, including the values of
+/// attributes that are not indexed, and errors are reported the same way. The values are
+/// checked while walking the tree-sitter tree, without building the tree of
+/// [`parse`](crate::parse).
+pub fn parse_borrowed(input: &str) -> Result<SleighBorrowed<'_>, SleighParseError> {
+    check_root(input)?;
+    let mut parser = Parser::new();
+    parser
+        .set_language(&grammar::language())
+        .expect("Incompatible tree-sitter language");
+    let tree = parser
+        .parse(input, None)
+        .expect("Failed to parse with no timeout or cancellation");
+    check_syntax(&tree, input)?;
+    with_leaf_errors(input, || Ok(collect(tree.root_node(), input)))
+}
+
+/// Where the walk in [`collect`] is relative to the next symbol
+enum SymbolState<'a> {
+    /// Not in a symbol. Headers in the symbol table's header list are skipped.
+    Outside,
+    /// In a symbol, before its header
+    Symbol,
+    /// In the header of a symbol
+    Header(Option<Cow<'a, str>>),
+}
+
+/// Collect the borrowed fields of the tree under `root`, and check the values of the other
+/// leaves, with a single cursor so the walk itself does not allocate per node
+fn collect<'a>(root: Node, input: &'a str) -> SleighBorrowed<'a> {
+    let checks = &*LEAF_CHECKS;
+    let mut sleigh = SleighBorrowed::default();
+    let mut state = SymbolState::Outside;
+    let mut cursor = root.walk();
+    loop {
+        let node = cursor.node();
+        if let Some(check) = checks.get(&node.kind_id()) {
+            check_leaf(*check, node, input);
+        }
+        match (node.kind(), &mut state) {
+            (SOURCE_FILE_NAME, _) => sleigh.source_files.push(value(node, input)),
+            (SYMBOL, _) => state = SymbolState::Symbol,
+            (SYMBOL_HEADER, SymbolState::Symbol) => state = SymbolState::Header(None),
+            (SYMBOL_HEADER_NAME, SymbolState::Header(name)) => *name = Some(value(node, input)),
+            (SYMBOL_HEADER_ID, SymbolState::Header(name)) => {
+                if let Some(name) = name.take() {
+                    let id = hex_value(node, input);
+                    sleigh.symbols.push(BorrowedSymbol { name, id });
+                }
+                state = SymbolState::Outside;
+            }
+            (CONSTRUCTOR, _) => sleigh.constructors.push(BorrowedConstructor::default()),
+            (PRINT_PIECE, _) => {
+                if let Some(constructor) = sleigh.constructors.last_mut() {
+                    constructor.pieces.push(value(node, input));
+                }
+            }
+            _ => {}
+        }
+        if cursor.goto_first_child() {
+            continue;
+        }
+        while !cursor.goto_next_sibling() {
+            if !cursor.goto_parent() {
+                return sleigh;
+            }
+        }
+    }
+}
+
+/// The quoted value of the attribute leaf `node`, unescaped only if it contains an escape
+fn value<'a>(node: Node, input: &'a str) -> Cow<'a, str> {
+    let raw = raw_value(&input[node.byte_range()]);
+    if raw.contains('&') {
+        Cow::Owned(raw.xml_unescape())
+    } else {
+        Cow::Borrowed(raw)
+    }
+}
+
+/// The quoted value of the attribute leaf text `text`, as it appears in the input
+fn raw_value(text: &str) -> &str {
+    text.find(['"', '\''])
+        .and_then(|start| {
+            let quote = text[start..].chars().next()?;
+            let end = text.rfind(quote)?;
+            (start < end).then(|| &text[start + 1..end])
+        })
+        .unwrap_or("")
+}
+
+/// The hexadecimal value of the attribute leaf `node`. The grammar only accepts hex digits
+/// after an optional `-` and the `0x` or `0X` prefix, so the conversion cannot fail on a
+/// successful parse.
+fn hex_value(node: Node, input: &str) -> HexInteger {
+    raw_value(&input[node.byte_range()]).leaf_hex_integer("SymbolHeader::id")
+}
+
+/// Check the value of the leaf `node` with the leaf transforms of the grammar, which record
+/// the first invalid value for [`with_leaf_errors`]. The values they build are dropped.
+fn check_leaf(check: LeafCheck, node: Node, input: &str) {
+    let text = &input[node.byte_range()];
+    match check {
+        LeafCheck::Integer(field) => {
+            raw_value(text).leaf_integer(field);
+        }
+        LeafCheck::Bool(field) => {
+            raw_value(text).leaf_bool(field);
+        }
+        LeafCheck::SleighAttributes => {
+            sleigh_attributes_from_attributes(text);
+        }
+        LeafCheck::AddrSpace => {
+            addr_space_from_attributes(text);
+        }
+        LeafCheck::OverlaySpace => {
+            overlay_space_from_attributes(text);
+        }
+    }
+}
@@ -0,0 +1,105 @@
+use crate::grammar::{
+    Constructor, Sleigh, SleighSymbolType, SubtableSymbol, SymbolTable, TripleSymbol,
+};
+
+impl Sleigh {
+    /// Compare two specifications, ignoring the order of collections whose order SLEIGH does
+    /// not depend on. These are compared as sets:
+    ///
+    /// - the source files, which are identified by their `index`
+    /// - the address spaces, which are identified by their `index`
+    /// - the scopes, which are identified by their `id`
+    /// - the named templates of each constructor, which are identified by their `section`
+    ///
+    /// Everything else is compared in order, as [`PartialEq`] does: the symbol headers and
+    /// symbols, which are paired by position, the constructors of each subtable, which are
+    /// numbered by position, and the unknown root attributes.
+    pub fn structurally_eq(&self, other: &Sleigh) -> bool {
+        self.version() == other.version()
+            && self.bigendian() == other.bigendian()
+            && self.align() == other.align()
+            && self.uniqbase() == other.uniqbase()
+            && self.maxdelay() == other.maxdelay()
+            && self.uniqmask() == other.uniqmask()
+            && self.numsections() == other.numsections()
+            && self.unknown_attributes() == other.unknown_attributes()
+            && same_elements(
+                self.sourcefiles().source_files(),
+                other.sourcefiles().source_files(),
+            )
+            && self.spaces().defaultspace() == other.spaces().defaultspace()
+            && same_elements(self.spaces().spaces(), other.spaces().spaces())
+            && symbol_tables_eq(self.symbol_table(), other.symbol_table())
+    }
+}
+
+fn symbol_tables_eq(left: &SymbolTable, right: &SymbolTable) -> bool {
+    left.scopesize() == right.scopesize()
+        && left.symbolsize() == right.symbolsize()
+        && same_elements(left.scopes(), right.scopes())
+        && left.symbol_headers() == right.symbol_headers()
+        && left.symbols().len() == right.symbols().len()
+        && left
+            .symbols()
+            .iter()
+            .zip(right.symbols())
+            .all(|(left, right)| symbols_eq(left, right))
+}
+
+/// Whether `left` and `right` are equal as [`Sleigh::structurally_eq`] compares symbols
+pub(crate) fn symbols_eq(left: &SleighSymbolType, right: &SleighSymbolType) -> bool {
+    match (left, right) {
+        (
+            SleighSymbolType::TripleSymbol(TripleSymbol::SubtableSymbol { subtable: left, .. }),
+            SleighSymbolType::TripleSymbol(TripleSymbol::SubtableSymbol {
+                subtable: right, ..
+            }),
+        ) => subtables_eq(left, right),
+        _ => left == right,
+    }
+}
+
+fn subtables_eq(left: &SubtableSymbol, right: &SubtableSymbol) -> bool {
+    left.header() == right.header()
+        && left.numct() == right.numct()
+        && left.decisiontree() == right.decisiontree()
+        && left.constructors().len() == right.constructors().len()
+        && left
+            .constructors()
+            .iter()
+            .zip(right.constructors())
+            .all(|(left, right)| constructors_eq(left, right))
+}
+
+fn constructors_eq(left: &Constructor, right: &Constructor) -> bool {
+    left.parent() == right.parent()
+        && left.first() == right.first()
+        && left.length() == right.length()
+        && left.line() == right.line()
+        && left.operands() == right.operands()
+        && left.printpiece() == right.printpiece()
+        && left.contexts() == right.contexts()
+        && left.templ() == right.templ()
+        && same_elements(left.namedtempl(), right.namedtempl())
+}
+
+/// Whether `left` and `right` hold equal elements, with the same multiplicity, in any order
+fn same_elements<T: PartialEq>(left: &[T], right: &[T]) -> bool {
+    if left.len() != right.len() {
+        return false;
+    }
+    let mut matched = vec![false; right.len()];
+    left.iter().all(|element| {
+        let position = right
+            .iter()
+            .zip(&matched)
+            .position(|(candidate, matched)| !matched && candidate == element);
+        match position {
+            Some(position) => {
+                matched[position] = true;
+                true
+            }
+            None => false,
+        }
+    })
+}
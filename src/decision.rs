@@ -0,0 +1,90 @@
+use std::fmt::Write;
+
+use crate::grammar::{DecisionNode, DisjointPatternType};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+/// The shape of a decision tree, as returned by [`DecisionNode::stats`]
+pub struct DecisionStats {
+    /// The number of nodes on the longest path from the root to a leaf, counting both. A
+    /// tree that is a single leaf has depth 1.
+    pub max_depth: usize,
+    /// The number of nodes in the tree, including the root
+    pub node_count: usize,
+    /// The number of constructor candidates at the leaves of the tree
+    pub pair_count: usize,
+}
+
+impl DecisionNode {
+    /// The depth, size, and number of candidates of the tree below this node, for profiling
+    /// how a processor's instructions are decoded
+    pub fn stats(&self) -> DecisionStats {
+        self.children().iter().map(DecisionNode::stats).fold(
+            DecisionStats {
+                max_depth: 1,
+                node_count: 1,
+                pair_count: self.pairs().len(),
+            },
+            |stats, child| DecisionStats {
+                max_depth: stats.max_depth.max(child.max_depth + 1),
+                node_count: stats.node_count + child.node_count,
+                pair_count: stats.pair_count + child.pair_count,
+            },
+        )
+    }
+
+    /// The tree below this node as a GraphViz DOT graph. Nodes are labeled with the bits
+    /// they decide on, and edges to children with the index of the child. Each pair is a
+    /// box for its constructor, reached by an edge labeled with the constructor id and the
+    /// kind of its pattern.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph decision {\n");
+        let mut next = 0;
+        self.write_dot(&mut dot, &mut next);
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// Write this node and its subtree as DOT statements, numbering nodes `n<id>` from
+    /// `next` and their pairs `n<id>p<index>`, and return the id of this node
+    fn write_dot(&self, dot: &mut String, next: &mut usize) -> usize {
+        let id = *next;
+        *next += 1;
+        // Writing to a String cannot fail
+        let _ = writeln!(
+            dot,
+            "    n{} [label=\"start={} bitsize={} context={}\"];",
+            id,
+            self.start(),
+            self.bitsize(),
+            self.context()
+        );
+        for (index, pair) in self.pairs().iter().enumerate() {
+            let kind = match pair.pattern() {
+                DisjointPatternType::Instruction(_) => "instruction",
+                DisjointPatternType::Context(_) => "context",
+                DisjointPatternType::Combine(_) => "combine",
+            };
+            let _ = writeln!(
+                dot,
+                "    n{}p{} [shape=box, label=\"{}\"];",
+                id,
+                index,
+                pair.id()
+            );
+            let _ = writeln!(
+                dot,
+                "    n{} -> n{}p{} [label=\"{} {}\"];",
+                id,
+                id,
+                index,
+                pair.id(),
+                kind
+            );
+        }
+        for (index, child) in self.children().iter().enumerate() {
+            let child = child.write_dot(dot, next);
+            let _ = writeln!(dot, "    n{} -> n{} [label=\"{}\"];", id, child, index);
+        }
+        id
+    }
+}
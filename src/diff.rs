@@ -0,0 +1,112 @@
+use std::collections::{BTreeSet, HashMap};
+
+use crate::{
+    compare::symbols_eq,
+    grammar::{Sleigh, SleighSymbolType},
+    HexInteger,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+/// A difference between two specifications, as reported by [`Sleigh::diff`]
+pub enum SleighDiff {
+    /// An attribute of the `<sleigh>` root differs. Values are written as they appear in a
+    /// `.sla` file, and an attribute absent from one side is `None`.
+    Attribute {
+        name: String,
+        left: Option<String>,
+        right: Option<String>,
+    },
+    /// A symbol is only in the right specification
+    SymbolAdded { name: String, id: HexInteger },
+    /// A symbol is only in the left specification
+    SymbolRemoved { name: String, id: HexInteger },
+    /// A symbol is in both specifications, but its body differs
+    SymbolChanged { name: String, id: HexInteger },
+}
+
+impl Sleigh {
+    /// The differences from `self` to `other`: the root attributes that differ, by name,
+    /// then the symbols removed or changed, in the symbol table order of `self`, then the symbols
+    /// added, in the order of `other`.
+    ///
+    /// Symbols are matched by name and id, so reordering the symbol table is not a
+    /// difference, and unknown root attributes are matched by name. Symbol bodies are
+    /// compared as [`Sleigh::structurally_eq`] compares them, so reordering the named
+    /// templates of a constructor is not a difference either.
+    pub fn diff(&self, other: &Sleigh) -> Vec<SleighDiff> {
+        let left = attributes(self);
+        let right = attributes(other);
+        let names = left.keys().chain(right.keys()).collect::<BTreeSet<_>>();
+        let mut diffs = names
+            .into_iter()
+            .filter_map(|name| {
+                let left = left.get(name).cloned().flatten();
+                let right = right.get(name).cloned().flatten();
+                (left != right).then(|| SleighDiff::Attribute {
+                    name: name.clone(),
+                    left,
+                    right,
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let key = |symbol: &SleighSymbolType| (symbol.name().to_string(), symbol.id().clone());
+        let theirs = other
+            .symbol_table()
+            .symbols()
+            .iter()
+            .map(|symbol| (key(symbol), symbol))
+            .collect::<HashMap<_, _>>();
+        let ours = self
+            .symbol_table()
+            .symbols()
+            .iter()
+            .map(|symbol| (key(symbol), symbol))
+            .collect::<HashMap<_, _>>();
+        for symbol in self.symbol_table().symbols() {
+            let (name, id) = key(symbol);
+            match theirs.get(&(name.clone(), id.clone())) {
+                None => diffs.push(SleighDiff::SymbolRemoved { name, id }),
+                Some(theirs) if !symbols_eq(symbol, theirs) => {
+                    diffs.push(SleighDiff::SymbolChanged { name, id })
+                }
+                Some(_) => {}
+            }
+        }
+        for symbol in other.symbol_table().symbols() {
+            let key = key(symbol);
+            if !ours.contains_key(&key) {
+                let (name, id) = key;
+                diffs.push(SleighDiff::SymbolAdded { name, id });
+            }
+        }
+        diffs
+    }
+}
+
+/// The root attributes of `sleigh` by name, with their values as written in a `.sla` file
+fn attributes(sleigh: &Sleigh) -> HashMap<String, Option<String>> {
+    let known = [
+        ("version", sleigh.version().map(ToString::to_string)),
+        (
+            "bigendian",
+            sleigh.bigendian_attribute().map(|value| value.to_string()),
+        ),
+        ("align", Some(sleigh.align().to_string())),
+        ("uniqbase", Some(sleigh.uniqbase().to_string())),
+        ("maxdelay", sleigh.maxdelay().map(ToString::to_string)),
+        ("uniqmask", sleigh.uniqmask().map(ToString::to_string)),
+        ("numsections", sleigh.numsections().map(ToString::to_string)),
+    ];
+    known
+        .into_iter()
+        .map(|(name, value)| (name.to_string(), value))
+        .chain(sleigh.unknown_attributes().iter().map(|attribute| {
+            (
+                attribute.name().to_string(),
+                Some(attribute.value().to_string()),
+            )
+        }))
+        .collect()
+}
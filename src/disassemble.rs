@@ -0,0 +1,627 @@
+use std::{cmp::max, fmt, ptr};
+
+use malachite::{num::conversion::traits::WrappingFrom, Integer};
+
+use crate::{
+    grammar::{
+        Constructor, ContextChangeType, ContextField, DecisionNode, DecisionNodePair,
+        DisjointPatternType, FamilySymbol, OperandSymbol, OperandValue, Operation, PatternBlock,
+        PatternExpressionType, PatternValueType, PatternlessSymbol, PrintPieceType, Sleigh,
+        SleighSymbolType, SpecificSymbol, SubtableSymbol, SymbolTable, TokenField, TripleSymbol,
+        ValueSymbolType,
+    },
+    PatternContext,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// A single instruction decoded by [`Sleigh::disassemble`]
+pub struct DisassembledInstruction {
+    /// The address the instruction was decoded at
+    pub address: Integer,
+    /// The number of bytes the instruction occupies
+    pub length: usize,
+    /// The rendered mnemonic, e.g. `add`
+    pub mnemonic: String,
+    /// The rendered operands, e.g. `r1, r2`
+    pub body: String,
+    /// The context words after the instruction's context changes were applied
+    pub context: Vec<u8>,
+}
+
+impl fmt::Display for DisassembledInstruction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.body.is_empty() {
+            write!(f, "{}", self.mnemonic)
+        } else {
+            write!(f, "{} {}", self.mnemonic, self.body)
+        }
+    }
+}
+
+impl Sleigh {
+    /// Decode the instruction at the start of `bytes`, which is located at `address`.
+    /// `context` holds the context register as big-endian 32-bit words, as Ghidra lays it
+    /// out. The root `instruction` subtable's decision tree is walked to select
+    /// constructors, operands are resolved recursively, context changes are applied, and
+    /// the mnemonic and operands are rendered from the print pieces.
+    ///
+    /// Returns `None` if no constructor matches, if `bytes` is too short for the matched
+    /// instruction, or if an operand cannot be evaluated.
+    pub fn disassemble(
+        &self,
+        bytes: &[u8],
+        address: &Integer,
+        context: &[u8],
+    ) -> Option<DisassembledInstruction> {
+        let mut disassembler = Disassembler::new(self, bytes, address, context);
+        let root = disassembler.root?;
+        let node = disassembler.resolve(root, None, 0, 0)?;
+        let length = disassembler.nodes[node].length;
+        if length > bytes.len() {
+            return None;
+        }
+        disassembler.next = Some(address.clone() + Integer::from(length));
+        let (mnemonic, body) = disassembler.render(node)?;
+        Some(DisassembledInstruction {
+            address: address.clone(),
+            length,
+            mnemonic,
+            body,
+            context: disassembler.context,
+        })
+    }
+}
+
+/// The resolved state of one constructor in the instruction's constructor tree
+struct Node<'a> {
+    constructor: &'a Constructor,
+    parent: Option<usize>,
+    /// Offset of the constructor from the start of the instruction
+    offset: usize,
+    length: usize,
+    operands: Vec<OperandState>,
+}
+
+struct OperandState {
+    /// Offset of the operand from the start of the instruction
+    offset: usize,
+    length: usize,
+    /// The node of the sub-constructor, for operands defined by a subtable
+    node: Option<usize>,
+}
+
+/// How deep subtable operands may nest before decoding gives up, so a subtable that
+/// resolves to itself cannot recurse without bound
+const MAX_DEPTH: usize = 64;
+
+struct Disassembler<'a> {
+    symbols: &'a SymbolTable,
+    root: Option<&'a SubtableSymbol>,
+    bytes: &'a [u8],
+    context: Vec<u8>,
+    address: &'a Integer,
+    wordsize: Integer,
+    next: Option<Integer>,
+    nodes: Vec<Node<'a>>,
+}
+
+impl<'a> Disassembler<'a> {
+    fn new(sleigh: &'a Sleigh, bytes: &'a [u8], address: &'a Integer, context: &[u8]) -> Self {
+        let symbols = sleigh.symbol_table();
+        let root = sleigh
+            .subtables()
+            .find(|subtable| subtable.header().name() == "instruction");
+        let wordsize = sleigh
+            .spaces()
+            .default_space()
+            .and_then(|space| space.wordsize().cloned())
+            // A zero word size is read as byte addressing rather than dividing by zero
+            .filter(|wordsize| *wordsize != 0)
+            .unwrap_or(Integer::from(1));
+        Self {
+            symbols,
+            root,
+            bytes,
+            context: context.to_vec(),
+            address,
+            wordsize,
+            next: None,
+            nodes: Vec::new(),
+        }
+    }
+
+    fn subtable(&self, id: &Integer) -> Option<&'a SubtableSymbol> {
+        match self.symbols.symbol_by_id(id)? {
+            SleighSymbolType::TripleSymbol(TripleSymbol::SubtableSymbol { subtable, .. }) => {
+                Some(subtable)
+            }
+            _ => None,
+        }
+    }
+
+    fn operand(&self, id: &Integer) -> Option<&'a OperandSymbol> {
+        match self.symbols.symbol_by_id(id)? {
+            SleighSymbolType::TripleSymbol(TripleSymbol::SpecificSymbol(
+                SpecificSymbol::OperandSymbol(operand),
+            )) => Some(operand),
+            _ => None,
+        }
+    }
+
+    /// Select a constructor from `subtable` for the instruction bytes at `offset`, apply its
+    /// context changes, and resolve its operands. Returns the index of the new node, or
+    /// `None` if subtables nest more than [`MAX_DEPTH`] deep.
+    fn resolve(
+        &mut self,
+        subtable: &'a SubtableSymbol,
+        parent: Option<usize>,
+        offset: usize,
+        depth: usize,
+    ) -> Option<usize> {
+        if depth > MAX_DEPTH {
+            return None;
+        }
+        let constructor = self.select(subtable, subtable.decisiontree(), offset)?;
+        let node = self.nodes.len();
+        self.nodes.push(Node {
+            constructor,
+            parent,
+            offset,
+            length: 0,
+            operands: Vec::new(),
+        });
+
+        for change in constructor.contexts() {
+            // Commits only affect the context of later addresses
+            if let ContextChangeType::Operation(operation) = change {
+                self.apply(operation, node)?;
+            }
+        }
+
+        for operand in constructor.operands() {
+            let symbol = self.operand(operand.id())?;
+            let base = if *symbol.base() < 0 {
+                self.nodes[node].offset
+            } else {
+                let base = self.nodes[node]
+                    .operands
+                    .get(usize::try_from(symbol.base()).ok()?)?;
+                base.offset.checked_add(base.length)?
+            };
+            let start = base.checked_add(usize::try_from(symbol.off()).ok()?)?;
+            let state = match symbol.subsym().and_then(|id| self.subtable(id)) {
+                Some(subtable) => {
+                    let child = self.resolve(subtable, Some(node), start, depth + 1)?;
+                    OperandState {
+                        offset: start,
+                        length: self.nodes[child].length,
+                        node: Some(child),
+                    }
+                }
+                None => OperandState {
+                    offset: start,
+                    length: usize::try_from(symbol.minlen()).ok()?,
+                    node: None,
+                },
+            };
+            self.nodes[node].operands.push(state);
+        }
+
+        let end = self.nodes[node].operands.iter().try_fold(
+            offset.checked_add(usize::try_from(constructor.length()).ok()?)?,
+            |end, operand| Some(max(end, operand.offset.checked_add(operand.length)?)),
+        )?;
+        self.nodes[node].length = end - offset;
+        Some(node)
+    }
+
+    fn select(
+        &self,
+        subtable: &'a SubtableSymbol,
+        decision: &'a DecisionNode,
+        offset: usize,
+    ) -> Option<&'a Constructor> {
+        let instruction = self.bytes.get(offset..).unwrap_or_default();
+        let pair = decision.resolve_pair(instruction, &self.context)?;
+        subtable
+            .constructors()
+            .get(usize::try_from(pair.id()).ok()?)
+    }
+
+    fn apply(&mut self, operation: &Operation, node: usize) -> Option<()> {
+        let value = self.evaluate(operation.patexp(), node, self.nodes[node].offset)?;
+        // Context words are 32 bits, so larger shifts are malformed
+        let shift = u64::try_from(operation.shift())
+            .ok()
+            .filter(|shift| *shift < 32)?;
+        let value = u32::wrapping_from(&(value << shift));
+        let mask = u32::wrapping_from(&operation.mask().0);
+        let start = usize::try_from(operation.i()).ok()?.checked_mul(4)?;
+        let word = self.context.get_mut(start..start.checked_add(4)?)?;
+        let current = u32::from_be_bytes(word.try_into().ok()?);
+        word.copy_from_slice(&((current & !mask) | (value & mask)).to_be_bytes());
+        Some(())
+    }
+
+    /// Evaluate `expression` for the constructor at `node`, reading instruction bytes
+    /// relative to `offset`
+    fn evaluate(
+        &self,
+        expression: &PatternExpressionType,
+        node: usize,
+        offset: usize,
+    ) -> Option<Integer> {
+        expression.evaluate(&Frame {
+            disassembler: self,
+            node,
+            offset,
+        })
+    }
+
+    fn value(&self, value: &PatternValueType, node: usize, offset: usize) -> Option<Integer> {
+        value.evaluate(&Frame {
+            disassembler: self,
+            node,
+            offset,
+        })
+    }
+
+    /// The value of another operand of a constructor, found by walking up from `node` to
+    /// the constructor the operand belongs to
+    fn operand_value(&self, operand: &OperandValue, node: usize) -> Option<Integer> {
+        let constructor = self
+            .subtable(operand.table())?
+            .constructor(usize::try_from(&operand.constructor_id().0).ok()?)?;
+        let mut node = node;
+        while !ptr::eq(self.nodes[node].constructor, constructor) {
+            node = self.nodes[node].parent?;
+        }
+        let index = usize::try_from(operand.index()).ok()?;
+        let symbol = self.operand(constructor.operands().get(index)?.id())?;
+        // Context changes are applied before the operands are resolved, so the offset of
+        // the operand may have to be calculated here
+        let offset = if *symbol.base() < 0 {
+            self.nodes[node]
+                .offset
+                .checked_add(usize::try_from(symbol.off()).ok()?)?
+        } else {
+            self.nodes[node].operands.get(index)?.offset
+        };
+        if let Some(expression) = symbol.defexp() {
+            return self.evaluate(expression, node, offset);
+        }
+        let Some(id) = symbol.subsym() else {
+            return Some(Integer::from(0));
+        };
+        match self.symbols.symbol_by_id(id)? {
+            SleighSymbolType::TripleSymbol(TripleSymbol::FamilySymbol(
+                FamilySymbol::ValueSymbol(value),
+            )) => self.value(patval(value), node, offset),
+            SleighSymbolType::TripleSymbol(TripleSymbol::SpecificSymbol(specific)) => {
+                match specific {
+                    SpecificSymbol::PatternlessSymbol(_) => Some(Integer::from(0)),
+                    SpecificSymbol::OperandSymbol(operand) => {
+                        self.operand_value(operand.localexp(), node)
+                    }
+                    SpecificSymbol::StartSymbol(_) => Some(self.address.clone() / &self.wordsize),
+                    SpecificSymbol::EndSymbol(_) => Some(self.next.clone()? / &self.wordsize),
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// Render the mnemonic and operands of the root constructor at `node`
+    fn render(&self, node: usize) -> Option<(String, String)> {
+        let constructor = self.nodes[node].constructor;
+        let pieces = constructor.printpiece();
+        // A constructor which prints as nothing but a subtable operand defers to it
+        if let [PrintPieceType::Operand(operand)] = pieces {
+            let index = usize::try_from(operand.id()).ok()?;
+            if let Some(child) = self.nodes[node].operands.get(index)?.node {
+                return self.render(child);
+            }
+        }
+        let (mnemonic, body) = match usize::try_from(constructor.first()) {
+            Ok(first) => (
+                pieces.get(..first).unwrap_or(pieces),
+                pieces.get(first + 1..).unwrap_or_default(),
+            ),
+            Err(_) => (pieces, &[][..]),
+        };
+        let mut rendered = (String::new(), String::new());
+        self.print(node, mnemonic, &mut rendered.0)?;
+        self.print(node, body, &mut rendered.1)?;
+        Some(rendered)
+    }
+
+    fn print(&self, node: usize, pieces: &[PrintPieceType], out: &mut String) -> Option<()> {
+        for piece in pieces {
+            match piece {
+                PrintPieceType::Print(print) => out.push_str(print.piece()),
+                PrintPieceType::Operand(operand) => {
+                    self.print_operand(node, usize::try_from(operand.id()).ok()?, out)?
+                }
+            }
+        }
+        Some(())
+    }
+
+    fn print_operand(&self, node: usize, index: usize, out: &mut String) -> Option<()> {
+        let constructor = self.nodes[node].constructor;
+        let symbol = self.operand(constructor.operands().get(index)?.id())?;
+        let state = self.nodes[node].operands.get(index)?;
+        if let Some(child) = state.node {
+            return self.print(child, self.nodes[child].constructor.printpiece(), out);
+        }
+        let Some(id) = symbol.subsym() else {
+            out.push_str(&hex(&self.evaluate(
+                symbol.defexp()?,
+                node,
+                state.offset,
+            )?));
+            return Some(());
+        };
+        let index = |value| {
+            self.value(value, node, state.offset)
+                .and_then(|v| usize::try_from(&v).ok())
+        };
+        match self.symbols.symbol_by_id(id)? {
+            SleighSymbolType::TripleSymbol(TripleSymbol::FamilySymbol(
+                FamilySymbol::ValueSymbol(value),
+            )) => match value {
+                ValueSymbolType::ValueSymbol(_) | ValueSymbolType::ContextSymbol(_) => {
+                    out.push_str(&hex(&self.value(patval(value), node, state.offset)?))
+                }
+                ValueSymbolType::ValueMapSymbol(map) => {
+                    out.push_str(&hex(map.valuetable().get(index(map.patval())?)?.val()))
+                }
+                ValueSymbolType::NameSymbol(names) => {
+                    out.push_str(names.nametable().get(index(names.patval())?)?.name()?)
+                }
+                ValueSymbolType::VarNodeListSymbol(list) => {
+                    let varnode = list
+                        .varnode_table()
+                        .get(index(list.patval())?)?
+                        .as_option()?;
+                    out.push_str(self.symbols.symbol_by_id(varnode.id())?.header().name())
+                }
+            },
+            SleighSymbolType::TripleSymbol(TripleSymbol::SpecificSymbol(specific)) => {
+                match specific {
+                    SpecificSymbol::PatternlessSymbol(PatternlessSymbol::VarNodeSymbol(
+                        varnode,
+                    )) => out.push_str(varnode.header().name()),
+                    SpecificSymbol::PatternlessSymbol(PatternlessSymbol::EpsilonSymbol(_)) => {
+                        out.push('0')
+                    }
+                    SpecificSymbol::StartSymbol(_) => out.push_str(&hex(self.address)),
+                    SpecificSymbol::EndSymbol(_) => out.push_str(&hex(self.next.as_ref()?)),
+                    _ => return None,
+                }
+            }
+            _ => return None,
+        }
+        Some(())
+    }
+}
+
+/// The state pattern expressions of the constructor at `node` are evaluated in
+struct Frame<'d, 'a> {
+    disassembler: &'d Disassembler<'a>,
+    node: usize,
+    /// Offset of the token fields from the start of the instruction
+    offset: usize,
+}
+
+impl PatternContext for Frame<'_, '_> {
+    fn token_field(&self, field: &TokenField) -> Option<Integer> {
+        field.extract(
+            self.disassembler
+                .bytes
+                .get(self.offset..)
+                .unwrap_or_default(),
+        )
+    }
+
+    fn context_field(&self, field: &ContextField) -> Option<Integer> {
+        context_field(field, &self.disassembler.context)
+    }
+
+    fn operand(&self, operand: &OperandValue) -> Option<Integer> {
+        self.disassembler.operand_value(operand, self.node)
+    }
+
+    fn start(&self) -> Option<Integer> {
+        Some(self.disassembler.address.clone() / &self.disassembler.wordsize)
+    }
+
+    fn end(&self) -> Option<Integer> {
+        Some(self.disassembler.next.clone()? / &self.disassembler.wordsize)
+    }
+
+    // `next2` would require decoding the following instruction as well
+}
+
+/// The pattern value a value, name, context, or varnode list symbol is indexed by
+pub(crate) fn patval(value: &ValueSymbolType) -> &PatternValueType {
+    match value {
+        ValueSymbolType::ValueMapSymbol(symbol) => symbol.patval(),
+        ValueSymbolType::NameSymbol(symbol) => symbol.patval(),
+        ValueSymbolType::ContextSymbol(symbol) => symbol.patval(),
+        ValueSymbolType::VarNodeListSymbol(symbol) => symbol.patval(),
+        ValueSymbolType::ValueSymbol(symbol) => symbol.patval(),
+    }
+}
+
+/// Format a value the way Ghidra prints operand values
+pub(crate) fn hex(value: &Integer) -> String {
+    if *value < 0 {
+        format!("-0x{:x}", -value)
+    } else {
+        format!("0x{:x}", value)
+    }
+}
+
+/// Read `size` bytes starting at `start` as a big-endian value. Bytes past the end of `buf`
+/// read as zero. Returns `None` if the end of the bytes overflows a `usize`.
+fn bytes(buf: &[u8], start: usize, size: usize) -> Option<u128> {
+    Some((start..start.checked_add(size)?).fold(0, |value, i| {
+        (value << 8) | u128::from(buf.get(i).copied().unwrap_or_default())
+    }))
+}
+
+/// Read `size` bits starting `start` bits from the most significant bit of `buf`. Returns
+/// `None` if `size` is zero or the bits do not fit in a `u128`.
+fn bits(buf: &[u8], start: usize, size: u32) -> Option<u128> {
+    let mask = u128::MAX.checked_shr(128u32.checked_sub(size)?)?;
+    let first = start / 8;
+    let count = (start % 8 + size as usize).div_ceil(8);
+    if count > 16 {
+        return None;
+    }
+    let value = bytes(buf, first, count)?;
+    let unused = count as u32 * 8 - (start % 8) as u32 - size;
+    Some((value >> unused) & mask)
+}
+
+/// Sign or zero extend `value` from bit `bit`. Returns `None` if `bit` is past the end of
+/// a `u128`.
+fn extend(value: u128, bit: usize, signed: bool) -> Option<Integer> {
+    let bit = u32::try_from(bit).ok()?;
+    let value = value & u128::MAX.checked_shr(127u32.checked_sub(bit)?)?;
+    Some(if signed && value >> bit & 1 == 1 {
+        Integer::from(value) - (Integer::from(1) << (u64::from(bit) + 1))
+    } else {
+        Integer::from(value)
+    })
+}
+
+impl DecisionNode {
+    /// Walk the decision tree for the instruction at the start of `instr_bytes`, given the
+    /// context register `context_bits` as big-endian 32-bit words. At each node the decision
+    /// bits select a child, and at a leaf the first pattern matching both the instruction
+    /// and the context is returned.
+    ///
+    /// Returns `None` if a decision selects a missing child or no pattern of the leaf
+    /// matches.
+    pub fn resolve(&self, instr_bytes: &[u8], context_bits: &[u8]) -> Option<&DisjointPatternType> {
+        self.resolve_pair(instr_bytes, context_bits)
+            .map(DecisionNodePair::pattern)
+    }
+
+    fn resolve_pair(&self, instruction: &[u8], context: &[u8]) -> Option<&DecisionNodePair> {
+        let size = u32::try_from(self.bitsize()).ok()?;
+        if size == 0 {
+            return self
+                .pairs()
+                .iter()
+                .find(|pair| pattern_matches(pair.pattern(), instruction, context));
+        }
+        let start = usize::try_from(self.start()).ok()?;
+        let value = if self.context() {
+            bits(context, start, size)?
+        } else {
+            bits(instruction, start, size)?
+        };
+        self.children()
+            .get(usize::try_from(value).ok()?)?
+            .resolve_pair(instruction, context)
+    }
+}
+
+fn pattern_matches(pattern: &DisjointPatternType, instruction: &[u8], context: &[u8]) -> bool {
+    match pattern {
+        DisjointPatternType::Instruction(pattern) => pattern.mask_value().matches(instruction),
+        DisjointPatternType::Context(pattern) => pattern.mask_value().matches_extended(context),
+        DisjointPatternType::Combine(pattern) => {
+            pattern.context().mask_value().matches_extended(context)
+                && pattern.instr().mask_value().matches(instruction)
+        }
+    }
+}
+
+impl PatternBlock {
+    /// Whether `bytes` match the pattern: for each word starting `offset` bytes in,
+    /// `word & mask == val`. Returns `false` if `bytes` ends before a byte the pattern
+    /// constrains.
+    pub fn matches(&self, bytes: &[u8]) -> bool {
+        let Ok(offset) = usize::try_from(self.offset()) else {
+            return false;
+        };
+        let mut constrained = self.mask_vals().iter().enumerate().flat_map(|(i, word)| {
+            u32::wrapping_from(&word.mask().0)
+                .to_be_bytes()
+                .into_iter()
+                .enumerate()
+                .filter(|(_, mask)| *mask != 0)
+                .map(move |(j, _)| offset.checked_add(i * 4 + j + 1))
+        });
+        // An end that overflows is past the end of any buffer
+        constrained
+            .try_fold(0, |end, byte| Some(end.max(byte?)))
+            .is_some_and(|end| end <= bytes.len())
+            && self.matches_extended(bytes)
+    }
+
+    /// Like [`PatternBlock::matches`], but bytes past the end of `buf` read as zero, as the
+    /// unset words of the context register do
+    fn matches_extended(&self, buf: &[u8]) -> bool {
+        if *self.nonzero() <= 0 {
+            return *self.nonzero() == 0;
+        }
+        let Ok(offset) = usize::try_from(self.offset()) else {
+            return false;
+        };
+        self.mask_vals().iter().enumerate().all(|(i, word)| {
+            let Some(data) = offset
+                .checked_add(i * 4)
+                .and_then(|start| bytes(buf, start, 4))
+            else {
+                return false;
+            };
+            u32::wrapping_from(&word.mask().0) & data as u32 == u32::wrapping_from(&word.val().0)
+        })
+    }
+}
+
+impl TokenField {
+    /// The value of the field in the token at the start of `buf`, as Ghidra's
+    /// `TokenField::getValue` computes it: bytes `bytestart..=byteend` are read in the
+    /// token's byte order, shifted right by `shift`, and sign extended if `signbit` is set.
+    /// Bytes past the end of `buf` read as zero.
+    ///
+    /// Returns `None` if the field's byte or bit range is invalid or wider than 16 bytes.
+    pub fn extract(&self, buf: &[u8]) -> Option<Integer> {
+        let start = usize::try_from(self.bytestart()).ok()?;
+        let size = usize::try_from(self.byteend())
+            .ok()?
+            .checked_sub(start)?
+            .checked_add(1)?;
+        if size > 16 {
+            return None;
+        }
+        let mut value = bytes(buf, start, size)?;
+        if !self.bigendian() {
+            value = value.swap_bytes() >> (128 - size * 8);
+        }
+        let value = value.checked_shr(u32::try_from(self.shift()).ok()?)?;
+        let bit = usize::try_from(&(self.bitend().clone() - self.bitstart())).ok()?;
+        extend(value, bit, self.signbit())
+    }
+}
+
+fn context_field(field: &ContextField, buf: &[u8]) -> Option<Integer> {
+    let start = usize::try_from(field.startbyte()).ok()?;
+    let size = usize::try_from(field.endbyte())
+        .ok()?
+        .checked_sub(start)?
+        .checked_add(1)?;
+    if size > 16 {
+        return None;
+    }
+    let value = bytes(buf, start, size)?.checked_shr(u32::try_from(field.shift()).ok()?)?;
+    let bit = usize::try_from(&(field.endbit().clone() - field.startbit())).ok()?;
+    extend(value, bit, field.signbit())
+}
@@ -0,0 +1,30 @@
+use std::fmt;
+
+use crate::grammar::{AddrSpace, SymbolHeader, TokenField};
+
+/// Written as the name, size, word size, and endianness, e.g. `ram[size=8, wordsize=1, be]`.
+/// A missing word size is shown as Ghidra's default of 1.
+impl fmt::Display for AddrSpace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}[size={}, wordsize=", self.name(), self.size())?;
+        match self.wordsize() {
+            Some(wordsize) => write!(f, "{}", wordsize)?,
+            None => f.write_str("1")?,
+        }
+        f.write_str(if self.bigendian() { ", be]" } else { ", le]" })
+    }
+}
+
+/// Written as the bit range of the field, most significant bit first, e.g. `tok[7..0]`
+impl fmt::Display for TokenField {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "tok[{}..{}]", self.bitend(), self.bitstart())
+    }
+}
+
+/// Written as the name and hexadecimal id, e.g. `instruction#0x0`
+impl fmt::Display for SymbolHeader {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}#{}", self.name(), self.id())
+    }
+}
@@ -0,0 +1,55 @@
+use crate::grammar::{AddrSpace, Sleigh, TokenField, TokenSymbol};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+/// The byte order of a specification, address space, token, or token field, as given by its
+/// `bigendian` attribute
+pub enum Endian {
+    Big,
+    Little,
+}
+
+impl From<bool> for Endian {
+    /// [`Endian::Big`] for a `bigendian` attribute of `true`
+    fn from(bigendian: bool) -> Self {
+        if bigendian {
+            Endian::Big
+        } else {
+            Endian::Little
+        }
+    }
+}
+
+impl From<Endian> for bool {
+    /// The `bigendian` attribute for `endian`
+    fn from(endian: Endian) -> Self {
+        endian == Endian::Big
+    }
+}
+
+impl Sleigh {
+    /// The byte order of the specification, from [`Sleigh::bigendian`]
+    pub fn endian(&self) -> Endian {
+        self.bigendian().into()
+    }
+}
+
+impl AddrSpace {
+    /// The byte order of the space
+    pub fn endian(&self) -> Endian {
+        self.bigendian().into()
+    }
+}
+
+impl TokenField {
+    /// The byte order of the token the field is read from
+    pub fn endian(&self) -> Endian {
+        self.bigendian().into()
+    }
+}
+
+impl TokenSymbol {
+    /// The byte order the token is read in
+    pub fn endian(&self) -> Endian {
+        self.bigendian().into()
+    }
+}
@@ -0,0 +1,391 @@
+use std::{
+    cell::{Cell, RefCell},
+    error::Error,
+    fmt, fs,
+    io::{self, Read},
+    path::Path,
+    str::{self, FromStr, Utf8Error},
+};
+
+use rust_sitter::{
+    errors::{collect_parsing_errors, ParseError},
+    tree_sitter::Tree,
+    Extract,
+};
+
+use crate::grammar::{self, Sleigh};
+
+thread_local! {
+    /// The first error recorded by a leaf transform during the current parse, with its byte
+    /// offset in the input if it is known. Transforms cannot return a `Result`, so they
+    /// record here and return a placeholder value instead.
+    static LEAF_ERROR: RefCell<Option<(SleighParseError, Option<usize>)>> =
+        const { RefCell::new(None) };
+    /// The address and length of the input of the current parse, so a transform's text can
+    /// be located in it
+    static INPUT: Cell<(usize, usize)> = const { Cell::new((0, 0)) };
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+/// Where in the input an error occurred
+pub struct SourceLocation {
+    /// The byte offset from the start of the input
+    pub offset: usize,
+    /// The line, starting from 1
+    pub line: usize,
+    /// The column in bytes, starting from 1
+    pub column: usize,
+}
+
+impl SourceLocation {
+    /// The location of byte `offset` of `input`
+    fn new(input: &str, offset: usize) -> Self {
+        let before = &input.as_bytes()[..offset];
+        let line_start = before
+            .iter()
+            .rposition(|&byte| byte == b'\n')
+            .map_or(0, |newline| newline + 1);
+        SourceLocation {
+            offset,
+            line: before.iter().filter(|&&byte| byte == b'\n').count() + 1,
+            column: offset - line_start + 1,
+        }
+    }
+}
+
+impl fmt::Display for SourceLocation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}, column {}", self.line, self.column)
+    }
+}
+
+#[derive(Debug)]
+#[non_exhaustive]
+/// An error encountered while parsing a `.sla` specification
+pub enum SleighParseError {
+    /// The input does not match the grammar
+    Syntax(Vec<ParseError>),
+    /// An attribute that should hold an integer could not be parsed as one
+    InvalidInteger {
+        field: &'static str,
+        raw: String,
+        location: Option<SourceLocation>,
+    },
+    /// An attribute that should hold `true` or `false` could not be parsed as one
+    InvalidBool {
+        field: &'static str,
+        raw: String,
+        location: Option<SourceLocation>,
+    },
+    /// An attribute matched the grammar, but its value could not be captured from it.
+    /// `location` is the start of the element's attributes, when they are known.
+    MissingCapture {
+        field: &'static str,
+        location: Option<SourceLocation>,
+    },
+    /// The input could not be read
+    Io(io::Error),
+    /// The input is not valid UTF-8
+    Utf8(Utf8Error),
+    /// The input contains nothing but whitespace, comments, and processing instructions
+    EmptyInput,
+    /// The root element of the input is not `<sleigh>`
+    NotASleighFile,
+}
+
+impl fmt::Display for SleighParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SleighParseError::Syntax(errors) => match errors.first() {
+                Some(error) => write!(
+                    f,
+                    "{} syntax error(s), the first at bytes {}..{}",
+                    errors.len(),
+                    error.start,
+                    error.end
+                ),
+                None => write!(f, "syntax error"),
+            },
+            SleighParseError::InvalidInteger {
+                field,
+                raw,
+                location,
+            } => {
+                write!(f, "invalid integer {:?} for {}", raw, field)?;
+                write_location(f, location)
+            }
+            SleighParseError::InvalidBool {
+                field,
+                raw,
+                location,
+            } => {
+                write!(f, "invalid boolean {:?} for {}", raw, field)?;
+                write_location(f, location)
+            }
+            SleighParseError::MissingCapture { field, location } => {
+                write!(f, "no value captured for {}", field)?;
+                write_location(f, location)
+            }
+            SleighParseError::Io(error) => write!(f, "failed to read input: {}", error),
+            SleighParseError::Utf8(error) => write!(f, "input is not valid UTF-8: {}", error),
+            SleighParseError::EmptyInput => write!(f, "input is empty"),
+            SleighParseError::NotASleighFile => {
+                write!(
+                    f,
+                    "input is not a sleigh specification, the root element is not <sleigh>"
+                )
+            }
+        }
+    }
+}
+
+impl Error for SleighParseError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            SleighParseError::Io(error) => Some(error),
+            SleighParseError::Utf8(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+fn write_location(f: &mut fmt::Formatter<'_>, location: &Option<SourceLocation>) -> fmt::Result {
+    match location {
+        Some(location) => write!(f, " at {}", location),
+        None => Ok(()),
+    }
+}
+
+/// Record an error from a leaf transform. Only the first error of a parse is kept. `at` is
+/// the text the error was found in, which locates it when it is part of the input.
+pub(crate) fn record(error: SleighParseError, at: Option<&str>) {
+    let offset = at.and_then(|at| {
+        let (start, len) = INPUT.get();
+        (at.as_ptr() as usize)
+            .checked_sub(start)
+            .filter(|offset| *offset <= len)
+    });
+    LEAF_ERROR.with(|recorded| {
+        recorded.borrow_mut().get_or_insert((error, offset));
+    });
+}
+
+impl SleighParseError {
+    /// Where in the input the error occurred, if it is known. Only errors from attribute
+    /// values are located; see [`SleighParseError::Syntax`] for the spans of syntax errors.
+    pub fn location(&self) -> Option<SourceLocation> {
+        match self {
+            SleighParseError::InvalidInteger { location, .. }
+            | SleighParseError::InvalidBool { location, .. }
+            | SleighParseError::MissingCapture { location, .. } => *location,
+            _ => None,
+        }
+    }
+
+    fn with_location(mut self, located: SourceLocation) -> Self {
+        if let SleighParseError::InvalidInteger { location, .. }
+        | SleighParseError::InvalidBool { location, .. }
+        | SleighParseError::MissingCapture { location, .. } = &mut self
+        {
+            *location = Some(located);
+        }
+        self
+    }
+}
+
+/// Build a specification from `input` with `build`, and return the first error its leaf
+/// transforms record instead, if there is one
+pub(crate) fn with_leaf_errors<T>(
+    input: &str,
+    build: impl FnOnce() -> Result<T, SleighParseError>,
+) -> Result<T, SleighParseError> {
+    LEAF_ERROR.with(|recorded| recorded.borrow_mut().take());
+    INPUT.set((input.as_ptr() as usize, input.len()));
+    let built = build();
+    INPUT.set((0, 0));
+    match LEAF_ERROR.with(|recorded| recorded.borrow_mut().take()) {
+        Some((error, Some(offset))) => Err(error.with_location(SourceLocation::new(input, offset))),
+        Some((error, None)) => Err(error),
+        None => built,
+    }
+}
+
+/// Parse a `.sla` specification. Malformed input is reported as a [`SleighParseError`]
+/// rather than a panic.
+pub fn parse(input: &str) -> Result<Sleigh, SleighParseError> {
+    check_root(input)?;
+    with_leaf_errors(input, || {
+        grammar::parse(input).map_err(SleighParseError::Syntax)
+    })
+}
+
+/// Build the specification from `tree`, an existing parse of `input`, reporting errors as
+/// [`parse`] does
+pub(crate) fn extract(tree: &Tree, input: &str) -> Result<Sleigh, SleighParseError> {
+    check_root(input)?;
+    check_syntax(tree, input)?;
+    let root = tree.root_node();
+    with_leaf_errors(input, || {
+        Ok(<Sleigh as Extract<_>>::extract(
+            Some(root),
+            input.as_bytes(),
+            0,
+            None,
+        ))
+    })
+}
+
+/// Report the syntax errors in `tree`, an existing parse of `input`, as [`parse`] does
+pub(crate) fn check_syntax(tree: &Tree, input: &str) -> Result<(), SleighParseError> {
+    let root = tree.root_node();
+    if root.has_error() {
+        let mut errors = Vec::new();
+        collect_parsing_errors(&root, input.as_bytes(), &mut errors);
+        return Err(SleighParseError::Syntax(errors));
+    }
+    Ok(())
+}
+
+/// Parse a `.sla` specification from the start of `input`, up to and including its closing
+/// `</sleigh>` tag, and return it with the unconsumed rest of `input`. This reads
+/// specifications embedded in a larger container, or followed by other content.
+pub fn parse_prefix(input: &str) -> Result<(Sleigh, &str), SleighParseError> {
+    let end = root_end(input).unwrap_or(input.len());
+    Ok((parse(&input[..end])?, &input[end..]))
+}
+
+/// The offset just past the first `</sleigh>` closing tag outside a comment
+fn root_end(input: &str) -> Option<usize> {
+    let mut position = 0;
+    while let Some(open) = input[position..].find('<') {
+        let start = position + open;
+        let rest = &input[start..];
+        if let Some(comment) = rest.strip_prefix("<!--") {
+            position = start + 4 + comment.find("-->")? + 3;
+            continue;
+        }
+        let close = rest[1..]
+            .trim_start()
+            .strip_prefix('/')
+            .map(str::trim_start)
+            .and_then(|rest| rest.strip_prefix("sleigh"))
+            .map(str::trim_start)
+            .and_then(|rest| rest.strip_prefix('>'));
+        if let Some(after) = close {
+            return Some(input.len() - after.len());
+        }
+        position = start + 1;
+    }
+    None
+}
+
+/// Report input with no root element, or a root element other than `<sleigh>`, before it
+/// reaches the grammar, whose errors for these cases are hard to read
+pub(crate) fn check_root(input: &str) -> Result<(), SleighParseError> {
+    let mut rest = input.trim_start();
+    // Skip the prolog the grammar also skips: comments and processing instructions
+    loop {
+        let (open, close) = if rest.starts_with("<!--") {
+            ("<!--", "-->")
+        } else if rest.starts_with("<?") {
+            ("<?", "?>")
+        } else {
+            break;
+        };
+        rest = match rest[open.len()..].find(close) {
+            Some(end) => rest[open.len() + end + close.len()..].trim_start(),
+            // Unterminated, so leave it for the grammar to report
+            None => break,
+        };
+    }
+    if rest.is_empty() {
+        return Err(SleighParseError::EmptyInput);
+    }
+    match rest.strip_prefix("<sleigh") {
+        Some(after) if !after.starts_with(|c: char| c.is_alphanumeric() || c == '_') => Ok(()),
+        _ => Err(SleighParseError::NotASleighFile),
+    }
+}
+
+/// Parses with [`parse`], so a specification can be read with `str::parse`:
+///
+/// ```
+/// use tree_sitter_sleigh::grammar::Sleigh;
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let text = std::fs::read_to_string("Processors/Toy/data/languages/toy_be.sla")?;
+/// let sleigh: Sleigh = text.parse()?;
+/// assert!(sleigh.bigendian());
+/// # Ok(())
+/// # }
+/// ```
+impl FromStr for Sleigh {
+    type Err = SleighParseError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        parse(input)
+    }
+}
+
+/// Parses with [`parse`], so a specification can be converted with `?` and `TryInto`:
+///
+/// ```
+/// use tree_sitter_sleigh::grammar::Sleigh;
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let text = std::fs::read_to_string("Processors/Toy/data/languages/toy_be.sla")?;
+/// let s = Sleigh::try_from(text.as_str())?;
+/// assert!(s.bigendian());
+/// # Ok(())
+/// # }
+/// ```
+impl TryFrom<&str> for Sleigh {
+    type Error = SleighParseError;
+
+    fn try_from(input: &str) -> Result<Self, Self::Error> {
+        parse(input)
+    }
+}
+
+/// Parses with [`parse`], for text already held as an owned `String`
+///
+/// ```
+/// use tree_sitter_sleigh::grammar::Sleigh;
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let text = std::fs::read_to_string("Processors/Toy/data/languages/toy_be.sla")?;
+/// let s = Sleigh::try_from(text)?;
+/// assert!(s.bigendian());
+/// # Ok(())
+/// # }
+/// ```
+impl TryFrom<String> for Sleigh {
+    type Error = SleighParseError;
+
+    fn try_from(input: String) -> Result<Self, Self::Error> {
+        parse(&input)
+    }
+}
+
+/// Parse a `.sla` specification from raw bytes. Input that is not UTF-8 is reported as
+/// [`SleighParseError::Utf8`].
+pub fn parse_bytes(input: &[u8]) -> Result<Sleigh, SleighParseError> {
+    parse(str::from_utf8(input).map_err(SleighParseError::Utf8)?)
+}
+
+/// Parse a `.sla` specification read to the end from `reader`, for example a decompressing
+/// stream. The whole input is buffered before parsing.
+pub fn parse_reader<R: Read>(mut reader: R) -> Result<Sleigh, SleighParseError> {
+    let mut input = Vec::new();
+    reader
+        .read_to_end(&mut input)
+        .map_err(SleighParseError::Io)?;
+    parse_bytes(&input)
+}
+
+/// Parse the `.sla` specification in the file at `path`. A file that cannot be read is
+/// reported as [`SleighParseError::Io`], and one that is not UTF-8 as
+/// [`SleighParseError::Utf8`].
+pub fn parse_file<P: AsRef<Path>>(path: P) -> Result<Sleigh, SleighParseError> {
+    parse_bytes(&fs::read(path).map_err(SleighParseError::Io)?)
+}
@@ -0,0 +1,257 @@
+use malachite::Integer;
+
+use crate::{
+    disassemble::hex,
+    grammar::{
+        BinaryExpressionType, ContextField, OperandValue, PatternExpressionType, PatternValueType,
+        TokenField, UnaryExpressionType,
+    },
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+/// Flat tag identifying the kind of a [`PatternExpressionType`] without matching through
+/// the nested value, binary, and unary expression enums
+pub enum ExprKind {
+    TokenField,
+    ContextField,
+    Constant,
+    Operand,
+    Start,
+    End,
+    Next2,
+    Plus,
+    Sub,
+    Mult,
+    LeftShift,
+    RightShift,
+    And,
+    Or,
+    Xor,
+    Div,
+    Minus,
+    Not,
+}
+
+/// The decoding state a [`PatternExpressionType`] is evaluated against. Token and context
+/// fields must be supplied; the remaining leaves evaluate to `None` unless overridden.
+pub trait PatternContext {
+    /// The value of `field` in the instruction being decoded
+    fn token_field(&self, field: &TokenField) -> Option<Integer>;
+
+    /// The value of `field` in the current context
+    fn context_field(&self, field: &ContextField) -> Option<Integer>;
+
+    /// The value of another operand of the constructor being decoded
+    fn operand(&self, _operand: &OperandValue) -> Option<Integer> {
+        None
+    }
+
+    /// The address of the instruction being decoded, in words (`inst_start`)
+    fn start(&self) -> Option<Integer> {
+        None
+    }
+
+    /// The address of the next instruction, in words (`inst_next`)
+    fn end(&self) -> Option<Integer> {
+        None
+    }
+
+    /// The address of the instruction after the next one, in words (`inst_next2`)
+    fn next2(&self) -> Option<Integer> {
+        None
+    }
+}
+
+impl PatternValueType {
+    /// The value of this leaf in `ctx`. Constants evaluate to themselves.
+    pub fn evaluate(&self, ctx: &dyn PatternContext) -> Option<Integer> {
+        match self {
+            PatternValueType::TokenField(field) => ctx.token_field(field),
+            PatternValueType::ContextField(field) => ctx.context_field(field),
+            PatternValueType::ConstantValue(constant) => Some(constant.val().clone()),
+            PatternValueType::OperandValue(operand) => ctx.operand(operand),
+            PatternValueType::StartInstructionValue { .. } => ctx.start(),
+            PatternValueType::EndInstructionValue { .. } => ctx.end(),
+            PatternValueType::Next2InstructionValue { .. } => ctx.next2(),
+        }
+    }
+}
+
+impl PatternValueType {
+    /// The placeholder for this leaf in [`PatternExpressionType::to_infix`]
+    fn to_infix(&self) -> String {
+        match self {
+            PatternValueType::TokenField(field) => field.to_string(),
+            PatternValueType::ContextField(field) => {
+                format!("ctx[{}..{}]", field.endbit(), field.startbit())
+            }
+            PatternValueType::ConstantValue(constant) => hex(constant.val()),
+            PatternValueType::OperandValue(operand) => format!("op{}", operand.index()),
+            PatternValueType::StartInstructionValue { .. } => "inst_start".to_string(),
+            PatternValueType::EndInstructionValue { .. } => "inst_next".to_string(),
+            PatternValueType::Next2InstructionValue { .. } => "inst_next2".to_string(),
+        }
+    }
+}
+
+/// The binding strength of the shift operators in [`BinaryExpressionType::operator`]
+const SHIFT: u8 = 3;
+
+impl BinaryExpressionType {
+    /// The operator and its SLEIGH binding strength, higher binding tighter
+    fn operator(&self) -> (&'static str, u8) {
+        match self {
+            BinaryExpressionType::Mult { .. } => ("*", 5),
+            BinaryExpressionType::Div { .. } => ("/", 5),
+            BinaryExpressionType::Plus { .. } => ("+", 4),
+            BinaryExpressionType::Sub { .. } => ("-", 4),
+            BinaryExpressionType::LeftShift { .. } => ("<<", SHIFT),
+            BinaryExpressionType::RightShift { .. } => (">>", SHIFT),
+            BinaryExpressionType::And { .. } => ("&", 2),
+            BinaryExpressionType::Xor { .. } => ("^", 1),
+            BinaryExpressionType::Or { .. } => ("|", 0),
+        }
+    }
+
+    /// The left and right operands
+    pub(crate) fn operands(&self) -> (&PatternExpressionType, &PatternExpressionType) {
+        match self {
+            BinaryExpressionType::Plus { left, right, .. }
+            | BinaryExpressionType::Sub { left, right, .. }
+            | BinaryExpressionType::Mult { left, right, .. }
+            | BinaryExpressionType::LeftShift { left, right, .. }
+            | BinaryExpressionType::RightShift { left, right, .. }
+            | BinaryExpressionType::And { left, right, .. }
+            | BinaryExpressionType::Or { left, right, .. }
+            | BinaryExpressionType::Xor { left, right, .. }
+            | BinaryExpressionType::Div { left, right, .. } => (left, right),
+        }
+    }
+}
+
+impl PatternExpressionType {
+    /// Render this expression in SLEIGH's infix syntax, e.g. `(tok[7..0] + 0x4) << 0x2`.
+    /// Token fields are written as in their [`Display`](std::fmt::Display), context fields
+    /// as `ctx[end..start]`, operands as `op` and their index, and the instruction
+    /// addresses as `inst_start`, `inst_next`, and `inst_next2`. Parentheses are added
+    /// where SLEIGH's precedence and left associativity require them, and around operands
+    /// of a different operator under a shift or bitwise operator.
+    pub fn to_infix(&self) -> String {
+        match self {
+            PatternExpressionType::PatternValue(value) => value.to_infix(),
+            PatternExpressionType::BinaryExpression(binary) => {
+                let (operator, precedence) = binary.operator();
+                let (left, right) = binary.operands();
+                format!(
+                    "{} {} {}",
+                    left.to_infix_operand(precedence, false),
+                    operator,
+                    right.to_infix_operand(precedence, true)
+                )
+            }
+            PatternExpressionType::UnaryExpression(unary) => {
+                let (operator, inner) = match unary.as_ref() {
+                    UnaryExpressionType::Minus { inner, .. } => ("-", inner),
+                    UnaryExpressionType::Not { inner, .. } => ("~", inner),
+                };
+                format!("{}{}", operator, inner.to_infix_operand(u8::MAX, false))
+            }
+        }
+    }
+
+    /// Render this expression as an operand of an operator binding with `precedence`,
+    /// parenthesized if it binds more loosely, or equally on the `right` of a left
+    /// associative operator. Other operators are also parenthesized under shifts and
+    /// bitwise operators, where their precedence is easily misread.
+    fn to_infix_operand(&self, precedence: u8, right: bool) -> String {
+        let parenthesize = match self {
+            PatternExpressionType::BinaryExpression(binary) => {
+                let (_, inner) = binary.operator();
+                inner < precedence
+                    || (right && inner == precedence)
+                    || (precedence <= SHIFT && inner != precedence)
+            }
+            PatternExpressionType::PatternValue(PatternValueType::ConstantValue(constant)) => {
+                // A negative constant reads as a unary minus
+                precedence == u8::MAX && *constant.val() < 0
+            }
+            _ => false,
+        };
+        if parenthesize {
+            format!("({})", self.to_infix())
+        } else {
+            self.to_infix()
+        }
+    }
+
+    /// The value of this expression in `ctx`, with Ghidra's semantics for each operator.
+    /// Returns `None` if a leaf has no value, a shift amount is negative or too large, or
+    /// a division is by zero.
+    pub fn evaluate(&self, ctx: &dyn PatternContext) -> Option<Integer> {
+        match self {
+            PatternExpressionType::PatternValue(value) => value.evaluate(ctx),
+            PatternExpressionType::BinaryExpression(binary) => {
+                let (left, right) = binary.operands();
+                let left = left.evaluate(ctx)?;
+                let right = right.evaluate(ctx)?;
+                Some(match binary.as_ref() {
+                    BinaryExpressionType::Plus { .. } => left + right,
+                    BinaryExpressionType::Sub { .. } => left - right,
+                    BinaryExpressionType::Mult { .. } => left * right,
+                    BinaryExpressionType::LeftShift { .. } => left << shift_amount(&right)?,
+                    BinaryExpressionType::RightShift { .. } => left >> shift_amount(&right)?,
+                    BinaryExpressionType::And { .. } => left & right,
+                    BinaryExpressionType::Or { .. } => left | right,
+                    BinaryExpressionType::Xor { .. } => left ^ right,
+                    BinaryExpressionType::Div { .. } => {
+                        if right == 0 {
+                            return None;
+                        }
+                        left / right
+                    }
+                })
+            }
+            PatternExpressionType::UnaryExpression(unary) => match unary.as_ref() {
+                UnaryExpressionType::Minus { inner, .. } => Some(-inner.evaluate(ctx)?),
+                UnaryExpressionType::Not { inner, .. } => Some(!inner.evaluate(ctx)?),
+            },
+        }
+    }
+
+    /// The flat kind of this expression node
+    pub fn kind(&self) -> ExprKind {
+        match self {
+            PatternExpressionType::PatternValue(value) => match value {
+                PatternValueType::TokenField(_) => ExprKind::TokenField,
+                PatternValueType::ContextField(_) => ExprKind::ContextField,
+                PatternValueType::ConstantValue(_) => ExprKind::Constant,
+                PatternValueType::OperandValue(_) => ExprKind::Operand,
+                PatternValueType::StartInstructionValue { .. } => ExprKind::Start,
+                PatternValueType::EndInstructionValue { .. } => ExprKind::End,
+                PatternValueType::Next2InstructionValue { .. } => ExprKind::Next2,
+            },
+            PatternExpressionType::BinaryExpression(binary) => match binary.as_ref() {
+                BinaryExpressionType::Plus { .. } => ExprKind::Plus,
+                BinaryExpressionType::Sub { .. } => ExprKind::Sub,
+                BinaryExpressionType::Mult { .. } => ExprKind::Mult,
+                BinaryExpressionType::LeftShift { .. } => ExprKind::LeftShift,
+                BinaryExpressionType::RightShift { .. } => ExprKind::RightShift,
+                BinaryExpressionType::And { .. } => ExprKind::And,
+                BinaryExpressionType::Or { .. } => ExprKind::Or,
+                BinaryExpressionType::Xor { .. } => ExprKind::Xor,
+                BinaryExpressionType::Div { .. } => ExprKind::Div,
+            },
+            PatternExpressionType::UnaryExpression(unary) => match unary.as_ref() {
+                UnaryExpressionType::Minus { .. } => ExprKind::Minus,
+                UnaryExpressionType::Not { .. } => ExprKind::Not,
+            },
+        }
+    }
+}
+
+/// The shift amount `right` as a `u64`, or `None` if it is negative or at least 64, since
+/// Ghidra evaluates pattern expressions as 64-bit `intb`
+fn shift_amount(right: &Integer) -> Option<u64> {
+    u64::try_from(right).ok().filter(|amount| *amount < 64)
+}
@@ -0,0 +1,137 @@
+use malachite::Integer;
+
+use crate::{
+    grammar::{AddrSpace, SourceFiles, Spaces, UnknownAttribute},
+    parse, HexInteger, SleighParseError,
+};
+
+/// The empty symbol table [`parse_header_only`] closes the truncated input with
+const EMPTY_SYMBOL_TABLE: &str =
+    "<symbol_table scopesize=\"0\" symbolsize=\"0\">\n</symbol_table>\n</sleigh>";
+
+#[derive(Debug, Clone, PartialEq)]
+/// The root attributes, source files, and address spaces of a specification, as returned by
+/// [`parse_header_only`]. The getters match those of [`Sleigh`](crate::grammar::Sleigh).
+pub struct SleighHeader {
+    version: Option<Integer>,
+    bigendian: Option<bool>,
+    align: Integer,
+    uniqbase: HexInteger,
+    maxdelay: Option<HexInteger>,
+    uniqmask: Option<HexInteger>,
+    numsections: Option<Integer>,
+    unknown_attributes: Vec<UnknownAttribute>,
+    sourcefiles: SourceFiles,
+    spaces: Spaces,
+}
+
+impl SleighHeader {
+    /// The `.sla` format version, if the file declares one
+    pub fn version(&self) -> Option<&Integer> {
+        self.version.as_ref()
+    }
+
+    /// Whether the processor is big endian, falling back to the default space as
+    /// [`Sleigh::bigendian`](crate::grammar::Sleigh::bigendian) does
+    pub fn bigendian(&self) -> bool {
+        self.bigendian.unwrap_or_else(|| {
+            self.spaces
+                .by_name(self.spaces.defaultspace())
+                .is_some_and(AddrSpace::bigendian)
+        })
+    }
+
+    /// The `bigendian` attribute of the root element, if present
+    pub fn bigendian_attribute(&self) -> Option<bool> {
+        self.bigendian
+    }
+
+    /// The instruction alignment in bytes
+    pub fn align(&self) -> &Integer {
+        &self.align
+    }
+
+    /// The first offset in the unique space available for temporaries
+    pub fn uniqbase(&self) -> &HexInteger {
+        &self.uniqbase
+    }
+
+    /// The largest delay slot depth of any instruction, if declared
+    pub fn maxdelay(&self) -> Option<&HexInteger> {
+        self.maxdelay.as_ref()
+    }
+
+    /// The mask applied to unique space offsets, if declared
+    pub fn uniqmask(&self) -> Option<&HexInteger> {
+        self.uniqmask.as_ref()
+    }
+
+    /// The number of named p-code sections, if declared
+    pub fn numsections(&self) -> Option<&Integer> {
+        self.numsections.as_ref()
+    }
+
+    /// Root attributes this crate does not know about, in the order they appear
+    pub fn unknown_attributes(&self) -> &[UnknownAttribute] {
+        &self.unknown_attributes
+    }
+
+    /// The `.slaspec`/`.sinc` files the specification was compiled from
+    pub fn sourcefiles(&self) -> &SourceFiles {
+        &self.sourcefiles
+    }
+
+    /// The address spaces
+    pub fn spaces(&self) -> &Spaces {
+        &self.spaces
+    }
+}
+
+/// Parse only the root attributes, source files, and address spaces of a `.sla`
+/// specification, for indexing many specifications by architecture. Parsing stops at the
+/// `<symbol_table>` element, so the symbol table, which is nearly all of a specification, is
+/// neither parsed nor checked.
+///
+/// Errors before the symbol table are reported as [`parse`] reports them, at the same
+/// locations.
+pub fn parse_header_only(input: &str) -> Result<SleighHeader, SleighParseError> {
+    let sleigh = match symbol_table_start(input) {
+        Some(start) => parse(&format!("{}{EMPTY_SYMBOL_TABLE}", &input[..start]))?,
+        // Without a symbol table the input is malformed, so report it as parse does
+        None => parse(input)?,
+    };
+    Ok(SleighHeader {
+        version: sleigh.version().cloned(),
+        bigendian: sleigh.bigendian_attribute(),
+        align: sleigh.align().clone(),
+        uniqbase: sleigh.uniqbase().clone(),
+        maxdelay: sleigh.maxdelay().cloned(),
+        uniqmask: sleigh.uniqmask().cloned(),
+        numsections: sleigh.numsections().cloned(),
+        unknown_attributes: sleigh.unknown_attributes().to_vec(),
+        sourcefiles: sleigh.sourcefiles().clone(),
+        spaces: sleigh.spaces().clone(),
+    })
+}
+
+/// The offset of the first `<symbol_table` opening tag outside a comment
+fn symbol_table_start(input: &str) -> Option<usize> {
+    let mut position = 0;
+    while let Some(open) = input[position..].find('<') {
+        let start = position + open;
+        let rest = &input[start..];
+        if let Some(comment) = rest.strip_prefix("<!--") {
+            position = start + 4 + comment.find("-->")? + 3;
+            continue;
+        }
+        let is_symbol_table = rest[1..]
+            .trim_start()
+            .strip_prefix("symbol_table")
+            .is_some_and(|after| !after.starts_with(|c: char| c.is_alphanumeric() || c == '_'));
+        if is_symbol_table {
+            return Some(start);
+        }
+        position = start + 1;
+    }
+    None
+}
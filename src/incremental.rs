@@ -0,0 +1,70 @@
+use rust_sitter::tree_sitter::{InputEdit, Parser, Tree};
+
+use crate::{
+    error::extract,
+    grammar::{self, Sleigh},
+    SleighParseError,
+};
+
+/// A parsed specification kept together with its tree-sitter tree, so that edits to the
+/// text can be reparsed incrementally, e.g. in an editor.
+///
+/// Only the tree-sitter parse is incremental: after each edit the typed [`Sleigh`] is
+/// rebuilt in full from the new tree. Reparsing saves the time spent matching unchanged
+/// text against the grammar, not the time spent building the typed tree.
+pub struct SleighTree {
+    parser: Parser,
+    tree: Tree,
+    sleigh: Sleigh,
+}
+
+impl SleighTree {
+    /// Parse a `.sla` specification, keeping its tree for later edits. Errors are reported
+    /// as [`parse`](crate::parse) reports them.
+    pub fn parse(input: &str) -> Result<Self, SleighParseError> {
+        let mut parser = Parser::new();
+        parser
+            .set_language(&grammar::language())
+            .expect("Incompatible tree-sitter language");
+        let tree = parser
+            .parse(input, None)
+            .expect("Failed to parse with no timeout or cancellation");
+        let sleigh = extract(&tree, input)?;
+        Ok(Self {
+            parser,
+            tree,
+            sleigh,
+        })
+    }
+
+    /// The specification, as of the last edit that parsed successfully
+    pub fn sleigh(&self) -> &Sleigh {
+        &self.sleigh
+    }
+
+    /// The tree-sitter tree of the text as of the last edit
+    pub fn tree(&self) -> &Tree {
+        &self.tree
+    }
+
+    /// Consume the tree, keeping only the specification
+    pub fn into_sleigh(self) -> Sleigh {
+        self.sleigh
+    }
+
+    /// Apply `edit`, which turned the previous text into `new_text`, and reparse, reusing
+    /// the parts of the previous tree the edit did not touch.
+    ///
+    /// If `new_text` does not parse, the error is returned and [`Self::sleigh`] keeps the
+    /// last specification that parsed successfully. The tree still follows `new_text`, so
+    /// later edits are applied to it as usual.
+    pub fn reparse(&mut self, edit: InputEdit, new_text: &str) -> Result<(), SleighParseError> {
+        self.tree.edit(&edit);
+        self.tree = self
+            .parser
+            .parse(new_text, Some(&self.tree))
+            .expect("Failed to parse with no timeout or cancellation");
+        self.sleigh = extract(&self.tree, new_text)?;
+        Ok(())
+    }
+}
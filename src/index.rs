@@ -0,0 +1,39 @@
+use std::{collections::HashMap, fmt, sync::OnceLock};
+
+use malachite::Integer;
+
+#[derive(Clone, Default)]
+/// A lazily built map from an id to a position in a list of the parsed tree. The index is
+/// derived from the tree, so it never takes part in equality and is not serialized.
+pub(crate) struct IdIndex(OnceLock<HashMap<Integer, usize>>);
+
+impl IdIndex {
+    /// The position of `id`, building the index from `ids` on first use
+    pub(crate) fn get<'a, I>(&self, id: &Integer, ids: impl FnOnce() -> I) -> Option<usize>
+    where
+        I: IntoIterator<Item = &'a Integer>,
+    {
+        self.0
+            .get_or_init(|| {
+                ids()
+                    .into_iter()
+                    .enumerate()
+                    .map(|(position, id)| (id.clone(), position))
+                    .collect()
+            })
+            .get(id)
+            .copied()
+    }
+}
+
+impl PartialEq for IdIndex {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl fmt::Debug for IdIndex {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("IdIndex")
+    }
+}
@@ -0,0 +1,134 @@
+use std::{error::Error, fmt, ops::Deref};
+
+use malachite::{num::logic::traits::SignificantBits, Integer};
+
+#[derive(Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+/// An [`Integer`] attribute the `.sla` format writes in hexadecimal, such as ids, masks,
+/// and offsets. It dereferences to the [`Integer`], and both `Display` and `Debug` print it
+/// in hex with a `0x` prefix, as it appears in the specification.
+pub struct HexInteger(pub Integer);
+
+impl Deref for HexInteger {
+    type Target = Integer;
+
+    fn deref(&self) -> &Integer {
+        &self.0
+    }
+}
+
+impl From<Integer> for HexInteger {
+    fn from(value: Integer) -> Self {
+        HexInteger(value)
+    }
+}
+
+impl From<HexInteger> for Integer {
+    fn from(value: HexInteger) -> Self {
+        value.0
+    }
+}
+
+impl PartialEq<Integer> for HexInteger {
+    fn eq(&self, other: &Integer) -> bool {
+        self.0 == *other
+    }
+}
+
+impl PartialEq<HexInteger> for Integer {
+    fn eq(&self, other: &HexInteger) -> bool {
+        *self == other.0
+    }
+}
+
+impl fmt::Display for HexInteger {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.0 < 0 {
+            write!(f, "-0x{:x}", -&self.0)
+        } else {
+            write!(f, "0x{:x}", self.0)
+        }
+    }
+}
+
+impl fmt::Debug for HexInteger {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// An [`Integer`] from the parsed tree does not fit in the requested machine integer type
+pub struct IntegerRangeError {
+    value: Integer,
+    target: &'static str,
+}
+
+impl IntegerRangeError {
+    /// The value that could not be converted
+    pub fn value(&self) -> &Integer {
+        &self.value
+    }
+
+    /// The name of the type the conversion was to, e.g. `u64`
+    pub fn target(&self) -> &'static str {
+        self.target
+    }
+}
+
+impl fmt::Display for IntegerRangeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} does not fit in {}", self.value, self.target)
+    }
+}
+
+impl Error for IntegerRangeError {}
+
+/// Range-checked conversions from the [`Integer`]s stored in the parsed tree to machine
+/// integers, and the sign and bit length of those values, so callers do not need malachite's
+/// conversion or logic traits
+pub trait IntegerExt {
+    /// The value as an `i64`, or an error if it is out of range
+    fn as_i64(&self) -> Result<i64, IntegerRangeError>;
+
+    /// The value as a `u64`, or an error if it is negative or out of range
+    fn as_u64(&self) -> Result<u64, IntegerRangeError>;
+
+    /// The value as a `usize`, or an error if it is negative or out of range
+    fn as_usize(&self) -> Result<usize, IntegerRangeError>;
+
+    /// Whether the value is less than zero
+    fn is_negative(&self) -> bool;
+
+    /// The number of bits needed to write the absolute value, e.g. 32 for a mask of
+    /// `0xff000000`. Zero has no significant bits.
+    fn significant_bits(&self) -> u64;
+}
+
+impl IntegerExt for Integer {
+    fn as_i64(&self) -> Result<i64, IntegerRangeError> {
+        i64::try_from(self).map_err(|_| range_error(self, "i64"))
+    }
+
+    fn as_u64(&self) -> Result<u64, IntegerRangeError> {
+        u64::try_from(self).map_err(|_| range_error(self, "u64"))
+    }
+
+    fn as_usize(&self) -> Result<usize, IntegerRangeError> {
+        usize::try_from(self).map_err(|_| range_error(self, "usize"))
+    }
+
+    fn is_negative(&self) -> bool {
+        *self < 0
+    }
+
+    fn significant_bits(&self) -> u64 {
+        SignificantBits::significant_bits(self)
+    }
+}
+
+fn range_error(value: &Integer, target: &'static str) -> IntegerRangeError {
+    IntegerRangeError {
+        value: value.clone(),
+        target,
+    }
+}
@@ -1,24 +1,104 @@
-pub use grammar::parse;
+pub use bit_range::BitRange;
+pub use borrowed::{parse_borrowed, BorrowedConstructor, BorrowedSymbol, SleighBorrowed};
+pub use decision::DecisionStats;
+pub use diff::SleighDiff;
+pub use disassemble::DisassembledInstruction;
+pub use endian::Endian;
+pub use error::{
+    parse, parse_bytes, parse_file, parse_prefix, parse_reader, SleighParseError, SourceLocation,
+};
+pub use expression::{ExprKind, PatternContext};
+pub use header::{parse_header_only, SleighHeader};
+pub use incremental::SleighTree;
+pub use integer::{HexInteger, IntegerExt, IntegerRangeError};
+#[cfg(feature = "rayon")]
+pub use parallel::parse_many;
+pub use path::NodeRef;
+pub use progress::{parse_with_progress, ParseProgress};
+pub use scope::ScopeTree;
+pub use span::{parse_with_spans, Span, SpanMap};
+pub use template::{ConstTplKind, HandleLocation, PcodeOp, TemplateContext};
+pub use validate::ValidationIssue;
+pub use visitor::Visitor;
 
+mod addr_space;
+mod bit_range;
+mod borrowed;
+mod compare;
+mod decision;
+mod diff;
+mod disassemble;
+mod display;
+mod endian;
+mod error;
+mod expression;
+mod header;
+mod incremental;
+mod index;
+mod integer;
+mod minimal;
+#[cfg(feature = "rayon")]
+mod parallel;
+mod path;
+mod progress;
+mod scope;
+#[cfg(feature = "serde")]
+mod serde_integer;
+mod sleigh_attributes;
+mod span;
+mod template;
+mod validate;
+mod visitor;
+mod writer;
+
+/// The parsed tree of a `.sla` specification, with one type per element.
+///
+/// Every type is `Clone`. The small leaf types, [`AddrSpace`](grammar::AddrSpace),
+/// [`OverlaySpace`](grammar::OverlaySpace),
+/// [`SourceFile`](grammar::SourceFile), [`Scope`](grammar::Scope),
+/// [`SymbolHeader`](grammar::SymbolHeader), [`TokenField`](grammar::TokenField),
+/// [`ContextField`](grammar::ContextField), [`ConstantValue`](grammar::ConstantValue),
+/// [`OperandValue`](grammar::OperandValue), [`ConstructorOperand`](grammar::ConstructorOperand),
+/// [`OperandPrint`](grammar::OperandPrint), and [`Print`](grammar::Print), are also `Eq` and
+/// `Hash`. Larger types are not, since hashing them would walk their whole subtree: key
+/// symbols by [`SleighSymbolType::id`](grammar::SleighSymbolType::id) and constructors by
+/// their parent and position, as [`Sleigh::constructor_by_id`](grammar::Sleigh::constructor_by_id)
+/// does.
 #[rust_sitter::grammar("sleigh")]
-#[allow(
-    clippy::declare_interior_mutable_const,
-    clippy::borrow_interior_mutable_const,
-    clippy::large_enum_variant
-)]
+#[allow(clippy::large_enum_variant)]
 pub mod grammar {
-    use malachite::{num::conversion::traits::FromStringBase, Integer};
-    use regex::Regex;
-    use std::cell::OnceCell;
+    use malachite::{
+        num::conversion::traits::{FromStringBase, SaturatingFrom},
+        Integer,
+    };
+    use regex::{Captures, Regex};
+    use std::{
+        collections::{BTreeSet, HashMap},
+        str::ParseBoolError,
+        sync::LazyLock,
+    };
     use typed_builder::TypedBuilder;
 
-    trait XmlUnescape {
+    pub use crate::addr_space::{AddrSpace, OverlaySpace};
+    pub use crate::sleigh_attributes::{SleighAttributes, UnknownAttribute};
+
+    use crate::{
+        error::{record, SleighParseError},
+        index::IdIndex,
+        integer::HexInteger,
+    };
+
+    pub(crate) trait XmlUnescape {
         /// Unescape XML:
         /// - `&amp;` -> `&`
         /// - `&lt;` -> `<`
         /// - `&gt;` -> `>`
         /// - `&quot;` -> `"`
         /// - `&apos;` -> `'`
+        /// - `&#NN;` and `&#xNN;` -> the character with that decimal or hexadecimal code point
+        ///
+        /// Malformed references, including `&#XNN;`, which XML does not allow, are left as they
+        /// are.
         fn xml_unescape(&self) -> String;
     }
 
@@ -27,542 +107,973 @@ pub mod grammar {
         S: AsRef<str>,
     {
         fn xml_unescape(&self) -> String {
-            self.as_ref()
-                .replace("&amp;", "&")
-                .replace("&lt;", "<")
-                .replace("&gt;", ">")
-                .replace("&quot;", "\"")
-                .replace("&apos;", "'")
+            let mut rest = self.as_ref();
+            let mut unescaped = String::with_capacity(rest.len());
+            while let Some(index) = rest.find('&') {
+                unescaped.push_str(&rest[..index]);
+                rest = &rest[index..];
+                let entity = rest[1..].find(';').map(|end| &rest[1..end + 1]);
+                let character = entity.and_then(|entity| match entity {
+                    "amp" => Some('&'),
+                    "lt" => Some('<'),
+                    "gt" => Some('>'),
+                    "quot" => Some('"'),
+                    "apos" => Some('\''),
+                    _ => {
+                        let reference = entity.strip_prefix('#')?;
+                        let code = match reference.strip_prefix('x') {
+                            Some(hex) if hex.chars().all(|c| c.is_ascii_hexdigit()) => {
+                                u32::from_str_radix(hex, 16).ok()
+                            }
+                            None if reference.chars().all(|c| c.is_ascii_digit()) => {
+                                reference.parse().ok()
+                            }
+                            _ => None,
+                        };
+                        code.and_then(char::from_u32)
+                    }
+                });
+                match (entity, character) {
+                    (Some(entity), Some(character)) => {
+                        unescaped.push(character);
+                        rest = &rest[entity.len() + 2..];
+                    }
+                    _ => {
+                        unescaped.push('&');
+                        rest = &rest[1..];
+                    }
+                }
+            }
+            unescaped.push_str(rest);
+            unescaped
         }
     }
 
-    impl Sleigh {
-        const VERSION_REGEX: OnceCell<Regex> = OnceCell::new();
-        const BIGENDIAN_REGEX: OnceCell<Regex> = OnceCell::new();
-        const ALIGN_REGEX: OnceCell<Regex> = OnceCell::new();
-        const UNIQBASE_REGEX: OnceCell<Regex> = OnceCell::new();
-        const MAXDELAY_REGEX: OnceCell<Regex> = OnceCell::new();
-        const UNIQMASK_REGEX: OnceCell<Regex> = OnceCell::new();
-        const NUMSECTIONS_REGEX: OnceCell<Regex> = OnceCell::new();
+    /// Parse a boolean attribute. Ghidra writes `true` and `false`, but other tooling may
+    /// capitalize them or write `1` and `0`, so any case of either is accepted.
+    pub(crate) fn parse_sleigh_bool(value: &str) -> Result<bool, ParseBoolError> {
+        match value {
+            "1" => Ok(true),
+            "0" => Ok(false),
+            _ => value.to_ascii_lowercase().parse(),
+        }
+    }
+
+    trait LeafCapture<'v> {
+        /// Get capture group `group` of a leaf's regular expression match, if it is present.
+        /// Attribute values may be quoted with `"` or `'`, so attribute expressions repeat
+        /// their groups once per quote: `group` is numbered within the `"` alternative and
+        /// read from whichever alternative matched.
+        fn leaf_optional_capture(&self, group: usize) -> Option<&'v str>;
+        /// Get capture group `group` as [`LeafCapture::leaf_optional_capture`] does. If it is
+        /// absent, a [`SleighParseError::MissingCapture`] is recorded for `field` and `""`
+        /// returned.
+        fn leaf_capture(&self, field: &'static str, group: usize) -> &'v str;
+    }
+
+    impl<'v> LeafCapture<'v> for Option<Captures<'v>> {
+        fn leaf_optional_capture(&self, group: usize) -> Option<&'v str> {
+            let captures = self.as_ref()?;
+            let alternative = (captures.len() - 1) / 2;
+            captures
+                .get(group)
+                .or_else(|| captures.get(group + alternative))
+                .map(|capture| capture.as_str())
+        }
+
+        fn leaf_capture(&self, field: &'static str, group: usize) -> &'v str {
+            match self.leaf_optional_capture(group) {
+                Some(capture) => capture,
+                None => {
+                    record(
+                        SleighParseError::MissingCapture {
+                            field,
+                            location: None,
+                        },
+                        None,
+                    );
+                    ""
+                }
+            }
+        }
+    }
+
+    pub(crate) trait LeafValue {
+        /// Parse a decimal integer of any size, with an optional leading `+` or `-`. If it is
+        /// invalid, e.g. `1.0` or `1e3`, a [`SleighParseError::InvalidInteger`] is recorded for
+        /// `field` and zero returned.
+        fn leaf_integer(&self, field: &'static str) -> Integer;
+        /// Parse a hexadecimal integer with its `0x` or `0X` prefix and an optional leading
+        /// `-`. If it is invalid, a [`SleighParseError::InvalidInteger`] is recorded for
+        /// `field` and zero returned.
+        fn leaf_hex_integer(&self, field: &'static str) -> HexInteger;
+        /// Parse a boolean with [`parse_sleigh_bool`]. If it is invalid, a
+        /// [`SleighParseError::InvalidBool`] is recorded for `field` and `false` returned.
+        fn leaf_bool(&self, field: &'static str) -> bool;
+    }
+
+    impl LeafValue for str {
+        fn leaf_integer(&self, field: &'static str) -> Integer {
+            let digits = self
+                .strip_prefix('+')
+                .filter(|digits| !digits.starts_with('-'))
+                .unwrap_or(self);
+            digits.parse().unwrap_or_else(|_| {
+                record(
+                    SleighParseError::InvalidInteger {
+                        field,
+                        raw: self.to_string(),
+                        location: None,
+                    },
+                    Some(self),
+                );
+                Integer::default()
+            })
+        }
+
+        fn leaf_hex_integer(&self, field: &'static str) -> HexInteger {
+            let (negative, unsigned) = match self.strip_prefix('-') {
+                Some(unsigned) => (true, unsigned),
+                None => (false, self),
+            };
+            let value = unsigned
+                .strip_prefix("0x")
+                .or_else(|| unsigned.strip_prefix("0X"))
+                .filter(|digits| !digits.starts_with(['-', '+']))
+                .and_then(|digits| Integer::from_string_base(16, digits))
+                .map(|value| if negative { -value } else { value });
+            HexInteger(value.unwrap_or_else(|| {
+                record(
+                    SleighParseError::InvalidInteger {
+                        field,
+                        raw: self.to_string(),
+                        location: None,
+                    },
+                    Some(self),
+                );
+                Integer::default()
+            }))
+        }
+
+        fn leaf_bool(&self, field: &'static str) -> bool {
+            parse_sleigh_bool(self).unwrap_or_else(|_| {
+                record(
+                    SleighParseError::InvalidBool {
+                        field,
+                        raw: self.to_string(),
+                        location: None,
+                    },
+                    Some(self),
+                );
+                false
+            })
+        }
+    }
+
+    static SLEIGH_ATTRIBUTE_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(r#"([a-zA-Z_][a-zA-Z0-9_.:-]*)\s*=\s*(?:"([^"]*)"|'([^']*)')"#)
+            .expect("Invalid regular expression")
+    });
+
+    /// Build the [`SleighAttributes`] of the `<sleigh>` root from its attributes, which may
+    /// appear in any order. Attributes this crate does not know about are kept in order in
+    /// [`Sleigh::unknown_attributes`].
+    pub(crate) fn sleigh_attributes_from_attributes(v: &str) -> SleighAttributes {
+        let mut attributes = HashMap::new();
+        let mut unknown_attributes = Vec::new();
+        for captures in SLEIGH_ATTRIBUTE_REGEX.captures_iter(v) {
+            let (Some(name), Some(value)) =
+                (captures.get(1), captures.get(2).or_else(|| captures.get(3)))
+            else {
+                continue;
+            };
+            match name.as_str() {
+                "version" | "bigendian" | "align" | "uniqbase" | "maxdelay" | "uniqmask"
+                | "numsections" => {
+                    attributes.insert(name.as_str(), value.as_str());
+                }
+                _ => unknown_attributes.push(UnknownAttribute {
+                    name: name.as_str().to_string(),
+                    value: value.as_str().xml_unescape(),
+                }),
+            }
+        }
+        let required =
+            |name: &str, field: &'static str| required_attribute(v, &attributes, name, field);
+        SleighAttributes {
+            version: attributes
+                .get("version")
+                .map(|v| v.leaf_integer("Sleigh::version")),
+            bigendian: attributes
+                .get("bigendian")
+                .map(|v| v.leaf_bool("Sleigh::bigendian")),
+            align: required("align", "Sleigh::align").leaf_integer("Sleigh::align"),
+            uniqbase: required("uniqbase", "Sleigh::uniqbase").leaf_hex_integer("Sleigh::uniqbase"),
+            maxdelay: attributes
+                .get("maxdelay")
+                .map(|v| v.leaf_hex_integer("Sleigh::maxdelay")),
+            uniqmask: attributes
+                .get("uniqmask")
+                .map(|v| v.leaf_hex_integer("Sleigh::uniqmask")),
+            numsections: attributes
+                .get("numsections")
+                .map(|v| v.leaf_integer("Sleigh::numsections")),
+            unknown_attributes,
+        }
     }
 
     #[rust_sitter::language]
-    #[derive(TypedBuilder, Debug, PartialEq)]
+    #[derive(TypedBuilder, Debug, Clone, PartialEq)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     /// Sleigh Base
     ///
     pub struct Sleigh {
         #[rust_sitter::leaf(pattern = r#"<sleigh"#)]
         #[builder(default, setter(skip))]
+        #[cfg_attr(feature = "serde", serde(skip))]
         _open: (),
         #[rust_sitter::leaf(
-            pattern = r#"version\s*=\s*"(-?[0-9]+)""#,
-            transform = |v| {
-                Sleigh::VERSION_REGEX
-                    .get_or_init(|| {
-                        Regex::new(r#"version\s*=\s*"(-?[0-9]+)""#).expect("Invalid regular expression")
-                    })
-                    .captures(v)
-                    .expect("No captures or no capture group")
-                    .get(1)
-                    .expect("No capture group")
-                    .as_str()
-                    .parse()
-                    .expect("Invalid integer")
-            }
-        )]
-        #[builder(setter(transform = |v: impl Into<Integer>| {
-            Some(v.into())
-        }))]
-        /// Technically, version is optional
-        version: Option<Integer>,
-        #[rust_sitter::leaf(
-            pattern = r#"bigendian\s*=\s*"([a-z]+)""#,
-            transform = |v| {
-                Sleigh::BIGENDIAN_REGEX
-                    .get_or_init(|| {
-                        Regex::new(r#"bigendian\s*=\s*"([a-z]+)""#)
-                            .expect("Invalid regular expression")
-                    })
-                    .captures(v)
-                    .expect("No captures or no capture group")
-                    .get(1)
-                    .expect("No capture group")
-                    .as_str()
-                    .parse()
-                    .expect("Invalid boolean")
-            }
-        )]
-        bigendian: bool,
-        #[rust_sitter::leaf(
-            pattern = r#"align\s*=\s*"(-?[0-9]+)""#,
-            transform = |v| {
-                Sleigh::ALIGN_REGEX
-                    .get_or_init(|| {
-                        Regex::new(r#"align\s*=\s*"(-?[0-9]+)""#).expect("Invalid regular expression")
-                    })
-                    .captures(v)
-                    .expect("No captures or no capture group")
-                    .get(1)
-                    .expect("No capture group")
-                    .as_str()
-                    .parse()
-                    .expect("Invalid integer")
-            }
-        )]
-        #[builder(setter(transform = |v: impl Into<Integer>| {
-            v.into()
-        }))]
-        align: Integer,
-        #[rust_sitter::leaf(
-            pattern = r#"uniqbase\s*=\s*"0x([0-9a-fA-F]+)""#,
-            transform = |v| {
-                Integer::from_string_base(16, Sleigh::UNIQBASE_REGEX
-                    .get_or_init(|| {
-                        Regex::new(r#"uniqbase\s*=\s*"0x([0-9a-fA-F]+)""#)
-                            .expect("Invalid regular expression")
-                    })
-                    .captures(v)
-                    .expect("No captures or no capture group")
-                    .get(1)
-                    .expect("No capture group")
-                    .as_str()).expect("Invalid integer")
-            }
-        )]
-        #[builder(setter(transform = |v: impl Into<Integer>| {
-            v.into()
-        }))]
-        uniqbase: Integer,
-        #[rust_sitter::leaf(
-            pattern = r#"maxdelay\s*=\s*"0x([0-9a-fA-F]+)""#,
-            transform = |v| {
-                Integer::from_string_base(16, Sleigh::MAXDELAY_REGEX
-                    .get_or_init(|| {
-                        Regex::new(r#"maxdelay\s*=\s*"0x([0-9a-fA-F]+)""#)
-                            .expect("Invalid regular expression")
-                    })
-                    .captures(v)
-                    .expect("No captures or no capture group")
-                    .get(1)
-                    .expect("No capture group")
-                    .as_str()).expect("Invalid integer")
-            }
-        )]
-        #[builder(default, setter(transform = |v: impl Into<Integer>| {
-            Some(v.into())
-        }))]
-        /// `maxdelay` is used, but is only usually set to 0x1 (1 delay slot)
-        maxdelay: Option<Integer>,
-        #[rust_sitter::leaf(
-            pattern = r#"uniqmask\s*=\s*"0x([0-9a-fA-F]+)""#,
-            transform = |v| {
-                Integer::from_string_base(16, Sleigh::UNIQMASK_REGEX
-                    .get_or_init(|| {
-                        Regex::new(r#"uniqmask\s*=\s*"0x([0-9a-fA-F]+)""#)
-                            .expect("Invalid regular expression")
-                    })
-                    .captures(v)
-                    .expect("No captures or no capture group")
-                    .get(1)
-                    .expect("No capture group")
-                    .as_str()).expect("Invalid integer")
-            }
-        )]
-        #[builder(default, setter(transform = |v: impl Into<Integer>| {
-            Some(v.into())
-        }))]
-        /// `maxdelay` is used, but is only usually set to 0x1 (1 delay slot)
-        uniqmask: Option<Integer>,
-        #[rust_sitter::leaf(
-            pattern = r#"numsections\s*=\s*"0x([0-9a-fA-F]+)""#,
-            transform = |v| {
-                Sleigh::NUMSECTIONS_REGEX
-                    .get_or_init(|| {
-                        Regex::new(r#"numsections\s*=\s*"([0-9]+)""#).expect("Invalid regular expression")
-                    })
-                    .captures(v)
-                    .expect("No captures or no capture group")
-                    .get(1)
-                    .expect("No capture group")
-                    .as_str()
-                    .parse()
-                    .expect("Invalid integer")
-            }
+            pattern = r#"([a-zA-Z_][a-zA-Z0-9_.:-]*\s*=\s*(?:"[^"]*"|'[^']*')\s*)+"#,
+            transform = sleigh_attributes_from_attributes
         )]
-        #[builder(default, setter(transform = |v: impl Into<Integer>| {
-            Some(v.into())
-        }))]
-        numsections: Option<Integer>,
+        #[cfg_attr(feature = "serde", serde(flatten))]
+        attributes: SleighAttributes,
         #[rust_sitter::leaf(pattern = r#">"#)]
         #[builder(default, setter(skip))]
+        #[cfg_attr(feature = "serde", serde(skip))]
         _close: (),
         sourcefiles: SourceFiles,
         spaces: Spaces,
         symbol_table: SymbolTable,
         #[rust_sitter::leaf(pattern = r#"<\s*/\s*sleigh\s*>"#)]
         #[builder(default, setter(skip))]
+        #[cfg_attr(feature = "serde", serde(skip))]
         _end: (),
     }
 
-    #[derive(TypedBuilder, Debug, PartialEq)]
+    impl Sleigh {
+        /// The `.sla` format version, if the file declares one. A `version="4"` header is
+        /// accepted and read as version 3 is, with any root attributes this crate does not
+        /// know kept in [`Sleigh::unknown_attributes`]. Only version 3 specifications are
+        /// tested in full.
+        pub fn version(&self) -> Option<&Integer> {
+            self.attributes.version.as_ref()
+        }
+
+        /// Whether the processor is big endian. If the root element has no `bigendian`
+        /// attribute, this is the endianness of the default space, or little endian if there
+        /// is no default space either.
+        pub fn bigendian(&self) -> bool {
+            self.attributes.bigendian.unwrap_or_else(|| {
+                self.spaces
+                    .by_name(self.spaces.defaultspace())
+                    .is_some_and(AddrSpace::bigendian)
+            })
+        }
+
+        /// The `bigendian` attribute of the root element, if present
+        pub fn bigendian_attribute(&self) -> Option<bool> {
+            self.attributes.bigendian
+        }
+
+        /// The instruction alignment in bytes
+        pub fn align(&self) -> &Integer {
+            &self.attributes.align
+        }
+
+        /// The first offset in the unique space available for temporaries
+        pub fn uniqbase(&self) -> &HexInteger {
+            &self.attributes.uniqbase
+        }
+
+        /// The largest delay slot depth of any instruction, if declared
+        pub fn maxdelay(&self) -> Option<&HexInteger> {
+            self.attributes.maxdelay.as_ref()
+        }
+
+        /// The mask applied to unique space offsets, if declared
+        pub fn uniqmask(&self) -> Option<&HexInteger> {
+            self.attributes.uniqmask.as_ref()
+        }
+
+        /// The number of named p-code sections, if declared
+        pub fn numsections(&self) -> Option<&Integer> {
+            self.attributes.numsections.as_ref()
+        }
+
+        /// Root attributes this crate does not know about, in the order they appear. They may
+        /// appear anywhere among the known attributes, but are written back out after them.
+        pub fn unknown_attributes(&self) -> &[UnknownAttribute] {
+            &self.attributes.unknown_attributes
+        }
+
+        /// The `.slaspec`/`.sinc` files the specification was compiled from
+        pub fn sourcefiles(&self) -> &SourceFiles {
+            &self.sourcefiles
+        }
+
+        /// The address spaces
+        pub fn spaces(&self) -> &Spaces {
+            &self.spaces
+        }
+
+        /// The symbol table
+        pub fn symbol_table(&self) -> &SymbolTable {
+            &self.symbol_table
+        }
+
+        /// Consume the specification, keeping only the symbol bodies in table order
+        pub fn into_symbols(self) -> Vec<SleighSymbolType> {
+            self.symbol_table.symbols
+        }
+
+        /// Every subtable symbol, including the root `instruction` table, in symbol table order
+        pub fn subtables(&self) -> impl Iterator<Item = &SubtableSymbol> {
+            self.symbol_table
+                .symbols
+                .iter()
+                .filter_map(|symbol| match symbol {
+                    SleighSymbolType::TripleSymbol(TripleSymbol::SubtableSymbol {
+                        subtable,
+                        ..
+                    }) => Some(subtable),
+                    _ => None,
+                })
+        }
+
+        /// Every constructor of every subtable, in symbol table order
+        pub fn constructors(&self) -> impl Iterator<Item = &Constructor> {
+            self.subtables()
+                .flat_map(|subtable| subtable.constructors.iter())
+        }
+
+        /// The constructor with id `index` in the subtable whose symbol id is `subtable_id`,
+        /// as referenced by [`OperandValue::table`] and [`OperandValue::constructor_id`]
+        pub fn constructor_by_id(
+            &self,
+            subtable_id: &Integer,
+            index: usize,
+        ) -> Option<&Constructor> {
+            match self.symbol_table.symbol_by_id(subtable_id)? {
+                SleighSymbolType::TripleSymbol(TripleSymbol::SubtableSymbol {
+                    subtable, ..
+                }) => subtable.constructor(index),
+                _ => None,
+            }
+        }
+
+        /// Every constructor, grouped by [`Constructor::parent`]. The key is the id of the
+        /// subtable symbol the constructors belong to, and each group is in symbol table order.
+        pub fn constructors_by_subtable(&self) -> HashMap<Integer, Vec<&Constructor>> {
+            let mut groups = HashMap::<Integer, Vec<&Constructor>>::new();
+            for constructor in self.constructors() {
+                groups
+                    .entry(constructor.parent().0.clone())
+                    .or_default()
+                    .push(constructor);
+            }
+            groups
+        }
+
+        /// Every varnode symbol, i.e. every named register or fixed storage location, in
+        /// symbol table order
+        pub fn varnodes(&self) -> impl Iterator<Item = &VarNodeSymbol> {
+            self.symbol_table
+                .symbols
+                .iter()
+                .filter_map(|symbol| match symbol {
+                    SleighSymbolType::TripleSymbol(TripleSymbol::SpecificSymbol(
+                        SpecificSymbol::PatternlessSymbol(PatternlessSymbol::VarNodeSymbol(
+                            varnode,
+                        )),
+                    )) => Some(varnode),
+                    _ => None,
+                })
+        }
+
+        /// Every context field, e.g. ARM's `TMode`, in symbol table order. Fields are
+        /// located in their context register by [`ContextSymbol::varnode`] and
+        /// [`ContextSymbol::bit_range`].
+        pub fn context_fields(&self) -> Vec<&ContextSymbol> {
+            self.symbol_table
+                .symbols
+                .iter()
+                .filter_map(|symbol| match symbol {
+                    SleighSymbolType::TripleSymbol(TripleSymbol::FamilySymbol(
+                        FamilySymbol::ValueSymbol(ValueSymbolType::ContextSymbol(context)),
+                    )) => Some(context),
+                    _ => None,
+                })
+                .collect()
+        }
+
+        /// Every operand symbol, i.e. every constructor operand, in symbol table order
+        pub fn operand_symbols(&self) -> impl Iterator<Item = &OperandSymbol> {
+            self.symbol_table
+                .symbols
+                .iter()
+                .filter_map(|symbol| match symbol {
+                    SleighSymbolType::TripleSymbol(TripleSymbol::SpecificSymbol(
+                        SpecificSymbol::OperandSymbol(operand),
+                    )) => Some(operand),
+                    _ => None,
+                })
+        }
+
+        /// Every plain value symbol, i.e. every token or context field attached to neither
+        /// values, names nor registers, in symbol table order
+        pub fn value_symbols(&self) -> impl Iterator<Item = &ValueSymbol> {
+            self.symbol_table
+                .symbols
+                .iter()
+                .filter_map(|symbol| match symbol {
+                    SleighSymbolType::TripleSymbol(TripleSymbol::FamilySymbol(
+                        FamilySymbol::ValueSymbol(ValueSymbolType::ValueSymbol(value)),
+                    )) => Some(value),
+                    _ => None,
+                })
+        }
+
+        /// Every context symbol, in symbol table order. This is [`Sleigh::context_fields`]
+        /// without the allocation.
+        pub fn context_symbols(&self) -> impl Iterator<Item = &ContextSymbol> {
+            self.symbol_table
+                .symbols
+                .iter()
+                .filter_map(|symbol| match symbol {
+                    SleighSymbolType::TripleSymbol(TripleSymbol::FamilySymbol(
+                        FamilySymbol::ValueSymbol(ValueSymbolType::ContextSymbol(context)),
+                    )) => Some(context),
+                    _ => None,
+                })
+        }
+
+        /// Every subtable symbol, in symbol table order. This is the same as
+        /// [`Sleigh::subtables`], named to match the other symbol collectors.
+        pub fn subtable_symbols(&self) -> impl Iterator<Item = &SubtableSymbol> {
+            self.subtables()
+        }
+
+        /// The user-defined p-code operations, i.e. the table `CALLOTHER` ops index into, as
+        /// `(name, index)` pairs sorted by index. Indices too large for an `i64` saturate.
+        ///
+        /// Operations are read from their symbol bodies: `<userop_head>` headers carry no
+        /// index, so an operation declared only by its header is not listed.
+        pub fn userops(&self) -> Vec<(&str, i64)> {
+            let mut userops = self
+                .symbol_table
+                .symbols
+                .iter()
+                .filter_map(|symbol| match symbol {
+                    SleighSymbolType::UserOpSymbol { user_op, .. } => Some((
+                        user_op.header().name(),
+                        i64::saturating_from(user_op.index()),
+                    )),
+                    _ => None,
+                })
+                .collect::<Vec<_>>();
+            userops.sort_by_key(|(_, index)| *index);
+            userops
+        }
+
+        /// The varnode symbol named `name`, e.g. `EAX`. Names are case sensitive.
+        pub fn register_by_name(&self, name: &str) -> Option<&VarNodeSymbol> {
+            self.varnodes()
+                .find(|varnode| varnode.header().name() == name)
+        }
+
+        /// The varnode symbol covering `size` bytes at `offset` in `space`, e.g. `AL` for
+        /// `("register", 0x0, 1)` on x86. The smallest symbol containing the whole range is
+        /// returned, so an exact match is preferred when there is one, and otherwise e.g. `EAX`
+        /// for the unnamed byte at `0x2`. Symbols only partially overlapping the range are
+        /// never returned.
+        pub fn register_at(
+            &self,
+            space: &str,
+            offset: &Integer,
+            size: &Integer,
+        ) -> Option<&VarNodeSymbol> {
+            let end = offset + size;
+            self.varnodes()
+                .filter(|varnode| {
+                    varnode.space == space
+                        && varnode.offset.0 <= *offset
+                        && end <= &varnode.offset.0 + &varnode.size
+                })
+                .min_by_key(|varnode| &varnode.size)
+        }
+
+        /// Every literal constant (`<const_tpl type="real" .../>`) used in the semantic
+        /// templates of a constructor, paired with the constructor it appears in. Both
+        /// the main template and any named section templates are searched.
+        pub fn constant_usages(
+            &self,
+        ) -> impl Iterator<Item = (&Constructor, &ConstantTemplateType)> {
+            self.constant_templates()
+                .filter(|(_, constant)| matches!(constant, ConstantTemplateType::Real { .. }))
+        }
+
+        /// Every constant of any type used in the semantic templates of a constructor,
+        /// paired with the constructor it appears in
+        pub(crate) fn constant_templates(
+            &self,
+        ) -> impl Iterator<Item = (&Constructor, &ConstantTemplateType)> {
+            self.constructors().flat_map(|constructor| {
+                constructor
+                    .templ
+                    .iter()
+                    .chain(constructor.namedtempl.iter())
+                    .flat_map(ConstructorTemplate::constant_templates)
+                    .map(move |constant| (constructor, constant))
+            })
+        }
+
+        /// The name of every address space referenced by a varnode symbol or by a `spaceid`
+        /// constant in the semantic templates of a constructor or macro. Apart from `const`,
+        /// which every specification has but none declares, names missing from
+        /// [`Spaces::by_name`] are references to undeclared spaces.
+        pub fn referenced_space_names(&self) -> BTreeSet<String> {
+            let varnodes = self.varnodes().map(|varnode| varnode.space.as_str());
+            let macros = self
+                .symbol_table
+                .symbols
+                .iter()
+                .filter_map(|symbol| match symbol {
+                    SleighSymbolType::MacroSymbol(symbol) => symbol.templ(),
+                    _ => None,
+                });
+            let templates = self
+                .constructors()
+                .flat_map(|constructor| constructor.templ.iter().chain(&constructor.namedtempl))
+                .chain(macros)
+                .flat_map(ConstructorTemplate::constant_templates)
+                .filter_map(|constant| match constant {
+                    ConstantTemplateType::SpaceId { name, .. } => Some(name.as_str()),
+                    _ => None,
+                });
+            varnodes.chain(templates).map(str::to_string).collect()
+        }
+
+        /// Every operand whose defining symbol is the subtable with id `subtable_id`, paired
+        /// with the constructor the operand belongs to. Empty if `subtable_id` is not the id
+        /// of a subtable.
+        pub fn operands_using_subtable(
+            &self,
+            subtable_id: &Integer,
+        ) -> Vec<(&Constructor, &OperandSymbol)> {
+            if !matches!(
+                self.symbol_table.symbol_by_id(subtable_id),
+                Some(SleighSymbolType::TripleSymbol(
+                    TripleSymbol::SubtableSymbol { .. }
+                ))
+            ) {
+                return Vec::new();
+            }
+            let operands = self
+                .symbol_table
+                .symbols
+                .iter()
+                .filter_map(|symbol| match symbol {
+                    SleighSymbolType::TripleSymbol(TripleSymbol::SpecificSymbol(
+                        SpecificSymbol::OperandSymbol(operand),
+                    )) if operand.subsym.as_deref() == Some(subtable_id) => {
+                        Some((&operand.header.id, operand))
+                    }
+                    _ => None,
+                })
+                .collect::<HashMap<_, _>>();
+
+            self.constructors()
+                .flat_map(|constructor| {
+                    constructor
+                        .operands
+                        .iter()
+                        .filter_map(|operand| operands.get(&operand.id))
+                        .map(move |operand| (constructor, *operand))
+                })
+                .collect()
+        }
+    }
+
+    #[derive(TypedBuilder, Debug, Clone, PartialEq, Default)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct SourceFiles {
         #[rust_sitter::leaf(pattern = r#"<\s*sourcefiles\s*>"#)]
         #[builder(default, setter(skip))]
+        #[cfg_attr(feature = "serde", serde(skip))]
         _start: (),
         #[builder(default)]
         source_files: Vec<SourceFile>,
         #[rust_sitter::leaf(pattern = r#"<\s*/\s*sourcefiles\s*>"#)]
         #[builder(default, setter(skip))]
+        #[cfg_attr(feature = "serde", serde(skip))]
         _end: (),
     }
 
-    impl SourceFile {
-        const NAME_REGEX: OnceCell<Regex> = OnceCell::new();
-        const INDEX_REGEX: OnceCell<Regex> = OnceCell::new();
+    impl SourceFiles {
+        /// The source files, in index order
+        pub fn source_files(&self) -> &[SourceFile] {
+            &self.source_files
+        }
     }
 
-    #[derive(TypedBuilder, Debug, PartialEq)]
+    static SOURCE_FILE_NAME_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(r#"name\s*=\s*(?:"([^"]+)"|'([^']+)')"#).expect("Invalid regular expression")
+    });
+    static SOURCE_FILE_INDEX_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(r#"index\s*=\s*(?:"([-+]?[0-9][0-9.eE+-]*)"|'([-+]?[0-9][0-9.eE+-]*)')"#)
+            .expect("Invalid regular expression")
+    });
+
+    #[derive(TypedBuilder, Debug, Clone, PartialEq, Eq, Hash)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct SourceFile {
         #[rust_sitter::leaf(pattern = r#"<\s*sourcefile"#)]
         #[builder(default, setter(skip))]
+        #[cfg_attr(feature = "serde", serde(skip))]
         _start: (),
         #[rust_sitter::leaf(
-            pattern = r#"name\s*=\s*"([^"]+)""#,
+            pattern = r#"name\s*=\s*(?:"([^"]+)"|'([^']+)')"#,
             transform = |v| {
-                SourceFile::NAME_REGEX
-                    .get_or_init(|| {
-                        Regex::new(r#"name\s*=\s*"([^"]+)""#).expect("Invalid regular expression")
-                    })
+                SOURCE_FILE_NAME_REGEX
                     .captures(v)
-                    .expect("No captures or no capture group")
-                    .get(1)
-                    .expect("No capture group")
-                    .as_str()
+                    .leaf_capture("SourceFile::name", 1)
                     .to_string()
                     .xml_unescape()
             }
         )]
         name: String,
         #[rust_sitter::leaf(
-            pattern = r#"index\s*=\s*"(-?[0-9]+)""#,
+            pattern = r#"index\s*=\s*(?:"([-+]?[0-9][0-9.eE+-]*)"|'([-+]?[0-9][0-9.eE+-]*)')"#,
             transform = |v| {
-                SourceFile::INDEX_REGEX
-                    .get_or_init(|| {
-                        Regex::new(r#"index\s*=\s*"(-?[0-9]+)""#).expect("Invalid regular expression")
-                    })
+                SOURCE_FILE_INDEX_REGEX
                     .captures(v)
-                    .expect("No captures or no capture group")
-                    .get(1)
-                    .expect("No capture group")
-                    .as_str()
-                    .parse()
-                    .expect("Invalid integer")
+                    .leaf_capture("SourceFile::index", 1)
+                    .leaf_integer("SourceFile::index")
             }
         )]
         #[builder(default, setter(transform = |v: impl Into<Integer>| {
             v.into()
         }))]
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_integer"))]
         index: Integer,
         #[rust_sitter::leaf(pattern = r#"/\s*>"#)]
         #[builder(default, setter(skip))]
+        #[cfg_attr(feature = "serde", serde(skip))]
         _end: (),
     }
 
-    impl Spaces {
-        const DEFAULTSPACE_REGEX: OnceCell<Regex> = OnceCell::new();
+    impl SourceFile {
+        /// The file name
+        pub fn name(&self) -> &str {
+            &self.name
+        }
+
+        /// The index constructors use to refer to this file in their `line` attribute
+        pub fn index(&self) -> &Integer {
+            &self.index
+        }
     }
 
-    #[derive(TypedBuilder, Debug, PartialEq)]
+    static SPACES_DEFAULTSPACE_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(r#"defaultspace\s*=\s*(?:"([^"]+)"|'([^']+)')"#)
+            .expect("Invalid regular expression")
+    });
+
+    #[derive(TypedBuilder, Debug, Clone, PartialEq, Default)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct Spaces {
         #[rust_sitter::leaf(pattern = r#"<\s*spaces"#)]
         #[builder(default, setter(skip))]
+        #[cfg_attr(feature = "serde", serde(skip))]
         _start: (),
-        #[rust_sitter::leaf(pattern = r#"defaultspace\s*=\s*"([^"]+)""#, transform = |v| {
-            Spaces::DEFAULTSPACE_REGEX
-                .get_or_init(|| {
-                    Regex::new(r#"defaultspace\s*=\s*"([^"]+)""#).expect("Invalid regular expression")
-                })
+        #[rust_sitter::leaf(pattern = r#"defaultspace\s*=\s*(?:"([^"]+)"|'([^']+)')"#, transform = |v| {
+            SPACES_DEFAULTSPACE_REGEX
                 .captures(v)
-                .expect("No captures or no capture group")
-                .get(1)
-                .expect("No capture group")
-                .as_str()
+                .leaf_capture("Spaces::defaultspace", 1)
                 .to_string()
                 .xml_unescape()
         })]
         defaultspace: String,
         #[rust_sitter::leaf(pattern = r#">"#)]
         #[builder(default, setter(skip))]
+        #[cfg_attr(feature = "serde", serde(skip))]
         _close: (),
         #[builder(default)]
         spaces: Vec<AddrSpaceType>,
         #[rust_sitter::leaf(pattern = r#"<\s*/\s*spaces\s*>"#)]
         #[builder(default, setter(skip))]
+        #[cfg_attr(feature = "serde", serde(skip))]
         _end: (),
     }
 
-    impl AddrSpace {
-        pub const NAME_REGEX: OnceCell<Regex> = OnceCell::new();
-        pub const INDEX_REGEX: OnceCell<Regex> = OnceCell::new();
-        pub const BIGENDIAN_REGEX: OnceCell<Regex> = OnceCell::new();
-        pub const DELAY_REGEX: OnceCell<Regex> = OnceCell::new();
-        pub const DEADCODEDELAY_REGEX: OnceCell<Regex> = OnceCell::new();
-        pub const SIZE_REGEX: OnceCell<Regex> = OnceCell::new();
-        pub const WORDSIZE_REGEX: OnceCell<Regex> = OnceCell::new();
-        pub const PHYSICAL_REGEX: OnceCell<Regex> = OnceCell::new();
+    impl Spaces {
+        /// The name of the default address space
+        pub fn defaultspace(&self) -> &str {
+            &self.defaultspace
+        }
+
+        /// The address spaces, in index order
+        pub fn spaces(&self) -> &[AddrSpaceType] {
+            &self.spaces
+        }
+
+        /// The address space named `name`
+        pub fn by_name(&self, name: &str) -> Option<&AddrSpace> {
+            self.spaces
+                .iter()
+                .map(AddrSpaceType::space)
+                .find(|space| space.name() == name)
+        }
+
+        /// The default address space, named by `defaultspace`
+        pub fn default_space(&self) -> Option<&AddrSpace> {
+            self.by_name(&self.defaultspace)
+        }
     }
 
-    #[derive(TypedBuilder, Debug, PartialEq)]
-    pub struct AddrSpace {
-        #[rust_sitter::leaf(
-            pattern = r#"name\s*=\s*"([^"]+)""#,
-            transform = |v| {
-                AddrSpace::NAME_REGEX
-                    .get_or_init(|| {
-                        Regex::new(r#"name\s*=\s*"([^"]+)""#).expect("Invalid regular expression")
-                    })
-                    .captures(v)
-                    .expect("No captures or no capture group")
-                    .get(1)
-                    .expect("No capture group")
-                    .as_str()
-                    .to_string()
-                    .xml_unescape()
-            }
-        )]
-        name: String,
-        #[rust_sitter::leaf(
-            pattern = r#"index\s*=\s*"(-?[0-9]+)""#,
-            transform = |v| {
-                AddrSpace::INDEX_REGEX
-                    .get_or_init(|| {
-                        Regex::new(r#"index\s*=\s*"(-?[0-9]+)""#).expect("Invalid regular expression")
-                    })
-                    .captures(v)
-                    .expect("No captures or no capture group")
-                    .get(1)
-                    .expect("No capture group")
-                    .as_str()
-                    .parse()
-                    .expect("Invalid integer")
-            }
-        )]
-        #[builder(setter(transform = |v: impl Into<Integer>| {
-            v.into()
-        }))]
-        index: Integer,
-        #[rust_sitter::leaf(
-            pattern = r#"bigendian\s*=\s*"([a-z]+)""#,
-            transform = |v| {
-                AddrSpace::BIGENDIAN_REGEX
-                    .get_or_init(|| {
-                        Regex::new(r#"bigendian\s*=\s*"([a-z]+)""#)
-                            .expect("Invalid regular expression")
-                    })
-                    .captures(v)
-                    .expect("No captures or no capture group")
-                    .get(1)
-                    .expect("No capture group")
-                    .as_str()
-                    .parse()
-                    .expect("Invalid boolean")
-            }
-        )]
-        bigendian: bool,
-        #[rust_sitter::leaf(
-            pattern = r#"delay\s*=\s*"(-?[0-9]+)""#,
-            transform = |v| {
-                AddrSpace::DELAY_REGEX
-                    .get_or_init(|| {
-                        Regex::new(r#"delay\s*=\s*"(-?[0-9]+)""#).expect("Invalid regular expression")
-                    })
-                    .captures(v)
-                    .expect("No captures or no capture group")
-                    .get(1)
-                    .expect("No capture group")
-                    .as_str()
-                    .parse()
-                    .expect("Invalid integer")
-            }
-        )]
-        #[builder(setter(transform = |v: impl Into<Integer>| {
-            v.into()
-        }))]
-        delay: Integer,
-        #[rust_sitter::leaf(
-            pattern = r#"deadcodedelay\s*=\s*"(-?[0-9]+)""#,
-            transform = |v| {
-                AddrSpace::DEADCODEDELAY_REGEX
-                    .get_or_init(|| {
-                        Regex::new(r#"deadcodedelay\s*=\s*"(-?[0-9]+)""#)
-                            .expect("Invalid regular expression")
-                    })
-                    .captures(v)
-                    .expect("No captures or no capture group")
-                    .get(1)
-                    .expect("No capture group")
-                    .as_str()
-                    .parse()
-                    .expect("Invalid integer")
-            }
-        )]
-        #[builder(default, setter(transform = |v: impl Into<Integer>| {
-            Some(v.into())
-        }))]
-        deadcodedelay: Option<Integer>,
-        #[rust_sitter::leaf(
-            pattern = r#"size\s*=\s*"(-?[0-9]+)""#,
-            transform = |v| {
-                AddrSpace::SIZE_REGEX
-                    .get_or_init(|| {
-                        Regex::new(r#"size\s*=\s*"(-?[0-9]+)""#).expect("Invalid regular expression")
-                    })
-                    .captures(v)
-                    .expect("No captures or no capture group")
-                    .get(1)
-                    .expect("No capture group")
-                    .as_str()
-                    .parse()
-                    .expect("Invalid integer")
-            }
-        )]
-        #[builder(setter(transform = |v: impl Into<Integer>| {
-            v.into()
-        }))]
-        size: Integer,
-        #[rust_sitter::leaf(
-            pattern = r#"wordsize\s*=\s*"(-?[0-9]+)""#,
-            transform = |v| {
-                AddrSpace::WORDSIZE_REGEX
-                    .get_or_init(|| {
-                        Regex::new(r#"wordsize\s*=\s*"(-?[0-9]+)""#)
-                            .expect("Invalid regular expression")
-                    })
-                    .captures(v)
-                    .expect("No captures or no capture group")
-                    .get(1)
-                    .expect("No capture group")
-                    .as_str()
-                    .parse()
-                    .expect("Invalid integer")
-            }
-        )]
-        #[builder(default, setter(transform = |v: impl Into<Integer>| {
-            Some(v.into())
-        }))]
-        wordsize: Option<Integer>,
-        #[rust_sitter::leaf(
-            pattern = r#"physical\s*=\s*"([a-z]+)""#,
-            transform = |v| {
-                AddrSpace::PHYSICAL_REGEX
-                    .get_or_init(|| {
-                        Regex::new(r#"physical\s*=\s*"([a-z]+)""#)
-                            .expect("Invalid regular expression")
-                    })
-                    .captures(v)
-                    .expect("No captures or no capture group")
-                    .get(1)
-                    .expect("No capture group")
-                    .as_str()
-                    .parse()
-                    .expect("Invalid boolean")
-            }
-        )]
-        physical: bool,
+    static ADDR_SPACE_ATTRIBUTE_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(r#"([a-zA-Z_]+)\s*=\s*(?:"([^"]*)"|'([^']*)')"#)
+            .expect("Invalid regular expression")
+    });
+
+    /// The attributes of a space element by name, which may appear in any order
+    fn space_attributes(v: &str) -> HashMap<&str, &str> {
+        ADDR_SPACE_ATTRIBUTE_REGEX
+            .captures_iter(v)
+            .filter_map(|captures| {
+                let value = captures.get(2).or_else(|| captures.get(3))?;
+                Some((captures.get(1)?.as_str(), value.as_str()))
+            })
+            .collect()
     }
 
-    #[derive(Debug, PartialEq)]
+    /// The value of the required space attribute `name`. If it is absent, a
+    /// [`SleighParseError::MissingCapture`] is recorded for `field` at the attributes `v`
+    /// and `""` returned.
+    fn required_attribute<'v>(
+        v: &str,
+        attributes: &HashMap<&str, &'v str>,
+        name: &str,
+        field: &'static str,
+    ) -> &'v str {
+        attributes.get(name).copied().unwrap_or_else(|| {
+            record(
+                SleighParseError::MissingCapture {
+                    field,
+                    location: None,
+                },
+                Some(v),
+            );
+            ""
+        })
+    }
+
+    /// Build an [`AddrSpace`] from the attributes of a space element. Attributes this crate
+    /// does not know about are ignored.
+    pub(crate) fn addr_space_from_attributes(v: &str) -> AddrSpace {
+        addr_space(v, &space_attributes(v))
+    }
+
+    /// Build an [`OverlaySpace`] from the attributes of a `space_overlay` element
+    pub(crate) fn overlay_space_from_attributes(v: &str) -> OverlaySpace {
+        let attributes = space_attributes(v);
+        OverlaySpace {
+            space: addr_space(v, &attributes),
+            base: required_attribute(v, &attributes, "base", "OverlaySpace::base").xml_unescape(),
+        }
+    }
+
+    fn addr_space(v: &str, attributes: &HashMap<&str, &str>) -> AddrSpace {
+        let required =
+            |name: &str, field: &'static str| required_attribute(v, attributes, name, field);
+        AddrSpace {
+            name: required("name", "AddrSpace::name").xml_unescape(),
+            index: required("index", "AddrSpace::index").leaf_integer("AddrSpace::index"),
+            bigendian: attributes
+                .get("bigendian")
+                .is_some_and(|v| v.leaf_bool("AddrSpace::bigendian")),
+            delay: attributes
+                .get("delay")
+                .map(|v| v.leaf_integer("AddrSpace::delay"))
+                .unwrap_or_default(),
+            deadcodedelay: attributes
+                .get("deadcodedelay")
+                .map(|v| v.leaf_integer("AddrSpace::deadcodedelay")),
+            size: required("size", "AddrSpace::size").leaf_integer("AddrSpace::size"),
+            wordsize: attributes
+                .get("wordsize")
+                .map(|v| v.leaf_integer("AddrSpace::wordsize")),
+            physical: attributes
+                .get("physical")
+                .is_some_and(|v| v.leaf_bool("AddrSpace::physical")),
+        }
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    #[non_exhaustive]
     pub enum AddrSpaceType {
         Base {
             #[rust_sitter::leaf(pattern = r#"<\s*space_base"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _start: (),
+            #[rust_sitter::leaf(
+                pattern = r#"([a-zA-Z_]+\s*=\s*(?:"[^"]*"|'[^']*')\s*)+"#,
+                transform = addr_space_from_attributes
+            )]
             space: AddrSpace,
             #[rust_sitter::leaf(pattern = r#"/\s*>"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _end: (),
         },
         Unique {
             #[rust_sitter::leaf(pattern = r#"<\s*space_unique"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _start: (),
+            #[rust_sitter::leaf(
+                pattern = r#"([a-zA-Z_]+\s*=\s*(?:"[^"]*"|'[^']*')\s*)+"#,
+                transform = addr_space_from_attributes
+            )]
             space: AddrSpace,
             #[rust_sitter::leaf(pattern = r#"/\s*>"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _end: (),
         },
         Other {
             #[rust_sitter::leaf(pattern = r#"<\s*space_other"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _start: (),
+            #[rust_sitter::leaf(
+                pattern = r#"([a-zA-Z_]+\s*=\s*(?:"[^"]*"|'[^']*')\s*)+"#,
+                transform = addr_space_from_attributes
+            )]
             space: AddrSpace,
             #[rust_sitter::leaf(pattern = r#"/\s*>"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _end: (),
         },
         Overlay {
             #[rust_sitter::leaf(pattern = r#"<\s*space_overlay"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _start: (),
-            space: AddrSpace,
+            #[rust_sitter::leaf(
+                pattern = r#"([a-zA-Z_]+\s*=\s*(?:"[^"]*"|'[^']*')\s*)+"#,
+                transform = overlay_space_from_attributes
+            )]
+            overlay: OverlaySpace,
             #[rust_sitter::leaf(pattern = r#"/\s*>"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _end: (),
         },
         Space {
             #[rust_sitter::leaf(pattern = r#"<\s*space"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _start: (),
+            #[rust_sitter::leaf(
+                pattern = r#"([a-zA-Z_]+\s*=\s*(?:"[^"]*"|'[^']*')\s*)+"#,
+                transform = addr_space_from_attributes
+            )]
             space: AddrSpace,
             #[rust_sitter::leaf(pattern = r#"/\s*>"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _end: (),
         },
     }
 
-    impl SymbolTable {
-        const SCOPESIZE_REGEX: OnceCell<Regex> = OnceCell::new();
-        const SYMBOLSIZE_REGEX: OnceCell<Regex> = OnceCell::new();
+    impl AddrSpaceType {
+        /// The attributes of the space, whatever its kind
+        pub fn space(&self) -> &AddrSpace {
+            match self {
+                AddrSpaceType::Base { space, .. }
+                | AddrSpaceType::Unique { space, .. }
+                | AddrSpaceType::Other { space, .. }
+                | AddrSpaceType::Space { space, .. } => space,
+                AddrSpaceType::Overlay { overlay, .. } => &overlay.space,
+            }
+        }
+
+        /// The overlay attributes of the space, if it is an overlay (`space_overlay`)
+        pub fn overlay(&self) -> Option<&OverlaySpace> {
+            match self {
+                AddrSpaceType::Overlay { overlay, .. } => Some(overlay),
+                _ => None,
+            }
+        }
+
+        /// Whether this is a virtual space relative to a base register (`space_base`)
+        pub fn is_base(&self) -> bool {
+            matches!(self, AddrSpaceType::Base { .. })
+        }
+
+        /// Whether this is the unique space of temporaries (`space_unique`)
+        pub fn is_unique(&self) -> bool {
+            matches!(self, AddrSpaceType::Unique { .. })
+        }
+
+        /// Whether this is the `OTHER` space of non-addressable data (`space_other`)
+        pub fn is_other(&self) -> bool {
+            matches!(self, AddrSpaceType::Other { .. })
+        }
+
+        /// Whether this is an overlay of another space (`space_overlay`)
+        pub fn is_overlay(&self) -> bool {
+            matches!(self, AddrSpaceType::Overlay { .. })
+        }
+
+        /// Whether this is a plain processor space (`space`), e.g. `ram` or `register`
+        pub fn is_space(&self) -> bool {
+            matches!(self, AddrSpaceType::Space { .. })
+        }
     }
 
-    #[derive(TypedBuilder, Debug, PartialEq)]
+    static SYMBOL_TABLE_SCOPESIZE_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(r#"scopesize\s*=\s*(?:"([-+]?[0-9][0-9.eE+-]*)"|'([-+]?[0-9][0-9.eE+-]*)')"#)
+            .expect("Invalid regular expression")
+    });
+    static SYMBOL_TABLE_SYMBOLSIZE_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(r#"symbolsize\s*=\s*(?:"([-+]?[0-9][0-9.eE+-]*)"|'([-+]?[0-9][0-9.eE+-]*)')"#)
+            .expect("Invalid regular expression")
+    });
+
+    #[derive(TypedBuilder, Debug, Clone, PartialEq, Default)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct SymbolTable {
         #[rust_sitter::leaf(pattern = r#"<\s*symbol_table"#)]
         #[builder(default, setter(skip))]
+        #[cfg_attr(feature = "serde", serde(skip))]
         _start: (),
         #[rust_sitter::leaf(
-            pattern = r#"scopesize\s*=\s*"(-?[0-9]+)""#,
+            pattern = r#"scopesize\s*=\s*(?:"([-+]?[0-9][0-9.eE+-]*)"|'([-+]?[0-9][0-9.eE+-]*)')"#,
             transform = |v| {
-                SymbolTable::SCOPESIZE_REGEX
-                    .get_or_init(|| {
-                        Regex::new(r#"scopesize\s*=\s*"(-?[0-9]+)""#)
-                            .expect("Invalid regular expression")
-                    })
+                SYMBOL_TABLE_SCOPESIZE_REGEX
                     .captures(v)
-                    .expect("No captures or no capture group")
-                    .get(1)
-                    .expect("No capture group")
-                    .as_str()
-                    .parse()
-                    .expect("Invalid integer")
+                    .leaf_capture("SymbolTable::scopesize", 1)
+                    .leaf_integer("SymbolTable::scopesize")
             }
         )]
         #[builder(setter(transform = |v: impl Into<Integer>| {
             v.into()
         }))]
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_integer"))]
         scopesize: Integer,
         #[rust_sitter::leaf(
-            pattern = r#"symbolsize\s*=\s*"(-?[0-9]+)""#,
+            pattern = r#"symbolsize\s*=\s*(?:"([-+]?[0-9][0-9.eE+-]*)"|'([-+]?[0-9][0-9.eE+-]*)')"#,
             transform = |v| {
-                SymbolTable::SYMBOLSIZE_REGEX
-                    .get_or_init(|| {
-                        Regex::new(r#"symbolsize\s*=\s*"(-?[0-9]+)""#)
-                            .expect("Invalid regular expression")
-                    })
+                SYMBOL_TABLE_SYMBOLSIZE_REGEX
                     .captures(v)
-                    .expect("No captures or no capture group")
-                    .get(1)
-                    .expect("No capture group")
-                    .as_str()
-                    .parse()
-                    .expect("Invalid integer")
+                    .leaf_capture("SymbolTable::symbolsize", 1)
+                    .leaf_integer("SymbolTable::symbolsize")
             }
         )]
         #[builder(setter(transform = |v: impl Into<Integer>| {
             v.into()
         }))]
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_integer"))]
         symbolsize: Integer,
         #[rust_sitter::leaf(pattern = r#">"#)]
         #[builder(default, setter(skip))]
+        #[cfg_attr(feature = "serde", serde(skip))]
         _close: (),
         #[builder(default)]
         scopes: Vec<Scope>,
@@ -572,582 +1083,930 @@ pub mod grammar {
         symbols: Vec<SleighSymbolType>,
         #[rust_sitter::leaf(pattern = r#"<\s*/\s*symbol_table\s*>"#)]
         #[builder(default, setter(skip))]
+        #[cfg_attr(feature = "serde", serde(skip))]
         _end: (),
+        #[rust_sitter::skip(IdIndex::default())]
+        #[builder(default, setter(skip))]
+        #[cfg_attr(feature = "serde", serde(skip))]
+        header_index: IdIndex,
+        #[rust_sitter::skip(IdIndex::default())]
+        #[builder(default, setter(skip))]
+        #[cfg_attr(feature = "serde", serde(skip))]
+        symbol_index: IdIndex,
     }
 
-    impl Scope {
-        const ID_REGEX: OnceCell<Regex> = OnceCell::new();
-        const PARENT_REGEX: OnceCell<Regex> = OnceCell::new();
+    impl SymbolTable {
+        /// The number of scopes
+        pub fn scopesize(&self) -> &Integer {
+            &self.scopesize
+        }
+
+        /// The number of symbols
+        pub fn symbolsize(&self) -> &Integer {
+            &self.symbolsize
+        }
+
+        /// The scopes, in id order
+        pub fn scopes(&self) -> &[Scope] {
+            &self.scopes
+        }
+
+        /// The symbol headers, which declare every symbol before its body
+        pub fn symbol_headers(&self) -> &[SymbolHeaderType] {
+            &self.symbol_headers
+        }
+
+        /// The symbol bodies, in the same order as the headers
+        pub fn symbols(&self) -> &[SleighSymbolType] {
+            &self.symbols
+        }
+
+        /// The header of the symbol whose [`SymbolHeader::id`] is `id`. The lookup index is
+        /// built on the first call and reused afterwards.
+        pub fn symbol_header_by_id(&self, id: &Integer) -> Option<&SymbolHeaderType> {
+            let position = self.header_index.get(id, || {
+                self.symbol_headers
+                    .iter()
+                    .map(|header| &header.header().id().0)
+            })?;
+            self.symbol_headers.get(position)
+        }
+
+        /// The symbol whose [`SymbolHeader::id`] is `id`. The lookup index is built on the
+        /// first call and reused afterwards.
+        pub fn symbol_by_id(&self, id: &Integer) -> Option<&SleighSymbolType> {
+            let position = self.symbol_index.get(id, || {
+                self.symbols.iter().map(|symbol| &symbol.header().id().0)
+            })?;
+            self.symbols.get(position)
+        }
+
+        /// Add `scope` to the table, keeping [`SymbolTable::scopesize`] equal to the number
+        /// of scopes
+        pub fn push_scope(&mut self, scope: Scope) {
+            self.scopes.push(scope);
+            self.scopesize = Integer::from(self.scopes.len());
+        }
+
+        /// Add a symbol to the table, with its `header` and body `symbol`, keeping
+        /// [`SymbolTable::symbolsize`] equal to the number of headers. The lookup indices are
+        /// rebuilt on their next use.
+        pub fn push_symbol(&mut self, header: SymbolHeaderType, symbol: SleighSymbolType) {
+            self.symbol_headers.push(header);
+            self.symbols.push(symbol);
+            self.symbolsize = Integer::from(self.symbol_headers.len());
+            self.header_index = IdIndex::default();
+            self.symbol_index = IdIndex::default();
+        }
+    }
+
+    /// Consumes the table, yielding the symbol bodies by value in table order
+    impl IntoIterator for SymbolTable {
+        type Item = SleighSymbolType;
+        type IntoIter = std::vec::IntoIter<SleighSymbolType>;
+
+        fn into_iter(self) -> Self::IntoIter {
+            self.symbols.into_iter()
+        }
+    }
+
+    impl<'a> IntoIterator for &'a SymbolTable {
+        type Item = &'a SleighSymbolType;
+        type IntoIter = std::slice::Iter<'a, SleighSymbolType>;
+
+        fn into_iter(self) -> Self::IntoIter {
+            self.symbols.iter()
+        }
     }
 
-    #[derive(TypedBuilder, Debug, PartialEq)]
+    static SCOPE_ID_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(r#"id\s*=\s*(?:"(-?0[xX][0-9a-fA-F]+)"|'(-?0[xX][0-9a-fA-F]+)')"#)
+            .expect("Invalid regular expression")
+    });
+    static SCOPE_PARENT_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(r#"parent\s*=\s*(?:"(-?0[xX][0-9a-fA-F]+)"|'(-?0[xX][0-9a-fA-F]+)')"#)
+            .expect("Invalid regular expression")
+    });
+
+    #[derive(TypedBuilder, Debug, Clone, PartialEq, Eq, Hash)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct Scope {
         #[rust_sitter::leaf(pattern = r#"<\s*scope"#)]
         #[builder(default, setter(skip))]
+        #[cfg_attr(feature = "serde", serde(skip))]
         _start: (),
         #[rust_sitter::leaf(
-            pattern = r#"id\s*=\s*"0x([0-9a-fA-F]+)""#,
+            pattern = r#"id\s*=\s*(?:"(-?0[xX][0-9a-fA-F]+)"|'(-?0[xX][0-9a-fA-F]+)')"#,
             transform = |v| {
-                Integer::from_string_base(16, Scope::ID_REGEX
-                    .get_or_init(|| {
-                        Regex::new(r#"id\s*=\s*"0x([0-9a-fA-F]+)""#)
-                            .expect("Invalid regular expression")
-                    })
+                SCOPE_ID_REGEX
                     .captures(v)
-                    .expect("No captures or no capture group")
-                    .get(1)
-                    .expect("No capture group")
-                    .as_str()).expect("Invalid integer")
+                    .leaf_capture("Scope::id", 1)
+                    .leaf_hex_integer("Scope::id")
             }
         )]
         #[builder(setter(transform = |v: impl Into<Integer>| {
-            v.into()
+            HexInteger(v.into())
         }))]
-        id: Integer,
+        id: HexInteger,
         #[rust_sitter::leaf(
-            pattern = r#"parent\s*=\s*"0x([0-9a-fA-F]+)""#,
+            pattern = r#"parent\s*=\s*(?:"(-?0[xX][0-9a-fA-F]+)"|'(-?0[xX][0-9a-fA-F]+)')"#,
             transform = |v| {
-                Integer::from_string_base(16, Scope::PARENT_REGEX
-                    .get_or_init(|| {
-                        Regex::new(r#"parent\s*=\s*"0x([0-9a-fA-F]+)""#)
-                            .expect("Invalid regular expression")
-                    })
+                SCOPE_PARENT_REGEX
                     .captures(v)
-                    .expect("No captures or no capture group")
-                    .get(1)
-                    .expect("No capture group")
-                    .as_str()).expect("Invalid integer")
+                    .leaf_capture("Scope::parent", 1)
+                    .leaf_hex_integer("Scope::parent")
             }
         )]
         #[builder(setter(transform = |v: impl Into<Integer>| {
-            v.into()
+            HexInteger(v.into())
         }))]
-        parent: Integer,
+        parent: HexInteger,
         #[rust_sitter::leaf(pattern = r#"/\s*>"#)]
         #[builder(default, setter(skip))]
+        #[cfg_attr(feature = "serde", serde(skip))]
         _end: (),
     }
 
-    impl SymbolHeader {
-        const NAME_REGEX: OnceCell<Regex> = OnceCell::new();
-        const ID_REGEX: OnceCell<Regex> = OnceCell::new();
-        const SCOPE_REGEX: OnceCell<Regex> = OnceCell::new();
+    impl Scope {
+        /// The scope id
+        pub fn id(&self) -> &HexInteger {
+            &self.id
+        }
+
+        /// The id of the enclosing scope. The global scope is its own parent.
+        pub fn parent(&self) -> &HexInteger {
+            &self.parent
+        }
     }
 
-    #[derive(TypedBuilder, Debug, PartialEq)]
+    static SYMBOL_HEADER_NAME_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(r#"name\s*=\s*(?:"([^"]+)"|'([^']+)')"#).expect("Invalid regular expression")
+    });
+    static SYMBOL_HEADER_ID_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(r#"id\s*=\s*(?:"(-?0[xX][0-9a-fA-F]+)"|'(-?0[xX][0-9a-fA-F]+)')"#)
+            .expect("Invalid regular expression")
+    });
+    static SYMBOL_HEADER_SCOPE_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(r#"scope\s*=\s*(?:"(-?0[xX][0-9a-fA-F]+)"|'(-?0[xX][0-9a-fA-F]+)')"#)
+            .expect("Invalid regular expression")
+    });
+
+    #[derive(TypedBuilder, Debug, Clone, PartialEq, Eq, Hash)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct SymbolHeader {
         #[rust_sitter::leaf(
-            pattern = r#"name\s*=\s*"([^"]+)""#,
+            pattern = r#"name\s*=\s*(?:"([^"]+)"|'([^']+)')"#,
             transform = |v| {
-                SymbolHeader::NAME_REGEX
-                    .get_or_init(|| {
-                        Regex::new(r#"name\s*=\s*"([^"]+)""#).expect("Invalid regular expression")
-                    })
+                SYMBOL_HEADER_NAME_REGEX
                     .captures(v)
-                    .expect("No captures or no capture group")
-                    .get(1)
-                    .expect("No capture group")
-                    .as_str()
+                    .leaf_capture("SymbolHeader::name", 1)
                     .to_string()
                     .xml_unescape()
             }
         )]
         name: String,
         #[rust_sitter::leaf(
-            pattern = r#"id\s*=\s*"0x([0-9a-fA-F]+)""#,
+            pattern = r#"id\s*=\s*(?:"(-?0[xX][0-9a-fA-F]+)"|'(-?0[xX][0-9a-fA-F]+)')"#,
             transform = |v| {
-                Integer::from_string_base(16, SymbolHeader::ID_REGEX
-                    .get_or_init(|| {
-                        Regex::new(r#"id\s*=\s*"0x([0-9a-fA-F]+)""#)
-                            .expect("Invalid regular expression")
-                    })
+                SYMBOL_HEADER_ID_REGEX
                     .captures(v)
-                    .expect("No captures or no capture group").get(1).expect("No capture group").as_str()).expect("Invalid integer")
+                    .leaf_capture("SymbolHeader::id", 1)
+                    .leaf_hex_integer("SymbolHeader::id")
             }
         )]
         #[builder(setter(transform = |v: impl Into<Integer>| {
-            v.into()
+            HexInteger(v.into())
         }))]
-        id: Integer,
+        id: HexInteger,
         #[rust_sitter::leaf(
-            pattern = r#"scope\s*=\s*"0x([0-9a-fA-F]+)""#,
+            pattern = r#"scope\s*=\s*(?:"(-?0[xX][0-9a-fA-F]+)"|'(-?0[xX][0-9a-fA-F]+)')"#,
             transform = |v| {
-                Integer::from_string_base(16, SymbolHeader::SCOPE_REGEX
-                    .get_or_init(|| {
-                        Regex::new(r#"scope\s*=\s*"0x([0-9a-fA-F]+)""#)
-                            .expect("Invalid regular expression")
-                    })
+                SYMBOL_HEADER_SCOPE_REGEX
                     .captures(v)
-                    .expect("No captures or no capture group").get(1).expect("No capture group").as_str()).expect("Invalid integer")
+                    .leaf_capture("SymbolHeader::scope", 1)
+                    .leaf_hex_integer("SymbolHeader::scope")
             }
         )]
         #[builder(setter(transform = |v: impl Into<Integer>| {
-            v.into()
+            HexInteger(v.into())
         }))]
-        scope: Integer,
+        scope: HexInteger,
     }
 
-    #[derive(Debug, PartialEq)]
+    impl SymbolHeader {
+        /// The symbol name
+        pub fn name(&self) -> &str {
+            &self.name
+        }
+
+        /// The symbol id, unique across the symbol table
+        pub fn id(&self) -> &HexInteger {
+            &self.id
+        }
+
+        /// The id of the scope the symbol is declared in
+        pub fn scope(&self) -> &HexInteger {
+            &self.scope
+        }
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    #[non_exhaustive]
     pub enum SymbolHeaderType {
+        Space {
+            #[rust_sitter::leaf(pattern = r#"<\s*space_sym_head"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
+            _start: (),
+            header: SymbolHeader,
+            #[rust_sitter::leaf(pattern = r#"/\s*>|>\s*<\s*/\s*space_sym_head\s*>"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
+            _end: (),
+        },
+        Token {
+            #[rust_sitter::leaf(pattern = r#"<\s*token_sym_head"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
+            _start: (),
+            header: SymbolHeader,
+            #[rust_sitter::leaf(pattern = r#"/\s*>|>\s*<\s*/\s*token_sym_head\s*>"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
+            _end: (),
+        },
+        Section {
+            #[rust_sitter::leaf(pattern = r#"<\s*section_sym_head"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
+            _start: (),
+            header: SymbolHeader,
+            #[rust_sitter::leaf(pattern = r#"/\s*>|>\s*<\s*/\s*section_sym_head\s*>"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
+            _end: (),
+        },
+        Macro {
+            #[rust_sitter::leaf(pattern = r#"<\s*macro_sym_head"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
+            _start: (),
+            header: SymbolHeader,
+            #[rust_sitter::leaf(pattern = r#"/\s*>|>\s*<\s*/\s*macro_sym_head\s*>"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
+            _end: (),
+        },
+        Label {
+            #[rust_sitter::leaf(pattern = r#"<\s*label_sym_head"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
+            _start: (),
+            header: SymbolHeader,
+            #[rust_sitter::leaf(pattern = r#"/\s*>|>\s*<\s*/\s*label_sym_head\s*>"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
+            _end: (),
+        },
+        BitRange {
+            #[rust_sitter::leaf(pattern = r#"<\s*bitrange_sym_head"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
+            _start: (),
+            header: SymbolHeader,
+            #[rust_sitter::leaf(pattern = r#"/\s*>|>\s*<\s*/\s*bitrange_sym_head\s*>"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
+            _end: (),
+        },
         UserOp {
             #[rust_sitter::leaf(pattern = r#"<\s*userop_head"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _start: (),
             header: SymbolHeader,
-            #[rust_sitter::leaf(pattern = r#"/\s*>"#)]
+            #[rust_sitter::leaf(pattern = r#"/\s*>|>\s*<\s*/\s*userop_head\s*>"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _end: (),
         },
         Epsilon {
             #[rust_sitter::leaf(pattern = r#"<\s*epsilon_sym_head"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _start: (),
             header: SymbolHeader,
-            #[rust_sitter::leaf(pattern = r#"/\s*>"#)]
+            #[rust_sitter::leaf(pattern = r#"/\s*>|>\s*<\s*/\s*epsilon_sym_head\s*>"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _end: (),
         },
         Value {
             #[rust_sitter::leaf(pattern = r#"<\s*value_sym_head"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _start: (),
             header: SymbolHeader,
-            #[rust_sitter::leaf(pattern = r#"/\s*>"#)]
+            #[rust_sitter::leaf(pattern = r#"/\s*>|>\s*<\s*/\s*value_sym_head\s*>"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _end: (),
         },
         ValueMap {
             #[rust_sitter::leaf(pattern = r#"<\s*valuemap_sym_head"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _start: (),
             header: SymbolHeader,
-            #[rust_sitter::leaf(pattern = r#"/\s*>"#)]
+            #[rust_sitter::leaf(pattern = r#"/\s*>|>\s*<\s*/\s*valuemap_sym_head\s*>"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _end: (),
         },
         Name {
             #[rust_sitter::leaf(pattern = r#"<\s*name_sym_head"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _start: (),
             header: SymbolHeader,
-            #[rust_sitter::leaf(pattern = r#"/\s*>"#)]
+            #[rust_sitter::leaf(pattern = r#"/\s*>|>\s*<\s*/\s*name_sym_head\s*>"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _end: (),
         },
         VarNode {
             #[rust_sitter::leaf(pattern = r#"<\s*varnode_sym_head"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _start: (),
             header: SymbolHeader,
-            #[rust_sitter::leaf(pattern = r#"/\s*>"#)]
+            #[rust_sitter::leaf(pattern = r#"/\s*>|>\s*<\s*/\s*varnode_sym_head\s*>"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _end: (),
         },
         Context {
             #[rust_sitter::leaf(pattern = r#"<\s*context_sym_head"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _start: (),
             header: SymbolHeader,
-            #[rust_sitter::leaf(pattern = r#"/\s*>"#)]
+            #[rust_sitter::leaf(pattern = r#"/\s*>|>\s*<\s*/\s*context_sym_head\s*>"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _end: (),
         },
         VarNodeList {
             #[rust_sitter::leaf(pattern = r#"<\s*varlist_sym_head"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _start: (),
             header: SymbolHeader,
-            #[rust_sitter::leaf(pattern = r#"/\s*>"#)]
+            #[rust_sitter::leaf(pattern = r#"/\s*>|>\s*<\s*/\s*varlist_sym_head\s*>"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _end: (),
         },
         Operand {
             #[rust_sitter::leaf(pattern = r#"<\s*operand_sym_head"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _start: (),
             header: SymbolHeader,
-            #[rust_sitter::leaf(pattern = r#"/\s*>"#)]
+            #[rust_sitter::leaf(pattern = r#"/\s*>|>\s*<\s*/\s*operand_sym_head\s*>"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _end: (),
         },
         Start {
             #[rust_sitter::leaf(pattern = r#"<\s*start_sym_head"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _start: (),
             header: SymbolHeader,
-            #[rust_sitter::leaf(pattern = r#"/\s*>"#)]
+            #[rust_sitter::leaf(pattern = r#"/\s*>|>\s*<\s*/\s*start_sym_head\s*>"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _end: (),
         },
         End {
             #[rust_sitter::leaf(pattern = r#"<\s*end_sym_head"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _start: (),
             header: SymbolHeader,
-            #[rust_sitter::leaf(pattern = r#"/\s*>"#)]
+            #[rust_sitter::leaf(pattern = r#"/\s*>|>\s*<\s*/\s*end_sym_head\s*>"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _end: (),
         },
         Next2 {
             #[rust_sitter::leaf(pattern = r#"<\s*next2_sym_head"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _start: (),
             header: SymbolHeader,
-            #[rust_sitter::leaf(pattern = r#"/\s*>"#)]
+            #[rust_sitter::leaf(pattern = r#"/\s*>|>\s*<\s*/\s*next2_sym_head\s*>"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _end: (),
         },
         FlowDest {
             #[rust_sitter::leaf(pattern = r#"<\s*flowdest_sym_head"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _start: (),
             header: SymbolHeader,
-            #[rust_sitter::leaf(pattern = r#"/\s*>"#)]
+            #[rust_sitter::leaf(pattern = r#"/\s*>|>\s*<\s*/\s*flowdest_sym_head\s*>"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _end: (),
         },
         FlowRef {
             #[rust_sitter::leaf(pattern = r#"<\s*flowref_sym_head"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _start: (),
             header: SymbolHeader,
-            #[rust_sitter::leaf(pattern = r#"/\s*>"#)]
+            #[rust_sitter::leaf(pattern = r#"/\s*>|>\s*<\s*/\s*flowref_sym_head\s*>"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _end: (),
         },
         SubTable {
             #[rust_sitter::leaf(pattern = r#"<\s*subtable_sym_head"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _start: (),
             header: SymbolHeader,
-            #[rust_sitter::leaf(pattern = r#"/\s*>"#)]
+            #[rust_sitter::leaf(pattern = r#"/\s*>|>\s*<\s*/\s*subtable_sym_head\s*>"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _close: (),
         },
     }
 
-    #[derive(Debug, PartialEq)]
+    impl SymbolHeaderType {
+        /// The header, whichever kind of symbol this declares
+        pub fn header(&self) -> &SymbolHeader {
+            match self {
+                SymbolHeaderType::Space { header, .. }
+                | SymbolHeaderType::Token { header, .. }
+                | SymbolHeaderType::Section { header, .. }
+                | SymbolHeaderType::Macro { header, .. }
+                | SymbolHeaderType::Label { header, .. }
+                | SymbolHeaderType::BitRange { header, .. }
+                | SymbolHeaderType::UserOp { header, .. }
+                | SymbolHeaderType::Epsilon { header, .. }
+                | SymbolHeaderType::Value { header, .. }
+                | SymbolHeaderType::ValueMap { header, .. }
+                | SymbolHeaderType::Name { header, .. }
+                | SymbolHeaderType::VarNode { header, .. }
+                | SymbolHeaderType::Context { header, .. }
+                | SymbolHeaderType::VarNodeList { header, .. }
+                | SymbolHeaderType::Operand { header, .. }
+                | SymbolHeaderType::Start { header, .. }
+                | SymbolHeaderType::End { header, .. }
+                | SymbolHeaderType::Next2 { header, .. }
+                | SymbolHeaderType::FlowDest { header, .. }
+                | SymbolHeaderType::FlowRef { header, .. }
+                | SymbolHeaderType::SubTable { header, .. } => header,
+            }
+        }
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    #[non_exhaustive]
     pub enum PatternExpressionType {
         PatternValue(PatternValueType),
         BinaryExpression(Box<BinaryExpressionType>),
         UnaryExpression(Box<UnaryExpressionType>),
     }
 
-    impl TokenField {
-        const BIGENDIAN_REGEX: OnceCell<Regex> = OnceCell::new();
-        const SIGNBIT_REGEX: OnceCell<Regex> = OnceCell::new();
-        const BITSTART_REGEX: OnceCell<Regex> = OnceCell::new();
-        const BITEND_REGEX: OnceCell<Regex> = OnceCell::new();
-        const BYTESTART_REGEX: OnceCell<Regex> = OnceCell::new();
-        const BYTEEND_REGEX: OnceCell<Regex> = OnceCell::new();
-        const SHIFT_REGEX: OnceCell<Regex> = OnceCell::new();
-    }
+    static TOKEN_FIELD_BIGENDIAN_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(r#"bigendian\s*=\s*(?:"([a-zA-Z0-9]+)"|'([a-zA-Z0-9]+)')"#)
+            .expect("Invalid regular expression")
+    });
+    static TOKEN_FIELD_SIGNBIT_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(r#"signbit\s*=\s*(?:"([a-zA-Z0-9]+)"|'([a-zA-Z0-9]+)')"#)
+            .expect("Invalid regular expression")
+    });
+    static TOKEN_FIELD_BITSTART_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(r#"bitstart\s*=\s*(?:"([-+]?[0-9][0-9.eE+-]*)"|'([-+]?[0-9][0-9.eE+-]*)')"#)
+            .expect("Invalid regular expression")
+    });
+    static TOKEN_FIELD_BITEND_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(r#"bitend\s*=\s*(?:"([-+]?[0-9][0-9.eE+-]*)"|'([-+]?[0-9][0-9.eE+-]*)')"#)
+            .expect("Invalid regular expression")
+    });
+    static TOKEN_FIELD_BYTESTART_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(r#"bytestart\s*=\s*(?:"([-+]?[0-9][0-9.eE+-]*)"|'([-+]?[0-9][0-9.eE+-]*)')"#)
+            .expect("Invalid regular expression")
+    });
+    static TOKEN_FIELD_BYTEEND_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(r#"byteend\s*=\s*(?:"([-+]?[0-9][0-9.eE+-]*)"|'([-+]?[0-9][0-9.eE+-]*)')"#)
+            .expect("Invalid regular expression")
+    });
+    static TOKEN_FIELD_SHIFT_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(r#"shift\s*=\s*(?:"([-+]?[0-9][0-9.eE+-]*)"|'([-+]?[0-9][0-9.eE+-]*)')"#)
+            .expect("Invalid regular expression")
+    });
 
-    #[derive(TypedBuilder, Debug, PartialEq)]
+    #[derive(TypedBuilder, Debug, Clone, PartialEq, Eq, Hash)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct TokenField {
         #[rust_sitter::leaf(pattern = r#"<\s*tokenfield"#)]
         #[builder(default, setter(skip))]
+        #[cfg_attr(feature = "serde", serde(skip))]
         _start: (),
         #[rust_sitter::leaf(
-            pattern = r#"bigendian\s*=\s*"([a-z]+)""#,
+            pattern = r#"bigendian\s*=\s*(?:"([a-zA-Z0-9]+)"|'([a-zA-Z0-9]+)')"#,
             transform = |v| {
-                TokenField::BIGENDIAN_REGEX
-                    .get_or_init(|| {
-                        Regex::new(r#"bigendian\s*=\s*"([a-z]+)""#)
-                            .expect("Invalid regular expression")
-                    })
+                TOKEN_FIELD_BIGENDIAN_REGEX
                     .captures(v)
-                    .expect("No captures or no capture group").get(1).expect("No capture group").as_str().parse().expect("Invalid boolean")
+                    .leaf_capture("TokenField::bigendian", 1)
+                    .leaf_bool("TokenField::bigendian")
             }
         )]
         bigendian: bool,
         #[rust_sitter::leaf(
-            pattern = r#"signbit\s*=\s*"([a-z]+)""#,
+            pattern = r#"signbit\s*=\s*(?:"([a-zA-Z0-9]+)"|'([a-zA-Z0-9]+)')"#,
             transform = |v| {
-                TokenField::SIGNBIT_REGEX
-                    .get_or_init(|| {
-                        Regex::new(r#"signbit\s*=\s*"([a-z]+)""#)
-                            .expect("Invalid regular expression")
-                    })
+                TOKEN_FIELD_SIGNBIT_REGEX
                     .captures(v)
-                    .expect("No captures or no capture group").get(1).expect("No capture group").as_str().parse().expect("Invalid boolean")
+                    .leaf_capture("TokenField::signbit", 1)
+                    .leaf_bool("TokenField::signbit")
             }
         )]
         signbit: bool,
         #[rust_sitter::leaf(
-            pattern = r#"bitstart\s*=\s*"(-?[0-9]+)""#,
+            pattern = r#"bitstart\s*=\s*(?:"([-+]?[0-9][0-9.eE+-]*)"|'([-+]?[0-9][0-9.eE+-]*)')"#,
             transform = |v| {
-                TokenField::BITSTART_REGEX
-                    .get_or_init(|| {
-                        Regex::new(r#"bitstart\s*=\s*"(-?[0-9]+)""#)
-                            .expect("Invalid regular expression")
-                    })
+                TOKEN_FIELD_BITSTART_REGEX
                     .captures(v)
-                    .expect("No captures or no capture group").get(1).expect("No capture group").as_str().parse().expect("Invalid integer")
+                    .leaf_capture("TokenField::bitstart", 1)
+                    .leaf_integer("TokenField::bitstart")
             }
         )]
         #[builder(setter(transform = |v: impl Into<Integer>| {
             v.into()
         }))]
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_integer"))]
         bitstart: Integer,
         #[rust_sitter::leaf(
-            pattern = r#"bitend\s*=\s*"(-?[0-9]+)""#,
+            pattern = r#"bitend\s*=\s*(?:"([-+]?[0-9][0-9.eE+-]*)"|'([-+]?[0-9][0-9.eE+-]*)')"#,
             transform = |v| {
-                TokenField::BITEND_REGEX
-                    .get_or_init(|| {
-                        Regex::new(r#"bitend\s*=\s*"(-?[0-9]+)""#)
-                            .expect("Invalid regular expression")
-                    })
+                TOKEN_FIELD_BITEND_REGEX
                     .captures(v)
-                    .expect("No captures or no capture group").get(1).expect("No capture group").as_str().parse().expect("Invalid integer")
+                    .leaf_capture("TokenField::bitend", 1)
+                    .leaf_integer("TokenField::bitend")
             }
         )]
         #[builder(setter(transform = |v: impl Into<Integer>| {
             v.into()
         }))]
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_integer"))]
         bitend: Integer,
         #[rust_sitter::leaf(
-            pattern = r#"bytestart\s*=\s*"(-?[0-9]+)""#,
+            pattern = r#"bytestart\s*=\s*(?:"([-+]?[0-9][0-9.eE+-]*)"|'([-+]?[0-9][0-9.eE+-]*)')"#,
             transform = |v| {
-                TokenField::BYTESTART_REGEX
-                    .get_or_init(|| {
-                        Regex::new(r#"bytestart\s*=\s*"(-?[0-9]+)""#)
-                            .expect("Invalid regular expression")
-                    })
+                TOKEN_FIELD_BYTESTART_REGEX
                     .captures(v)
-                    .expect("No captures or no capture group").get(1).expect("No capture group").as_str().parse().expect("Invalid integer")
+                    .leaf_capture("TokenField::bytestart", 1)
+                    .leaf_integer("TokenField::bytestart")
             }
         )]
         #[builder(setter(transform = |v: impl Into<Integer>| {
             v.into()
         }))]
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_integer"))]
         bytestart: Integer,
         #[rust_sitter::leaf(
-            pattern = r#"byteend\s*=\s*"(-?[0-9]+)""#,
+            pattern = r#"byteend\s*=\s*(?:"([-+]?[0-9][0-9.eE+-]*)"|'([-+]?[0-9][0-9.eE+-]*)')"#,
             transform = |v| {
-                TokenField::BYTEEND_REGEX
-                    .get_or_init(|| {
-                        Regex::new(r#"byteend\s*=\s*"(-?[0-9]+)""#)
-                            .expect("Invalid regular expression")
-                    })
+                TOKEN_FIELD_BYTEEND_REGEX
                     .captures(v)
-                    .expect("No captures or no capture group").get(1).expect("No capture group").as_str().parse().expect("Invalid integer")
+                    .leaf_capture("TokenField::byteend", 1)
+                    .leaf_integer("TokenField::byteend")
             }
         )]
         #[builder(setter(transform = |v: impl Into<Integer>| {
             v.into()
         }))]
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_integer"))]
         byteend: Integer,
         #[rust_sitter::leaf(
-            pattern = r#"shift\s*=\s*"(-?[0-9]+)""#,
+            pattern = r#"shift\s*=\s*(?:"([-+]?[0-9][0-9.eE+-]*)"|'([-+]?[0-9][0-9.eE+-]*)')"#,
             transform = |v| {
-                TokenField::SHIFT_REGEX
-                    .get_or_init(|| {
-                        Regex::new(r#"shift\s*=\s*"(-?[0-9]+)""#).expect("Invalid regular expression")
-                    })
+                TOKEN_FIELD_SHIFT_REGEX
                     .captures(v)
-                    .expect("No captures or no capture group").get(1).expect("No capture group").as_str().parse().expect("Invalid integer")
+                    .leaf_capture("TokenField::shift", 1)
+                    .leaf_integer("TokenField::shift")
             }
         )]
         #[builder(setter(transform = |v: impl Into<Integer>| {
             v.into()
         }))]
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_integer"))]
         shift: Integer,
         #[rust_sitter::leaf(pattern = r#"/\s*>"#)]
         #[builder(default, setter(skip))]
+        #[cfg_attr(feature = "serde", serde(skip))]
         _end: (),
     }
 
-    impl ContextField {
-        const SIGNBIT_REGEX: OnceCell<Regex> = OnceCell::new();
-        const STARTBIT_REGEX: OnceCell<Regex> = OnceCell::new();
-        const ENDBIT_REGEX: OnceCell<Regex> = OnceCell::new();
-        const STARTBYTE_REGEX: OnceCell<Regex> = OnceCell::new();
-        const ENDBYTE_REGEX: OnceCell<Regex> = OnceCell::new();
-        const SHIFT_REGEX: OnceCell<Regex> = OnceCell::new();
+    impl TokenField {
+        /// Whether the token is read big endian
+        pub fn bigendian(&self) -> bool {
+            self.bigendian
+        }
+
+        /// Whether the field is sign extended
+        pub fn signbit(&self) -> bool {
+            self.signbit
+        }
+
+        /// The least significant bit of the field within the token
+        pub fn bitstart(&self) -> &Integer {
+            &self.bitstart
+        }
+
+        /// The most significant bit of the field within the token
+        pub fn bitend(&self) -> &Integer {
+            &self.bitend
+        }
+
+        /// The first byte of the token covered by the field
+        pub fn bytestart(&self) -> &Integer {
+            &self.bytestart
+        }
+
+        /// The last byte of the token covered by the field
+        pub fn byteend(&self) -> &Integer {
+            &self.byteend
+        }
+
+        /// The right shift applied after extracting the covered bytes
+        pub fn shift(&self) -> &Integer {
+            &self.shift
+        }
     }
 
-    #[derive(TypedBuilder, Debug, PartialEq)]
+    static CONTEXT_FIELD_SIGNBIT_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(r#"signbit\s*=\s*(?:"([a-zA-Z0-9]+)"|'([a-zA-Z0-9]+)')"#)
+            .expect("Invalid regular expression")
+    });
+    static CONTEXT_FIELD_STARTBIT_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(r#"startbit\s*=\s*(?:"([-+]?[0-9][0-9.eE+-]*)"|'([-+]?[0-9][0-9.eE+-]*)')"#)
+            .expect("Invalid regular expression")
+    });
+    static CONTEXT_FIELD_ENDBIT_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(r#"endbit\s*=\s*(?:"([-+]?[0-9][0-9.eE+-]*)"|'([-+]?[0-9][0-9.eE+-]*)')"#)
+            .expect("Invalid regular expression")
+    });
+    static CONTEXT_FIELD_STARTBYTE_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(r#"startbyte\s*=\s*(?:"([-+]?[0-9][0-9.eE+-]*)"|'([-+]?[0-9][0-9.eE+-]*)')"#)
+            .expect("Invalid regular expression")
+    });
+    static CONTEXT_FIELD_ENDBYTE_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(r#"endbyte\s*=\s*(?:"([-+]?[0-9][0-9.eE+-]*)"|'([-+]?[0-9][0-9.eE+-]*)')"#)
+            .expect("Invalid regular expression")
+    });
+    static CONTEXT_FIELD_SHIFT_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(r#"shift\s*=\s*(?:"([-+]?[0-9][0-9.eE+-]*)"|'([-+]?[0-9][0-9.eE+-]*)')"#)
+            .expect("Invalid regular expression")
+    });
+
+    #[derive(TypedBuilder, Debug, Clone, PartialEq, Eq, Hash)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct ContextField {
         #[rust_sitter::leaf(pattern = r#"<\s*contextfield"#)]
         #[builder(default, setter(skip))]
+        #[cfg_attr(feature = "serde", serde(skip))]
         _start: (),
         #[rust_sitter::leaf(
-            pattern = r#"signbit\s*=\s*"([a-z]+)""#,
+            pattern = r#"signbit\s*=\s*(?:"([a-zA-Z0-9]+)"|'([a-zA-Z0-9]+)')"#,
             transform = |v| {
-                ContextField::SIGNBIT_REGEX
-                    .get_or_init(|| {
-                        Regex::new(r#"signbit\s*=\s*"([a-z]+)""#)
-                            .expect("Invalid regular expression")
-                    })
+                CONTEXT_FIELD_SIGNBIT_REGEX
                     .captures(v)
-                    .expect("No captures or no capture group")
-                    .get(1)
-                    .expect("No capture group").as_str().parse().expect("Invalid boolean")
+                    .leaf_capture("ContextField::signbit", 1)
+                    .leaf_bool("ContextField::signbit")
             }
         )]
         signbit: bool,
         #[rust_sitter::leaf(
-            pattern = r#"startbit\s*=\s*"(-?[0-9]+)""#,
+            pattern = r#"startbit\s*=\s*(?:"([-+]?[0-9][0-9.eE+-]*)"|'([-+]?[0-9][0-9.eE+-]*)')"#,
             transform = |v| {
-                ContextField::STARTBIT_REGEX
-                    .get_or_init(|| {
-                        Regex::new(r#"startbit\s*=\s*"(-?[0-9]+)""#)
-                            .expect("Invalid regular expression")
-                    })
+                CONTEXT_FIELD_STARTBIT_REGEX
                     .captures(v)
-                    .expect("No captures or no capture group")
-                    .get(1)
-                    .expect("No capture group").as_str().parse().expect("Invalid integer")
+                    .leaf_capture("ContextField::startbit", 1)
+                    .leaf_integer("ContextField::startbit")
             }
         )]
         #[builder(setter(transform = |v: impl Into<Integer>| {
             v.into()
         }))]
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_integer"))]
         startbit: Integer,
         #[rust_sitter::leaf(
-            pattern = r#"endbit\s*=\s*"(-?[0-9]+)""#,
+            pattern = r#"endbit\s*=\s*(?:"([-+]?[0-9][0-9.eE+-]*)"|'([-+]?[0-9][0-9.eE+-]*)')"#,
             transform = |v| {
-                ContextField::ENDBIT_REGEX
-                    .get_or_init(|| {
-                        Regex::new(r#"endbit\s*=\s*"(-?[0-9]+)""#).expect("Invalid regular expression")
-                    })
+                CONTEXT_FIELD_ENDBIT_REGEX
                     .captures(v)
-                    .expect("No captures or no capture group")
-                    .get(1)
-                    .expect("No capture group").as_str().parse().expect("Invalid integer")
+                    .leaf_capture("ContextField::endbit", 1)
+                    .leaf_integer("ContextField::endbit")
             }
         )]
         #[builder(setter(transform = |v: impl Into<Integer>| {
             v.into()
         }))]
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_integer"))]
         endbit: Integer,
         #[rust_sitter::leaf(
-            pattern = r#"startbyte\s*=\s*"(-?[0-9]+)""#,
+            pattern = r#"startbyte\s*=\s*(?:"([-+]?[0-9][0-9.eE+-]*)"|'([-+]?[0-9][0-9.eE+-]*)')"#,
             transform = |v| {
-                ContextField::STARTBYTE_REGEX
-                    .get_or_init(|| {
-                        Regex::new(r#"startbyte\s*=\s*"(-?[0-9]+)""#)
-                            .expect("Invalid regular expression")
-                    })
+                CONTEXT_FIELD_STARTBYTE_REGEX
                     .captures(v)
-                    .expect("No captures or no capture group").get(1).expect("No capture group").as_str().parse().expect("Invalid integer")
+                    .leaf_capture("ContextField::startbyte", 1)
+                    .leaf_integer("ContextField::startbyte")
             }
         )]
         #[builder(setter(transform = |v: impl Into<Integer>| {
             v.into()
         }))]
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_integer"))]
         startbyte: Integer,
         #[rust_sitter::leaf(
-            pattern = r#"endbyte\s*=\s*"(-?[0-9]+)""#,
+            pattern = r#"endbyte\s*=\s*(?:"([-+]?[0-9][0-9.eE+-]*)"|'([-+]?[0-9][0-9.eE+-]*)')"#,
             transform = |v| {
-                ContextField::ENDBYTE_REGEX
-                    .get_or_init(|| {
-                        Regex::new(r#"endbyte\s*=\s*"(-?[0-9]+)""#)
-                            .expect("Invalid regular expression")
-                    })
+                CONTEXT_FIELD_ENDBYTE_REGEX
                     .captures(v)
-                    .expect("No captures or no capture group").get(1).expect("No capture group").as_str().parse().expect("Invalid integer")
+                    .leaf_capture("ContextField::endbyte", 1)
+                    .leaf_integer("ContextField::endbyte")
             }
         )]
         #[builder(setter(transform = |v: impl Into<Integer>| {
             v.into()
         }))]
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_integer"))]
         endbyte: Integer,
         #[rust_sitter::leaf(
-            pattern = r#"shift\s*=\s*"(-?[0-9]+)""#,
+            pattern = r#"shift\s*=\s*(?:"([-+]?[0-9][0-9.eE+-]*)"|'([-+]?[0-9][0-9.eE+-]*)')"#,
             transform = |v| {
-                ContextField::SHIFT_REGEX
-                    .get_or_init(|| {
-                        Regex::new(r#"shift\s*=\s*"(-?[0-9]+)""#).expect("Invalid regular expression")
-                    })
+                CONTEXT_FIELD_SHIFT_REGEX
                     .captures(v)
-                    .expect("No captures or no capture group").get(1).expect("No capture group").as_str().parse().expect("Invalid integer")
+                    .leaf_capture("ContextField::shift", 1)
+                    .leaf_integer("ContextField::shift")
             }
         )]
         #[builder(setter(transform = |v: impl Into<Integer>| {
             v.into()
         }))]
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_integer"))]
         shift: Integer,
         #[rust_sitter::leaf(pattern = r#"/\s*>"#)]
         #[builder(default, setter(skip))]
+        #[cfg_attr(feature = "serde", serde(skip))]
         _end: (),
     }
 
-    impl ConstantValue {
-        const VAL_REGEX: OnceCell<Regex> = OnceCell::new();
+    impl ContextField {
+        /// Whether the field is sign extended
+        pub fn signbit(&self) -> bool {
+            self.signbit
+        }
+
+        /// The first bit of the field within the context
+        pub fn startbit(&self) -> &Integer {
+            &self.startbit
+        }
+
+        /// The last bit of the field within the context
+        pub fn endbit(&self) -> &Integer {
+            &self.endbit
+        }
+
+        /// The first context byte covered by the field
+        pub fn startbyte(&self) -> &Integer {
+            &self.startbyte
+        }
+
+        /// The last context byte covered by the field
+        pub fn endbyte(&self) -> &Integer {
+            &self.endbyte
+        }
+
+        /// The right shift applied after extracting the covered bytes
+        pub fn shift(&self) -> &Integer {
+            &self.shift
+        }
     }
 
-    #[derive(TypedBuilder, Debug, PartialEq)]
+    static CONSTANT_VALUE_VAL_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(r#"val\s*=\s*(?:"([-+]?[0-9][0-9.eE+-]*)"|'([-+]?[0-9][0-9.eE+-]*)')"#)
+            .expect("Invalid regular expression")
+    });
+
+    #[derive(TypedBuilder, Debug, Clone, PartialEq, Eq, Hash)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct ConstantValue {
         #[rust_sitter::leaf(pattern = r#"<\s*intb"#)]
         #[builder(default, setter(skip))]
+        #[cfg_attr(feature = "serde", serde(skip))]
         _start: (),
         #[rust_sitter::leaf(
-            pattern = r#"val\s*=\s*"(-?[0-9]+)""#,
+            pattern = r#"val\s*=\s*(?:"([-+]?[0-9][0-9.eE+-]*)"|'([-+]?[0-9][0-9.eE+-]*)')"#,
             transform = |v| {
-                ConstantValue::VAL_REGEX
-                    .get_or_init(|| {
-                        Regex::new(r#"val\s*=\s*"(-?[0-9]+)""#).expect("Invalid regular expression")
-                    })
+                CONSTANT_VALUE_VAL_REGEX
                     .captures(v)
-                    .expect("No captures or no capture group")
-                    .get(1)
-                    .expect("No capture group").as_str().parse().expect("Invalid integer")
+                    .leaf_capture("ConstantValue::val", 1)
+                    .leaf_integer("ConstantValue::val")
             }
         )]
         #[builder(setter(transform = |v: impl Into<Integer>| {
             v.into()
         }))]
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_integer"))]
         val: Integer,
         #[rust_sitter::leaf(pattern = r#"/\s*>"#)]
         #[builder(default, setter(skip))]
+        #[cfg_attr(feature = "serde", serde(skip))]
         _end: (),
     }
 
-    impl OperandValue {
-        // DEC:
-        const INDEX_REGEX: OnceCell<Regex> = OnceCell::new();
-        // HEX:
-        const TABLE_REGEX: OnceCell<Regex> = OnceCell::new();
-        /// HEX: Constructor ID
-        const CONSTRUCTOR_ID_REGEX: OnceCell<Regex> = OnceCell::new();
+    impl ConstantValue {
+        /// The constant
+        pub fn val(&self) -> &Integer {
+            &self.val
+        }
     }
 
-    #[derive(TypedBuilder, Debug, PartialEq)]
+    // DEC:
+    static OPERAND_VALUE_INDEX_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(r#"index\s*=\s*(?:"([-+]?[0-9][0-9.eE+-]*)"|'([-+]?[0-9][0-9.eE+-]*)')"#)
+            .expect("Invalid regular expression")
+    });
+    // HEX:
+    static OPERAND_VALUE_TABLE_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(r#"table\s*=\s*(?:"(-?0[xX][0-9a-fA-F]+)"|'(-?0[xX][0-9a-fA-F]+)')"#)
+            .expect("Invalid regular expression")
+    });
+    /// HEX: Constructor ID
+    static OPERAND_VALUE_CONSTRUCTOR_ID_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(r#"ct\s*=\s*(?:"(-?0[xX][0-9a-fA-F]+)"|'(-?0[xX][0-9a-fA-F]+)')"#)
+            .expect("Invalid regular expression")
+    });
+
+    #[derive(TypedBuilder, Debug, Clone, PartialEq, Eq, Hash)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct OperandValue {
         #[rust_sitter::leaf(pattern = r#"<\s*operand_exp"#)]
         #[builder(default, setter(skip))]
+        #[cfg_attr(feature = "serde", serde(skip))]
         _start: (),
         #[rust_sitter::leaf(
-            pattern = r#"index\s*=\s*"(-?[0-9]+)""#,
+            pattern = r#"index\s*=\s*(?:"([-+]?[0-9][0-9.eE+-]*)"|'([-+]?[0-9][0-9.eE+-]*)')"#,
             transform = |v| {
-                OperandValue::INDEX_REGEX
-                    .get_or_init(|| {
-                        Regex::new(r#"index\s*=\s*"(-?[0-9]+)""#).expect("Invalid regular expression")
-                    })
+                OPERAND_VALUE_INDEX_REGEX
                     .captures(v)
-                    .expect("No captures or no capture group")
-                    .get(1)
-                    .expect("No capture group").as_str().parse().expect("Invalid integer")
+                    .leaf_capture("OperandValue::index", 1)
+                    .leaf_integer("OperandValue::index")
             }
         )]
         #[builder(setter(transform = |v: impl Into<Integer>| {
             v.into()
         }))]
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_integer"))]
         index: Integer,
         #[rust_sitter::leaf(
-            pattern = r#"table\s*=\s*"0x([0-9a-fA-F]+)""#,
+            pattern = r#"table\s*=\s*(?:"(-?0[xX][0-9a-fA-F]+)"|'(-?0[xX][0-9a-fA-F]+)')"#,
             transform = |v| {
-                Integer::from_string_base(16, OperandValue::TABLE_REGEX
-                    .get_or_init(|| {
-                        Regex::new(r#"table\s*=\s*"0x([0-9a-fA-F]+)""#)
-                            .expect("Invalid regular expression")
-                    })
+                OPERAND_VALUE_TABLE_REGEX
                     .captures(v)
-                    .expect("No captures or no capture group")
-                    .get(1)
-                    .expect("No capture group").as_str()).expect("Invalid integer")
+                    .leaf_capture("OperandValue::table", 1)
+                    .leaf_hex_integer("OperandValue::table")
             }
         )]
         #[builder(setter(transform = |v: impl Into<Integer>| {
-            v.into()
+            HexInteger(v.into())
         }))]
-        table: Integer,
+        table: HexInteger,
         #[rust_sitter::leaf(
-            pattern = r#"ct\s*=\s*"0x([0-9a-fA-F]+)""#,
+            pattern = r#"ct\s*=\s*(?:"(-?0[xX][0-9a-fA-F]+)"|'(-?0[xX][0-9a-fA-F]+)')"#,
             transform = |v| {
-                Integer::from_string_base(16, OperandValue::CONSTRUCTOR_ID_REGEX
-                    .get_or_init(|| {
-                        Regex::new(r#"ct\s*=\s*"0x([0-9a-fA-F]+)""#)
-                            .expect("Invalid regular expression")
-                    })
+                OPERAND_VALUE_CONSTRUCTOR_ID_REGEX
                     .captures(v)
-                    .expect("No captures or no capture group")
-                    .get(1)
-                    .expect("No capture group").as_str()).expect("Invalid integer")
+                    .leaf_capture("OperandValue::constructor_id", 1)
+                    .leaf_hex_integer("OperandValue::constructor_id")
             }
         )]
         #[builder(setter(transform = |v: impl Into<Integer>| {
-            v.into()
+            HexInteger(v.into())
         }))]
-        constructor_id: Integer,
+        constructor_id: HexInteger,
         #[rust_sitter::leaf(pattern = r#"/\s*>"#)]
         #[builder(default, setter(skip))]
+        #[cfg_attr(feature = "serde", serde(skip))]
         _end: (),
     }
 
-    #[derive(Debug, PartialEq)]
+    impl OperandValue {
+        /// The index of the operand within its constructor
+        pub fn index(&self) -> &Integer {
+            &self.index
+        }
+
+        /// The id of the subtable containing the constructor
+        pub fn table(&self) -> &HexInteger {
+            &self.table
+        }
+
+        /// The id of the constructor within its subtable
+        pub fn constructor_id(&self) -> &HexInteger {
+            &self.constructor_id
+        }
+
+        /// The subtable and constructor the operand belongs to, resolved from [`Self::table`]
+        /// and [`Self::constructor_id`] in `table`. `None` if `table` does not name a
+        /// subtable, or the subtable has no constructor with that id.
+        pub fn resolve<'a>(
+            &self,
+            table: &'a SymbolTable,
+        ) -> Option<(&'a SubtableSymbol, &'a Constructor)> {
+            let subtable = match table.symbol_by_id(&self.table)? {
+                SleighSymbolType::TripleSymbol(TripleSymbol::SubtableSymbol {
+                    subtable, ..
+                }) => subtable,
+                _ => return None,
+            };
+            let constructor = subtable.constructor(usize::try_from(&*self.constructor_id).ok()?)?;
+            Some((subtable, constructor))
+        }
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    #[non_exhaustive]
     /// Class Inheritance goes:
     /// - PatternExpression:
     ///   - PatternValue : PatternExpression
@@ -1167,19 +2026,24 @@ pub mod grammar {
         OperandValue(OperandValue),
         StartInstructionValue {
             #[rust_sitter::leaf(pattern = r#"<\s*start_exp\s*/\s*>"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _tag: (),
         },
         EndInstructionValue {
             #[rust_sitter::leaf(pattern = r#"<\s*end_exp\s*/\s*>"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _tag: (),
         },
         Next2InstructionValue {
             #[rust_sitter::leaf(pattern = r#"<\s*next2_exp\s*/\s*>"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _tag: (),
         },
     }
 
-    #[derive(Debug, PartialEq)]
+    #[derive(Debug, Clone, PartialEq)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    #[non_exhaustive]
     /// Class Inheritance goes:
     /// - PatternExpression:
     ///   - BinaryExpression : PatternExpression
@@ -1197,79 +2061,99 @@ pub mod grammar {
     pub enum BinaryExpressionType {
         Plus {
             #[rust_sitter::leaf(pattern = r#"<\s*plus_exp\s*>"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _start: (),
             left: PatternExpressionType,
             right: PatternExpressionType,
             #[rust_sitter::leaf(pattern = r#"<\s*/\s*plus_exp\s*>"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _end: (),
         },
         Sub {
             #[rust_sitter::leaf(pattern = r#"<\s*sub_exp\s*>"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _start: (),
             left: PatternExpressionType,
             right: PatternExpressionType,
             #[rust_sitter::leaf(pattern = r#"<\s*/\s*sub_exp\s*>"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _end: (),
         },
         Mult {
             #[rust_sitter::leaf(pattern = r#"<\s*mult_exp\s*>"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _start: (),
             left: PatternExpressionType,
             right: PatternExpressionType,
             #[rust_sitter::leaf(pattern = r#"<\s*/\s*mult_exp\s*>"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _end: (),
         },
         LeftShift {
             #[rust_sitter::leaf(pattern = r#"<\s*lshift_exp\s*>"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _start: (),
             left: PatternExpressionType,
             right: PatternExpressionType,
             #[rust_sitter::leaf(pattern = r#"<\s*/\s*lshift_exp\s*>"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _end: (),
         },
         RightShift {
             #[rust_sitter::leaf(pattern = r#"<\s*rshift_exp\s*>"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _start: (),
             left: PatternExpressionType,
             right: PatternExpressionType,
             #[rust_sitter::leaf(pattern = r#"<\s*/\s*rshift_exp\s*>"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _end: (),
         },
         And {
             #[rust_sitter::leaf(pattern = r#"<\s*and_exp\s*>"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _start: (),
             left: PatternExpressionType,
             right: PatternExpressionType,
             #[rust_sitter::leaf(pattern = r#"<\s*/\s*and_exp\s*>"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _end: (),
         },
         Or {
             #[rust_sitter::leaf(pattern = r#"<\s*or_exp\s*>"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _start: (),
             left: PatternExpressionType,
             right: PatternExpressionType,
             #[rust_sitter::leaf(pattern = r#"<\s*/\s*or_exp\s*>"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _end: (),
         },
         Xor {
             #[rust_sitter::leaf(pattern = r#"<\s*xor_exp\s*>"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _start: (),
             left: PatternExpressionType,
             right: PatternExpressionType,
             #[rust_sitter::leaf(pattern = r#"<\s*/\s*xor_exp\s*>"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _end: (),
         },
         Div {
             #[rust_sitter::leaf(pattern = r#"<\s*div_exp\s*>"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _start: (),
             left: PatternExpressionType,
             right: PatternExpressionType,
             #[rust_sitter::leaf(pattern = r#"<\s*/\s*div_exp\s*>"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _end: (),
         },
     }
 
-    #[derive(Debug, PartialEq)]
+    #[derive(Debug, Clone, PartialEq)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    #[non_exhaustive]
     /// Class Inheritance goes:
     /// - PatternExpression:
     ///   - PatternValue : PatternExpression
@@ -1280,539 +2164,1155 @@ pub mod grammar {
     pub enum UnaryExpressionType {
         Minus {
             #[rust_sitter::leaf(pattern = r#"<\s*minus_exp\s*>"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _start: (),
             inner: PatternExpressionType,
             #[rust_sitter::leaf(pattern = r#"<\s*/\s*minus_exp\s*>"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _end: (),
         },
         Not {
             #[rust_sitter::leaf(pattern = r#"<\s*not_exp\s*>"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _start: (),
             inner: PatternExpressionType,
             #[rust_sitter::leaf(pattern = r#"<\s*/\s*not_exp\s*>"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _end: (),
         },
     }
 
-    #[derive(TypedBuilder, Debug, PartialEq)]
+    #[derive(TypedBuilder, Debug, Clone, PartialEq)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct Value {
         header: SymbolHeader,
         #[rust_sitter::leaf(pattern = r#">"#)]
         #[builder(default, setter(skip))]
+        #[cfg_attr(feature = "serde", serde(skip))]
         _close: (),
         patval: PatternValueType,
     }
 
-    impl UserOpSymbol {
-        const INDEX_REGEX: OnceCell<Regex> = OnceCell::new();
-    }
+    static USER_OP_SYMBOL_INDEX_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(r#"index\s*=\s*(?:"([-+]?[0-9][0-9.eE+-]*)"|'([-+]?[0-9][0-9.eE+-]*)')"#)
+            .expect("Invalid regular expression")
+    });
 
-    #[derive(TypedBuilder, Debug, PartialEq)]
+    #[derive(TypedBuilder, Debug, Clone, PartialEq)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct UserOpSymbol {
         header: SymbolHeader,
         #[rust_sitter::leaf(
-            pattern = r#"index\s*=\s*"(-?[0-9]+)""#,
+            pattern = r#"index\s*=\s*(?:"([-+]?[0-9][0-9.eE+-]*)"|'([-+]?[0-9][0-9.eE+-]*)')"#,
             transform = |v| {
-                UserOpSymbol::INDEX_REGEX
-                    .get_or_init(|| {
-                        Regex::new(r#"index\s*=\s*"(-?[0-9]+)""#).expect("Invalid regular expression")
-                    })
+                USER_OP_SYMBOL_INDEX_REGEX
                     .captures(v)
-                    .expect("No captures or no capture group")
-                    .get(1)
-                    .expect("No capture group").as_str().parse().expect("Invalid integer")
+                    .leaf_capture("UserOpSymbol::index", 1)
+                    .leaf_integer("UserOpSymbol::index")
             }
         )]
         #[builder(setter(transform = |v: impl Into<Integer>| {
             v.into()
         }))]
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_integer"))]
         index: Integer,
     }
 
-    #[derive(Debug, PartialEq)]
-    /// Class Inheritance goes:
-    /// - SleighSymbol
-    ///   - SpaceSymbol : SleighSymbol
-    ///   - TokenSymbol : SleighSymbol
-    ///   - SectionSymbol : SleighSymbol
-    ///   - UserOpSymbol : SleighSymbol
-    ///   - TripleSymbol : SleighSymbol
-    ///     - FamilySymbol : TripleSymbol
-    ///       - ValueSymbol : FamilySymbol
-    ///         - ValueMapSymbol : ValueSymbol
-    ///         - NameSymbol : ValueSymbol
-    ///         - ContextSymbol : ValueSymbol
-    ///         - VarNodeListSymbol : ValueSymbol
-    ///     - SpecificSymbol : TripleSymbol
-    ///       - PatternlessSymbol : SpecificSymbol
-    ///         - EpsilonSymbol : PatternlessSymbol
-    ///         - VarNodeSymbol : PatternlessSymbol
-    ///       - OperandSymbol : SpecificSymbol
-    ///       - StartSymbol : SpecificSymbol
-    ///       - EndSymbol : SpecificSymbol
-    ///       - Next2Symbol : SpecificSymbol
-    ///       - FlowDestSymbol : SpecificSymbol
-    ///       - FlowRefSymbol : SpecificSymbol
-    ///     - SubtableSymbol : SpecificSymbol
-    ///   - BitRangeSymbol : SleighSymbol
-    ///   - MacroSymbol : SleighSymbol
-    ///   - LabelSymbol : SleighSymbol
-    pub enum SleighSymbolType {
-        // NOTE: Not saved/restored
-        // SpaceSymbol(SpaceSymbol),
-        // TokenSymbol(TokenSymbol),
-        // SectionSymbol(SectionSymbol),
-        UserOpSymbol {
-            #[rust_sitter::leaf(pattern = r#"<\s*userop"#)]
-            _start: (),
-            user_op: UserOpSymbol,
-            #[rust_sitter::leaf(pattern = r#"/\s*>"#)]
-            _end: (),
-        },
-        TripleSymbol(TripleSymbol),
-        // NOTE: Not saved/restored
-        // BitRangeSymbol(BitRangeSymbol),
-    }
+    impl UserOpSymbol {
+        /// The symbol header
+        pub fn header(&self) -> &SymbolHeader {
+            &self.header
+        }
 
-    impl ConstructorOperand {
-        // HEX:
-        const ID_REGEX: OnceCell<Regex> = OnceCell::new();
+        /// The index `CALLOTHER` ops use to refer to this operation
+        pub fn index(&self) -> &Integer {
+            &self.index
+        }
     }
-    #[derive(TypedBuilder, Debug, PartialEq)]
-    pub struct ConstructorOperand {
-        #[rust_sitter::leaf(pattern = r#"<\s*oper"#)]
+
+    // STRING
+    static SPACE_SYMBOL_SPACE_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(r#"space\s*=\s*(?:"([^"]*)"|'([^']*)')"#).expect("Invalid regular expression")
+    });
+
+    #[derive(TypedBuilder, Debug, Clone, PartialEq)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    pub struct SpaceSymbol {
+        #[rust_sitter::leaf(pattern = r#"<\s*space_sym"#)]
         #[builder(default, setter(skip))]
+        #[cfg_attr(feature = "serde", serde(skip))]
         _start: (),
+        header: SymbolHeader,
         #[rust_sitter::leaf(
-            pattern = r#"id\s*=\s*"0x([0-9a-fA-F]+)""#,
+            pattern = r#"space\s*=\s*(?:"([^"]*)"|'([^']*)')"#,
             transform = |v| {
-                Integer::from_string_base(16, ConstructorOperand::ID_REGEX
-                    .get_or_init(|| {
-                        Regex::new(r#"id\s*=\s*"0x([0-9a-fA-F]+)""#)
-                            .expect("Invalid regular expression")
-                    })
+                SPACE_SYMBOL_SPACE_REGEX
                     .captures(v)
-                    .expect("No captures or no capture group")
-                    .get(1)
-                    .expect("No capture group").as_str()).expect("Invalid integer")
+                    .leaf_capture("SpaceSymbol::space", 1)
+                    .to_string()
+                    .xml_unescape()
             }
         )]
-        #[builder(setter(transform = |v: impl Into<Integer>| {
-            v.into()
-        }))]
-        id: Integer,
-        #[rust_sitter::leaf(pattern = r#"/\s*>"#)]
+        space: String,
+        #[rust_sitter::leaf(pattern = r#"/\s*>|>\s*<\s*/\s*space_sym\s*>"#)]
         #[builder(default, setter(skip))]
+        #[cfg_attr(feature = "serde", serde(skip))]
         _end: (),
     }
 
-    impl OperandPrint {
-        // DEC
-        const ID_REGEX: OnceCell<Regex> = OnceCell::new();
+    impl SpaceSymbol {
+        /// The symbol header
+        pub fn header(&self) -> &SymbolHeader {
+            &self.header
+        }
+
+        /// The name of the address space the symbol refers to
+        pub fn space(&self) -> &str {
+            &self.space
+        }
     }
 
-    #[derive(TypedBuilder, Debug, PartialEq)]
-    pub struct OperandPrint {
-        #[rust_sitter::leaf(pattern = r#"<\s*opprint"#)]
+    // DEC
+    static TOKEN_SYMBOL_SIZE_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(r#"size\s*=\s*(?:"([-+]?[0-9][0-9.eE+-]*)"|'([-+]?[0-9][0-9.eE+-]*)')"#)
+            .expect("Invalid regular expression")
+    });
+    // BOOLEAN
+    static TOKEN_SYMBOL_BIGENDIAN_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(r#"bigendian\s*=\s*(?:"([a-zA-Z0-9]+)"|'([a-zA-Z0-9]+)')"#)
+            .expect("Invalid regular expression")
+    });
+
+    #[derive(TypedBuilder, Debug, Clone, PartialEq)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    pub struct TokenSymbol {
+        #[rust_sitter::leaf(pattern = r#"<\s*token_sym"#)]
         #[builder(default, setter(skip))]
+        #[cfg_attr(feature = "serde", serde(skip))]
         _start: (),
+        header: SymbolHeader,
         #[rust_sitter::leaf(
-            pattern = r#"id\s*=\s*"(-?[0-9]+)""#,
+            pattern = r#"size\s*=\s*(?:"([-+]?[0-9][0-9.eE+-]*)"|'([-+]?[0-9][0-9.eE+-]*)')"#,
             transform = |v| {
-                OperandPrint::ID_REGEX
-                    .get_or_init(|| {
-                        Regex::new(r#"id\s*=\s*"(-?[0-9]+)""#).expect("Invalid regular expression")
-                    })
+                TOKEN_SYMBOL_SIZE_REGEX
                     .captures(v)
-                    .expect("No captures or no capture group")
-                    .get(1)
-                    .expect("No capture group").as_str().parse().expect("Invalid integer")
+                    .leaf_capture("TokenSymbol::size", 1)
+                    .leaf_integer("TokenSymbol::size")
             }
         )]
         #[builder(setter(transform = |v: impl Into<Integer>| {
             v.into()
         }))]
-        id: Integer,
-        #[rust_sitter::leaf(pattern = r#"/\s*>"#)]
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_integer"))]
+        size: Integer,
+        #[rust_sitter::leaf(
+            pattern = r#"bigendian\s*=\s*(?:"([a-zA-Z0-9]+)"|'([a-zA-Z0-9]+)')"#,
+            transform = |v| {
+                TOKEN_SYMBOL_BIGENDIAN_REGEX
+                    .captures(v)
+                    .leaf_capture("TokenSymbol::bigendian", 1)
+                    .leaf_bool("TokenSymbol::bigendian")
+            }
+        )]
+        bigendian: bool,
+        #[rust_sitter::leaf(pattern = r#"/\s*>|>\s*<\s*/\s*token_sym\s*>"#)]
         #[builder(default, setter(skip))]
+        #[cfg_attr(feature = "serde", serde(skip))]
         _end: (),
     }
 
-    impl Print {
-        // ESCAPED STRING
-        const PIECE_REGEX: OnceCell<Regex> = OnceCell::new();
+    impl TokenSymbol {
+        /// The symbol header
+        pub fn header(&self) -> &SymbolHeader {
+            &self.header
+        }
+
+        /// The size of the token in bytes
+        pub fn size(&self) -> &Integer {
+            &self.size
+        }
+
+        /// Whether the token is read big endian
+        pub fn bigendian(&self) -> bool {
+            self.bigendian
+        }
     }
 
-    #[derive(TypedBuilder, Debug, PartialEq)]
-    pub struct Print {
-        #[rust_sitter::leaf(pattern = r#"<\s*print"#)]
+    // DEC
+    static SECTION_SYMBOL_INDEX_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(r#"index\s*=\s*(?:"([-+]?[0-9][0-9.eE+-]*)"|'([-+]?[0-9][0-9.eE+-]*)')"#)
+            .expect("Invalid regular expression")
+    });
+
+    #[derive(TypedBuilder, Debug, Clone, PartialEq)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    pub struct SectionSymbol {
+        #[rust_sitter::leaf(pattern = r#"<\s*section_sym"#)]
         #[builder(default, setter(skip))]
+        #[cfg_attr(feature = "serde", serde(skip))]
         _start: (),
+        header: SymbolHeader,
         #[rust_sitter::leaf(
-            pattern = r#"piece\s*=\s*"([^"]*)""#,
+            pattern = r#"index\s*=\s*(?:"([-+]?[0-9][0-9.eE+-]*)"|'([-+]?[0-9][0-9.eE+-]*)')"#,
             transform = |v| {
-                Print::PIECE_REGEX
-                    .get_or_init(|| {
-                        Regex::new(r#"piece\s*=\s*"([^"]*)""#).expect("Invalid regular expression")
-                    })
+                SECTION_SYMBOL_INDEX_REGEX
                     .captures(v)
-                    .expect("No captures or no capture group")
-                    .get(1)
-                    .expect("No capture group")
-                    .as_str()
-                    .to_string()
-                    .xml_unescape()
+                    .leaf_capture("SectionSymbol::index", 1)
+                    .leaf_integer("SectionSymbol::index")
             }
         )]
-        piece: String,
-        #[rust_sitter::leaf(pattern = r#"/\s*>"#)]
+        #[builder(setter(transform = |v: impl Into<Integer>| {
+            v.into()
+        }))]
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_integer"))]
+        index: Integer,
+        #[rust_sitter::leaf(pattern = r#"/\s*>|>\s*<\s*/\s*section_sym\s*>"#)]
         #[builder(default, setter(skip))]
+        #[cfg_attr(feature = "serde", serde(skip))]
         _end: (),
     }
 
-    #[derive(Debug, PartialEq)]
+    impl SectionSymbol {
+        /// The symbol header
+        pub fn header(&self) -> &SymbolHeader {
+            &self.header
+        }
+
+        /// The index of the named p-code section, matching [`ConstructorTemplate::section`]
+        pub fn index(&self) -> &Integer {
+            &self.index
+        }
+    }
+
+    // DEC
+    static MACRO_SYMBOL_INDEX_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(r#"index\s*=\s*(?:"([-+]?[0-9][0-9.eE+-]*)"|'([-+]?[0-9][0-9.eE+-]*)')"#)
+            .expect("Invalid regular expression")
+    });
+
+    #[derive(TypedBuilder, Debug, Clone, PartialEq)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    pub struct MacroSymbol {
+        #[rust_sitter::leaf(pattern = r#"<\s*macro_sym"#)]
+        #[builder(default, setter(skip))]
+        #[cfg_attr(feature = "serde", serde(skip))]
+        _start: (),
+        header: SymbolHeader,
+        #[rust_sitter::leaf(
+            pattern = r#"index\s*=\s*(?:"([-+]?[0-9][0-9.eE+-]*)"|'([-+]?[0-9][0-9.eE+-]*)')"#,
+            transform = |v| {
+                MACRO_SYMBOL_INDEX_REGEX
+                    .captures(v)
+                    .leaf_capture("MacroSymbol::index", 1)
+                    .leaf_integer("MacroSymbol::index")
+            }
+        )]
+        #[builder(setter(transform = |v: impl Into<Integer>| {
+            v.into()
+        }))]
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_integer"))]
+        index: Integer,
+        #[rust_sitter::leaf(pattern = r#">"#)]
+        #[builder(default, setter(skip))]
+        #[cfg_attr(feature = "serde", serde(skip))]
+        _close: (),
+        operands: Vec<ConstructorOperand>,
+        templ: Option<ConstructorTemplate>,
+        #[rust_sitter::leaf(pattern = r#"<\s*/\s*macro_sym\s*>"#)]
+        #[builder(default, setter(skip))]
+        #[cfg_attr(feature = "serde", serde(skip))]
+        _end: (),
+    }
+
+    impl MacroSymbol {
+        /// The symbol header
+        pub fn header(&self) -> &SymbolHeader {
+            &self.header
+        }
+
+        /// The index of the macro
+        pub fn index(&self) -> &Integer {
+            &self.index
+        }
+
+        /// The operands (macro parameters), in order
+        pub fn operands(&self) -> &[ConstructorOperand] {
+            &self.operands
+        }
+
+        /// The p-code template of the macro body, if any
+        pub fn templ(&self) -> Option<&ConstructorTemplate> {
+            self.templ.as_ref()
+        }
+    }
+
+    // DEC
+    static LABEL_SYMBOL_INDEX_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(r#"index\s*=\s*(?:"([-+]?[0-9][0-9.eE+-]*)"|'([-+]?[0-9][0-9.eE+-]*)')"#)
+            .expect("Invalid regular expression")
+    });
+    // DEC
+    static LABEL_SYMBOL_REFCOUNT_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(r#"refcount\s*=\s*(?:"([-+]?[0-9][0-9.eE+-]*)"|'([-+]?[0-9][0-9.eE+-]*)')"#)
+            .expect("Invalid regular expression")
+    });
+    // BOOLEAN
+    static LABEL_SYMBOL_PLACED_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(r#"placed\s*=\s*(?:"([a-zA-Z0-9]+)"|'([a-zA-Z0-9]+)')"#)
+            .expect("Invalid regular expression")
+    });
+
+    #[derive(TypedBuilder, Debug, Clone, PartialEq)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    pub struct LabelSymbol {
+        #[rust_sitter::leaf(pattern = r#"<\s*label_sym"#)]
+        #[builder(default, setter(skip))]
+        #[cfg_attr(feature = "serde", serde(skip))]
+        _start: (),
+        header: SymbolHeader,
+        #[rust_sitter::leaf(
+            pattern = r#"index\s*=\s*(?:"([-+]?[0-9][0-9.eE+-]*)"|'([-+]?[0-9][0-9.eE+-]*)')"#,
+            transform = |v| {
+                LABEL_SYMBOL_INDEX_REGEX
+                    .captures(v)
+                    .leaf_capture("LabelSymbol::index", 1)
+                    .leaf_integer("LabelSymbol::index")
+            }
+        )]
+        #[builder(setter(transform = |v: impl Into<Integer>| {
+            v.into()
+        }))]
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_integer"))]
+        index: Integer,
+        #[rust_sitter::leaf(
+            pattern = r#"refcount\s*=\s*(?:"([-+]?[0-9][0-9.eE+-]*)"|'([-+]?[0-9][0-9.eE+-]*)')"#,
+            transform = |v| {
+                LABEL_SYMBOL_REFCOUNT_REGEX
+                    .captures(v)
+                    .leaf_capture("LabelSymbol::refcount", 1)
+                    .leaf_integer("LabelSymbol::refcount")
+            }
+        )]
+        #[builder(setter(transform = |v: impl Into<Integer>| {
+            v.into()
+        }))]
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_integer"))]
+        refcount: Integer,
+        #[rust_sitter::leaf(
+            pattern = r#"placed\s*=\s*(?:"([a-zA-Z0-9]+)"|'([a-zA-Z0-9]+)')"#,
+            transform = |v| {
+                LABEL_SYMBOL_PLACED_REGEX
+                    .captures(v)
+                    .leaf_capture("LabelSymbol::placed", 1)
+                    .leaf_bool("LabelSymbol::placed")
+            }
+        )]
+        placed: bool,
+        #[rust_sitter::leaf(pattern = r#"/\s*>|>\s*<\s*/\s*label_sym\s*>"#)]
+        #[builder(default, setter(skip))]
+        #[cfg_attr(feature = "serde", serde(skip))]
+        _end: (),
+    }
+
+    impl LabelSymbol {
+        /// The symbol header
+        pub fn header(&self) -> &SymbolHeader {
+            &self.header
+        }
+
+        /// The index of the label within its p-code section
+        pub fn index(&self) -> &Integer {
+            &self.index
+        }
+
+        /// The number of references to the label
+        pub fn refcount(&self) -> &Integer {
+            &self.refcount
+        }
+
+        /// Whether the label has been placed in the p-code
+        pub fn placed(&self) -> bool {
+            self.placed
+        }
+    }
+
+    // HEX
+    static BIT_RANGE_SYMBOL_VARNODE_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(r#"varnode\s*=\s*(?:"(-?0[xX][0-9a-fA-F]+)"|'(-?0[xX][0-9a-fA-F]+)')"#)
+            .expect("Invalid regular expression")
+    });
+    // DEC
+    static BIT_RANGE_SYMBOL_BITOFFSET_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(r#"bitoffset\s*=\s*(?:"([-+]?[0-9][0-9.eE+-]*)"|'([-+]?[0-9][0-9.eE+-]*)')"#)
+            .expect("Invalid regular expression")
+    });
+    // DEC
+    static BIT_RANGE_SYMBOL_NUMBITS_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(r#"numbits\s*=\s*(?:"([-+]?[0-9][0-9.eE+-]*)"|'([-+]?[0-9][0-9.eE+-]*)')"#)
+            .expect("Invalid regular expression")
+    });
+
+    #[derive(TypedBuilder, Debug, Clone, PartialEq)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    pub struct BitRangeSymbol {
+        #[rust_sitter::leaf(pattern = r#"<\s*bitrange_sym"#)]
+        #[builder(default, setter(skip))]
+        #[cfg_attr(feature = "serde", serde(skip))]
+        _start: (),
+        header: SymbolHeader,
+        #[rust_sitter::leaf(
+            pattern = r#"varnode\s*=\s*(?:"(-?0[xX][0-9a-fA-F]+)"|'(-?0[xX][0-9a-fA-F]+)')"#,
+            transform = |v| {
+                BIT_RANGE_SYMBOL_VARNODE_REGEX
+                    .captures(v)
+                    .leaf_capture("BitRangeSymbol::varnode", 1)
+                    .leaf_hex_integer("BitRangeSymbol::varnode")
+            }
+        )]
+        #[builder(setter(transform = |v: impl Into<Integer>| {
+            HexInteger(v.into())
+        }))]
+        varnode: HexInteger,
+        #[rust_sitter::leaf(
+            pattern = r#"bitoffset\s*=\s*(?:"([-+]?[0-9][0-9.eE+-]*)"|'([-+]?[0-9][0-9.eE+-]*)')"#,
+            transform = |v| {
+                BIT_RANGE_SYMBOL_BITOFFSET_REGEX
+                    .captures(v)
+                    .leaf_capture("BitRangeSymbol::bitoffset", 1)
+                    .leaf_integer("BitRangeSymbol::bitoffset")
+            }
+        )]
+        #[builder(setter(transform = |v: impl Into<Integer>| {
+            v.into()
+        }))]
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_integer"))]
+        bitoffset: Integer,
+        #[rust_sitter::leaf(
+            pattern = r#"numbits\s*=\s*(?:"([-+]?[0-9][0-9.eE+-]*)"|'([-+]?[0-9][0-9.eE+-]*)')"#,
+            transform = |v| {
+                BIT_RANGE_SYMBOL_NUMBITS_REGEX
+                    .captures(v)
+                    .leaf_capture("BitRangeSymbol::numbits", 1)
+                    .leaf_integer("BitRangeSymbol::numbits")
+            }
+        )]
+        #[builder(setter(transform = |v: impl Into<Integer>| {
+            v.into()
+        }))]
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_integer"))]
+        numbits: Integer,
+        #[rust_sitter::leaf(pattern = r#"/\s*>|>\s*<\s*/\s*bitrange_sym\s*>"#)]
+        #[builder(default, setter(skip))]
+        #[cfg_attr(feature = "serde", serde(skip))]
+        _end: (),
+    }
+
+    impl BitRangeSymbol {
+        /// The symbol header
+        pub fn header(&self) -> &SymbolHeader {
+            &self.header
+        }
+
+        /// The symbol id of the varnode the bits are taken from
+        pub fn varnode(&self) -> &HexInteger {
+            &self.varnode
+        }
+
+        /// The least significant bit of the range within the varnode
+        pub fn bitoffset(&self) -> &Integer {
+            &self.bitoffset
+        }
+
+        /// The number of bits in the range
+        pub fn numbits(&self) -> &Integer {
+            &self.numbits
+        }
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    #[non_exhaustive]
+    /// Class Inheritance goes:
+    /// - SleighSymbol
+    ///   - SpaceSymbol : SleighSymbol
+    ///   - TokenSymbol : SleighSymbol
+    ///   - SectionSymbol : SleighSymbol
+    ///   - UserOpSymbol : SleighSymbol
+    ///   - TripleSymbol : SleighSymbol
+    ///     - FamilySymbol : TripleSymbol
+    ///       - ValueSymbol : FamilySymbol
+    ///         - ValueMapSymbol : ValueSymbol
+    ///         - NameSymbol : ValueSymbol
+    ///         - ContextSymbol : ValueSymbol
+    ///         - VarNodeListSymbol : ValueSymbol
+    ///     - SpecificSymbol : TripleSymbol
+    ///       - PatternlessSymbol : SpecificSymbol
+    ///         - EpsilonSymbol : PatternlessSymbol
+    ///         - VarNodeSymbol : PatternlessSymbol
+    ///       - OperandSymbol : SpecificSymbol
+    ///       - StartSymbol : SpecificSymbol
+    ///       - EndSymbol : SpecificSymbol
+    ///       - Next2Symbol : SpecificSymbol
+    ///       - FlowDestSymbol : SpecificSymbol
+    ///       - FlowRefSymbol : SpecificSymbol
+    ///     - SubtableSymbol : SpecificSymbol
+    ///   - BitRangeSymbol : SleighSymbol
+    ///   - MacroSymbol : SleighSymbol
+    ///   - LabelSymbol : SleighSymbol
+    pub enum SleighSymbolType {
+        // NOTE: Ghidra does not save these three, but some older custom builds do
+        SpaceSymbol(SpaceSymbol),
+        TokenSymbol(TokenSymbol),
+        SectionSymbol(SectionSymbol),
+        MacroSymbol(MacroSymbol),
+        LabelSymbol(LabelSymbol),
+        UserOpSymbol {
+            #[rust_sitter::leaf(pattern = r#"<\s*userop"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
+            _start: (),
+            user_op: UserOpSymbol,
+            #[rust_sitter::leaf(pattern = r#"/\s*>|>\s*<\s*/\s*userop\s*>"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
+            _end: (),
+        },
+        TripleSymbol(TripleSymbol),
+        // NOTE: Also not saved by Ghidra, but present in some exported files
+        BitRangeSymbol(BitRangeSymbol),
+    }
+
+    impl SleighSymbolType {
+        /// The name of the symbol
+        pub fn name(&self) -> &str {
+            self.header().name()
+        }
+
+        /// The id of the symbol
+        pub fn id(&self) -> &HexInteger {
+            self.header().id()
+        }
+
+        /// The header of the symbol, whichever kind of symbol it is
+        pub fn header(&self) -> &SymbolHeader {
+            match self {
+                SleighSymbolType::SpaceSymbol(symbol) => symbol.header(),
+                SleighSymbolType::TokenSymbol(symbol) => symbol.header(),
+                SleighSymbolType::SectionSymbol(symbol) => symbol.header(),
+                SleighSymbolType::MacroSymbol(symbol) => symbol.header(),
+                SleighSymbolType::LabelSymbol(symbol) => symbol.header(),
+                SleighSymbolType::BitRangeSymbol(symbol) => symbol.header(),
+                SleighSymbolType::UserOpSymbol { user_op, .. } => user_op.header(),
+                SleighSymbolType::TripleSymbol(triple) => match triple {
+                    TripleSymbol::FamilySymbol(FamilySymbol::ValueSymbol(value)) => match value {
+                        ValueSymbolType::ValueMapSymbol(symbol) => symbol.header(),
+                        ValueSymbolType::NameSymbol(symbol) => symbol.header(),
+                        ValueSymbolType::ContextSymbol(symbol) => symbol.header(),
+                        ValueSymbolType::VarNodeListSymbol(symbol) => symbol.header(),
+                        ValueSymbolType::ValueSymbol(symbol) => symbol.header(),
+                    },
+                    TripleSymbol::SpecificSymbol(specific) => match specific {
+                        SpecificSymbol::PatternlessSymbol(PatternlessSymbol::EpsilonSymbol(
+                            symbol,
+                        )) => symbol.header(),
+                        SpecificSymbol::PatternlessSymbol(PatternlessSymbol::VarNodeSymbol(
+                            symbol,
+                        )) => symbol.header(),
+                        SpecificSymbol::OperandSymbol(symbol) => symbol.header(),
+                        SpecificSymbol::StartSymbol(symbol) => symbol.header(),
+                        SpecificSymbol::EndSymbol(symbol) => symbol.header(),
+                        SpecificSymbol::Next2Symbol(symbol) => symbol.header(),
+                        SpecificSymbol::FlowDestSymbol(symbol) => symbol.header(),
+                        SpecificSymbol::FlowRefSymbol(symbol) => symbol.header(),
+                    },
+                    TripleSymbol::SubtableSymbol { subtable, .. } => subtable.header(),
+                },
+            }
+        }
+    }
+
+    // HEX:
+    static CONSTRUCTOR_OPERAND_ID_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(r#"id\s*=\s*(?:"(-?0[xX][0-9a-fA-F]+)"|'(-?0[xX][0-9a-fA-F]+)')"#)
+            .expect("Invalid regular expression")
+    });
+    #[derive(TypedBuilder, Debug, Clone, PartialEq, Eq, Hash)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    pub struct ConstructorOperand {
+        #[rust_sitter::leaf(pattern = r#"<\s*oper"#)]
+        #[builder(default, setter(skip))]
+        #[cfg_attr(feature = "serde", serde(skip))]
+        _start: (),
+        #[rust_sitter::leaf(
+            pattern = r#"id\s*=\s*(?:"(-?0[xX][0-9a-fA-F]+)"|'(-?0[xX][0-9a-fA-F]+)')"#,
+            transform = |v| {
+                CONSTRUCTOR_OPERAND_ID_REGEX
+                    .captures(v)
+                    .leaf_capture("ConstructorOperand::id", 1)
+                    .leaf_hex_integer("ConstructorOperand::id")
+            }
+        )]
+        #[builder(setter(transform = |v: impl Into<Integer>| {
+            HexInteger(v.into())
+        }))]
+        id: HexInteger,
+        #[rust_sitter::leaf(pattern = r#"/\s*>"#)]
+        #[builder(default, setter(skip))]
+        #[cfg_attr(feature = "serde", serde(skip))]
+        _end: (),
+    }
+
+    impl ConstructorOperand {
+        /// The symbol id of the operand
+        pub fn id(&self) -> &HexInteger {
+            &self.id
+        }
+    }
+
+    // DEC
+    static OPERAND_PRINT_ID_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(r#"id\s*=\s*(?:"([-+]?[0-9][0-9.eE+-]*)"|'([-+]?[0-9][0-9.eE+-]*)')"#)
+            .expect("Invalid regular expression")
+    });
+
+    #[derive(TypedBuilder, Debug, Clone, PartialEq, Eq, Hash)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    pub struct OperandPrint {
+        #[rust_sitter::leaf(pattern = r#"<\s*opprint"#)]
+        #[builder(default, setter(skip))]
+        #[cfg_attr(feature = "serde", serde(skip))]
+        _start: (),
+        #[rust_sitter::leaf(
+            pattern = r#"id\s*=\s*(?:"([-+]?[0-9][0-9.eE+-]*)"|'([-+]?[0-9][0-9.eE+-]*)')"#,
+            transform = |v| {
+                OPERAND_PRINT_ID_REGEX
+                    .captures(v)
+                    .leaf_capture("OperandPrint::id", 1)
+                    .leaf_integer("OperandPrint::id")
+            }
+        )]
+        #[builder(setter(transform = |v: impl Into<Integer>| {
+            v.into()
+        }))]
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_integer"))]
+        id: Integer,
+        #[rust_sitter::leaf(pattern = r#"/\s*>"#)]
+        #[builder(default, setter(skip))]
+        #[cfg_attr(feature = "serde", serde(skip))]
+        _end: (),
+    }
+
+    impl OperandPrint {
+        /// The index of the operand to print
+        pub fn id(&self) -> &Integer {
+            &self.id
+        }
+    }
+
+    // ESCAPED STRING
+    static PRINT_PIECE_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(r#"piece\s*=\s*(?:"([^"]*)"|'([^']*)')"#).expect("Invalid regular expression")
+    });
+
+    #[derive(TypedBuilder, Debug, Clone, PartialEq, Eq, Hash)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    pub struct Print {
+        #[rust_sitter::leaf(pattern = r#"<\s*print"#)]
+        #[builder(default, setter(skip))]
+        #[cfg_attr(feature = "serde", serde(skip))]
+        _start: (),
+        #[rust_sitter::leaf(
+            pattern = r#"piece\s*=\s*(?:"([^"]*)"|'([^']*)')"#,
+            transform = |v| {
+                PRINT_PIECE_REGEX
+                    .captures(v)
+                    .leaf_capture("Print::piece", 1)
+                    .to_string()
+                    .xml_unescape()
+            }
+        )]
+        piece: String,
+        #[rust_sitter::leaf(pattern = r#"/\s*>"#)]
+        #[builder(default, setter(skip))]
+        #[cfg_attr(feature = "serde", serde(skip))]
+        _end: (),
+    }
+
+    impl Print {
+        /// The literal text to print
+        pub fn piece(&self) -> &str {
+            &self.piece
+        }
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    #[non_exhaustive]
     pub enum PrintPieceType {
         Operand(OperandPrint),
         Print(Print),
     }
 
-    impl Operation {
-        // DEC
-        const I_REGEX: OnceCell<Regex> = OnceCell::new();
-        // DEC
-        const SHIFT_REGEX: OnceCell<Regex> = OnceCell::new();
-        // HEX
-        const MASK_REGEX: OnceCell<Regex> = OnceCell::new();
-    }
+    // DEC
+    static OPERATION_I_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(r#"i\s*=\s*(?:"([-+]?[0-9][0-9.eE+-]*)"|'([-+]?[0-9][0-9.eE+-]*)')"#)
+            .expect("Invalid regular expression")
+    });
+    // DEC
+    static OPERATION_SHIFT_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(r#"shift\s*=\s*(?:"([-+]?[0-9][0-9.eE+-]*)"|'([-+]?[0-9][0-9.eE+-]*)')"#)
+            .expect("Invalid regular expression")
+    });
+    // HEX
+    static OPERATION_MASK_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(r#"mask\s*=\s*(?:"(-?0[xX][0-9a-fA-F]+)"|'(-?0[xX][0-9a-fA-F]+)')"#)
+            .expect("Invalid regular expression")
+    });
 
-    #[derive(TypedBuilder, Debug, PartialEq)]
+    #[derive(TypedBuilder, Debug, Clone, PartialEq)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct Operation {
         #[rust_sitter::leaf(pattern = r#"<\s*context_op"#)]
         #[builder(default, setter(skip))]
+        #[cfg_attr(feature = "serde", serde(skip))]
         _start: (),
         #[rust_sitter::leaf(
-            pattern = r#"i\s*=\s*"(-?[0-9]+)""#,
+            pattern = r#"i\s*=\s*(?:"([-+]?[0-9][0-9.eE+-]*)"|'([-+]?[0-9][0-9.eE+-]*)')"#,
             transform = |v| {
-                Operation::I_REGEX
-                    .get_or_init(|| {
-                        Regex::new(r#"i\s*=\s*"(-?[0-9]+)""#).expect("Invalid regular expression")
-                    })
+                OPERATION_I_REGEX
                     .captures(v)
-                    .expect("No captures or no capture group").get(1).expect("No capture group").as_str().parse().expect("Invalid integer")
+                    .leaf_capture("Operation::i", 1)
+                    .leaf_integer("Operation::i")
             }
         )]
         #[builder(setter(transform = |v: impl Into<Integer>| {
             v.into()
         }))]
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_integer"))]
         i: Integer,
         #[rust_sitter::leaf(
-            pattern = r#"shift\s*=\s*"(-?[0-9]+)""#,
+            pattern = r#"shift\s*=\s*(?:"([-+]?[0-9][0-9.eE+-]*)"|'([-+]?[0-9][0-9.eE+-]*)')"#,
             transform = |v| {
-                Operation::SHIFT_REGEX
-                    .get_or_init(|| {
-                        Regex::new(r#"shift\s*=\s*"(-?[0-9]+)""#).expect("Invalid regular expression")
-                    })
+                OPERATION_SHIFT_REGEX
                     .captures(v)
-                    .expect("No captures or no capture group").get(1).expect("No capture group").as_str().parse().expect("Invalid integer")
+                    .leaf_capture("Operation::shift", 1)
+                    .leaf_integer("Operation::shift")
             }
         )]
         #[builder(setter(transform = |v: impl Into<Integer>| {
             v.into()
         }))]
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_integer"))]
         shift: Integer,
         #[rust_sitter::leaf(
-            pattern = r#"mask\s*=\s*"0x([0-9a-fA-F]+)""#,
+            pattern = r#"mask\s*=\s*(?:"(-?0[xX][0-9a-fA-F]+)"|'(-?0[xX][0-9a-fA-F]+)')"#,
             transform = |v| {
-                Integer::from_string_base(16, Operation::MASK_REGEX
-                    .get_or_init(|| {
-                        Regex::new(r#"mask\s*=\s*"0x([0-9a-fA-F]+)""#)
-                            .expect("Invalid regular expression")
-                    })
+                OPERATION_MASK_REGEX
                     .captures(v)
-                    .expect("No captures or no capture group")
-                    .get(1)
-                    .expect("No capture group").as_str()).expect("Invalid integer")
+                    .leaf_capture("Operation::mask", 1)
+                    .leaf_hex_integer("Operation::mask")
             }
         )]
         #[builder(setter(transform = |v: impl Into<Integer>| {
-            v.into()
+            HexInteger(v.into())
         }))]
-        mask: Integer,
+        mask: HexInteger,
         #[rust_sitter::leaf(pattern = r#">"#)]
         #[builder(default, setter(skip))]
+        #[cfg_attr(feature = "serde", serde(skip))]
         _close: (),
         patexp: PatternExpressionType,
         #[rust_sitter::leaf(pattern = r#"<\s*/\s*context_op\s*>"#)]
         #[builder(default, setter(skip))]
+        #[cfg_attr(feature = "serde", serde(skip))]
         _end: (),
     }
 
-    impl Commit {
-        // HEX
-        const ID_REGEX: OnceCell<Regex> = OnceCell::new();
-        // DEC
-        const NUM_REGEX: OnceCell<Regex> = OnceCell::new();
-        // HEX
-        const MASK_REGEX: OnceCell<Regex> = OnceCell::new();
-        // BOOLEAN
-        const FLOW_REGEX: OnceCell<Regex> = OnceCell::new();
+    impl Operation {
+        /// The index of the context word to change
+        pub fn i(&self) -> &Integer {
+            &self.i
+        }
+
+        /// The left shift applied to the value before it is masked in
+        pub fn shift(&self) -> &Integer {
+            &self.shift
+        }
+
+        /// The bits of the context word to change
+        pub fn mask(&self) -> &HexInteger {
+            &self.mask
+        }
+
+        /// The expression computing the new value
+        pub fn patexp(&self) -> &PatternExpressionType {
+            &self.patexp
+        }
     }
 
-    #[derive(TypedBuilder, Debug, PartialEq)]
+    // HEX
+    static COMMIT_ID_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(r#"id\s*=\s*(?:"(-?0[xX][0-9a-fA-F]+)"|'(-?0[xX][0-9a-fA-F]+)')"#)
+            .expect("Invalid regular expression")
+    });
+    // DEC
+    static COMMIT_NUM_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(r#"num\s*=\s*(?:"([-+]?[0-9][0-9.eE+-]*)"|'([-+]?[0-9][0-9.eE+-]*)')"#)
+            .expect("Invalid regular expression")
+    });
+    // HEX
+    static COMMIT_MASK_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(r#"mask\s*=\s*(?:"(-?0[xX][0-9a-fA-F]+)"|'(-?0[xX][0-9a-fA-F]+)')"#)
+            .expect("Invalid regular expression")
+    });
+    // BOOLEAN
+    static COMMIT_FLOW_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(r#"flow\s*=\s*(?:"([a-zA-Z0-9]+)"|'([a-zA-Z0-9]+)')"#)
+            .expect("Invalid regular expression")
+    });
+
+    #[derive(TypedBuilder, Debug, Clone, PartialEq)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct Commit {
         #[rust_sitter::leaf(pattern = r#"<\s*commit"#)]
         #[builder(default, setter(skip))]
+        #[cfg_attr(feature = "serde", serde(skip))]
         _start: (),
         #[rust_sitter::leaf(
-            pattern = r#"id\s*=\s*"0x([0-9a-fA-F]+)""#,
+            pattern = r#"id\s*=\s*(?:"(-?0[xX][0-9a-fA-F]+)"|'(-?0[xX][0-9a-fA-F]+)')"#,
             transform = |v| {
-                Integer::from_string_base(16, Commit::ID_REGEX
-                    .get_or_init(|| {
-                        Regex::new(r#"id\s*=\s*"0x([0-9a-fA-F]+)""#)
-                            .expect("Invalid regular expression")
-                    })
+                COMMIT_ID_REGEX
                     .captures(v)
-                    .expect("No captures or no capture group")
-                    .get(1)
-                    .expect("No capture group").as_str()).expect("Invalid integer")
+                    .leaf_capture("Commit::id", 1)
+                    .leaf_hex_integer("Commit::id")
             }
         )]
         #[builder(setter(transform = |v: impl Into<Integer>| {
-            v.into()
+            HexInteger(v.into())
         }))]
-        id: Integer,
+        id: HexInteger,
         #[rust_sitter::leaf(
-            pattern = r#"num\s*=\s*"(-?[0-9]+)""#,
+            pattern = r#"num\s*=\s*(?:"([-+]?[0-9][0-9.eE+-]*)"|'([-+]?[0-9][0-9.eE+-]*)')"#,
             transform = |v| {
-                Commit::NUM_REGEX
-                    .get_or_init(|| {
-                        Regex::new(r#"num\s*=\s*"(-?[0-9]+)""#).expect("Invalid regular expression")
-                    })
+                COMMIT_NUM_REGEX
                     .captures(v)
-                    .expect("No captures or no capture group").get(1).expect("No capture group").as_str().parse().expect("Invalid integer")
+                    .leaf_capture("Commit::num", 1)
+                    .leaf_integer("Commit::num")
             }
         )]
         #[builder(setter(transform = |v: impl Into<Integer>| {
             v.into()
         }))]
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_integer"))]
         num: Integer,
         #[rust_sitter::leaf(
-            pattern = r#"mask\s*=\s*"0x([0-9a-fA-F]+)""#,
+            pattern = r#"mask\s*=\s*(?:"(-?0[xX][0-9a-fA-F]+)"|'(-?0[xX][0-9a-fA-F]+)')"#,
             transform = |v| {
-                Integer::from_string_base(16, Commit::MASK_REGEX
-                    .get_or_init(|| {
-                        Regex::new(r#"mask\s*=\s*"0x([0-9a-fA-F]+)""#)
-                            .expect("Invalid regular expression")
-                    })
+                COMMIT_MASK_REGEX
                     .captures(v)
-                    .expect("No captures or no capture group")
-                    .get(1)
-                    .expect("No capture group").as_str()).expect("Invalid integer")
+                    .leaf_capture("Commit::mask", 1)
+                    .leaf_hex_integer("Commit::mask")
             }
         )]
         #[builder(setter(transform = |v: impl Into<Integer>| {
-            v.into()
+            HexInteger(v.into())
         }))]
-        mask: Integer,
+        mask: HexInteger,
         #[rust_sitter::leaf(
-            pattern = r#"flow\s*=\s*"([a-z]+)""#,
+            pattern = r#"flow\s*=\s*(?:"([a-zA-Z0-9]+)"|'([a-zA-Z0-9]+)')"#,
             transform = |v| {
-                Commit::FLOW_REGEX
-                    .get_or_init(|| {
-                        Regex::new(r#"flow\s*=\s*"([a-z]+)""#).expect("Invalid regular expression")
-                    })
+                COMMIT_FLOW_REGEX
                     .captures(v)
-                    .expect("No captures or no capture group").get(1).expect("No capture group").as_str().parse().expect("Invalid boolean")
+                    .leaf_capture("Commit::flow", 1)
+                    .leaf_bool("Commit::flow")
             }
         )]
         flow: bool,
         #[rust_sitter::leaf(pattern = r#"/\s*>"#)]
         #[builder(default, setter(skip))]
+        #[cfg_attr(feature = "serde", serde(skip))]
         _end: (),
     }
 
-    #[derive(Debug, PartialEq)]
+    impl Commit {
+        /// The id of the context symbol to commit
+        pub fn id(&self) -> &HexInteger {
+            &self.id
+        }
+
+        /// The index of the context word to commit
+        pub fn num(&self) -> &Integer {
+            &self.num
+        }
+
+        /// The bits of the context word to commit
+        pub fn mask(&self) -> &HexInteger {
+            &self.mask
+        }
+
+        /// Whether the committed value flows to following instructions
+        pub fn flow(&self) -> bool {
+            self.flow
+        }
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    #[non_exhaustive]
     pub enum ContextChangeType {
         Operation(Operation),
         Commit(Commit),
     }
 
-    #[derive(Debug, PartialEq)]
+    #[derive(Debug, Clone, PartialEq)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    #[non_exhaustive]
     pub enum ConstantTemplateSelector {
         Space {
-            #[rust_sitter::leaf(pattern = r#"s\s*=\s*"space""#)]
+            #[rust_sitter::leaf(pattern = r#"s\s*=\s*(?:"space"|'space')"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _space: (),
         },
         Offset {
-            #[rust_sitter::leaf(pattern = r#"s\s*=\s*"offset""#)]
+            #[rust_sitter::leaf(pattern = r#"s\s*=\s*(?:"offset"|'offset')"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _offset: (),
         },
         Size {
-            #[rust_sitter::leaf(pattern = r#"s\s*=\s*"size""#)]
+            #[rust_sitter::leaf(pattern = r#"s\s*=\s*(?:"size"|'size')"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _size: (),
         },
         OffsetPlus {
-            #[rust_sitter::leaf(pattern = r#"s\s*=\s*"offset_plus""#)]
+            #[rust_sitter::leaf(pattern = r#"s\s*=\s*(?:"offset_plus"|'offset_plus')"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _offset_plus: (),
         },
     }
 
-    impl ConstantTemplateType {
-        // DEC or HEX depending on type
-        const DEC_VAL_REGEX: OnceCell<Regex> = OnceCell::new();
-        const HEX_VAL_REGEX: OnceCell<Regex> = OnceCell::new();
-        // HEX
-        const PLUS_REGEX: OnceCell<Regex> = OnceCell::new();
-        // STRING
-        const NAME_REGEX: OnceCell<Regex> = OnceCell::new();
-    }
+    // DEC or HEX depending on type
+    static CONSTANT_TEMPLATE_TYPE_DEC_VAL_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(r#"val\s*=\s*(?:"([-+]?[0-9][0-9.eE+-]*)"|'([-+]?[0-9][0-9.eE+-]*)')"#)
+            .expect("Invalid regular expression")
+    });
+    static CONSTANT_TEMPLATE_TYPE_HEX_VAL_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(r#"val\s*=\s*(?:"(-?0[xX][0-9a-fA-F]+)"|'(-?0[xX][0-9a-fA-F]+)')"#)
+            .expect("Invalid regular expression")
+    });
+    // HEX
+    static CONSTANT_TEMPLATE_TYPE_PLUS_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(r#"plus\s*=\s*(?:"(-?0[xX][0-9a-fA-F]+)"|'(-?0[xX][0-9a-fA-F]+)')"#)
+            .expect("Invalid regular expression")
+    });
+    // STRING
+    static CONSTANT_TEMPLATE_TYPE_NAME_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(r#"name\s*=\s*(?:"([^"]*)"|'([^']*)')"#).expect("Invalid regular expression")
+    });
+    // STRING
+    static CONSTANT_TEMPLATE_TYPE_TYPE_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(r#"<\s*const_tpl\s*type\s*=\s*(?:"([^"]*)"|'([^']*)')"#)
+            .expect("Invalid regular expression")
+    });
+    // DEC or HEX
+    static CONSTANT_TEMPLATE_TYPE_ANY_VAL_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(
+            r#"val\s*=\s*(?:"(-?0[xX][0-9a-fA-F]+|-?[0-9]+)"|'(-?0[xX][0-9a-fA-F]+|-?[0-9]+)')"#,
+        )
+        .expect("Invalid regular expression")
+    });
 
-    #[derive(Debug, PartialEq)]
+    #[derive(Debug, Clone, PartialEq)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    #[non_exhaustive]
     pub enum ConstantTemplateType {
         Real {
-            #[rust_sitter::leaf(pattern = r#"<\s*const_tpl\s*type\s*=\s*"real""#)]
+            #[rust_sitter::leaf(pattern = r#"<\s*const_tpl\s*type\s*=\s*(?:"real"|'real')"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _start: (),
             // This one is hex
             #[rust_sitter::leaf(
-                pattern = r#"val\s*=\s*"0x([0-9a-fA-F]+)""#,
+                pattern = r#"val\s*=\s*(?:"(-?0[xX][0-9a-fA-F]+)"|'(-?0[xX][0-9a-fA-F]+)')"#,
                 transform = |v| {
-                    Integer::from_string_base(16, ConstantTemplateType::HEX_VAL_REGEX
-                        .get_or_init(|| {
-                            Regex::new(r#"val\s*=\s*"0x([0-9a-fA-F]+)""#)
-                                .expect("Invalid regular expression")
-                        })
+                    CONSTANT_TEMPLATE_TYPE_HEX_VAL_REGEX
                         .captures(v)
-                        .expect("No captures or no capture group")
-                        .get(1)
-                        .expect("No capture group").as_str()).expect("Invalid integer")
+                        .leaf_capture("ConstantTemplateType::Real::val", 1)
+                        .leaf_hex_integer("ConstantTemplateType::Real::val")
                 }
             )]
-            val: Integer,
+            val: HexInteger,
             #[rust_sitter::leaf(pattern = r#"/\s*>"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _end: (),
         },
         Handle {
-            #[rust_sitter::leaf(pattern = r#"<\s*const_tpl\s*type\s*=\s*"handle""#)]
+            #[rust_sitter::leaf(pattern = r#"<\s*const_tpl\s*type\s*=\s*(?:"handle"|'handle')"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _start: (),
             #[rust_sitter::leaf(
-                pattern = r#"val\s*=\s*"(-?[0-9]+)""#,
+                pattern = r#"val\s*=\s*(?:"([-+]?[0-9][0-9.eE+-]*)"|'([-+]?[0-9][0-9.eE+-]*)')"#,
                 transform = |v| {
-                    ConstantTemplateType::DEC_VAL_REGEX
-                        .get_or_init(|| {
-                            Regex::new(r#"val\s*=\s*"(-?[0-9]+)""#).expect("Invalid regular expression")
-                        })
+                    CONSTANT_TEMPLATE_TYPE_DEC_VAL_REGEX
                         .captures(v)
-                        .expect("No captures or no capture group")
-                        .get(1)
-                        .expect("No capture group").as_str().parse().expect("Invalid integer")
+                        .leaf_capture("ConstantTemplateType::Handle::val", 1)
+                        .leaf_integer("ConstantTemplateType::Handle::val")
                 }
             )]
+            #[cfg_attr(feature = "serde", serde(with = "crate::serde_integer"))]
             val: Integer,
             selector: ConstantTemplateSelector,
             #[rust_sitter::leaf(
-                pattern = r#"plus\s*=\s*"0x([0-9a-fA-F]+)""#,
+                pattern = r#"plus\s*=\s*(?:"(-?0[xX][0-9a-fA-F]+)"|'(-?0[xX][0-9a-fA-F]+)')"#,
                 transform = |v| {
-                    Integer::from_string_base(16, ConstantTemplateType::PLUS_REGEX
-                        .get_or_init(|| {
-                            Regex::new(r#"plus\s*=\s*"0x([0-9a-fA-F]+)""#)
-                                .expect("Invalid regular expression")
-                        })
+                    CONSTANT_TEMPLATE_TYPE_PLUS_REGEX
                         .captures(v)
-                        .expect("No captures or no capture group")
-                        .get(1)
-                        .expect("No capture group").as_str()).expect("Invalid integer")
+                        .leaf_capture("ConstantTemplateType::Handle::plus", 1)
+                        .leaf_hex_integer("ConstantTemplateType::Handle::plus")
                 }
             )]
-            plus: Option<Integer>,
+            plus: Option<HexInteger>,
             #[rust_sitter::leaf(pattern = r#"/\s*>"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _end: (),
         },
         Start {
-            #[rust_sitter::leaf(pattern = r#"<\s*const_tpl\s*type\s*=\s*"start"\s*/\s*>"#)]
+            #[rust_sitter::leaf(
+                pattern = r#"<\s*const_tpl\s*type\s*=\s*(?:"start"|'start')\s*/\s*>"#
+            )]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _start: (),
         },
         End {
-            #[rust_sitter::leaf(pattern = r#"<\s*const_tpl\s*type\s*=\s*"end"\s*/\s*>"#)]
+            #[rust_sitter::leaf(pattern = r#"<\s*const_tpl\s*type\s*=\s*(?:"end"|'end')\s*/\s*>"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _end: (),
         },
         Next {
-            #[rust_sitter::leaf(pattern = r#"<\s*const_tpl\s*type\s*=\s*"next"\s*/\s*>"#)]
-            _next: (),
+            #[rust_sitter::leaf(
+                pattern = r#"<\s*const_tpl\s*type\s*=\s*(?:"next"|'next')\s*/\s*>"#
+            )]
+            #[cfg_attr(feature = "serde", serde(skip))]
+            _next: (),
         },
         Next2 {
-            #[rust_sitter::leaf(pattern = r#"<\s*const_tpl\s*type\s*=\s*"next2"\s*/\s*>"#)]
+            #[rust_sitter::leaf(
+                pattern = r#"<\s*const_tpl\s*type\s*=\s*(?:"next2"|'next2')\s*/\s*>"#
+            )]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _next2: (),
         },
         CurSpace {
-            #[rust_sitter::leaf(pattern = r#"<\s*const_tpl\s*type\s*=\s*"curspace"\s*/\s*>"#)]
+            #[rust_sitter::leaf(
+                pattern = r#"<\s*const_tpl\s*type\s*=\s*(?:"curspace"|'curspace')\s*/\s*>"#
+            )]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _cur_space: (),
         },
         CurSpaceSize {
-            #[rust_sitter::leaf(pattern = r#"<\s*const_tpl\s*type\s*=\s*"curspace_size"\s*/\s*>"#)]
+            #[rust_sitter::leaf(
+                pattern = r#"<\s*const_tpl\s*type\s*=\s*(?:"curspace_size"|'curspace_size')\s*/\s*>"#
+            )]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _cur_space_size: (),
         },
         SpaceId {
-            #[rust_sitter::leaf(pattern = r#"<\s*const_tpl\s*type\s*=\s*"spaceid""#)]
+            #[rust_sitter::leaf(pattern = r#"<\s*const_tpl\s*type\s*=\s*(?:"spaceid"|'spaceid')"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _start: (),
             #[rust_sitter::leaf(
-                pattern = r#"name\s*=\s*"([^"]*)""#,
+                pattern = r#"name\s*=\s*(?:"([^"]*)"|'([^']*)')"#,
                 transform = |v| {
-                    ConstantTemplateType::NAME_REGEX
-                        .get_or_init(|| {
-                            Regex::new(r#"name\s*=\s*"([^"]*)""#).expect("Invalid regular expression")
-                        })
+                    CONSTANT_TEMPLATE_TYPE_NAME_REGEX
                         .captures(v)
-                        .expect("No captures or no capture group")
-                        .get(1)
-                        .expect("No capture group")
-                        .as_str()
+                        .leaf_capture("ConstantTemplateType::SpaceId::name", 1)
                         .to_string()
                         .xml_unescape()
                 }
             )]
             name: String,
             #[rust_sitter::leaf(pattern = r#"/\s*>"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _end: (),
         },
         JumpRelative {
-            #[rust_sitter::leaf(pattern = r#"<\s*const_tpl\s*type\s*=\s*"relative""#)]
+            #[rust_sitter::leaf(
+                pattern = r#"<\s*const_tpl\s*type\s*=\s*(?:"relative"|'relative')"#
+            )]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _start: (),
             #[rust_sitter::leaf(
-                pattern = r#"val\s*=\s*"0x([0-9a-fA-F]+)""#,
+                pattern = r#"val\s*=\s*(?:"(-?0[xX][0-9a-fA-F]+)"|'(-?0[xX][0-9a-fA-F]+)')"#,
                 transform = |v| {
-                    Integer::from_string_base(16, ConstantTemplateType::HEX_VAL_REGEX
-                        .get_or_init(|| {
-                            Regex::new(r#"val\s*=\s*"0x([0-9a-fA-F]+)""#)
-                                .expect("Invalid regular expression")
-                        })
+                    CONSTANT_TEMPLATE_TYPE_HEX_VAL_REGEX
                         .captures(v)
-                        .expect("No captures or no capture group")
-                        .get(1)
-                        .expect("No capture group").as_str()).expect("Invalid integer")
+                        .leaf_capture("ConstantTemplateType::JumpRelative::val", 1)
+                        .leaf_hex_integer("ConstantTemplateType::JumpRelative::val")
                 }
             )]
-            val: Integer,
+            val: HexInteger,
             #[rust_sitter::leaf(pattern = r#"/\s*>"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _end: (),
         },
         FlowRef {
-            #[rust_sitter::leaf(pattern = r#"<\s*const_tpl\s*type\s*=\s*"flowref"\s*/\s*>"#)]
+            #[rust_sitter::leaf(
+                pattern = r#"<\s*const_tpl\s*type\s*=\s*(?:"flowref"|'flowref')\s*/\s*>"#
+            )]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _flow_ref: (),
         },
         FlowDest {
-            #[rust_sitter::leaf(pattern = r#"<\s*const_tpl\s*type\s*=\s*"flowdest"\s*/\s*>"#)]
+            #[rust_sitter::leaf(
+                pattern = r#"<\s*const_tpl\s*type\s*=\s*(?:"flowdest"|'flowdest')\s*/\s*>"#
+            )]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _flow_dest: (),
         },
         FlowDestSize {
-            #[rust_sitter::leaf(pattern = r#"<\s*const_tpl\s*type\s*=\s*"flowdest_size"\s*/\s*>"#)]
+            #[rust_sitter::leaf(
+                pattern = r#"<\s*const_tpl\s*type\s*=\s*(?:"flowdest_size"|'flowdest_size')\s*/\s*>"#
+            )]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _flow_dest_size: (),
         },
+        /// A `const_tpl` type this crate does not know about, e.g. one added by a newer
+        /// version of Ghidra. Only an optional `val` attribute is captured.
+        Unknown {
+            #[rust_sitter::leaf(
+                pattern = r#"<\s*const_tpl\s*type\s*=\s*(?:"([^"]*)"|'([^']*)')"#,
+                transform = |v| {
+                    CONSTANT_TEMPLATE_TYPE_TYPE_REGEX
+                        .captures(v)
+                        .leaf_capture("ConstantTemplateType::Unknown::type_name", 1)
+                        .to_string()
+                        .xml_unescape()
+                }
+            )]
+            type_name: String,
+            #[rust_sitter::leaf(
+                pattern = r#"val\s*=\s*(?:"(-?0[xX][0-9a-fA-F]+|-?[0-9]+)"|'(-?0[xX][0-9a-fA-F]+|-?[0-9]+)')"#,
+                transform = |v| {
+                    let val = CONSTANT_TEMPLATE_TYPE_ANY_VAL_REGEX
+                        .captures(v)
+                        .leaf_capture("ConstantTemplateType::Unknown::val", 1);
+                    if val.contains(['x', 'X']) {
+                        val.leaf_hex_integer("ConstantTemplateType::Unknown::val").0
+                    } else {
+                        val.leaf_integer("ConstantTemplateType::Unknown::val")
+                    }
+                }
+            )]
+            #[cfg_attr(feature = "serde", serde(with = "crate::serde_integer::option"))]
+            val: Option<Integer>,
+            #[rust_sitter::leaf(pattern = r#"/\s*>"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
+            _end: (),
+        },
     }
 
-    #[derive(TypedBuilder, Debug, PartialEq)]
+    #[derive(TypedBuilder, Debug, Clone, PartialEq)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct HandleTemplate {
         #[rust_sitter::leaf(pattern = r#"<\s*handle_tpl\s*>"#)]
         #[builder(default, setter(skip))]
+        #[cfg_attr(feature = "serde", serde(skip))]
         _start: (),
         space: ConstantTemplateType,
         size: ConstantTemplateType,
@@ -1823,519 +3323,1048 @@ pub mod grammar {
         temp_offset: ConstantTemplateType,
         #[rust_sitter::leaf(pattern = r#"<\s*/\s*handle_tpl\s*>"#)]
         #[builder(default, setter(skip))]
+        #[cfg_attr(feature = "serde", serde(skip))]
         _end: (),
     }
 
-    #[derive(TypedBuilder, Debug, PartialEq)]
+    impl HandleTemplate {
+        /// The space of the handle's location
+        pub fn space(&self) -> &ConstantTemplateType {
+            &self.space
+        }
+
+        /// The size of the handle's value
+        pub fn size(&self) -> &ConstantTemplateType {
+            &self.size
+        }
+
+        /// The space of the pointer, for dynamic handles
+        pub fn ptrspace(&self) -> &ConstantTemplateType {
+            &self.ptrspace
+        }
+
+        /// The offset of the pointer, for dynamic handles
+        pub fn ptroffset(&self) -> &ConstantTemplateType {
+            &self.ptroffset
+        }
+
+        /// The size of the pointer, for dynamic handles
+        pub fn ptrsize(&self) -> &ConstantTemplateType {
+            &self.ptrsize
+        }
+
+        /// The space of the temporary holding the dereferenced value
+        pub fn temp_space(&self) -> &ConstantTemplateType {
+            &self.temp_space
+        }
+
+        /// The offset of the temporary holding the dereferenced value
+        pub fn temp_offset(&self) -> &ConstantTemplateType {
+            &self.temp_offset
+        }
+
+        /// The seven constants of the handle, in the order they appear in the `.sla` file:
+        /// `space`, `size`, `ptrspace`, `ptroffset`, `ptrsize`, `temp_space`, `temp_offset`
+        pub fn components(&self) -> [&ConstantTemplateType; 7] {
+            [
+                &self.space,
+                &self.size,
+                &self.ptrspace,
+                &self.ptroffset,
+                &self.ptrsize,
+                &self.temp_space,
+                &self.temp_offset,
+            ]
+        }
+
+        fn constant_templates(&self) -> impl Iterator<Item = &ConstantTemplateType> {
+            self.components().into_iter()
+        }
+    }
+
+    #[derive(TypedBuilder, Debug, Clone, PartialEq)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct VarNodeTemplate {
         #[rust_sitter::leaf(pattern = r#"<\s*varnode_tpl\s*>"#)]
         #[builder(default, setter(skip))]
+        #[cfg_attr(feature = "serde", serde(skip))]
         _start: (),
         space: ConstantTemplateType,
         offset: ConstantTemplateType,
         size: ConstantTemplateType,
         #[rust_sitter::leaf(pattern = r#"<\s*/\s*varnode_tpl\s*>"#)]
         #[builder(default, setter(skip))]
+        #[cfg_attr(feature = "serde", serde(skip))]
         _end: (),
     }
 
-    #[derive(Debug, PartialEq)]
+    impl VarNodeTemplate {
+        /// The varnode's space
+        pub fn space(&self) -> &ConstantTemplateType {
+            &self.space
+        }
+
+        /// The varnode's offset
+        pub fn offset(&self) -> &ConstantTemplateType {
+            &self.offset
+        }
+
+        /// The varnode's size
+        pub fn size(&self) -> &ConstantTemplateType {
+            &self.size
+        }
+
+        fn constant_templates(&self) -> impl Iterator<Item = &ConstantTemplateType> {
+            [&self.space, &self.offset, &self.size].into_iter()
+        }
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    #[non_exhaustive]
     pub enum OperationCode {
         Blank {
-            #[rust_sitter::leaf(pattern = r#"BLANK"#)]
+            #[rust_sitter::leaf(pattern = r#""BLANK"|'BLANK'"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _blank: (),
         },
         Copy {
-            #[rust_sitter::leaf(pattern = r#"COPY"#)]
+            #[rust_sitter::leaf(pattern = r#""COPY"|'COPY'"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _copy: (),
         },
         Load {
-            #[rust_sitter::leaf(pattern = r#"LOAD"#)]
+            #[rust_sitter::leaf(pattern = r#""LOAD"|'LOAD'"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _load: (),
         },
         Store {
-            #[rust_sitter::leaf(pattern = r#"STORE"#)]
+            #[rust_sitter::leaf(pattern = r#""STORE"|'STORE'"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _store: (),
         },
         Branch {
-            #[rust_sitter::leaf(pattern = r#"BRANCH"#)]
+            #[rust_sitter::leaf(pattern = r#""BRANCH"|'BRANCH'"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _branch: (),
         },
         ConditionalBranch {
-            #[rust_sitter::leaf(pattern = r#"CBRANCH"#)]
+            #[rust_sitter::leaf(pattern = r#""CBRANCH"|'CBRANCH'"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _cbranch: (),
         },
         BranchIndirect {
-            #[rust_sitter::leaf(pattern = r#"BRANCHIND"#)]
+            #[rust_sitter::leaf(pattern = r#""BRANCHIND"|'BRANCHIND'"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _branchind: (),
         },
         Call {
-            #[rust_sitter::leaf(pattern = r#"CALL"#)]
+            #[rust_sitter::leaf(pattern = r#""CALL"|'CALL'"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _call: (),
         },
         CallIndirect {
-            #[rust_sitter::leaf(pattern = r#"CALLIND"#)]
+            #[rust_sitter::leaf(pattern = r#""CALLIND"|'CALLIND'"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _callind: (),
         },
         CallOther {
-            #[rust_sitter::leaf(pattern = r#"CALLOTHER"#)]
+            #[rust_sitter::leaf(pattern = r#""CALLOTHER"|'CALLOTHER'"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _callother: (),
         },
         Return {
-            #[rust_sitter::leaf(pattern = r#"RETURN"#)]
+            #[rust_sitter::leaf(pattern = r#""RETURN"|'RETURN'"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _return: (),
         },
         IntegerEqual {
-            #[rust_sitter::leaf(pattern = r#"INT_EQUAL"#)]
+            #[rust_sitter::leaf(pattern = r#""INT_EQUAL"|'INT_EQUAL'"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _int_equal: (),
         },
         IntegerNotEqual {
-            #[rust_sitter::leaf(pattern = r#"INT_NOTEQUAL"#)]
+            #[rust_sitter::leaf(pattern = r#""INT_NOTEQUAL"|'INT_NOTEQUAL'"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _int_notequal: (),
         },
         IntegerSignedLessThan {
-            #[rust_sitter::leaf(pattern = r#"INT_SLESS"#)]
+            #[rust_sitter::leaf(pattern = r#""INT_SLESS"|'INT_SLESS'"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _int_sless: (),
         },
         IntegerSignedLessThanOrEqual {
-            #[rust_sitter::leaf(pattern = r#"INT_SLESSEQUAL"#)]
+            #[rust_sitter::leaf(pattern = r#""INT_SLESSEQUAL"|'INT_SLESSEQUAL'"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _int_slessequal: (),
         },
         IntegerUnsignedLessThan {
-            #[rust_sitter::leaf(pattern = r#"INT_LESS"#)]
+            #[rust_sitter::leaf(pattern = r#""INT_LESS"|'INT_LESS'"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _int_less: (),
         },
         IntegerUnsignedLessThanOrEqual {
-            #[rust_sitter::leaf(pattern = r#"INT_LESSEQUAL"#)]
+            #[rust_sitter::leaf(pattern = r#""INT_LESSEQUAL"|'INT_LESSEQUAL'"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _int_lessequal: (),
         },
         IntegerZeroExtend {
-            #[rust_sitter::leaf(pattern = r#"INT_ZEXT"#)]
+            #[rust_sitter::leaf(pattern = r#""INT_ZEXT"|'INT_ZEXT'"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _int_zext: (),
         },
         IntegerSignExtend {
-            #[rust_sitter::leaf(pattern = r#"INT_SEXT"#)]
+            #[rust_sitter::leaf(pattern = r#""INT_SEXT"|'INT_SEXT'"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _int_sext: (),
         },
         IntegerAdd {
-            #[rust_sitter::leaf(pattern = r#"INT_ADD"#)]
+            #[rust_sitter::leaf(pattern = r#""INT_ADD"|'INT_ADD'"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _int_add: (),
         },
         IntegerSubtract {
-            #[rust_sitter::leaf(pattern = r#"INT_SUB"#)]
+            #[rust_sitter::leaf(pattern = r#""INT_SUB"|'INT_SUB'"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _int_sub: (),
         },
         IntegerCarry {
-            #[rust_sitter::leaf(pattern = r#"INT_CARRY"#)]
+            #[rust_sitter::leaf(pattern = r#""INT_CARRY"|'INT_CARRY'"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _int_carry: (),
         },
         IntegerSignedCarry {
-            #[rust_sitter::leaf(pattern = r#"INT_SCARRY"#)]
+            #[rust_sitter::leaf(pattern = r#""INT_SCARRY"|'INT_SCARRY'"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _int_scarry: (),
         },
         IntegerSignedBorrow {
-            #[rust_sitter::leaf(pattern = r#"INT_SBORROW"#)]
+            #[rust_sitter::leaf(pattern = r#""INT_SBORROW"|'INT_SBORROW'"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _int_sborrow: (),
         },
         IntegerTwosCompliment {
-            #[rust_sitter::leaf(pattern = r#"INT_2COMP"#)]
+            #[rust_sitter::leaf(pattern = r#""INT_2COMP"|'INT_2COMP'"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _int_2comp: (),
         },
         IntegerNegate {
-            #[rust_sitter::leaf(pattern = r#"INT_NEGATE"#)]
+            #[rust_sitter::leaf(pattern = r#""INT_NEGATE"|'INT_NEGATE'"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _int_negate: (),
         },
         IntegerXor {
-            #[rust_sitter::leaf(pattern = r#"INT_XOR"#)]
+            #[rust_sitter::leaf(pattern = r#""INT_XOR"|'INT_XOR'"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _int_xor: (),
         },
         IntegerAnd {
-            #[rust_sitter::leaf(pattern = r#"INT_AND"#)]
+            #[rust_sitter::leaf(pattern = r#""INT_AND"|'INT_AND'"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _int_and: (),
         },
         IntegerOr {
-            #[rust_sitter::leaf(pattern = r#"INT_OR"#)]
+            #[rust_sitter::leaf(pattern = r#""INT_OR"|'INT_OR'"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _int_or: (),
         },
         IntegerLeftShift {
-            #[rust_sitter::leaf(pattern = r#"INT_LEFT"#)]
+            #[rust_sitter::leaf(pattern = r#""INT_LEFT"|'INT_LEFT'"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _int_left: (),
         },
         IntegerRightShift {
-            #[rust_sitter::leaf(pattern = r#"INT_RIGHT"#)]
+            #[rust_sitter::leaf(pattern = r#""INT_RIGHT"|'INT_RIGHT'"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _int_right: (),
         },
         IntegerSignedRightShift {
-            #[rust_sitter::leaf(pattern = r#"INT_SRIGHT"#)]
+            #[rust_sitter::leaf(pattern = r#""INT_SRIGHT"|'INT_SRIGHT'"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _int_sright: (),
         },
         IntegerMultiply {
-            #[rust_sitter::leaf(pattern = r#"INT_MULT"#)]
+            #[rust_sitter::leaf(pattern = r#""INT_MULT"|'INT_MULT'"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _int_mult: (),
         },
         IntegerDivide {
-            #[rust_sitter::leaf(pattern = r#"INT_DIV"#)]
+            #[rust_sitter::leaf(pattern = r#""INT_DIV"|'INT_DIV'"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _int_div: (),
         },
         IntegerSignedDivide {
-            #[rust_sitter::leaf(pattern = r#"INT_SDIV"#)]
+            #[rust_sitter::leaf(pattern = r#""INT_SDIV"|'INT_SDIV'"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _int_sdiv: (),
         },
         IntegerRemainder {
-            #[rust_sitter::leaf(pattern = r#"INT_REM"#)]
+            #[rust_sitter::leaf(pattern = r#""INT_REM"|'INT_REM'"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _int_rem: (),
         },
         IntegerSignedRemainder {
-            #[rust_sitter::leaf(pattern = r#"INT_SREM"#)]
+            #[rust_sitter::leaf(pattern = r#""INT_SREM"|'INT_SREM'"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _int_srem: (),
         },
         BooleanNegate {
-            #[rust_sitter::leaf(pattern = r#"BOOL_NEGATE"#)]
+            #[rust_sitter::leaf(pattern = r#""BOOL_NEGATE"|'BOOL_NEGATE'"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _bool_negate: (),
         },
         BooleanXor {
-            #[rust_sitter::leaf(pattern = r#"BOOL_XOR"#)]
+            #[rust_sitter::leaf(pattern = r#""BOOL_XOR"|'BOOL_XOR'"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _bool_xor: (),
         },
         BooleanAnd {
-            #[rust_sitter::leaf(pattern = r#"BOOL_AND"#)]
+            #[rust_sitter::leaf(pattern = r#""BOOL_AND"|'BOOL_AND'"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _bool_and: (),
         },
         BooleanOr {
-            #[rust_sitter::leaf(pattern = r#"BOOL_OR"#)]
+            #[rust_sitter::leaf(pattern = r#""BOOL_OR"|'BOOL_OR'"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _bool_or: (),
         },
         FloatEqual {
-            #[rust_sitter::leaf(pattern = r#"FLOAT_EQUAL"#)]
+            #[rust_sitter::leaf(pattern = r#""FLOAT_EQUAL"|'FLOAT_EQUAL'"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _float_equal: (),
         },
         FloatNotEqual {
-            #[rust_sitter::leaf(pattern = r#"FLOAT_NOTEQUAL"#)]
+            #[rust_sitter::leaf(pattern = r#""FLOAT_NOTEQUAL"|'FLOAT_NOTEQUAL'"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _float_notequal: (),
         },
         FloatLessThan {
-            #[rust_sitter::leaf(pattern = r#"FLOAT_LESS"#)]
+            #[rust_sitter::leaf(pattern = r#""FLOAT_LESS"|'FLOAT_LESS'"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _float_less: (),
         },
         FloatLessThanOrEqual {
-            #[rust_sitter::leaf(pattern = r#"FLOAT_LESSEQUAL"#)]
+            #[rust_sitter::leaf(pattern = r#""FLOAT_LESSEQUAL"|'FLOAT_LESSEQUAL'"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _float_lessequal: (),
         },
         Unused1 {
-            #[rust_sitter::leaf(pattern = r#"UNUSED1"#)]
+            #[rust_sitter::leaf(pattern = r#""UNUSED1"|'UNUSED1'"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _unused1: (),
         },
         FloatNotANumber {
-            #[rust_sitter::leaf(pattern = r#"FLOAT_NAN"#)]
+            #[rust_sitter::leaf(pattern = r#""FLOAT_NAN"|'FLOAT_NAN'"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _float_nan: (),
         },
         FloatAdd {
-            #[rust_sitter::leaf(pattern = r#"FLOAT_ADD"#)]
+            #[rust_sitter::leaf(pattern = r#""FLOAT_ADD"|'FLOAT_ADD'"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _float_add: (),
         },
         FloatDivide {
-            #[rust_sitter::leaf(pattern = r#"FLOAT_DIV"#)]
+            #[rust_sitter::leaf(pattern = r#""FLOAT_DIV"|'FLOAT_DIV'"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _float_div: (),
         },
         FloatMultiply {
-            #[rust_sitter::leaf(pattern = r#"FLOAT_MULT"#)]
+            #[rust_sitter::leaf(pattern = r#""FLOAT_MULT"|'FLOAT_MULT'"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _float_mult: (),
         },
         FloatSubtract {
-            #[rust_sitter::leaf(pattern = r#"FLOAT_SUB"#)]
+            #[rust_sitter::leaf(pattern = r#""FLOAT_SUB"|'FLOAT_SUB'"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _float_sub: (),
         },
         FloatNegate {
-            #[rust_sitter::leaf(pattern = r#"FLOAT_NEG"#)]
+            #[rust_sitter::leaf(pattern = r#""FLOAT_NEG"|'FLOAT_NEG'"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _float_neg: (),
         },
         FloatAbsoluteValue {
-            #[rust_sitter::leaf(pattern = r#"FLOAT_ABS"#)]
+            #[rust_sitter::leaf(pattern = r#""FLOAT_ABS"|'FLOAT_ABS'"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _float_abs: (),
         },
         FloatSquareRoot {
-            #[rust_sitter::leaf(pattern = r#"FLOAT_SQRT"#)]
+            #[rust_sitter::leaf(pattern = r#""FLOAT_SQRT"|'FLOAT_SQRT'"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _float_sqrt: (),
         },
         IntegerToFloat {
-            #[rust_sitter::leaf(pattern = r#"INT2FLOAT"#)]
+            #[rust_sitter::leaf(pattern = r#""INT2FLOAT"|'INT2FLOAT'"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _int2float: (),
         },
         FloatToFloat {
-            #[rust_sitter::leaf(pattern = r#"FLOAT2FLOAT"#)]
+            #[rust_sitter::leaf(pattern = r#""FLOAT2FLOAT"|'FLOAT2FLOAT'"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _float2float: (),
         },
         Truncate {
-            #[rust_sitter::leaf(pattern = r#"TRUNC"#)]
+            #[rust_sitter::leaf(pattern = r#""TRUNC"|'TRUNC'"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _trunc: (),
         },
         Ceiling {
-            #[rust_sitter::leaf(pattern = r#"CEIL"#)]
+            #[rust_sitter::leaf(pattern = r#""CEIL"|'CEIL'"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _ceil: (),
         },
         Floor {
-            #[rust_sitter::leaf(pattern = r#"FLOOR"#)]
+            #[rust_sitter::leaf(pattern = r#""FLOOR"|'FLOOR'"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _floor: (),
         },
         Round {
-            #[rust_sitter::leaf(pattern = r#"ROUND"#)]
+            #[rust_sitter::leaf(pattern = r#""ROUND"|'ROUND'"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _round: (),
         },
         Build {
-            #[rust_sitter::leaf(pattern = r#"BUILD"#)]
+            #[rust_sitter::leaf(pattern = r#""BUILD"|'BUILD'"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _build: (),
         },
         DelaySlot {
-            #[rust_sitter::leaf(pattern = r#"DELAY_SLOT"#)]
+            #[rust_sitter::leaf(pattern = r#""DELAY_SLOT"|'DELAY_SLOT'"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _delay_slot: (),
         },
         Piece {
-            #[rust_sitter::leaf(pattern = r#"PIECE"#)]
+            #[rust_sitter::leaf(pattern = r#""PIECE"|'PIECE'"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _piece: (),
         },
         Subpiece {
-            #[rust_sitter::leaf(pattern = r#"SUBPIECE"#)]
+            #[rust_sitter::leaf(pattern = r#""SUBPIECE"|'SUBPIECE'"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _subpiece: (),
         },
         Cast {
-            #[rust_sitter::leaf(pattern = r#"CAST"#)]
+            #[rust_sitter::leaf(pattern = r#""CAST"|'CAST'"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _cast: (),
         },
         Label {
-            #[rust_sitter::leaf(pattern = r#"LABEL"#)]
+            #[rust_sitter::leaf(pattern = r#""LABEL"|'LABEL'"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _label: (),
         },
         CrossBuild {
-            #[rust_sitter::leaf(pattern = r#"CROSSBUILD"#)]
+            #[rust_sitter::leaf(pattern = r#""CROSSBUILD"|'CROSSBUILD'"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _crossbuild: (),
         },
         SegmentOp {
-            #[rust_sitter::leaf(pattern = r#"SEGMENTOP"#)]
+            #[rust_sitter::leaf(pattern = r#""SEGMENTOP"|'SEGMENTOP'"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _segmentop: (),
         },
         CpoolRef {
-            #[rust_sitter::leaf(pattern = r#"CPOOLREF"#)]
+            #[rust_sitter::leaf(pattern = r#""CPOOLREF"|'CPOOLREF'"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _cpoolref: (),
         },
         New {
-            #[rust_sitter::leaf(pattern = r#"NEW"#)]
+            #[rust_sitter::leaf(pattern = r#""NEW"|'NEW'"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _new: (),
         },
         Insert {
-            #[rust_sitter::leaf(pattern = r#"INSERT"#)]
+            #[rust_sitter::leaf(pattern = r#""INSERT"|'INSERT'"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _insert: (),
         },
         Extract {
-            #[rust_sitter::leaf(pattern = r#"EXTRACT"#)]
+            #[rust_sitter::leaf(pattern = r#""EXTRACT"|'EXTRACT'"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _extract: (),
         },
         PopCount {
-            #[rust_sitter::leaf(pattern = r#"POPCOUNT"#)]
+            #[rust_sitter::leaf(pattern = r#""POPCOUNT"|'POPCOUNT'"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _popcount: (),
         },
         LzCount {
-            #[rust_sitter::leaf(pattern = r#"LZCOUNT"#)]
+            #[rust_sitter::leaf(pattern = r#""LZCOUNT"|'LZCOUNT'"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _lzcnt: (),
         },
     }
 
-    #[derive(Debug, PartialEq)]
+    /// The p-code mnemonics indexed by Ghidra's `OpCode` ordinal (`CPUI_COPY` is 1). SLEIGH
+    /// reuses the ordinals the decompiler gives `MULTIEQUAL`, `INDIRECT`, `PTRADD`, and
+    /// `PTRSUB` for its own `BUILD`, `DELAY_SLOT`, `LABEL`, and `CROSSBUILD` directives.
+    const OPCODE_MNEMONICS: [&str; 74] = [
+        "BLANK",
+        "COPY",
+        "LOAD",
+        "STORE",
+        "BRANCH",
+        "CBRANCH",
+        "BRANCHIND",
+        "CALL",
+        "CALLIND",
+        "CALLOTHER",
+        "RETURN",
+        "INT_EQUAL",
+        "INT_NOTEQUAL",
+        "INT_SLESS",
+        "INT_SLESSEQUAL",
+        "INT_LESS",
+        "INT_LESSEQUAL",
+        "INT_ZEXT",
+        "INT_SEXT",
+        "INT_ADD",
+        "INT_SUB",
+        "INT_CARRY",
+        "INT_SCARRY",
+        "INT_SBORROW",
+        "INT_2COMP",
+        "INT_NEGATE",
+        "INT_XOR",
+        "INT_AND",
+        "INT_OR",
+        "INT_LEFT",
+        "INT_RIGHT",
+        "INT_SRIGHT",
+        "INT_MULT",
+        "INT_DIV",
+        "INT_SDIV",
+        "INT_REM",
+        "INT_SREM",
+        "BOOL_NEGATE",
+        "BOOL_XOR",
+        "BOOL_AND",
+        "BOOL_OR",
+        "FLOAT_EQUAL",
+        "FLOAT_NOTEQUAL",
+        "FLOAT_LESS",
+        "FLOAT_LESSEQUAL",
+        "UNUSED1",
+        "FLOAT_NAN",
+        "FLOAT_ADD",
+        "FLOAT_DIV",
+        "FLOAT_MULT",
+        "FLOAT_SUB",
+        "FLOAT_NEG",
+        "FLOAT_ABS",
+        "FLOAT_SQRT",
+        "INT2FLOAT",
+        "FLOAT2FLOAT",
+        "TRUNC",
+        "CEIL",
+        "FLOOR",
+        "ROUND",
+        "BUILD",
+        "DELAY_SLOT",
+        "PIECE",
+        "SUBPIECE",
+        "CAST",
+        "LABEL",
+        "CROSSBUILD",
+        "SEGMENTOP",
+        "CPOOLREF",
+        "NEW",
+        "INSERT",
+        "EXTRACT",
+        "POPCOUNT",
+        "LZCOUNT",
+    ];
+
+    impl OperationCode {
+        /// The p-code mnemonic of the operation, e.g. `INT_ADD`
+        pub fn mnemonic(&self) -> &'static str {
+            match self {
+                OperationCode::Blank { .. } => "BLANK",
+                OperationCode::Copy { .. } => "COPY",
+                OperationCode::Load { .. } => "LOAD",
+                OperationCode::Store { .. } => "STORE",
+                OperationCode::Branch { .. } => "BRANCH",
+                OperationCode::ConditionalBranch { .. } => "CBRANCH",
+                OperationCode::BranchIndirect { .. } => "BRANCHIND",
+                OperationCode::Call { .. } => "CALL",
+                OperationCode::CallIndirect { .. } => "CALLIND",
+                OperationCode::CallOther { .. } => "CALLOTHER",
+                OperationCode::Return { .. } => "RETURN",
+                OperationCode::IntegerEqual { .. } => "INT_EQUAL",
+                OperationCode::IntegerNotEqual { .. } => "INT_NOTEQUAL",
+                OperationCode::IntegerSignedLessThan { .. } => "INT_SLESS",
+                OperationCode::IntegerSignedLessThanOrEqual { .. } => "INT_SLESSEQUAL",
+                OperationCode::IntegerUnsignedLessThan { .. } => "INT_LESS",
+                OperationCode::IntegerUnsignedLessThanOrEqual { .. } => "INT_LESSEQUAL",
+                OperationCode::IntegerZeroExtend { .. } => "INT_ZEXT",
+                OperationCode::IntegerSignExtend { .. } => "INT_SEXT",
+                OperationCode::IntegerAdd { .. } => "INT_ADD",
+                OperationCode::IntegerSubtract { .. } => "INT_SUB",
+                OperationCode::IntegerCarry { .. } => "INT_CARRY",
+                OperationCode::IntegerSignedCarry { .. } => "INT_SCARRY",
+                OperationCode::IntegerSignedBorrow { .. } => "INT_SBORROW",
+                OperationCode::IntegerTwosCompliment { .. } => "INT_2COMP",
+                OperationCode::IntegerNegate { .. } => "INT_NEGATE",
+                OperationCode::IntegerXor { .. } => "INT_XOR",
+                OperationCode::IntegerAnd { .. } => "INT_AND",
+                OperationCode::IntegerOr { .. } => "INT_OR",
+                OperationCode::IntegerLeftShift { .. } => "INT_LEFT",
+                OperationCode::IntegerRightShift { .. } => "INT_RIGHT",
+                OperationCode::IntegerSignedRightShift { .. } => "INT_SRIGHT",
+                OperationCode::IntegerMultiply { .. } => "INT_MULT",
+                OperationCode::IntegerDivide { .. } => "INT_DIV",
+                OperationCode::IntegerSignedDivide { .. } => "INT_SDIV",
+                OperationCode::IntegerRemainder { .. } => "INT_REM",
+                OperationCode::IntegerSignedRemainder { .. } => "INT_SREM",
+                OperationCode::BooleanNegate { .. } => "BOOL_NEGATE",
+                OperationCode::BooleanXor { .. } => "BOOL_XOR",
+                OperationCode::BooleanAnd { .. } => "BOOL_AND",
+                OperationCode::BooleanOr { .. } => "BOOL_OR",
+                OperationCode::FloatEqual { .. } => "FLOAT_EQUAL",
+                OperationCode::FloatNotEqual { .. } => "FLOAT_NOTEQUAL",
+                OperationCode::FloatLessThan { .. } => "FLOAT_LESS",
+                OperationCode::FloatLessThanOrEqual { .. } => "FLOAT_LESSEQUAL",
+                OperationCode::Unused1 { .. } => "UNUSED1",
+                OperationCode::FloatNotANumber { .. } => "FLOAT_NAN",
+                OperationCode::FloatAdd { .. } => "FLOAT_ADD",
+                OperationCode::FloatDivide { .. } => "FLOAT_DIV",
+                OperationCode::FloatMultiply { .. } => "FLOAT_MULT",
+                OperationCode::FloatSubtract { .. } => "FLOAT_SUB",
+                OperationCode::FloatNegate { .. } => "FLOAT_NEG",
+                OperationCode::FloatAbsoluteValue { .. } => "FLOAT_ABS",
+                OperationCode::FloatSquareRoot { .. } => "FLOAT_SQRT",
+                OperationCode::IntegerToFloat { .. } => "INT2FLOAT",
+                OperationCode::FloatToFloat { .. } => "FLOAT2FLOAT",
+                OperationCode::Truncate { .. } => "TRUNC",
+                OperationCode::Ceiling { .. } => "CEIL",
+                OperationCode::Floor { .. } => "FLOOR",
+                OperationCode::Round { .. } => "ROUND",
+                OperationCode::Build { .. } => "BUILD",
+                OperationCode::DelaySlot { .. } => "DELAY_SLOT",
+                OperationCode::Piece { .. } => "PIECE",
+                OperationCode::Subpiece { .. } => "SUBPIECE",
+                OperationCode::Cast { .. } => "CAST",
+                OperationCode::Label { .. } => "LABEL",
+                OperationCode::CrossBuild { .. } => "CROSSBUILD",
+                OperationCode::SegmentOp { .. } => "SEGMENTOP",
+                OperationCode::CpoolRef { .. } => "CPOOLREF",
+                OperationCode::New { .. } => "NEW",
+                OperationCode::Insert { .. } => "INSERT",
+                OperationCode::Extract { .. } => "EXTRACT",
+                OperationCode::PopCount { .. } => "POPCOUNT",
+                OperationCode::LzCount { .. } => "LZCOUNT",
+            }
+        }
+
+        /// The ordinal of the operation in Ghidra's `OpCode` enum, e.g. 19 for `CPUI_INT_ADD`
+        pub fn opcode(&self) -> u32 {
+            let mnemonic = self.mnemonic();
+            OPCODE_MNEMONICS
+                .iter()
+                .position(|candidate| *candidate == mnemonic)
+                .expect("Every mnemonic has an opcode") as u32
+        }
+
+        /// The operation with the Ghidra `OpCode` ordinal `opcode`, if there is one
+        pub fn from_opcode(opcode: u32) -> Option<Self> {
+            OPCODE_MNEMONICS
+                .get(usize::try_from(opcode).ok()?)
+                .and_then(|mnemonic| Self::from_mnemonic(mnemonic))
+        }
+
+        /// The operation with the p-code mnemonic `mnemonic`, if there is one
+        pub fn from_mnemonic(mnemonic: &str) -> Option<Self> {
+            Some(match mnemonic {
+                "BLANK" => OperationCode::Blank { _blank: () },
+                "COPY" => OperationCode::Copy { _copy: () },
+                "LOAD" => OperationCode::Load { _load: () },
+                "STORE" => OperationCode::Store { _store: () },
+                "BRANCH" => OperationCode::Branch { _branch: () },
+                "CBRANCH" => OperationCode::ConditionalBranch { _cbranch: () },
+                "BRANCHIND" => OperationCode::BranchIndirect { _branchind: () },
+                "CALL" => OperationCode::Call { _call: () },
+                "CALLIND" => OperationCode::CallIndirect { _callind: () },
+                "CALLOTHER" => OperationCode::CallOther { _callother: () },
+                "RETURN" => OperationCode::Return { _return: () },
+                "INT_EQUAL" => OperationCode::IntegerEqual { _int_equal: () },
+                "INT_NOTEQUAL" => OperationCode::IntegerNotEqual { _int_notequal: () },
+                "INT_SLESS" => OperationCode::IntegerSignedLessThan { _int_sless: () },
+                "INT_SLESSEQUAL" => OperationCode::IntegerSignedLessThanOrEqual {
+                    _int_slessequal: (),
+                },
+                "INT_LESS" => OperationCode::IntegerUnsignedLessThan { _int_less: () },
+                "INT_LESSEQUAL" => {
+                    OperationCode::IntegerUnsignedLessThanOrEqual { _int_lessequal: () }
+                }
+                "INT_ZEXT" => OperationCode::IntegerZeroExtend { _int_zext: () },
+                "INT_SEXT" => OperationCode::IntegerSignExtend { _int_sext: () },
+                "INT_ADD" => OperationCode::IntegerAdd { _int_add: () },
+                "INT_SUB" => OperationCode::IntegerSubtract { _int_sub: () },
+                "INT_CARRY" => OperationCode::IntegerCarry { _int_carry: () },
+                "INT_SCARRY" => OperationCode::IntegerSignedCarry { _int_scarry: () },
+                "INT_SBORROW" => OperationCode::IntegerSignedBorrow { _int_sborrow: () },
+                "INT_2COMP" => OperationCode::IntegerTwosCompliment { _int_2comp: () },
+                "INT_NEGATE" => OperationCode::IntegerNegate { _int_negate: () },
+                "INT_XOR" => OperationCode::IntegerXor { _int_xor: () },
+                "INT_AND" => OperationCode::IntegerAnd { _int_and: () },
+                "INT_OR" => OperationCode::IntegerOr { _int_or: () },
+                "INT_LEFT" => OperationCode::IntegerLeftShift { _int_left: () },
+                "INT_RIGHT" => OperationCode::IntegerRightShift { _int_right: () },
+                "INT_SRIGHT" => OperationCode::IntegerSignedRightShift { _int_sright: () },
+                "INT_MULT" => OperationCode::IntegerMultiply { _int_mult: () },
+                "INT_DIV" => OperationCode::IntegerDivide { _int_div: () },
+                "INT_SDIV" => OperationCode::IntegerSignedDivide { _int_sdiv: () },
+                "INT_REM" => OperationCode::IntegerRemainder { _int_rem: () },
+                "INT_SREM" => OperationCode::IntegerSignedRemainder { _int_srem: () },
+                "BOOL_NEGATE" => OperationCode::BooleanNegate { _bool_negate: () },
+                "BOOL_XOR" => OperationCode::BooleanXor { _bool_xor: () },
+                "BOOL_AND" => OperationCode::BooleanAnd { _bool_and: () },
+                "BOOL_OR" => OperationCode::BooleanOr { _bool_or: () },
+                "FLOAT_EQUAL" => OperationCode::FloatEqual { _float_equal: () },
+                "FLOAT_NOTEQUAL" => OperationCode::FloatNotEqual {
+                    _float_notequal: (),
+                },
+                "FLOAT_LESS" => OperationCode::FloatLessThan { _float_less: () },
+                "FLOAT_LESSEQUAL" => OperationCode::FloatLessThanOrEqual {
+                    _float_lessequal: (),
+                },
+                "UNUSED1" => OperationCode::Unused1 { _unused1: () },
+                "FLOAT_NAN" => OperationCode::FloatNotANumber { _float_nan: () },
+                "FLOAT_ADD" => OperationCode::FloatAdd { _float_add: () },
+                "FLOAT_DIV" => OperationCode::FloatDivide { _float_div: () },
+                "FLOAT_MULT" => OperationCode::FloatMultiply { _float_mult: () },
+                "FLOAT_SUB" => OperationCode::FloatSubtract { _float_sub: () },
+                "FLOAT_NEG" => OperationCode::FloatNegate { _float_neg: () },
+                "FLOAT_ABS" => OperationCode::FloatAbsoluteValue { _float_abs: () },
+                "FLOAT_SQRT" => OperationCode::FloatSquareRoot { _float_sqrt: () },
+                "INT2FLOAT" => OperationCode::IntegerToFloat { _int2float: () },
+                "FLOAT2FLOAT" => OperationCode::FloatToFloat { _float2float: () },
+                "TRUNC" => OperationCode::Truncate { _trunc: () },
+                "CEIL" => OperationCode::Ceiling { _ceil: () },
+                "FLOOR" => OperationCode::Floor { _floor: () },
+                "ROUND" => OperationCode::Round { _round: () },
+                "BUILD" => OperationCode::Build { _build: () },
+                "DELAY_SLOT" => OperationCode::DelaySlot { _delay_slot: () },
+                "PIECE" => OperationCode::Piece { _piece: () },
+                "SUBPIECE" => OperationCode::Subpiece { _subpiece: () },
+                "CAST" => OperationCode::Cast { _cast: () },
+                "LABEL" => OperationCode::Label { _label: () },
+                "CROSSBUILD" => OperationCode::CrossBuild { _crossbuild: () },
+                "SEGMENTOP" => OperationCode::SegmentOp { _segmentop: () },
+                "CPOOLREF" => OperationCode::CpoolRef { _cpoolref: () },
+                "NEW" => OperationCode::New { _new: () },
+                "INSERT" => OperationCode::Insert { _insert: () },
+                "EXTRACT" => OperationCode::Extract { _extract: () },
+                "POPCOUNT" => OperationCode::PopCount { _popcount: () },
+                "LZCOUNT" => OperationCode::LzCount { _lzcnt: () },
+                _ => return None,
+            })
+        }
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    #[non_exhaustive]
     pub enum OperationTemplateOutput {
         Null {
             #[rust_sitter::leaf(pattern = r#"<\s*null\s*/\s*>"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _null: (),
         },
         Output(VarNodeTemplate),
     }
 
-    #[derive(TypedBuilder, Debug, PartialEq)]
+    impl OperationTemplateOutput {
+        /// The output varnode, or `None` if the operation has no output
+        pub fn as_option(&self) -> Option<&VarNodeTemplate> {
+            match self {
+                OperationTemplateOutput::Null { .. } => None,
+                OperationTemplateOutput::Output(output) => Some(output),
+            }
+        }
+    }
+
+    #[derive(TypedBuilder, Debug, Clone, PartialEq)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct OperationTemplate {
         #[rust_sitter::leaf(pattern = r#"<\s*op_tpl"#)]
         #[builder(default, setter(skip))]
+        #[cfg_attr(feature = "serde", serde(skip))]
         _start: (),
-        #[rust_sitter::leaf(pattern = r#"code\s*=\s*""#)]
+        #[rust_sitter::leaf(pattern = r#"code\s*=\s*"#)]
         #[builder(default, setter(skip))]
+        #[cfg_attr(feature = "serde", serde(skip))]
         _code_pre: (),
         code: OperationCode,
-        #[rust_sitter::leaf(pattern = r#""\s*>"#)]
+        #[rust_sitter::leaf(pattern = r#">"#)]
         #[builder(default, setter(skip))]
+        #[cfg_attr(feature = "serde", serde(skip))]
         _close: (),
         output: OperationTemplateOutput,
         input: Vec<VarNodeTemplate>,
         #[rust_sitter::leaf(pattern = r#"<\s*/\s*op_tpl\s*>"#)]
         #[builder(default, setter(skip))]
+        #[cfg_attr(feature = "serde", serde(skip))]
         _end: (),
     }
 
-    impl ConstructorTemplate {
-        // DEC
-        const SECTION_REGEX: OnceCell<Regex> = OnceCell::new();
-        // DEC
-        const DELAY_REGEX: OnceCell<Regex> = OnceCell::new();
-        // DEC
-        const NUMLABELS_REGEX: OnceCell<Regex> = OnceCell::new();
+    impl OperationTemplate {
+        /// The p-code operation
+        pub fn code(&self) -> &OperationCode {
+            &self.code
+        }
+
+        /// The output varnode, if the operation has one
+        pub fn output(&self) -> &OperationTemplateOutput {
+            &self.output
+        }
+
+        /// The input varnodes
+        pub fn input(&self) -> &[VarNodeTemplate] {
+            &self.input
+        }
+
+        fn constant_templates(&self) -> impl Iterator<Item = &ConstantTemplateType> {
+            self.output
+                .as_option()
+                .into_iter()
+                .chain(self.input.iter())
+                .flat_map(VarNodeTemplate::constant_templates)
+        }
     }
 
-    #[derive(Debug, PartialEq)]
+    // DEC
+    static CONSTRUCTOR_TEMPLATE_SECTION_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(r#"section\s*=\s*(?:"([-+]?[0-9][0-9.eE+-]*)"|'([-+]?[0-9][0-9.eE+-]*)')"#)
+            .expect("Invalid regular expression")
+    });
+    // DEC
+    static CONSTRUCTOR_TEMPLATE_DELAY_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(r#"delay\s*=\s*(?:"([-+]?[0-9][0-9.eE+-]*)"|'([-+]?[0-9][0-9.eE+-]*)')"#)
+            .expect("Invalid regular expression")
+    });
+    // DEC
+    static CONSTRUCTOR_TEMPLATE_NUMLABELS_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(r#"labels\s*=\s*(?:"([-+]?[0-9][0-9.eE+-]*)"|'([-+]?[0-9][0-9.eE+-]*)')"#)
+            .expect("Invalid regular expression")
+    });
+
+    #[derive(Debug, Clone, PartialEq)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    #[non_exhaustive]
     pub enum ConstructorTemplateResult {
         Null {
             #[rust_sitter::leaf(pattern = r#"<\s*null\s*/\s*>"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _null: (),
         },
         Result(HandleTemplate),
     }
 
-    #[derive(TypedBuilder, Debug, PartialEq)]
+    impl ConstructorTemplateResult {
+        /// The result handle, or `None` if the template exports nothing
+        pub fn as_option(&self) -> Option<&HandleTemplate> {
+            match self {
+                ConstructorTemplateResult::Null { .. } => None,
+                ConstructorTemplateResult::Result(result) => Some(result),
+            }
+        }
+    }
+
+    #[derive(TypedBuilder, Debug, Clone, PartialEq)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct ConstructorTemplate {
         #[rust_sitter::leaf(pattern = r#"<\s*construct_tpl"#)]
         #[builder(default, setter(skip))]
+        #[cfg_attr(feature = "serde", serde(skip))]
         _start: (),
         #[rust_sitter::leaf(
-            pattern = r#"section\s*=\s*"(-?[0-9]+)""#,
+            pattern = r#"section\s*=\s*(?:"([-+]?[0-9][0-9.eE+-]*)"|'([-+]?[0-9][0-9.eE+-]*)')"#,
             transform = |v| {
-                ConstructorTemplate::SECTION_REGEX
-                    .get_or_init(|| {
-                        Regex::new(r#"section\s*=\s*"(-?[0-9]+)""#)
-                            .expect("Invalid regular expression")
-                    })
+                CONSTRUCTOR_TEMPLATE_SECTION_REGEX
                     .captures(v)
-                    .expect("No captures or no capture group").get(1).expect("No capture group").as_str().parse().expect("Invalid integer")
+                    .leaf_capture("ConstructorTemplate::section", 1)
+                    .leaf_integer("ConstructorTemplate::section")
             }
         )]
         #[builder(setter(transform = |v: impl Into<Integer>| {
             Some(v.into())
         }))]
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_integer::option"))]
         section: Option<Integer>,
         #[rust_sitter::leaf(
-            pattern = r#"delay\s*=\s*"(-?[0-9]+)""#,
+            pattern = r#"delay\s*=\s*(?:"([-+]?[0-9][0-9.eE+-]*)"|'([-+]?[0-9][0-9.eE+-]*)')"#,
             transform = |v| {
-                ConstructorTemplate::DELAY_REGEX
-                    .get_or_init(|| {
-                        Regex::new(r#"delay\s*=\s*"(-?[0-9]+)""#).expect("Invalid regular expression")
-                    })
+                CONSTRUCTOR_TEMPLATE_DELAY_REGEX
                     .captures(v)
-                    .expect("No captures or no capture group").get(1).expect("No capture group").as_str().parse().expect("Invalid integer")
+                    .leaf_capture("ConstructorTemplate::delay", 1)
+                    .leaf_integer("ConstructorTemplate::delay")
             }
         )]
         #[builder(setter(transform = |v: impl Into<Integer>| {
             Some(v.into())
         }))]
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_integer::option"))]
         delay: Option<Integer>,
         #[rust_sitter::leaf(
-            pattern = r#"labels\s*=\s*"(-?[0-9]+)""#,
+            pattern = r#"labels\s*=\s*(?:"([-+]?[0-9][0-9.eE+-]*)"|'([-+]?[0-9][0-9.eE+-]*)')"#,
             transform = |v| {
-                ConstructorTemplate::NUMLABELS_REGEX
-                    .get_or_init(|| {
-                        Regex::new(r#"labels\s*=\s*"(-?[0-9]+)""#)
-                            .expect("Invalid regular expression")
-                    })
+                CONSTRUCTOR_TEMPLATE_NUMLABELS_REGEX
                     .captures(v)
-                    .expect("No captures or no capture group").get(1).expect("No capture group").as_str().parse().expect("Invalid integer")
+                    .leaf_capture("ConstructorTemplate::numlabels", 1)
+                    .leaf_integer("ConstructorTemplate::numlabels")
             }
         )]
         #[builder(setter(transform = |v: impl Into<Integer>| {
             Some(v.into())
         }))]
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_integer::option"))]
         numlabels: Option<Integer>,
         #[rust_sitter::leaf(pattern = r#">"#)]
         #[builder(default, setter(skip))]
+        #[cfg_attr(feature = "serde", serde(skip))]
         _close: (),
         result: ConstructorTemplateResult,
         vec: Vec<OperationTemplate>,
         #[rust_sitter::leaf(pattern = r#"<\s*/\s*construct_tpl\s*>"#)]
         #[builder(default, setter(skip))]
+        #[cfg_attr(feature = "serde", serde(skip))]
         _end: (),
     }
 
-    impl Constructor {
-        // HEX
-        const PARENT_REGEX: OnceCell<Regex> = OnceCell::new();
-        // DEC
-        const FIRST_REGEX: OnceCell<Regex> = OnceCell::new();
-        // DEC
-        const LENGTH_REGEX: OnceCell<Regex> = OnceCell::new();
-        // DEC:DEC
-        const LINE_REGEX: OnceCell<Regex> = OnceCell::new();
+    impl ConstructorTemplate {
+        /// The section id, for named sections
+        pub fn section(&self) -> Option<&Integer> {
+            self.section.as_ref()
+        }
+
+        /// The delay slot depth, if the template has delay slots
+        pub fn delay(&self) -> Option<&Integer> {
+            self.delay.as_ref()
+        }
+
+        /// The number of labels the template declares
+        pub fn numlabels(&self) -> Option<&Integer> {
+            self.numlabels.as_ref()
+        }
+
+        /// The handle the template exports
+        pub fn result(&self) -> &ConstructorTemplateResult {
+            &self.result
+        }
+
+        /// The p-code operations
+        pub fn vec(&self) -> &[OperationTemplate] {
+            &self.vec
+        }
+
+        fn constant_templates(&self) -> impl Iterator<Item = &ConstantTemplateType> {
+            self.result
+                .as_option()
+                .into_iter()
+                .flat_map(HandleTemplate::constant_templates)
+                .chain(
+                    self.vec
+                        .iter()
+                        .flat_map(OperationTemplate::constant_templates),
+                )
+        }
     }
 
-    #[derive(TypedBuilder, Debug, PartialEq)]
+    // HEX
+    static CONSTRUCTOR_PARENT_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(r#"parent\s*=\s*(?:"(-?0[xX][0-9a-fA-F]+)"|'(-?0[xX][0-9a-fA-F]+)')"#)
+            .expect("Invalid regular expression")
+    });
+    // DEC
+    static CONSTRUCTOR_FIRST_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(r#"first\s*=\s*(?:"([-+]?[0-9][0-9.eE+-]*)"|'([-+]?[0-9][0-9.eE+-]*)')"#)
+            .expect("Invalid regular expression")
+    });
+    // DEC
+    static CONSTRUCTOR_LENGTH_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(r#"length\s*=\s*(?:"([-+]?[0-9][0-9.eE+-]*)"|'([-+]?[0-9][0-9.eE+-]*)')"#)
+            .expect("Invalid regular expression")
+    });
+    // DEC:DEC, or DEC alone on some community specs
+    static CONSTRUCTOR_LINE_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(r#"line\s*=\s*(?:"(-?[0-9]+)(?::(-?[0-9]+))?"|'(-?[0-9]+)(?::(-?[0-9]+))?')"#)
+            .expect("Invalid regular expression")
+    });
+
+    #[derive(TypedBuilder, Debug, Clone, PartialEq)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     #[rust_sitter::prec_left(1)]
     pub struct Constructor {
         #[rust_sitter::leaf(pattern = r#"<\s*constructor"#)]
         #[builder(default, setter(skip))]
+        #[cfg_attr(feature = "serde", serde(skip))]
         _start: (),
         #[rust_sitter::leaf(
-            pattern = r#"parent\s*=\s*"0x([0-9a-fA-F]+)""#,
+            pattern = r#"parent\s*=\s*(?:"(-?0[xX][0-9a-fA-F]+)"|'(-?0[xX][0-9a-fA-F]+)')"#,
             transform = |v| {
-                Integer::from_string_base(16, Constructor::PARENT_REGEX
-                    .get_or_init(|| {
-                        Regex::new(r#"parent\s*=\s*"0x([0-9a-fA-F]+)""#)
-                            .expect("Invalid regular expression")
-                    })
+                CONSTRUCTOR_PARENT_REGEX
                     .captures(v)
-                    .expect("No captures or no capture group")
-                    .get(1)
-                    .expect("No capture group").as_str()).expect("Invalid integer")
+                    .leaf_capture("Constructor::parent", 1)
+                    .leaf_hex_integer("Constructor::parent")
             }
         )]
         #[builder(setter(transform = |v: impl Into<Integer>| {
-            v.into()
+            HexInteger(v.into())
         }))]
-        parent: Integer,
+        parent: HexInteger,
         #[rust_sitter::leaf(
-            pattern = r#"first\s*=\s*"(-?[0-9]+)""#,
+            pattern = r#"first\s*=\s*(?:"([-+]?[0-9][0-9.eE+-]*)"|'([-+]?[0-9][0-9.eE+-]*)')"#,
             transform = |v| {
-                Constructor::FIRST_REGEX
-                    .get_or_init(|| {
-                        Regex::new(r#"first\s*=\s*"(-?[0-9]+)""#).expect("Invalid regular expression")
-                    })
+                CONSTRUCTOR_FIRST_REGEX
                     .captures(v)
-                    .expect("No captures or no capture group")
-                    .get(1)
-                    .expect("No capture group").as_str().parse().expect("Invalid integer")
+                    .leaf_capture("Constructor::first", 1)
+                    .leaf_integer("Constructor::first")
             }
         )]
         #[builder(setter(transform = |v: impl Into<Integer>| {
             v.into()
         }))]
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_integer"))]
         first: Integer,
         #[rust_sitter::leaf(
-            pattern = r#"length\s*=\s*"(-?[0-9]+)""#,
+            pattern = r#"length\s*=\s*(?:"([-+]?[0-9][0-9.eE+-]*)"|'([-+]?[0-9][0-9.eE+-]*)')"#,
             transform = |v| {
-                Constructor::LENGTH_REGEX
-                    .get_or_init(|| {
-                        Regex::new(r#"length\s*=\s*"(-?[0-9]+)""#).expect("Invalid regular expression")
-                    })
+                CONSTRUCTOR_LENGTH_REGEX
                     .captures(v)
-                    .expect("No captures or no capture group").get(1).expect("No capture group").as_str().parse().expect("Invalid integer")
+                    .leaf_capture("Constructor::length", 1)
+                    .leaf_integer("Constructor::length")
             }
         )]
         #[builder(setter(transform = |v: impl Into<Integer>| {
             v.into()
         }))]
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_integer"))]
         length: Integer,
         #[rust_sitter::leaf(
-            pattern = r#"line\s*=\s*"(-?[0-9]+):(-?[0-9]+)""#,
+            pattern = r#"line\s*=\s*(?:"(-?[0-9]+)(?::(-?[0-9]+))?"|'(-?[0-9]+)(?::(-?[0-9]+))?')"#,
             transform = |v| {
-                let captures = Constructor::LINE_REGEX
-                    .get_or_init(|| {
-                        Regex::new(r#"line\s*=\s*"(-?[0-9]+):(-?[0-9]+)""#)
-                            .expect("Invalid regular expression")
-                    })
-                    .captures(v)
-                    .expect("No captures or no capture group");
-                let line = captures.get(1).expect("No capture group").as_str().parse().expect("Invalid integer");
-                let col = captures.get(2).expect("No capture group").as_str().parse().expect("Invalid integer");
-                (line, col)
+                let captures = CONSTRUCTOR_LINE_REGEX
+                    .captures(v);
+                let first = captures
+                    .leaf_capture("Constructor::line", 1)
+                    .leaf_integer("Constructor::line");
+                // A bare value is the line, in source file 0
+                match captures.leaf_optional_capture(2) {
+                    Some(line) => (first, line.leaf_integer("Constructor::line")),
+                    None => (Integer::from(0), first),
+                }
             }
         )]
         #[builder(setter(transform = |v: impl Into<(Integer, Integer)>| {
-            let (line, col) = v.into();
-            (line, col)
+            let (file, line) = v.into();
+            (file, line)
         }))]
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_integer::pair"))]
         line: (Integer, Integer),
         #[rust_sitter::leaf(pattern = r#">"#)]
         #[builder(default, setter(skip))]
+        #[cfg_attr(feature = "serde", serde(skip))]
         _close: (),
         operands: Vec<ConstructorOperand>,
         printpiece: Vec<PrintPieceType>,
@@ -2344,377 +4373,710 @@ pub mod grammar {
         namedtempl: Vec<ConstructorTemplate>,
         #[rust_sitter::leaf(pattern = r#"<\s*/\s*constructor\s*>"#)]
         #[builder(default, setter(skip))]
+        #[cfg_attr(feature = "serde", serde(skip))]
         _end: (),
     }
 
-    impl PatternBlockWord {
-        // HEX
-        const MASK_REGEX: OnceCell<Regex> = OnceCell::new();
-        const VAL_REGEX: OnceCell<Regex> = OnceCell::new();
+    impl Constructor {
+        /// The id of the subtable this constructor belongs to
+        pub fn parent(&self) -> &HexInteger {
+            &self.parent
+        }
+
+        /// The index of the print piece separating the mnemonic from the operands
+        pub fn first(&self) -> &Integer {
+            &self.first
+        }
+
+        /// The minimum length of the constructor's pattern in bytes
+        pub fn length(&self) -> &Integer {
+            &self.length
+        }
+
+        /// The source file index and line the constructor was defined on. A `line` attribute
+        /// without a source file index is read as a line in source file 0.
+        pub fn line(&self) -> &(Integer, Integer) {
+            &self.line
+        }
+
+        /// The operands, in order
+        pub fn operands(&self) -> &[ConstructorOperand] {
+            &self.operands
+        }
+
+        /// The display pieces, in order
+        pub fn printpiece(&self) -> &[PrintPieceType] {
+            &self.printpiece
+        }
+
+        /// The context changes applied when the constructor matches
+        pub fn contexts(&self) -> &[ContextChangeType] {
+            &self.contexts
+        }
+
+        /// The context operations of [`Constructor::contexts`], in order
+        pub fn context_ops(&self) -> impl Iterator<Item = &Operation> {
+            self.contexts.iter().filter_map(|change| match change {
+                ContextChangeType::Operation(operation) => Some(operation),
+                ContextChangeType::Commit(_) => None,
+            })
+        }
+
+        /// The context commits of [`Constructor::contexts`], in order
+        pub fn commits(&self) -> impl Iterator<Item = &Commit> {
+            self.contexts.iter().filter_map(|change| match change {
+                ContextChangeType::Commit(commit) => Some(commit),
+                ContextChangeType::Operation(_) => None,
+            })
+        }
+
+        /// The p-code template of the main section, if any
+        pub fn templ(&self) -> Option<&ConstructorTemplate> {
+            self.templ.as_ref()
+        }
+
+        /// The p-code templates of named sections
+        pub fn namedtempl(&self) -> &[ConstructorTemplate] {
+            &self.namedtempl
+        }
+
+        /// Whether the constructor has semantics, i.e. a main section template. A constructor
+        /// without one was declared `unimpl` and has no p-code at all. A template whose
+        /// [`ConstructorTemplate::result`] is [`ConstructorTemplateResult::Null`] is still a
+        /// body: it exports nothing, but may hold operations, or none for an empty `{}`.
+        pub fn has_semantic_body(&self) -> bool {
+            self.templ.is_some()
+        }
+
+        /// The display layout of the constructor: the print pieces concatenated, with each
+        /// operand written as `{n}` by its index, e.g. `imm {0}, {1}`. Operands are numbered
+        /// by their `id`, not their position among the pieces, so empty pieces contribute
+        /// nothing. Literal braces in the pieces are doubled, as `{{` and `}}`, so they cannot
+        /// be mistaken for an operand.
+        pub fn display_template(&self) -> String {
+            self.printpiece
+                .iter()
+                .map(|piece| match piece {
+                    PrintPieceType::Print(print) => {
+                        print.piece().replace('{', "{{").replace('}', "}}")
+                    }
+                    PrintPieceType::Operand(operand) => format!("{{{}}}", operand.id()),
+                })
+                .collect()
+        }
+
+        /// The operand symbol each operand refers to, in operand order. An entry is `None`
+        /// if the operand's id is not an operand symbol in `table`.
+        pub fn resolve_operands<'a>(
+            &'a self,
+            table: &'a SymbolTable,
+        ) -> Vec<Option<&'a OperandSymbol>> {
+            self.operands
+                .iter()
+                .map(|operand| match table.symbol_by_id(&operand.id) {
+                    Some(SleighSymbolType::TripleSymbol(TripleSymbol::SpecificSymbol(
+                        SpecificSymbol::OperandSymbol(symbol),
+                    ))) => Some(symbol),
+                    _ => None,
+                })
+                .collect()
+        }
+
+        /// The minimum length of the constructor's pattern in bytes, as an `i64`. See
+        /// [`Constructor::length`].
+        pub fn min_length(&self) -> i64 {
+            i64::saturating_from(&self.length)
+        }
+
+        /// The length in bytes of the constructor including its operands: the furthest end
+        /// of the constructor's own pattern or any operand. Each operand ends `off` bytes
+        /// after the end of its `base` operand (or after the start of the constructor if it
+        /// has none), plus its `minlen`.
+        ///
+        /// This is exact only when every operand has a fixed size. An operand defined by a
+        /// subtable whose constructors differ in length contributes its shortest length, so
+        /// the result is a lower bound for variable-length operands. Operands that do not
+        /// resolve in `table` contribute nothing.
+        pub fn length_with_operands(&self, table: &SymbolTable) -> i64 {
+            let mut ends: Vec<i64> = Vec::with_capacity(self.operands.len());
+            for operand in self.resolve_operands(table) {
+                let end = operand.map_or(0, |symbol| {
+                    let base = usize::try_from(symbol.base())
+                        .ok()
+                        .and_then(|base| ends.get(base).copied())
+                        .unwrap_or(0);
+                    base.saturating_add(i64::saturating_from(symbol.off()))
+                        .saturating_add(i64::saturating_from(symbol.minlen()))
+                });
+                ends.push(end);
+            }
+            ends.into_iter().fold(self.min_length(), i64::max)
+        }
+
+        /// The p-code template for `section`: the main template for `None`, otherwise the
+        /// named section template whose `section` index matches
+        pub fn template_for_section(&self, section: Option<i64>) -> Option<&ConstructorTemplate> {
+            match section {
+                None => self.templ.as_ref(),
+                Some(section) => self.namedtempl.iter().find(|templ| {
+                    templ
+                        .section
+                        .as_ref()
+                        .is_some_and(|index| *index == section)
+                }),
+            }
+        }
     }
 
-    #[derive(TypedBuilder, Debug, PartialEq)]
+    // HEX
+    static PATTERN_BLOCK_WORD_MASK_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(r#"mask\s*=\s*(?:"(-?0[xX][0-9a-fA-F]+)"|'(-?0[xX][0-9a-fA-F]+)')"#)
+            .expect("Invalid regular expression")
+    });
+    static PATTERN_BLOCK_WORD_VAL_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(r#"val\s*=\s*(?:"(-?0[xX][0-9a-fA-F]+)"|'(-?0[xX][0-9a-fA-F]+)')"#)
+            .expect("Invalid regular expression")
+    });
+
+    #[derive(TypedBuilder, Debug, Clone, PartialEq)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct PatternBlockWord {
         #[rust_sitter::leaf(pattern = r#"<\s*mask_word"#)]
         #[builder(default, setter(skip))]
+        #[cfg_attr(feature = "serde", serde(skip))]
         _start: (),
         #[rust_sitter::leaf(
-            pattern = r#"mask\s*=\s*"0x([0-9a-fA-F]+)""#,
+            pattern = r#"mask\s*=\s*(?:"(-?0[xX][0-9a-fA-F]+)"|'(-?0[xX][0-9a-fA-F]+)')"#,
             transform = |v| {
-                Integer::from_string_base(16, PatternBlockWord::MASK_REGEX
-                    .get_or_init(|| {
-                        Regex::new(r#"mask\s*=\s*"0x([0-9a-fA-F]+)""#)
-                            .expect("Invalid regular expression")
-                    })
+                PATTERN_BLOCK_WORD_MASK_REGEX
                     .captures(v)
-                    .expect("No captures or no capture group")
-                    .get(1)
-                    .expect("No capture group").as_str()).expect("Invalid integer")
+                    .leaf_capture("PatternBlockWord::mask", 1)
+                    .leaf_hex_integer("PatternBlockWord::mask")
             }
         )]
         #[builder(setter(transform = |v: impl Into<Integer>| {
-            v.into()
+            HexInteger(v.into())
         }))]
-        mask: Integer,
+        mask: HexInteger,
         #[rust_sitter::leaf(
-            pattern = r#"val\s*=\s*"0x([0-9a-fA-F]+)""#,
+            pattern = r#"val\s*=\s*(?:"(-?0[xX][0-9a-fA-F]+)"|'(-?0[xX][0-9a-fA-F]+)')"#,
             transform = |v| {
-                Integer::from_string_base(16, PatternBlockWord::VAL_REGEX
-                    .get_or_init(|| {
-                        Regex::new(r#"val\s*=\s*"0x([0-9a-fA-F]+)""#)
-                            .expect("Invalid regular expression")
-                    })
+                PATTERN_BLOCK_WORD_VAL_REGEX
                     .captures(v)
-                    .expect("No captures or no capture group")
-                    .get(1)
-                    .expect("No capture group").as_str()).expect("Invalid integer")
+                    .leaf_capture("PatternBlockWord::val", 1)
+                    .leaf_hex_integer("PatternBlockWord::val")
             }
         )]
         #[builder(setter(transform = |v: impl Into<Integer>| {
-            v.into()
+            HexInteger(v.into())
         }))]
-        val: Integer,
+        val: HexInteger,
         #[rust_sitter::leaf(pattern = r#"/\s*>"#)]
         #[builder(default, setter(skip))]
+        #[cfg_attr(feature = "serde", serde(skip))]
         _end: (),
     }
 
-    impl PatternBlock {
-        // DEC
-        const OFFSET_REGEX: OnceCell<Regex> = OnceCell::new();
-        // DEC
-        const NONZERO_REGEX: OnceCell<Regex> = OnceCell::new();
+    impl PatternBlockWord {
+        /// The bits of the word that must match
+        pub fn mask(&self) -> &HexInteger {
+            &self.mask
+        }
+
+        /// The value the masked bits must have
+        pub fn val(&self) -> &HexInteger {
+            &self.val
+        }
     }
 
-    #[derive(TypedBuilder, Debug, PartialEq)]
+    // DEC
+    static PATTERN_BLOCK_OFFSET_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(r#"offset\s*=\s*(?:"([-+]?[0-9][0-9.eE+-]*)"|'([-+]?[0-9][0-9.eE+-]*)')"#)
+            .expect("Invalid regular expression")
+    });
+    // DEC
+    static PATTERN_BLOCK_NONZERO_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(r#"nonzero\s*=\s*(?:"([-+]?[0-9][0-9.eE+-]*)"|'([-+]?[0-9][0-9.eE+-]*)')"#)
+            .expect("Invalid regular expression")
+    });
+
+    #[derive(TypedBuilder, Debug, Clone, PartialEq)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct PatternBlock {
         #[rust_sitter::leaf(pattern = r#"<\s*pat_block"#)]
         #[builder(default, setter(skip))]
+        #[cfg_attr(feature = "serde", serde(skip))]
         _start: (),
         #[rust_sitter::leaf(
-            pattern = r#"offset\s*=\s*"(-?[0-9]+)""#,
+            pattern = r#"offset\s*=\s*(?:"([-+]?[0-9][0-9.eE+-]*)"|'([-+]?[0-9][0-9.eE+-]*)')"#,
             transform = |v| {
-                PatternBlock::OFFSET_REGEX
-                    .get_or_init(|| {
-                        Regex::new(r#"offset\s*=\s*"(-?[0-9]+)""#).expect("Invalid regular expression")
-                    })
+                PATTERN_BLOCK_OFFSET_REGEX
                     .captures(v)
-                    .expect("No captures or no capture group")
-                    .get(1)
-                    .expect("No capture group").as_str().parse().expect("Invalid integer")
+                    .leaf_capture("PatternBlock::offset", 1)
+                    .leaf_integer("PatternBlock::offset")
             }
         )]
         #[builder(setter(transform = |v: impl Into<Integer>| {
             v.into()
         }))]
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_integer"))]
         offset: Integer,
         #[rust_sitter::leaf(
-            pattern = r#"nonzero\s*=\s*"(-?[0-9]+)""#,
+            pattern = r#"nonzero\s*=\s*(?:"([-+]?[0-9][0-9.eE+-]*)"|'([-+]?[0-9][0-9.eE+-]*)')"#,
             transform = |v| {
-                PatternBlock::NONZERO_REGEX
-                    .get_or_init(|| {
-                        Regex::new(r#"nonzero\s*=\s*"(-?[0-9]+)""#).expect("Invalid regular expression")
-                    })
+                PATTERN_BLOCK_NONZERO_REGEX
                     .captures(v)
-                    .expect("No captures or no capture group")
-                    .get(1)
-                    .expect("No capture group").as_str().parse().expect("Invalid integer")
+                    .leaf_capture("PatternBlock::nonzero", 1)
+                    .leaf_integer("PatternBlock::nonzero")
             }
         )]
         #[builder(setter(transform = |v: impl Into<Integer>| {
             v.into()
         }))]
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_integer"))]
         nonzero: Integer,
         #[rust_sitter::leaf(pattern = r#">"#)]
         #[builder(default, setter(skip))]
+        #[cfg_attr(feature = "serde", serde(skip))]
         _close: (),
         mask_vals: Vec<PatternBlockWord>,
         #[rust_sitter::leaf(pattern = r#"<\s*/\s*pat_block\s*>"#)]
         #[builder(default, setter(skip))]
+        #[cfg_attr(feature = "serde", serde(skip))]
         _end: (),
     }
 
-    #[derive(TypedBuilder, Debug, PartialEq)]
+    impl PatternBlock {
+        /// The byte offset of the first word
+        pub fn offset(&self) -> &Integer {
+            &self.offset
+        }
+
+        /// The number of bytes the pattern constrains
+        pub fn nonzero(&self) -> &Integer {
+            &self.nonzero
+        }
+
+        /// The mask and value words, in order
+        pub fn mask_vals(&self) -> &[PatternBlockWord] {
+            &self.mask_vals
+        }
+    }
+
+    #[derive(TypedBuilder, Debug, Clone, PartialEq)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct InstructionPattern {
         #[rust_sitter::leaf(pattern = r#"<\s*instruct_pat\s*>"#)]
         #[builder(default, setter(skip))]
+        #[cfg_attr(feature = "serde", serde(skip))]
         _start: (),
         mask_value: PatternBlock,
         #[rust_sitter::leaf(pattern = r#"<\s*/\s*instruct_pat\s*>"#)]
         #[builder(default, setter(skip))]
+        #[cfg_attr(feature = "serde", serde(skip))]
         _end: (),
     }
 
-    #[derive(TypedBuilder, Debug, PartialEq)]
+    impl InstructionPattern {
+        /// The instruction bytes the pattern matches
+        pub fn mask_value(&self) -> &PatternBlock {
+            &self.mask_value
+        }
+    }
+
+    #[derive(TypedBuilder, Debug, Clone, PartialEq)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct ContextPattern {
         #[rust_sitter::leaf(pattern = r#"<\s*context_pat\s*>"#)]
         #[builder(default, setter(skip))]
+        #[cfg_attr(feature = "serde", serde(skip))]
         _start: (),
         mask_value: PatternBlock,
         #[rust_sitter::leaf(pattern = r#"<\s*/\s*context_pat\s*>"#)]
         #[builder(default, setter(skip))]
+        #[cfg_attr(feature = "serde", serde(skip))]
         _end: (),
     }
 
-    #[derive(TypedBuilder, Debug, PartialEq)]
+    impl ContextPattern {
+        /// The context bits the pattern matches
+        pub fn mask_value(&self) -> &PatternBlock {
+            &self.mask_value
+        }
+    }
+
+    #[derive(TypedBuilder, Debug, Clone, PartialEq)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct CombinePattern {
         #[rust_sitter::leaf(pattern = r#"<\s*combine_pat\s*>"#)]
         #[builder(default, setter(skip))]
+        #[cfg_attr(feature = "serde", serde(skip))]
         _start: (),
         context: ContextPattern,
         instr: InstructionPattern,
         #[rust_sitter::leaf(pattern = r#"<\s*/\s*combine_pat\s*>"#)]
         #[builder(default, setter(skip))]
+        #[cfg_attr(feature = "serde", serde(skip))]
         _end: (),
     }
 
-    #[derive(Debug, PartialEq)]
+    impl CombinePattern {
+        /// The context half of the pattern
+        pub fn context(&self) -> &ContextPattern {
+            &self.context
+        }
+
+        /// The instruction half of the pattern
+        pub fn instr(&self) -> &InstructionPattern {
+            &self.instr
+        }
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    #[non_exhaustive]
     pub enum DisjointPatternType {
         Instruction(InstructionPattern),
         Context(ContextPattern),
         Combine(CombinePattern),
     }
 
-    impl DecisionNodePair {
-        // DEC
-        const ID_REGEX: OnceCell<Regex> = OnceCell::new();
-    }
+    // DEC
+    static DECISION_NODE_PAIR_ID_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(r#"id\s*=\s*(?:"([-+]?[0-9][0-9.eE+-]*)"|'([-+]?[0-9][0-9.eE+-]*)')"#)
+            .expect("Invalid regular expression")
+    });
 
-    #[derive(TypedBuilder, Debug, PartialEq)]
+    #[derive(TypedBuilder, Debug, Clone, PartialEq)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct DecisionNodePair {
         #[rust_sitter::leaf(pattern = r#"<\s*pair"#)]
         #[builder(default, setter(skip))]
+        #[cfg_attr(feature = "serde", serde(skip))]
         _start: (),
         #[rust_sitter::leaf(
-            pattern = r#"id\s*=\s*"(-?[0-9]+)""#,
+            pattern = r#"id\s*=\s*(?:"([-+]?[0-9][0-9.eE+-]*)"|'([-+]?[0-9][0-9.eE+-]*)')"#,
             transform = |v| {
-                DecisionNodePair::ID_REGEX
-                    .get_or_init(|| {
-                        Regex::new(r#"id\s*=\s*"(-?[0-9]+)""#).expect("Invalid regular expression")
-                    })
+                DECISION_NODE_PAIR_ID_REGEX
                     .captures(v)
-                    .expect("No captures or no capture group")
-                    .get(1)
-                    .expect("No capture group").as_str().parse().expect("Invalid integer")
+                    .leaf_capture("DecisionNodePair::id", 1)
+                    .leaf_integer("DecisionNodePair::id")
             }
         )]
         #[builder(setter(transform = |v: impl Into<Integer>| {
             v.into()
         }))]
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_integer"))]
         id: Integer,
         #[rust_sitter::leaf(pattern = r#">"#)]
         #[builder(default, setter(skip))]
+        #[cfg_attr(feature = "serde", serde(skip))]
         _close: (),
         pattern: DisjointPatternType,
         #[rust_sitter::leaf(pattern = r#"<\s*/\s*pair\s*>"#)]
         #[builder(default, setter(skip))]
+        #[cfg_attr(feature = "serde", serde(skip))]
         _end: (),
     }
 
-    impl DecisionNode {
-        // DEC
-        const NUMBER_REGEX: OnceCell<Regex> = OnceCell::new();
-        // BOOLEAN
-        const CONTEXT_REGEX: OnceCell<Regex> = OnceCell::new();
-        // DEC
-        const START_REGEX: OnceCell<Regex> = OnceCell::new();
-        // DEC
-        const SIZE_REGEX: OnceCell<Regex> = OnceCell::new();
+    impl DecisionNodePair {
+        /// The index of the constructor this pattern selects
+        pub fn id(&self) -> &Integer {
+            &self.id
+        }
+
+        /// The pattern the constructor must match
+        pub fn pattern(&self) -> &DisjointPatternType {
+            &self.pattern
+        }
     }
 
-    #[derive(TypedBuilder, Debug, PartialEq)]
+    // DEC
+    static DECISION_NODE_NUMBER_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(r#"number\s*=\s*(?:"([-+]?[0-9][0-9.eE+-]*)"|'([-+]?[0-9][0-9.eE+-]*)')"#)
+            .expect("Invalid regular expression")
+    });
+    // BOOLEAN
+    static DECISION_NODE_CONTEXT_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(r#"context\s*=\s*(?:"([a-zA-Z0-9]+)"|'([a-zA-Z0-9]+)')"#)
+            .expect("Invalid regular expression")
+    });
+    // DEC
+    static DECISION_NODE_START_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(r#"start\s*=\s*(?:"([-+]?[0-9][0-9.eE+-]*)"|'([-+]?[0-9][0-9.eE+-]*)')"#)
+            .expect("Invalid regular expression")
+    });
+    // DEC
+    static DECISION_NODE_SIZE_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(r#"size\s*=\s*(?:"([-+]?[0-9][0-9.eE+-]*)"|'([-+]?[0-9][0-9.eE+-]*)')"#)
+            .expect("Invalid regular expression")
+    });
+
+    #[derive(TypedBuilder, Debug, Clone, PartialEq)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct DecisionNode {
         #[rust_sitter::leaf(pattern = r#"<\s*decision"#)]
         #[builder(default, setter(skip))]
+        #[cfg_attr(feature = "serde", serde(skip))]
         _start: (),
         #[rust_sitter::leaf(
-            pattern = r#"number\s*=\s*"(-?[0-9]+)""#,
+            pattern = r#"number\s*=\s*(?:"([-+]?[0-9][0-9.eE+-]*)"|'([-+]?[0-9][0-9.eE+-]*)')"#,
             transform = |v| {
-                DecisionNode::NUMBER_REGEX
-                    .get_or_init(|| {
-                        Regex::new(r#"number\s*=\s*"(-?[0-9]+)""#).expect("Invalid regular expression")
-                    })
+                DECISION_NODE_NUMBER_REGEX
                     .captures(v)
-                    .expect("No captures or no capture group").get(1).expect("No capture group").as_str().parse().expect("Invalid integer")
+                    .leaf_capture("DecisionNode::number", 1)
+                    .leaf_integer("DecisionNode::number")
             }
         )]
         #[builder(setter(transform = |v: impl Into<Integer>| {
             v.into()
         }))]
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_integer"))]
         number: Integer,
         #[rust_sitter::leaf(
-            pattern = r#"context\s*=\s*"([a-z]+)""#,
+            pattern = r#"context\s*=\s*(?:"([a-zA-Z0-9]+)"|'([a-zA-Z0-9]+)')"#,
             transform = |v| {
-                DecisionNode::CONTEXT_REGEX
-                    .get_or_init(|| {
-                        Regex::new(r#"context\s*=\s*"([a-z]+)""#).expect("Invalid regular expression")
-                    })
+                DECISION_NODE_CONTEXT_REGEX
                     .captures(v)
-                    .expect("No captures or no capture group").get(1).expect("No capture group").as_str().parse().expect("Invalid boolean")
+                    .leaf_capture("DecisionNode::context", 1)
+                    .leaf_bool("DecisionNode::context")
             }
         )]
         context: bool,
         #[rust_sitter::leaf(
-            pattern = r#"start\s*=\s*"(-?[0-9]+)""#,
+            pattern = r#"start\s*=\s*(?:"([-+]?[0-9][0-9.eE+-]*)"|'([-+]?[0-9][0-9.eE+-]*)')"#,
             transform = |v| {
-                DecisionNode::START_REGEX
-                    .get_or_init(|| {
-                        Regex::new(r#"start\s*=\s*"(-?[0-9]+)""#).expect("Invalid regular expression")
-                    })
+                DECISION_NODE_START_REGEX
                     .captures(v)
-                    .expect("No captures or no capture group").get(1).expect("No capture group").as_str().parse().expect("Invalid integer")
+                    .leaf_capture("DecisionNode::start", 1)
+                    .leaf_integer("DecisionNode::start")
             }
         )]
         #[builder(setter(transform = |v: impl Into<Integer>| {
             v.into()
         }))]
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_integer"))]
         start: Integer,
         #[rust_sitter::leaf(
-            pattern = r#"size\s*=\s*"(-?[0-9]+)""#,
+            pattern = r#"size\s*=\s*(?:"([-+]?[0-9][0-9.eE+-]*)"|'([-+]?[0-9][0-9.eE+-]*)')"#,
             transform = |v| {
-                DecisionNode::SIZE_REGEX
-                    .get_or_init(|| {
-                        Regex::new(r#"size\s*=\s*"(-?[0-9]+)""#).expect("Invalid regular expression")
-                    })
+                DECISION_NODE_SIZE_REGEX
                     .captures(v)
-                    .expect("No captures or no capture group").get(1).expect("No capture group").as_str().parse().expect("Invalid integer")
+                    .leaf_capture("DecisionNode::bitsize", 1)
+                    .leaf_integer("DecisionNode::bitsize")
             }
         )]
         #[builder(setter(transform = |v: impl Into<Integer>| {
             v.into()
         }))]
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_integer"))]
         bitsize: Integer,
         #[rust_sitter::leaf(pattern = r#">"#)]
         #[builder(default, setter(skip))]
+        #[cfg_attr(feature = "serde", serde(skip))]
         _close: (),
         pairs: Vec<DecisionNodePair>,
         children: Vec<DecisionNode>,
         #[rust_sitter::leaf(pattern = r#"<\s*/\s*decision\s*>"#)]
         #[builder(default, setter(skip))]
+        #[cfg_attr(feature = "serde", serde(skip))]
         _end: (),
     }
 
-    impl SubtableSymbol {
-        const NUMCT_REGEX: OnceCell<Regex> = OnceCell::new();
+    impl DecisionNode {
+        /// The number of constructors below this node
+        pub fn number(&self) -> &Integer {
+            &self.number
+        }
+
+        /// Whether the node branches on context bits rather than instruction bits
+        pub fn context(&self) -> bool {
+            self.context
+        }
+
+        /// The first bit the node branches on
+        pub fn start(&self) -> &Integer {
+            &self.start
+        }
+
+        /// The number of bits the node branches on. Leaves branch on none.
+        pub fn bitsize(&self) -> &Integer {
+            &self.bitsize
+        }
+
+        /// The candidate constructors, for leaves
+        pub fn pairs(&self) -> &[DecisionNodePair] {
+            &self.pairs
+        }
+
+        /// The child nodes, one per value of the branched bits
+        pub fn children(&self) -> &[DecisionNode] {
+            &self.children
+        }
     }
 
-    #[derive(TypedBuilder, Debug, PartialEq)]
+    static SUBTABLE_SYMBOL_NUMCT_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(r#"numct\s*=\s*(?:"([-+]?[0-9][0-9.eE+-]*)"|'([-+]?[0-9][0-9.eE+-]*)')"#)
+            .expect("Invalid regular expression")
+    });
+
+    #[derive(TypedBuilder, Debug, Clone, PartialEq)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct SubtableSymbol {
         header: SymbolHeader,
         #[rust_sitter::leaf(
-            pattern = r#"numct\s*=\s*"(-?[0-9]+)""#,
+            pattern = r#"numct\s*=\s*(?:"([-+]?[0-9][0-9.eE+-]*)"|'([-+]?[0-9][0-9.eE+-]*)')"#,
             transform = |v| {
-                SubtableSymbol::NUMCT_REGEX
-                    .get_or_init(|| {
-                        Regex::new(r#"numct\s*=\s*"(-?[0-9]+)""#)
-                            .expect("Invalid regular expression")
-                    })
+                SUBTABLE_SYMBOL_NUMCT_REGEX
                     .captures(v)
-                    .expect("No captures or no capture group")
-                    .get(1)
-                    .expect("No capture group").as_str().parse().expect("Invalid integer")
+                    .leaf_capture("SubtableSymbol::numct", 1)
+                    .leaf_integer("SubtableSymbol::numct")
             }
         )]
-        #[builder(setter(transform = |v: impl Into<Integer>| {
-            v.into()
+        #[builder(default, setter(transform = |v: impl Into<Integer>| {
+            Some(v.into())
         }))]
-        numct: Integer,
+        /// `numct` is always written by Ghidra, but is tolerated missing since the
+        /// constructor list is the source of truth
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_integer::option"))]
+        numct: Option<Integer>,
         #[rust_sitter::leaf(pattern = r#">"#)]
         #[builder(default, setter(skip))]
+        #[cfg_attr(feature = "serde", serde(skip))]
         _close: (),
         constructors: Vec<Constructor>,
         decisiontree: DecisionNode,
     }
 
-    impl ValueTableValue {
-        // DEC
-        const VAL_REGEX: OnceCell<Regex> = OnceCell::new();
+    impl SubtableSymbol {
+        /// The symbol header
+        pub fn header(&self) -> &SymbolHeader {
+            &self.header
+        }
+
+        /// The number of constructors, if declared
+        pub fn numct(&self) -> Option<&Integer> {
+            self.numct.as_ref()
+        }
+
+        /// The constructors, in id order
+        pub fn constructors(&self) -> &[Constructor] {
+            &self.constructors
+        }
+
+        /// The constructor with id `index`, i.e. at that position in [`Self::constructors`]
+        pub fn constructor(&self, index: usize) -> Option<&Constructor> {
+            self.constructors.get(index)
+        }
+
+        /// The root of the tree selecting among the constructors
+        pub fn decisiontree(&self) -> &DecisionNode {
+            &self.decisiontree
+        }
+
+        /// The declared constructor count, or the number of parsed constructors if the
+        /// `numct` attribute is absent
+        pub fn numct_or_len(&self) -> Integer {
+            self.numct
+                .clone()
+                .unwrap_or_else(|| Integer::from(self.constructors.len()))
+        }
     }
 
-    #[derive(TypedBuilder, Debug, PartialEq)]
+    // DEC
+    static VALUE_TABLE_VALUE_VAL_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(r#"val\s*=\s*(?:"([-+]?[0-9][0-9.eE+-]*)"|'([-+]?[0-9][0-9.eE+-]*)')"#)
+            .expect("Invalid regular expression")
+    });
+
+    #[derive(TypedBuilder, Debug, Clone, PartialEq)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct ValueTableValue {
         #[rust_sitter::leaf(pattern = r#"<\s*valuetab"#)]
         #[builder(default, setter(skip))]
+        #[cfg_attr(feature = "serde", serde(skip))]
         _start: (),
         #[rust_sitter::leaf(
-            pattern = r#"val\s*=\s*"(-?[0-9]+)""#,
+            pattern = r#"val\s*=\s*(?:"([-+]?[0-9][0-9.eE+-]*)"|'([-+]?[0-9][0-9.eE+-]*)')"#,
             transform = |v| {
-                ValueTableValue::VAL_REGEX
-                    .get_or_init(|| {
-                        Regex::new(r#"val\s*=\s*"(-?[0-9]+)""#).expect("Invalid regular expression")
-                    })
+                VALUE_TABLE_VALUE_VAL_REGEX
                     .captures(v)
-                    .expect("No captures or no capture group")
-                    .get(1)
-                    .expect("No capture group").as_str().parse().expect("Invalid integer")
+                    .leaf_capture("ValueTableValue::val", 1)
+                    .leaf_integer("ValueTableValue::val")
             }
         )]
         #[builder(setter(transform = |v: impl Into<Integer>| {
             v.into()
         }))]
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_integer"))]
         val: Integer,
         #[rust_sitter::leaf(pattern = r#"/\s*>"#)]
         #[builder(default, setter(skip))]
+        #[cfg_attr(feature = "serde", serde(skip))]
         _end: (),
     }
 
-    #[derive(TypedBuilder, Debug, PartialEq)]
+    impl ValueTableValue {
+        /// The value
+        pub fn val(&self) -> &Integer {
+            &self.val
+        }
+    }
+
+    #[derive(TypedBuilder, Debug, Clone, PartialEq)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct ValueMapSymbol {
         #[rust_sitter::leaf(pattern = r#"<\s*valuemap_sym"#)]
         #[builder(default, setter(skip))]
+        #[cfg_attr(feature = "serde", serde(skip))]
         _start: (),
         header: SymbolHeader,
         #[rust_sitter::leaf(pattern = r#">"#)]
         #[builder(default, setter(skip))]
+        #[cfg_attr(feature = "serde", serde(skip))]
         _close: (),
         patval: PatternValueType,
         valuetable: Vec<ValueTableValue>,
         #[rust_sitter::leaf(pattern = r#"<\s*/\s*valuemap_sym\s*>"#)]
         #[builder(default, setter(skip))]
+        #[cfg_attr(feature = "serde", serde(skip))]
         _end: (),
     }
 
-    impl NameTableValue {
-        const NAME_REGEX: OnceCell<Regex> = OnceCell::new();
+    impl ValueMapSymbol {
+        /// The symbol header
+        pub fn header(&self) -> &SymbolHeader {
+            &self.header
+        }
+
+        /// The field indexing the table
+        pub fn patval(&self) -> &PatternValueType {
+            &self.patval
+        }
+
+        /// The values, indexed by the field
+        pub fn valuetable(&self) -> &[ValueTableValue] {
+            &self.valuetable
+        }
     }
 
-    #[derive(TypedBuilder, Debug, PartialEq)]
+    static NAME_TABLE_VALUE_NAME_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(r#"name\s*=\s*(?:"([^"]*)"|'([^']*)')"#).expect("Invalid regular expression")
+    });
+
+    #[derive(TypedBuilder, Debug, Clone, PartialEq)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct NameTableValue {
         #[rust_sitter::leaf(pattern = r#"<\s*nametab"#)]
         #[builder(default, setter(skip))]
+        #[cfg_attr(feature = "serde", serde(skip))]
         _start: (),
         #[rust_sitter::leaf(
-            pattern = r#"name\s*=\s*"([^"]*)""#,
+            pattern = r#"name\s*=\s*(?:"([^"]*)"|'([^']*)')"#,
             transform = |v| {
-                NameTableValue::NAME_REGEX
-                    .get_or_init(|| {
-                        Regex::new(r#"name\s*=\s*"([^"]*)""#).expect("Invalid regular expression")
-                    })
+                NAME_TABLE_VALUE_NAME_REGEX
                     .captures(v)
-                    .expect("No captures or no capture group")
-                    .get(1)
-                    .expect("No capture group")
-                    .as_str()
+                    .leaf_capture("NameTableValue::name", 1)
                     .to_string()
                     .xml_unescape()
             }
@@ -2725,189 +5087,317 @@ pub mod grammar {
         name: Option<String>,
         #[rust_sitter::leaf(pattern = r#"/\s*>"#)]
         #[builder(default, setter(skip))]
+        #[cfg_attr(feature = "serde", serde(skip))]
         _end: (),
     }
 
-    #[derive(TypedBuilder, Debug, PartialEq)]
+    impl NameTableValue {
+        /// The name, or `None` for an invalid entry
+        pub fn name(&self) -> Option<&str> {
+            self.name.as_deref()
+        }
+    }
+
+    #[derive(TypedBuilder, Debug, Clone, PartialEq)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct NameSymbol {
         #[rust_sitter::leaf(pattern = r#"<\s*name_sym"#)]
         #[builder(default, setter(skip))]
+        #[cfg_attr(feature = "serde", serde(skip))]
         _start: (),
         header: SymbolHeader,
         #[rust_sitter::leaf(pattern = r#">"#)]
         #[builder(default, setter(skip))]
+        #[cfg_attr(feature = "serde", serde(skip))]
         _close: (),
         patval: PatternValueType,
         nametable: Vec<NameTableValue>,
         #[rust_sitter::leaf(pattern = r#"<\s*/\s*name_sym\s*>"#)]
         #[builder(default, setter(skip))]
+        #[cfg_attr(feature = "serde", serde(skip))]
         _end: (),
     }
 
-    impl ContextSymbol {
-        // HEX
-        const VARNODE_REGEX: OnceCell<Regex> = OnceCell::new();
-        // DEC
-        const LOW_REGEX: OnceCell<Regex> = OnceCell::new();
-        // DEC
-        const HIGH_REGEX: OnceCell<Regex> = OnceCell::new();
-        // BOOLEAN
-        const FLOW_REGEX: OnceCell<Regex> = OnceCell::new();
+    impl NameSymbol {
+        /// The symbol header
+        pub fn header(&self) -> &SymbolHeader {
+            &self.header
+        }
+
+        /// The field indexing the table
+        pub fn patval(&self) -> &PatternValueType {
+            &self.patval
+        }
+
+        /// The names, indexed by the field
+        pub fn nametable(&self) -> &[NameTableValue] {
+            &self.nametable
+        }
     }
 
-    #[derive(TypedBuilder, Debug, PartialEq)]
+    // HEX
+    static CONTEXT_SYMBOL_VARNODE_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(r#"varnode\s*=\s*(?:"(-?0[xX][0-9a-fA-F]+)"|'(-?0[xX][0-9a-fA-F]+)')"#)
+            .expect("Invalid regular expression")
+    });
+    // DEC
+    static CONTEXT_SYMBOL_LOW_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(r#"low\s*=\s*(?:"([-+]?[0-9][0-9.eE+-]*)"|'([-+]?[0-9][0-9.eE+-]*)')"#)
+            .expect("Invalid regular expression")
+    });
+    // DEC
+    static CONTEXT_SYMBOL_HIGH_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(r#"high\s*=\s*(?:"([-+]?[0-9][0-9.eE+-]*)"|'([-+]?[0-9][0-9.eE+-]*)')"#)
+            .expect("Invalid regular expression")
+    });
+    // BOOLEAN
+    static CONTEXT_SYMBOL_FLOW_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(r#"flow\s*=\s*(?:"([a-zA-Z0-9]+)"|'([a-zA-Z0-9]+)')"#)
+            .expect("Invalid regular expression")
+    });
+
+    #[derive(TypedBuilder, Debug, Clone, PartialEq)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct ContextSymbol {
         #[rust_sitter::leaf(pattern = r#"<\s*context_sym"#)]
         #[builder(default, setter(skip))]
+        #[cfg_attr(feature = "serde", serde(skip))]
         _start: (),
         header: SymbolHeader,
         #[rust_sitter::leaf(
-            pattern = r#"varnode\s*=\s*"0x([0-9a-fA-F]+)""#,
+            pattern = r#"varnode\s*=\s*(?:"(-?0[xX][0-9a-fA-F]+)"|'(-?0[xX][0-9a-fA-F]+)')"#,
             transform = |v| {
-                Integer::from_string_base(16, ContextSymbol::VARNODE_REGEX
-                    .get_or_init(|| {
-                        Regex::new(r#"varnode\s*=\s*"0x([0-9a-fA-F]+)""#)
-                            .expect("Invalid regular expression")
-                    })
+                CONTEXT_SYMBOL_VARNODE_REGEX
                     .captures(v)
-                    .expect("No captures or no capture group")
-                    .get(1)
-                    .expect("No capture group").as_str()).expect("Invalid integer")
+                    .leaf_capture("ContextSymbol::varnode", 1)
+                    .leaf_hex_integer("ContextSymbol::varnode")
             }
         )]
         #[builder(setter(transform = |v: impl Into<Integer>| {
-            v.into()
+            HexInteger(v.into())
         }))]
-        varnode: Integer,
+        varnode: HexInteger,
         #[rust_sitter::leaf(
-            pattern = r#"low\s*=\s*"(-?[0-9]+)""#,
+            pattern = r#"low\s*=\s*(?:"([-+]?[0-9][0-9.eE+-]*)"|'([-+]?[0-9][0-9.eE+-]*)')"#,
             transform = |v| {
-                ContextSymbol::LOW_REGEX
-                    .get_or_init(|| {
-                        Regex::new(r#"low\s*=\s*"(-?[0-9]+)""#).expect("Invalid regular expression")
-                    })
+                CONTEXT_SYMBOL_LOW_REGEX
                     .captures(v)
-                    .expect("No captures or no capture group")
-                    .get(1)
-                    .expect("No capture group").as_str().parse().expect("Invalid integer")
+                    .leaf_capture("ContextSymbol::low", 1)
+                    .leaf_integer("ContextSymbol::low")
             }
         )]
         #[builder(setter(transform = |v: impl Into<Integer>| {
             v.into()
         }))]
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_integer"))]
         low: Integer,
         #[rust_sitter::leaf(
-            pattern = r#"high\s*=\s*"(-?[0-9]+)""#,
+            pattern = r#"high\s*=\s*(?:"([-+]?[0-9][0-9.eE+-]*)"|'([-+]?[0-9][0-9.eE+-]*)')"#,
             transform = |v| {
-                ContextSymbol::HIGH_REGEX
-                    .get_or_init(|| {
-                        Regex::new(r#"high\s*=\s*"(-?[0-9]+)""#).expect("Invalid regular expression")
-                    })
+                CONTEXT_SYMBOL_HIGH_REGEX
                     .captures(v)
-                    .expect("No captures or no capture group")
-                    .get(1)
-                    .expect("No capture group").as_str().parse().expect("Invalid integer")
+                    .leaf_capture("ContextSymbol::high", 1)
+                    .leaf_integer("ContextSymbol::high")
             }
         )]
         #[builder(setter(transform = |v: impl Into<Integer>| {
             v.into()
         }))]
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_integer"))]
         high: Integer,
         #[rust_sitter::leaf(
-            pattern = r#"flow\s*=\s*"([a-z]+)""#,
+            pattern = r#"flow\s*=\s*(?:"([a-zA-Z0-9]+)"|'([a-zA-Z0-9]+)')"#,
             transform = |v| {
-                ContextSymbol::FLOW_REGEX
-                    .get_or_init(|| {
-                        Regex::new(r#"flow\s*=\s*"([a-z]+)""#).expect("Invalid regular expression")
-                    })
+                CONTEXT_SYMBOL_FLOW_REGEX
                     .captures(v)
-                    .expect("No captures or no capture group").get(1).expect("No capture group").as_str().parse().expect("Invalid boolean")
+                    .leaf_capture("ContextSymbol::flow", 1)
+                    .leaf_bool("ContextSymbol::flow")
             }
         )]
         flow: bool,
         #[rust_sitter::leaf(pattern = r#">"#)]
         #[builder(default, setter(skip))]
+        #[cfg_attr(feature = "serde", serde(skip))]
         _close: (),
         patval: PatternValueType,
         #[rust_sitter::leaf(pattern = r#"<\s*/\s*context_sym\s*>"#)]
         #[builder(default, setter(skip))]
+        #[cfg_attr(feature = "serde", serde(skip))]
         _end: (),
     }
 
-    impl VarNodeTableValue {
-        // HEX
-        const ID_REGEX: OnceCell<Regex> = OnceCell::new();
+    impl ContextSymbol {
+        /// The symbol header
+        pub fn header(&self) -> &SymbolHeader {
+            &self.header
+        }
+
+        /// The id of the context register the field lives in
+        pub fn varnode(&self) -> &HexInteger {
+            &self.varnode
+        }
+
+        /// The least significant bit of the field within the register
+        pub fn low(&self) -> &Integer {
+            &self.low
+        }
+
+        /// The most significant bit of the field within the register
+        pub fn high(&self) -> &Integer {
+            &self.high
+        }
+
+        /// Whether changes to the field flow to following instructions
+        pub fn flow(&self) -> bool {
+            self.flow
+        }
+
+        /// The `(low, high)` bits of the field within the register, ordered so that
+        /// `low <= high`. Bits outside the range of a `u32` saturate.
+        pub fn bit_range(&self) -> (u32, u32) {
+            let low = u32::saturating_from(&self.low);
+            let high = u32::saturating_from(&self.high);
+            (low.min(high), low.max(high))
+        }
+
+        /// The context field
+        pub fn patval(&self) -> &PatternValueType {
+            &self.patval
+        }
     }
 
-    #[derive(TypedBuilder, Debug, PartialEq)]
+    // HEX
+    static VAR_NODE_TABLE_VALUE_ID_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(r#"id\s*=\s*(?:"(-?0[xX][0-9a-fA-F]+)"|'(-?0[xX][0-9a-fA-F]+)')"#)
+            .expect("Invalid regular expression")
+    });
+
+    #[derive(TypedBuilder, Debug, Clone, PartialEq)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct VarNodeTableValue {
         #[rust_sitter::leaf(pattern = r#"<\s*var"#)]
         #[builder(default, setter(skip))]
+        #[cfg_attr(feature = "serde", serde(skip))]
         _start: (),
         #[rust_sitter::leaf(
-            pattern = r#"id\s*=\s*"0x([0-9a-fA-F]+)""#,
+            pattern = r#"id\s*=\s*(?:"(-?0[xX][0-9a-fA-F]+)"|'(-?0[xX][0-9a-fA-F]+)')"#,
             transform = |v| {
-                Integer::from_string_base(16, VarNodeTableValue::ID_REGEX
-                    .get_or_init(|| {
-                        Regex::new(r#"id\s*=\s*"0x([0-9a-fA-F]+)""#)
-                            .expect("Invalid regular expression")
-                    })
+                VAR_NODE_TABLE_VALUE_ID_REGEX
                     .captures(v)
-                    .expect("No captures or no capture group")
-                    .get(1)
-                    .expect("No capture group").as_str()).expect("Invalid integer")
+                    .leaf_capture("VarNodeTableValue::id", 1)
+                    .leaf_hex_integer("VarNodeTableValue::id")
             }
         )]
         #[builder(setter(transform = |v: impl Into<Integer>| {
-            v.into()
+            HexInteger(v.into())
         }))]
-        id: Integer,
+        id: HexInteger,
         #[rust_sitter::leaf(pattern = r#"/\s*>"#)]
         #[builder(default, setter(skip))]
+        #[cfg_attr(feature = "serde", serde(skip))]
         _end: (),
     }
 
-    #[derive(Debug, PartialEq)]
+    impl VarNodeTableValue {
+        /// The id of the varnode symbol
+        pub fn id(&self) -> &HexInteger {
+            &self.id
+        }
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    #[non_exhaustive]
     pub enum VarNodeTableValueType {
         Null {
             #[rust_sitter::leaf(pattern = r#"<\s*null\s*/\s*>"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _null: (),
         },
         Value(VarNodeTableValue),
     }
 
-    #[derive(TypedBuilder, Debug, PartialEq)]
+    impl VarNodeTableValueType {
+        /// The varnode, or `None` for an unused slot of the list
+        pub fn as_option(&self) -> Option<&VarNodeTableValue> {
+            match self {
+                VarNodeTableValueType::Null { .. } => None,
+                VarNodeTableValueType::Value(value) => Some(value),
+            }
+        }
+    }
+
+    #[derive(TypedBuilder, Debug, Clone, PartialEq)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct VarNodeListSymbol {
         #[rust_sitter::leaf(pattern = r#"<\s*varlist_sym"#)]
         #[builder(default, setter(skip))]
+        #[cfg_attr(feature = "serde", serde(skip))]
         _start: (),
         header: SymbolHeader,
         #[rust_sitter::leaf(pattern = r#">"#)]
         #[builder(default, setter(skip))]
+        #[cfg_attr(feature = "serde", serde(skip))]
         _close: (),
         patval: PatternValueType,
         varnode_table: Vec<VarNodeTableValueType>,
         #[rust_sitter::leaf(pattern = r#"<\s*/\s*varlist_sym\s*>"#)]
         #[builder(default, setter(skip))]
+        #[cfg_attr(feature = "serde", serde(skip))]
         _end: (),
     }
 
-    #[derive(TypedBuilder, Debug, PartialEq)]
+    impl VarNodeListSymbol {
+        /// The symbol header
+        pub fn header(&self) -> &SymbolHeader {
+            &self.header
+        }
+
+        /// The field indexing the table
+        pub fn patval(&self) -> &PatternValueType {
+            &self.patval
+        }
+
+        /// The varnodes, indexed by the field
+        pub fn varnode_table(&self) -> &[VarNodeTableValueType] {
+            &self.varnode_table
+        }
+    }
+
+    #[derive(TypedBuilder, Debug, Clone, PartialEq)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct ValueSymbol {
         #[rust_sitter::leaf(pattern = r#"<\s*value_sym"#)]
         #[builder(default, setter(skip))]
+        #[cfg_attr(feature = "serde", serde(skip))]
         _start: (),
         header: SymbolHeader,
         #[rust_sitter::leaf(pattern = r#">"#)]
         #[builder(default, setter(skip))]
+        #[cfg_attr(feature = "serde", serde(skip))]
         _close: (),
         patval: PatternValueType,
         #[rust_sitter::leaf(pattern = r#"<\s*/\s*value_sym\s*>"#)]
         #[builder(default, setter(skip))]
+        #[cfg_attr(feature = "serde", serde(skip))]
         _end: (),
     }
 
-    #[derive(Debug, PartialEq)]
+    impl ValueSymbol {
+        /// The symbol header
+        pub fn header(&self) -> &SymbolHeader {
+            &self.header
+        }
+
+        /// The field the value is read from
+        pub fn patval(&self) -> &PatternValueType {
+            &self.patval
+        }
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    #[non_exhaustive]
     pub enum ValueSymbolType {
         ValueMapSymbol(ValueMapSymbol),
         NameSymbol(NameSymbol),
@@ -2916,283 +5406,429 @@ pub mod grammar {
         ValueSymbol(ValueSymbol),
     }
 
-    #[derive(Debug, PartialEq)]
+    #[derive(Debug, Clone, PartialEq)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    #[non_exhaustive]
     pub enum FamilySymbol {
         ValueSymbol(ValueSymbolType),
     }
 
-    #[derive(TypedBuilder, Debug, PartialEq)]
+    #[derive(TypedBuilder, Debug, Clone, PartialEq)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct EpsilonSymbol {
         #[rust_sitter::leaf(pattern = r#"<\s*epsilon_sym"#)]
         #[builder(default, setter(skip))]
+        #[cfg_attr(feature = "serde", serde(skip))]
         _start: (),
         header: SymbolHeader,
-        #[rust_sitter::leaf(pattern = r#"/\s*>"#)]
+        #[rust_sitter::leaf(pattern = r#"/\s*>|>\s*<\s*/\s*epsilon_sym\s*>"#)]
         #[builder(default, setter(skip))]
+        #[cfg_attr(feature = "serde", serde(skip))]
         _end: (),
     }
 
-    impl VarNodeSymbol {
-        // STRING
-        const SPACE_REGEX: OnceCell<Regex> = OnceCell::new();
-        // HEX
-        const OFFSET_REGEX: OnceCell<Regex> = OnceCell::new();
-        // DEC
-        const SIZE_REGEX: OnceCell<Regex> = OnceCell::new();
+    impl EpsilonSymbol {
+        /// The symbol header
+        pub fn header(&self) -> &SymbolHeader {
+            &self.header
+        }
     }
 
-    #[derive(TypedBuilder, Debug, PartialEq)]
+    // STRING
+    static VAR_NODE_SYMBOL_SPACE_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(r#"space\s*=\s*(?:"([^"]*)"|'([^']*)')"#).expect("Invalid regular expression")
+    });
+    // HEX
+    static VAR_NODE_SYMBOL_OFFSET_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(r#"offset\s*=\s*(?:"(-?0[xX][0-9a-fA-F]+)"|'(-?0[xX][0-9a-fA-F]+)')"#)
+            .expect("Invalid regular expression")
+    });
+    // DEC
+    static VAR_NODE_SYMBOL_SIZE_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(r#"size\s*=\s*(?:"([-+]?[0-9][0-9.eE+-]*)"|'([-+]?[0-9][0-9.eE+-]*)')"#)
+            .expect("Invalid regular expression")
+    });
+
+    #[derive(TypedBuilder, Debug, Clone, PartialEq)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct VarNodeSymbol {
         #[rust_sitter::leaf(pattern = r#"<\s*varnode_sym"#)]
         #[builder(default, setter(skip))]
+        #[cfg_attr(feature = "serde", serde(skip))]
         _start: (),
         header: SymbolHeader,
         #[rust_sitter::leaf(
-            pattern = r#"space\s*=\s*"([^"]*)""#,
+            pattern = r#"space\s*=\s*(?:"([^"]*)"|'([^']*)')"#,
             transform = |v| {
-                VarNodeSymbol::SPACE_REGEX
-                    .get_or_init(|| {
-                        Regex::new(r#"space\s*=\s*"([^"]*)""#).expect("Invalid regular expression")
-                    })
+                VAR_NODE_SYMBOL_SPACE_REGEX
                     .captures(v)
-                    .expect("No captures or no capture group")
-                    .get(1)
-                    .expect("No capture group")
-                    .as_str()
+                    .leaf_capture("VarNodeSymbol::space", 1)
                     .to_string()
                     .xml_unescape()
             }
         )]
         space: String,
         #[rust_sitter::leaf(
-            pattern = r#"offset\s*=\s*"0x([0-9a-fA-F]+)""#,
+            pattern = r#"offset\s*=\s*(?:"(-?0[xX][0-9a-fA-F]+)"|'(-?0[xX][0-9a-fA-F]+)')"#,
             transform = |v| {
-                Integer::from_string_base(16, VarNodeSymbol::OFFSET_REGEX
-                    .get_or_init(|| {
-                        Regex::new(r#"offset\s*=\s*"0x([0-9a-fA-F]+)""#)
-                            .expect("Invalid regular expression")
-                    })
+                VAR_NODE_SYMBOL_OFFSET_REGEX
                     .captures(v)
-                    .expect("No captures or no capture group")
-                    .get(1)
-                    .expect("No capture group").as_str()).expect("Invalid integer")
+                    .leaf_capture("VarNodeSymbol::offset", 1)
+                    .leaf_hex_integer("VarNodeSymbol::offset")
             }
         )]
         #[builder(setter(transform = |v: impl Into<Integer>| {
-            v.into()
+            HexInteger(v.into())
         }))]
-        offset: Integer,
+        offset: HexInteger,
         #[rust_sitter::leaf(
-            pattern = r#"size\s*=\s*"(-?[0-9]+)""#,
+            pattern = r#"size\s*=\s*(?:"([-+]?[0-9][0-9.eE+-]*)"|'([-+]?[0-9][0-9.eE+-]*)')"#,
             transform = |v| {
-                VarNodeSymbol::SIZE_REGEX
-                    .get_or_init(|| {
-                        Regex::new(r#"size\s*=\s*"(-?[0-9]+)""#).expect("Invalid regular expression")
-                    })
+                VAR_NODE_SYMBOL_SIZE_REGEX
                     .captures(v)
-                    .expect("No captures or no capture group")
-                    .get(1)
-                    .expect("No capture group").as_str().parse().expect("Invalid integer")
+                    .leaf_capture("VarNodeSymbol::size", 1)
+                    .leaf_integer("VarNodeSymbol::size")
             }
         )]
         #[builder(setter(transform = |v: impl Into<Integer>| {
             v.into()
         }))]
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_integer"))]
         size: Integer,
-        #[rust_sitter::leaf(pattern = r#">"#)]
-        #[builder(default, setter(skip))]
-        _close: (),
-        #[rust_sitter::leaf(pattern = r#"<\s*/\s*varnode_sym\s*>"#)]
+        /// Ghidra writes `<varnode_sym ...></varnode_sym>`, other tools self-close it
+        #[rust_sitter::leaf(pattern = r#"/\s*>|>\s*<\s*/\s*varnode_sym\s*>"#)]
         #[builder(default, setter(skip))]
+        #[cfg_attr(feature = "serde", serde(skip))]
         _end: (),
     }
 
-    #[derive(Debug, PartialEq)]
+    impl VarNodeSymbol {
+        /// The symbol header
+        pub fn header(&self) -> &SymbolHeader {
+            &self.header
+        }
+
+        /// The name of the varnode's space
+        pub fn space(&self) -> &str {
+            &self.space
+        }
+
+        /// The varnode's offset
+        pub fn offset(&self) -> &HexInteger {
+            &self.offset
+        }
+
+        /// The varnode's size in bytes
+        pub fn size(&self) -> &Integer {
+            &self.size
+        }
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    #[non_exhaustive]
     pub enum PatternlessSymbol {
         EpsilonSymbol(EpsilonSymbol),
         VarNodeSymbol(VarNodeSymbol),
     }
 
-    impl OperandSymbol {
-        // HEX
-        const SUBSYM_REGEX: OnceCell<Regex> = OnceCell::new();
-        // DEC
-        const OFF_REGEX: OnceCell<Regex> = OnceCell::new();
-        // DEC
-        const BASE_REGEX: OnceCell<Regex> = OnceCell::new();
-        // DEC
-        const MINLEN_REGEX: OnceCell<Regex> = OnceCell::new();
-        // DEC
-        const INDEX_REGEX: OnceCell<Regex> = OnceCell::new();
-        // BOOLEAN
-        const CODE_REGEX: OnceCell<Regex> = OnceCell::new();
-    }
-
-    #[derive(TypedBuilder, Debug, PartialEq)]
+    // HEX
+    static OPERAND_SYMBOL_SUBSYM_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(r#"subsym\s*=\s*(?:"(-?0[xX][0-9a-fA-F]+)"|'(-?0[xX][0-9a-fA-F]+)')"#)
+            .expect("Invalid regular expression")
+    });
+    // DEC
+    static OPERAND_SYMBOL_OFF_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(r#"off\s*=\s*(?:"([-+]?[0-9][0-9.eE+-]*)"|'([-+]?[0-9][0-9.eE+-]*)')"#)
+            .expect("Invalid regular expression")
+    });
+    // DEC
+    static OPERAND_SYMBOL_BASE_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(r#"base\s*=\s*(?:"([-+]?[0-9][0-9.eE+-]*)"|'([-+]?[0-9][0-9.eE+-]*)')"#)
+            .expect("Invalid regular expression")
+    });
+    // DEC
+    static OPERAND_SYMBOL_MINLEN_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(r#"minlen\s*=\s*(?:"([-+]?[0-9][0-9.eE+-]*)"|'([-+]?[0-9][0-9.eE+-]*)')"#)
+            .expect("Invalid regular expression")
+    });
+    // DEC
+    static OPERAND_SYMBOL_INDEX_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(r#"index\s*=\s*(?:"([-+]?[0-9][0-9.eE+-]*)"|'([-+]?[0-9][0-9.eE+-]*)')"#)
+            .expect("Invalid regular expression")
+    });
+    // BOOLEAN
+    static OPERAND_SYMBOL_CODE_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(r#"code\s*=\s*(?:"([a-zA-Z0-9]+)"|'([a-zA-Z0-9]+)')"#)
+            .expect("Invalid regular expression")
+    });
+
+    #[derive(TypedBuilder, Debug, Clone, PartialEq)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct OperandSymbol {
         #[rust_sitter::leaf(pattern = r#"<\s*operand_sym"#)]
         #[builder(default, setter(skip))]
+        #[cfg_attr(feature = "serde", serde(skip))]
         _start: (),
         header: SymbolHeader,
         #[rust_sitter::leaf(
-            pattern = r#"subsym\s*=\s*"0x([0-9a-fA-F]+)""#,
+            pattern = r#"subsym\s*=\s*(?:"(-?0[xX][0-9a-fA-F]+)"|'(-?0[xX][0-9a-fA-F]+)')"#,
             transform = |v| {
-                Integer::from_string_base(16, OperandSymbol::SUBSYM_REGEX
-                    .get_or_init(|| {
-                        Regex::new(r#"subsym\s*=\s*"0x([0-9a-fA-F]+)""#)
-                            .expect("Invalid regular expression")
-                    })
+                OPERAND_SYMBOL_SUBSYM_REGEX
                     .captures(v)
-                    .expect("No captures or no capture group")
-                    .get(1)
-                    .expect("No capture group").as_str()).expect("Invalid integer")
+                    .leaf_capture("OperandSymbol::subsym", 1)
+                    .leaf_hex_integer("OperandSymbol::subsym")
             }
         )]
         #[builder(setter(transform = |v: impl Into<Integer>| {
-            Some(v.into())
+            Some(HexInteger(v.into()))
         }))]
-        subsym: Option<Integer>,
+        subsym: Option<HexInteger>,
         #[rust_sitter::leaf(
-            pattern = r#"off\s*=\s*"(-?[0-9]+)""#,
+            pattern = r#"off\s*=\s*(?:"([-+]?[0-9][0-9.eE+-]*)"|'([-+]?[0-9][0-9.eE+-]*)')"#,
             transform = |v| {
-                OperandSymbol::OFF_REGEX
-                    .get_or_init(|| {
-                        Regex::new(r#"off\s*=\s*"(-?[0-9]+)""#).expect("Invalid regular expression")
-                    })
+                OPERAND_SYMBOL_OFF_REGEX
                     .captures(v)
-                    .expect("No captures or no capture group")
-                    .get(1)
-                    .expect("No capture group").as_str().parse().expect("Invalid integer")
+                    .leaf_capture("OperandSymbol::off", 1)
+                    .leaf_integer("OperandSymbol::off")
             }
         )]
         #[builder(setter(transform = |v: impl Into<Integer>| {
             v.into()
         }))]
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_integer"))]
         off: Integer,
         #[rust_sitter::leaf(
-            pattern = r#"base\s*=\s*"(-?[0-9]+)""#,
+            pattern = r#"base\s*=\s*(?:"([-+]?[0-9][0-9.eE+-]*)"|'([-+]?[0-9][0-9.eE+-]*)')"#,
             transform = |v| {
-                OperandSymbol::BASE_REGEX
-                    .get_or_init(|| {
-                        Regex::new(r#"base\s*=\s*"(-?[0-9]+)""#).expect("Invalid regular expression")
-                    })
+                OPERAND_SYMBOL_BASE_REGEX
                     .captures(v)
-                    .expect("No captures or no capture group").get(1).expect("No capture group").as_str().parse().expect("Invalid integer")
+                    .leaf_capture("OperandSymbol::base", 1)
+                    .leaf_integer("OperandSymbol::base")
             }
         )]
         #[builder(setter(transform = |v: impl Into<Integer>| {
             v.into()
         }))]
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_integer"))]
         base: Integer,
         #[rust_sitter::leaf(
-            pattern = r#"minlen\s*=\s*"(-?[0-9]+)""#,
+            pattern = r#"minlen\s*=\s*(?:"([-+]?[0-9][0-9.eE+-]*)"|'([-+]?[0-9][0-9.eE+-]*)')"#,
             transform = |v| {
-                OperandSymbol::MINLEN_REGEX
-                    .get_or_init(|| {
-                        Regex::new(r#"minlen\s*=\s*"(-?[0-9]+)""#)
-                            .expect("Invalid regular expression")
-                    })
+                OPERAND_SYMBOL_MINLEN_REGEX
                     .captures(v)
-                    .expect("No captures or no capture group").get(1).expect("No capture group").as_str().parse().expect("Invalid integer")
+                    .leaf_capture("OperandSymbol::minlen", 1)
+                    .leaf_integer("OperandSymbol::minlen")
             }
         )]
         #[builder(setter(transform = |v: impl Into<Integer>| {
             v.into()
         }))]
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_integer"))]
         minlen: Integer,
         #[rust_sitter::leaf(
-            pattern = r#"code\s*=\s*"([a-z]+)""#,
+            pattern = r#"code\s*=\s*(?:"([a-zA-Z0-9]+)"|'([a-zA-Z0-9]+)')"#,
             transform = |v| {
-                OperandSymbol::CODE_REGEX
-                    .get_or_init(|| {
-                        Regex::new(r#"code\s*=\s*"([a-z]+)""#).expect("Invalid regular expression")
-                    })
+                OPERAND_SYMBOL_CODE_REGEX
                     .captures(v)
-                    .expect("No captures or no capture group").get(1).expect("No capture group").as_str().parse().expect("Invalid boolean")
+                    .leaf_capture("OperandSymbol::code", 1)
+                    .leaf_bool("OperandSymbol::code")
             }
         )]
         code: Option<bool>,
         #[rust_sitter::leaf(
-            pattern = r#"index\s*=\s*"(-?[0-9]+)""#,
+            pattern = r#"index\s*=\s*(?:"([-+]?[0-9][0-9.eE+-]*)"|'([-+]?[0-9][0-9.eE+-]*)')"#,
             transform = |v| {
-                OperandSymbol::INDEX_REGEX
-                    .get_or_init(|| {
-                        Regex::new(r#"index\s*=\s*"(-?[0-9]+)""#).expect("Invalid regular expression")
-                    })
+                OPERAND_SYMBOL_INDEX_REGEX
                     .captures(v)
-                    .expect("No captures or no capture group").get(1).expect("No capture group").as_str().parse().expect("Invalid integer")
+                    .leaf_capture("OperandSymbol::index", 1)
+                    .leaf_integer("OperandSymbol::index")
             }
         )]
         #[builder(setter(transform = |v: impl Into<Integer>| {
             v.into()
         }))]
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_integer"))]
         index: Integer,
         #[rust_sitter::leaf(pattern = r#">"#)]
         #[builder(default, setter(skip))]
+        #[cfg_attr(feature = "serde", serde(skip))]
         _close: (),
         localexp: OperandValue,
         defexp: Option<PatternExpressionType>,
         #[rust_sitter::leaf(pattern = r#"<\s*/\s*operand_sym\s*>"#)]
         #[builder(default, setter(skip))]
+        #[cfg_attr(feature = "serde", serde(skip))]
         _end: (),
     }
 
-    #[derive(TypedBuilder, Debug, PartialEq)]
+    impl OperandSymbol {
+        /// The symbol header
+        pub fn header(&self) -> &SymbolHeader {
+            &self.header
+        }
+
+        /// The id of the subtable or symbol the operand refers to, if any
+        pub fn subsym(&self) -> Option<&HexInteger> {
+            self.subsym.as_ref()
+        }
+
+        /// The subtable the operand is decoded by, resolved from [`Self::subsym`] in `table`.
+        /// `None` if the operand has no `subsym`, or it refers to a symbol that is not a
+        /// subtable, e.g. a register list.
+        pub fn subtable<'a>(&self, table: &'a SymbolTable) -> Option<&'a SubtableSymbol> {
+            match table.symbol_by_id(self.subsym.as_ref()?)? {
+                SleighSymbolType::TripleSymbol(TripleSymbol::SubtableSymbol {
+                    subtable, ..
+                }) => Some(subtable),
+                _ => None,
+            }
+        }
+
+        /// The offset of the operand relative to `base`
+        pub fn off(&self) -> &Integer {
+            &self.off
+        }
+
+        /// The index of the operand this one is offset from, or -1 for the constructor start
+        pub fn base(&self) -> &Integer {
+            &self.base
+        }
+
+        /// The minimum length of the operand in bytes
+        pub fn minlen(&self) -> &Integer {
+            &self.minlen
+        }
+
+        /// Whether the operand is a code address, if declared
+        pub fn code(&self) -> Option<bool> {
+            self.code
+        }
+
+        /// The index of the operand within its constructor
+        pub fn index(&self) -> &Integer {
+            &self.index
+        }
+
+        /// The expression referring to this operand
+        pub fn localexp(&self) -> &OperandValue {
+            &self.localexp
+        }
+
+        /// The expression defining the operand's value, if it is not a subtable
+        pub fn defexp(&self) -> Option<&PatternExpressionType> {
+            self.defexp.as_ref()
+        }
+    }
+
+    #[derive(TypedBuilder, Debug, Clone, PartialEq)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct StartSymbol {
         #[rust_sitter::leaf(pattern = r#"<\s*start_sym"#)]
         #[builder(default, setter(skip))]
+        #[cfg_attr(feature = "serde", serde(skip))]
         _start: (),
         header: SymbolHeader,
-        #[rust_sitter::leaf(pattern = r#"/\s*>"#)]
+        #[rust_sitter::leaf(pattern = r#"/\s*>|>\s*<\s*/\s*start_sym\s*>"#)]
         #[builder(default, setter(skip))]
+        #[cfg_attr(feature = "serde", serde(skip))]
         _end: (),
     }
 
-    #[derive(TypedBuilder, Debug, PartialEq)]
+    impl StartSymbol {
+        /// The symbol header
+        pub fn header(&self) -> &SymbolHeader {
+            &self.header
+        }
+    }
+
+    #[derive(TypedBuilder, Debug, Clone, PartialEq)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct EndSymbol {
         #[rust_sitter::leaf(pattern = r#"<\s*end_sym"#)]
         #[builder(default, setter(skip))]
+        #[cfg_attr(feature = "serde", serde(skip))]
         _start: (),
         header: SymbolHeader,
-        #[rust_sitter::leaf(pattern = r#"/\s*>"#)]
+        #[rust_sitter::leaf(pattern = r#"/\s*>|>\s*<\s*/\s*end_sym\s*>"#)]
         #[builder(default, setter(skip))]
+        #[cfg_attr(feature = "serde", serde(skip))]
         _end: (),
     }
 
-    #[derive(TypedBuilder, Debug, PartialEq)]
+    impl EndSymbol {
+        /// The symbol header
+        pub fn header(&self) -> &SymbolHeader {
+            &self.header
+        }
+    }
+
+    #[derive(TypedBuilder, Debug, Clone, PartialEq)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct Next2Symbol {
         #[rust_sitter::leaf(pattern = r#"<\s*next2_sym"#)]
         #[builder(default, setter(skip))]
+        #[cfg_attr(feature = "serde", serde(skip))]
         _start: (),
         header: SymbolHeader,
-        #[rust_sitter::leaf(pattern = r#"/\s*>"#)]
+        #[rust_sitter::leaf(pattern = r#"/\s*>|>\s*<\s*/\s*next2_sym\s*>"#)]
         #[builder(default, setter(skip))]
+        #[cfg_attr(feature = "serde", serde(skip))]
         _end: (),
     }
 
-    #[derive(TypedBuilder, Debug, PartialEq)]
+    impl Next2Symbol {
+        /// The symbol header
+        pub fn header(&self) -> &SymbolHeader {
+            &self.header
+        }
+    }
+
+    #[derive(TypedBuilder, Debug, Clone, PartialEq)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct FlowDestSymbol {
         #[rust_sitter::leaf(pattern = r#"<\s*flowdest_sym"#)]
         #[builder(default, setter(skip))]
+        #[cfg_attr(feature = "serde", serde(skip))]
         _start: (),
         header: SymbolHeader,
-        #[rust_sitter::leaf(pattern = r#"/\s*>"#)]
+        #[rust_sitter::leaf(pattern = r#"/\s*>|>\s*<\s*/\s*flowdest_sym\s*>"#)]
         #[builder(default, setter(skip))]
+        #[cfg_attr(feature = "serde", serde(skip))]
         _end: (),
     }
 
-    #[derive(TypedBuilder, Debug, PartialEq)]
+    impl FlowDestSymbol {
+        /// The symbol header
+        pub fn header(&self) -> &SymbolHeader {
+            &self.header
+        }
+    }
+
+    #[derive(TypedBuilder, Debug, Clone, PartialEq)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct FlowRefSymbol {
         #[rust_sitter::leaf(pattern = r#"<\s*flowref_sym"#)]
         #[builder(default, setter(skip))]
+        #[cfg_attr(feature = "serde", serde(skip))]
         _start: (),
         header: SymbolHeader,
-        #[rust_sitter::leaf(pattern = r#"/\s*>"#)]
+        #[rust_sitter::leaf(pattern = r#"/\s*>|>\s*<\s*/\s*flowref_sym\s*>"#)]
         #[builder(default, setter(skip))]
+        #[cfg_attr(feature = "serde", serde(skip))]
         _end: (),
     }
 
-    #[derive(Debug, PartialEq)]
+    impl FlowRefSymbol {
+        /// The symbol header
+        pub fn header(&self) -> &SymbolHeader {
+            &self.header
+        }
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    #[non_exhaustive]
     pub enum SpecificSymbol {
         PatternlessSymbol(PatternlessSymbol),
         OperandSymbol(OperandSymbol),
@@ -3203,31 +5839,108 @@ pub mod grammar {
         FlowRefSymbol(FlowRefSymbol),
     }
 
-    #[derive(Debug, PartialEq)]
+    #[derive(Debug, Clone, PartialEq)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    #[non_exhaustive]
     pub enum TripleSymbol {
         FamilySymbol(FamilySymbol),
         SpecificSymbol(SpecificSymbol),
         SubtableSymbol {
             #[rust_sitter::leaf(pattern = r#"<\s*subtable_sym"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _start: (),
             subtable: SubtableSymbol,
             #[rust_sitter::leaf(pattern = r#"<\s*/\s*subtable_sym\s*>"#)]
+            #[cfg_attr(feature = "serde", serde(skip))]
             _end: (),
         },
     }
 
     #[rust_sitter::extra]
     #[derive(Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct Whitespace {
         #[rust_sitter::leaf(pattern = r"\s")]
+        #[cfg_attr(feature = "serde", serde(skip))]
         _whitespace: (),
     }
+
+    /// An XML comment, skipped anywhere in the input
+    #[rust_sitter::extra]
+    #[derive(Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    pub struct Comment {
+        #[rust_sitter::leaf(pattern = r"<!--([^-]|-[^-])*-->")]
+        #[cfg_attr(feature = "serde", serde(skip))]
+        _comment: (),
+    }
+
+    /// An XML processing instruction such as the `<?xml ...?>` declaration, skipped anywhere
+    /// in the input
+    #[rust_sitter::extra]
+    #[derive(Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    pub struct ProcessingInstruction {
+        #[rust_sitter::leaf(pattern = r"<\?([^?]|\?[^>])*\?>")]
+        #[cfg_attr(feature = "serde", serde(skip))]
+        _instruction: (),
+    }
 }
 
-#[allow(non_upper_case_globals)]
+#[allow(non_upper_case_globals, non_snake_case)]
 #[cfg(test)]
 mod test {
-    use crate::parse;
+    use crate::{
+        grammar::{
+            parse_sleigh_bool, AddrSpace, BinaryExpressionType, ConstantTemplateSelector,
+            ConstantTemplateType, ConstantValue, Constructor, ConstructorOperand,
+            ContextChangeType, ContextField, ContextPattern, DecisionNode, DecisionNodePair,
+            DisjointPatternType, FamilySymbol, HandleTemplate, InstructionPattern, OperandSymbol,
+            OperandValue, OperationCode, OperationTemplate, OverlaySpace, PatternBlock,
+            PatternBlockWord, PatternExpressionType, PatternValueType, PatternlessSymbol,
+            PrintPieceType, Scope, Sleigh, SleighSymbolType, SourceFiles, Spaces, SpecificSymbol,
+            SubtableSymbol, SymbolHeader, SymbolHeaderType, SymbolTable, TokenField, TripleSymbol,
+            UnaryExpressionType, ValueSymbolType, VarNodeSymbol, VarNodeTableValue,
+            VarNodeTableValueType, VarNodeTemplate, XmlUnescape,
+        },
+        parse, parse_borrowed, parse_bytes, parse_file, parse_header_only, parse_prefix,
+        parse_reader, parse_with_progress, parse_with_spans, BitRange, ConstTplKind, DecisionStats,
+        Endian, ExprKind, HandleLocation, HexInteger, IntegerExt, NodeRef, ParseProgress,
+        PatternContext, SleighDiff, SleighParseError, SleighTree, TemplateContext, Visitor,
+    };
+    use malachite::Integer;
+    use rust_sitter::tree_sitter::{InputEdit, Point};
+    use std::collections::HashMap;
+
+    const MINIMAL: &str = r#"<sleigh version="3" bigendian="true" align="1" uniqbase="0x100">
+<sourcefiles>
+<sourcefile name="test.sinc" index="0"/>
+</sourcefiles>
+<spaces defaultspace="ram">
+<space_unique name="unique" index="1" bigendian="true" delay="0" size="4" physical="true"/>
+<space name="ram" index="2" bigendian="true" delay="1" size="4" physical="true"/>
+</spaces>
+<symbol_table scopesize="1" symbolsize="1">
+<scope id="0x0" parent="0x0"/>
+<subtable_sym_head name="instruction" id="0x0" scope="0x0"/>
+<subtable_sym name="instruction" id="0x0" scope="0x0" numct="1">
+<constructor parent="0x0" first="0" length="1" line="0:1">
+<print piece="nop"/>
+<construct_tpl>
+<null/></construct_tpl>
+</constructor>
+<decision number="0" context="false" start="0" size="0">
+<pair id="0">
+<instruct_pat>
+<pat_block offset="0" nonzero="1">
+<mask_word mask="0xff000000" val="0x0"/>
+</pat_block>
+</instruct_pat>
+</pair>
+</decision>
+</subtable_sym>
+</symbol_table>
+</sleigh>"#;
 
     #[test]
     fn test_6502() {
@@ -3916,4 +6629,4082 @@ mod test {
         const X86: &str = include_str!("../Processors/x86/data/languages/x86.sla");
         parse(X86).expect("Failed to parse x86 sla");
     }
+
+    #[test]
+    fn test_spaces_by_name() {
+        const X86: &str = include_str!("../Processors/x86/data/languages/x86.sla");
+        let slgh = parse(X86).expect("Failed to parse x86 sla");
+        let spaces = slgh.spaces();
+        let ram = spaces.by_name("ram").expect("No ram space");
+        assert_eq!(ram.name(), "ram");
+        assert_eq!(ram.size(), &Integer::from(4));
+        assert_eq!(spaces.default_space(), Some(ram));
+        assert_eq!(
+            spaces.by_name("register").map(|space| space.index()),
+            Some(&Integer::from(4))
+        );
+        assert!(spaces.by_name("unique").is_some());
+        assert!(spaces.by_name("RAM").is_none());
+    }
+
+    #[test]
+    fn test_register_by_name() {
+        const X86: &str = include_str!("../Processors/x86/data/languages/x86.sla");
+        let slgh = parse(X86).expect("Failed to parse x86 sla");
+        assert_eq!(
+            slgh.varnodes().count(),
+            X86.matches("<varnode_sym ").count()
+        );
+        let eax = slgh.register_by_name("EAX").expect("No EAX register");
+        assert_eq!(eax.space(), "register");
+        assert_eq!(eax.offset(), &Integer::from(0));
+        assert_eq!(eax.size(), &Integer::from(4));
+        let esp = slgh.register_by_name("ESP").expect("No ESP register");
+        assert_eq!(esp.offset(), &Integer::from(0x10));
+        let al = slgh.register_by_name("AL").expect("No AL register");
+        assert_eq!((al.offset(), al.size()), (eax.offset(), &Integer::from(1)));
+        assert!(slgh.register_by_name("eax").is_none());
+    }
+
+    #[test]
+    fn test_deadcodedelay_or_default() {
+        let space = AddrSpace::builder()
+            .name("ram".to_string())
+            .index(3)
+            .bigendian(true)
+            .delay(1)
+            .size(4)
+            .physical(true)
+            .build();
+        assert_eq!(space.deadcodedelay_or_default(), 1);
+
+        let space = AddrSpace::builder()
+            .name("ram".to_string())
+            .index(3)
+            .bigendian(true)
+            .delay(1)
+            .deadcodedelay(0)
+            .size(4)
+            .physical(true)
+            .build();
+        assert_eq!(space.deadcodedelay_or_default(), 0);
+    }
+
+    #[test]
+    fn test_constant_usages() {
+        const toy_be: &str = include_str!("../Processors/Toy/data/languages/toy_be.sla");
+        let slgh = parse(toy_be).expect("Failed to parse toy_be sla");
+        let usages = slgh.constant_usages().collect::<Vec<_>>();
+        assert_eq!(
+            usages.len(),
+            toy_be.matches(r#"<const_tpl type="real""#).count()
+        );
+        assert!(usages
+            .iter()
+            .all(|(_, constant)| matches!(constant, ConstantTemplateType::Real { .. })));
+    }
+
+    #[test]
+    fn test_subtable_without_numct() {
+        let no_numct = MINIMAL.replacen(r#" numct="1""#, "", 1);
+        let slgh = parse(&no_numct).expect("Failed to parse sla without numct");
+        let instruction = slgh.subtables().next().expect("No instruction subtable");
+        assert_eq!(instruction.numct(), None);
+        assert_eq!(instruction.constructors().len(), 1);
+        assert_eq!(instruction.numct_or_len(), 1);
+
+        let subtable = SubtableSymbol::builder()
+            .header(
+                SymbolHeader::builder()
+                    .name("instruction".to_string())
+                    .id(0)
+                    .scope(0)
+                    .build(),
+            )
+            .constructors(vec![])
+            .decisiontree(
+                DecisionNode::builder()
+                    .number(0)
+                    .context(false)
+                    .start(0)
+                    .bitsize(0)
+                    .pairs(vec![])
+                    .children(vec![])
+                    .build(),
+            )
+            .build();
+        assert_eq!(subtable.numct_or_len(), 0);
+    }
+
+    #[test]
+    fn test_pattern_expression_kind() {
+        let constant = |val: i64| {
+            PatternExpressionType::PatternValue(PatternValueType::ConstantValue(
+                ConstantValue::builder().val(val).build(),
+            ))
+        };
+        assert_eq!(constant(1).kind(), ExprKind::Constant);
+
+        let plus = PatternExpressionType::BinaryExpression(Box::new(BinaryExpressionType::Plus {
+            _start: (),
+            left: constant(1),
+            right: constant(2),
+            _end: (),
+        }));
+        assert_eq!(plus.kind(), ExprKind::Plus);
+
+        let not = PatternExpressionType::UnaryExpression(Box::new(UnaryExpressionType::Not {
+            _start: (),
+            inner: plus,
+            _end: (),
+        }));
+        assert_eq!(not.kind(), ExprKind::Not);
+
+        let start = PatternExpressionType::PatternValue(PatternValueType::StartInstructionValue {
+            _tag: (),
+        });
+        assert_eq!(start.kind(), ExprKind::Start);
+    }
+
+    #[test]
+    fn test_disassemble() {
+        const toy_be: &str = include_str!("../Processors/Toy/data/languages/toy_be.sla");
+        const toy_le: &str = include_str!("../Processors/Toy/data/languages/toy_le.sla");
+        let be = parse(toy_be).expect("Failed to parse toy_be sla");
+        let le = parse(toy_le).expect("Failed to parse toy_le sla");
+        let address = Integer::from(0x100);
+
+        let add = be
+            .disassemble(&[0xc0, 0x12], &address, &[])
+            .expect("Failed to disassemble add");
+        assert_eq!(add.mnemonic, "add");
+        assert_eq!(add.body, "r1, r2");
+        assert_eq!(add.length, 2);
+        assert_eq!(add.to_string(), "add r1, r2");
+
+        let add = le
+            .disassemble(&[0x12, 0xc0], &address, &[])
+            .expect("Failed to disassemble little-endian add");
+        assert_eq!(add.to_string(), "add r1, r2");
+
+        let imm = be
+            .disassemble(&[0x13, 0x05], &address, &[])
+            .expect("Failed to disassemble imm");
+        assert_eq!(imm.to_string(), "imm r3, #0x105");
+
+        let simm = be
+            .disassemble(&[0xb0, 0x00], &address, &[])
+            .expect("Failed to disassemble simm");
+        assert_eq!(simm.to_string(), "simm r0, #-0x100");
+
+        let add = be
+            .disassemble(&[0xc8, 0x1e], &address, &[])
+            .expect("Failed to disassemble add immediate");
+        assert_eq!(add.to_string(), "add r1, #-0x2");
+
+        let br = be
+            .disassemble(&[0xef, 0xe7], &address, &[])
+            .expect("Failed to disassemble br");
+        assert_eq!(br.mnemonic, "br");
+        assert_eq!(br.body, "0xfe");
+
+        assert!(be.disassemble(&[0xc0], &address, &[]).is_none());
+        assert!(be.disassemble(&[0xf7, 0x00], &address, &[]).is_none());
+    }
+
+    #[test]
+    fn test_disassemble_malformed() {
+        let address = Integer::from(0);
+        let minimal = parse(MINIMAL).expect("Failed to parse minimal sla");
+        assert!(minimal.disassemble(&[0], &address, &[]).is_some());
+
+        let context_op = |i: i32, shift: i32| {
+            parse(&MINIMAL.replacen(
+                "<construct_tpl>",
+                &format!(
+                    r#"<context_op i="{}" shift="{}" mask="0xff000000" >
+<intb val="1"/>
+</context_op>
+<construct_tpl>"#,
+                    i, shift
+                ),
+                1,
+            ))
+            .expect("Failed to parse context_op")
+        };
+        let nop = context_op(0, 24)
+            .disassemble(&[0], &address, &[0; 4])
+            .expect("Failed to disassemble nop with a context change");
+        assert_eq!(nop.context, [1, 0, 0, 0]);
+        // Shifts past a context word, and words past the end of the context
+        assert!(context_op(0, 0xffffff)
+            .disassemble(&[0], &address, &[0; 4])
+            .is_none());
+        assert!(context_op(0, 24).disassemble(&[0], &address, &[]).is_none());
+        assert!(context_op(0x3fffffff, 24)
+            .disassemble(&[0], &address, &[0; 4])
+            .is_none());
+
+        // An operand that resolves to its own subtable at the same offset
+        let recursive = parse(
+            &MINIMAL
+                .replacen(r#"symbolsize="1""#, r#"symbolsize="2""#, 1)
+                .replacen(
+                    "<subtable_sym name=",
+                    r#"<operand_sym_head name="self" id="0x1" scope="0x0"/>
+<subtable_sym name="#,
+                    1,
+                )
+                .replacen(
+                    r#"<print piece="nop"/>"#,
+                    r#"<oper id="0x1"/>
+<print piece="nop"/>"#,
+                    1,
+                )
+                .replacen(
+                    "</symbol_table>",
+                    r#"<operand_sym name="self" id="0x1" scope="0x0" subsym="0x0" off="0" base="-1" minlen="0" index="0">
+<operand_exp index="0" table="0x0" ct="0x0"/>
+</operand_sym>
+</symbol_table>"#,
+                    1,
+                ),
+        )
+        .expect("Failed to parse recursive subtable");
+        assert!(recursive.disassemble(&[0], &address, &[]).is_none());
+
+        // A zero word size reads as byte addressing
+        const toy_be: &str = include_str!("../Processors/Toy/data/languages/toy_be.sla");
+        let zero = parse(&toy_be.replacen(
+            r#"<space name="ram" index="3" bigendian="true" delay="1" size="4""#,
+            r#"<space name="ram" index="3" bigendian="true" delay="1" size="4" wordsize="0""#,
+            1,
+        ))
+        .expect("Failed to parse zero word size");
+        let br = zero
+            .disassemble(&[0xef, 0xe7], &Integer::from(0x100), &[])
+            .expect("Failed to disassemble br");
+        assert_eq!(br.body, "0xfe");
+    }
+
+    #[test]
+    fn test_operands_using_subtable() {
+        const toy_be: &str = include_str!("../Processors/Toy/data/languages/toy_be.sla");
+        let slgh = parse(toy_be).expect("Failed to parse toy_be sla");
+        // Rel82
+        let operands = slgh.operands_using_subtable(&Integer::from(0x5d));
+        assert_eq!(operands.len(), toy_be.matches(r#"subsym="0x5d""#).count());
+        assert!(operands
+            .iter()
+            .all(|(_, operand)| operand.header().name() == "Rel82"));
+        assert!(slgh.operands_using_subtable(&Integer::from(-1)).is_empty());
+
+        // Operands defined by other symbols are not found by their ids
+        let other = slgh
+            .symbol_table()
+            .symbols()
+            .iter()
+            .find_map(|symbol| match symbol {
+                SleighSymbolType::TripleSymbol(TripleSymbol::SpecificSymbol(
+                    SpecificSymbol::OperandSymbol(operand),
+                )) => operand.subsym().filter(|id| {
+                    slgh.subtables()
+                        .all(|subtable| subtable.header().id() != *id)
+                }),
+                _ => None,
+            })
+            .expect("No operand defined by a symbol other than a subtable");
+        assert!(slgh.operands_using_subtable(other).is_empty());
+    }
+
+    #[test]
+    fn test_unknown_const_tpl() {
+        let unknown_const_tpl = MINIMAL.replacen(
+            "<null/></construct_tpl>",
+            r#"<null/><op_tpl code="COPY"><null/>
+<varnode_tpl><const_tpl type="spaceid" name="const"/><const_tpl type="mystery" val="0x10"/><const_tpl type="future"/></varnode_tpl>
+</op_tpl>
+</construct_tpl>"#,
+            1,
+        );
+        let slgh = parse(&unknown_const_tpl).expect("Failed to parse sla with unknown const_tpl");
+        let unknown = slgh
+            .constant_templates()
+            .filter_map(|(_, constant)| match constant {
+                ConstantTemplateType::Unknown { type_name, val, .. } => {
+                    Some((type_name.as_str(), val.clone()))
+                }
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            unknown,
+            [("mystery", Some(Integer::from(16))), ("future", None)]
+        );
+
+        // Known types are still parsed as such
+        const toy_be: &str = include_str!("../Processors/Toy/data/languages/toy_be.sla");
+        const SLA_8051: &str = include_str!("../Processors/8051/data/languages/8051.sla");
+        for sla in [toy_be, SLA_8051] {
+            let slgh = parse(sla).expect("Failed to parse sla");
+            assert!(slgh.constant_templates().next().is_some());
+            assert!(slgh
+                .constant_templates()
+                .all(|(_, constant)| !matches!(constant, ConstantTemplateType::Unknown { .. })));
+        }
+    }
+
+    #[test]
+    fn test_write_sla() {
+        const toy_be: &str = include_str!("../Processors/Toy/data/languages/toy_be.sla");
+        let slgh = parse(toy_be).expect("Failed to parse toy_be sla");
+        let mut out = Vec::new();
+        slgh.write_sla(&mut out)
+            .expect("Failed to write toy_be sla");
+        let out = String::from_utf8(out).expect("Written sla is not utf-8");
+        assert_eq!(out, toy_be);
+        assert_eq!(parse(&out).expect("Failed to reparse written sla"), slgh);
+    }
+
+    #[test]
+    fn test_write_sla_negative_hex() {
+        let input = MINIMAL.replacen(r#"uniqbase="0x100""#, r#"uniqbase="-0x100""#, 1);
+        let slgh = parse(&input).expect("Failed to parse negative uniqbase");
+        assert_eq!(slgh.uniqbase(), &Integer::from(-0x100));
+        let mut out = Vec::new();
+        slgh.write_sla(&mut out)
+            .expect("Failed to write negative uniqbase");
+        let out = String::from_utf8(out).expect("Written sla is not utf-8");
+        assert!(out.contains(r#"uniqbase="-0x100""#), "{}", out);
+        assert_eq!(parse(&out).expect("Failed to reparse written sla"), slgh);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        parse(MINIMAL).expect("Failed to parse minimal sla");
+
+        for (valid, corrupted) in [
+            (r#"version="3""#, r#"version="3"#),
+            (r#"version="3""#, r#"version=""#),
+            (r#"uniqbase="0x100""#, r#"uniqbase="0x"#),
+            (r#"<scope id="0x0""#, r#"<scope id="0x-1""#),
+            (r#"<scope id="0x0""#, r#"<scope id="0x"#),
+            (r#"<scope id="0x0""#, r#"<scope id=""#),
+        ] {
+            let input = MINIMAL.replacen(valid, corrupted, 1);
+            assert!(
+                matches!(parse(&input), Err(SleighParseError::Syntax(_))),
+                "{} was accepted",
+                corrupted
+            );
+        }
+
+        // The root attributes are parsed as a group, so their quoted values are checked by
+        // the group's transform, as those of spaces are
+        for (valid, corrupted, field) in [
+            (r#"version="3""#, r#"version="3x""#, "Sleigh::version"),
+            (
+                r#"uniqbase="0x100""#,
+                r#"uniqbase="0x10g""#,
+                "Sleigh::uniqbase",
+            ),
+            (
+                r#"uniqbase="0x100""#,
+                r#"uniqbase="256""#,
+                "Sleigh::uniqbase",
+            ),
+        ] {
+            match parse(&MINIMAL.replacen(valid, corrupted, 1)) {
+                Err(SleighParseError::InvalidInteger {
+                    field: error_field, ..
+                }) => assert_eq!(error_field, field),
+                other => panic!("Expected InvalidInteger for {}, got {:?}", corrupted, other),
+            }
+        }
+
+        let input = MINIMAL.replacen(r#"bigendian="true""#, r#"bigendian="yes""#, 1);
+        match parse(&input) {
+            Err(SleighParseError::InvalidBool { field, raw, .. }) => {
+                assert_eq!(field, "Sleigh::bigendian");
+                assert_eq!(raw, "yes");
+            }
+            other => panic!("Expected an invalid boolean, got {:?}", other),
+        }
+
+        for end in [0, 1, 7, 64, MINIMAL.len() / 2, MINIMAL.len() - 1] {
+            assert!(parse(&MINIMAL[..end]).is_err());
+        }
+
+        // An error from one parse does not leak into the next
+        parse(MINIMAL).expect("Failed to parse minimal sla after errors");
+    }
+
+    #[test]
+    fn test_accessors() {
+        const toy_be: &str = include_str!("../Processors/Toy/data/languages/toy_be.sla");
+        let slgh = parse(toy_be).expect("Failed to parse toy_be sla");
+        assert!(slgh.bigendian());
+        assert_eq!(slgh.uniqbase(), &Integer::from(0x1e00));
+        assert_eq!(slgh.spaces().defaultspace(), "ram");
+        assert_eq!(
+            slgh.sourcefiles().source_files()[0].name(),
+            "toyInstructions.sinc"
+        );
+
+        let field = slgh
+            .symbol_table()
+            .symbols()
+            .iter()
+            .find_map(|symbol| match symbol {
+                SleighSymbolType::TripleSymbol(TripleSymbol::FamilySymbol(
+                    FamilySymbol::ValueSymbol(ValueSymbolType::ValueSymbol(value)),
+                )) if value.header().name() == "op1515" => match value.patval() {
+                    PatternValueType::TokenField(field) => Some(field),
+                    _ => None,
+                },
+                _ => None,
+            })
+            .expect("No op1515 token field");
+        assert!(field.bigendian());
+        assert!(!field.signbit());
+        assert_eq!(field.bitstart(), &Integer::from(15));
+        assert_eq!(field.bitend(), &Integer::from(15));
+        assert_eq!(field.shift(), &Integer::from(7));
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_serde_round_trip() {
+        const toy_be: &str = include_str!("../Processors/Toy/data/languages/toy_be.sla");
+        let slgh = parse(toy_be).expect("Failed to parse toy_be sla");
+        let json = serde_json::to_string(&slgh).expect("Failed to serialize toy_be");
+        assert!(json.contains(r#""uniqbase":"7680""#));
+        assert!(!json.contains(r#""_start""#));
+        let deserialized: crate::grammar::Sleigh =
+            serde_json::from_str(&json).expect("Failed to deserialize toy_be");
+        assert_eq!(deserialized, slgh);
+    }
+
+    #[test]
+    fn test_to_sla_string() {
+        const toy_le: &str = include_str!("../Processors/Toy/data/languages/toy_le.sla");
+        let slgh = parse(toy_le).expect("Failed to parse toy_le sla");
+        assert_eq!(slgh.to_sla_string(), toy_le);
+        assert_eq!(slgh.to_string(), toy_le);
+
+        let escaped = toy_le.replacen(
+            r#"<sourcefile name="toyInstructions.sinc""#,
+            r#"<sourcefile name="&lt;toy&gt; &amp; &quot;friends&apos;.sinc""#,
+            1,
+        );
+        let slgh = parse(&escaped).expect("Failed to parse escaped toy_le sla");
+        assert_eq!(
+            slgh.sourcefiles().source_files()[0].name(),
+            "<toy> & \"friends'.sinc"
+        );
+        let written = slgh.to_sla_string();
+        assert_eq!(written, escaped);
+        assert_eq!(
+            parse(&written).expect("Failed to reparse written sla"),
+            slgh
+        );
+    }
+
+    #[test]
+    fn test_xml_unescape() {
+        assert_eq!("&#65;".xml_unescape(), "A");
+        assert_eq!("&#x41;".xml_unescape(), "A");
+        assert_eq!("&#x2d;&#x2D;".xml_unescape(), "--");
+        assert_eq!("a&#10;b".xml_unescape(), "a\nb");
+        assert_eq!("&#x1F600;".xml_unescape(), "\u{1F600}");
+        assert_eq!("&lt;&amp;lt;&gt;".xml_unescape(), "<&lt;>");
+        assert_eq!("&quot;&apos;".xml_unescape(), "\"'");
+
+        // Malformed references are passed through verbatim
+        assert_eq!("&#".xml_unescape(), "&#");
+        assert_eq!("&#;".xml_unescape(), "&#;");
+        assert_eq!("&#x;".xml_unescape(), "&#x;");
+        assert_eq!("&#xZZ;".xml_unescape(), "&#xZZ;");
+        assert_eq!("&#xD800;".xml_unescape(), "&#xD800;");
+        assert_eq!("&#-1;".xml_unescape(), "&#-1;");
+        assert_eq!("&#+65;".xml_unescape(), "&#+65;");
+        assert_eq!("&#x+41;".xml_unescape(), "&#x+41;");
+        // Only a lowercase `x` introduces a hexadecimal reference
+        assert_eq!("&#X2d;".xml_unescape(), "&#X2d;");
+        assert_eq!("&bogus; & done".xml_unescape(), "&bogus; & done");
+        assert_eq!("&#65".xml_unescape(), "&#65");
+    }
+
+    #[test]
+    fn test_lenient_bools() {
+        assert_eq!(parse_sleigh_bool("true"), Ok(true));
+        assert_eq!(parse_sleigh_bool("True"), Ok(true));
+        assert_eq!(parse_sleigh_bool("TRUE"), Ok(true));
+        assert_eq!(parse_sleigh_bool("1"), Ok(true));
+        assert_eq!(parse_sleigh_bool("false"), Ok(false));
+        assert_eq!(parse_sleigh_bool("False"), Ok(false));
+        assert_eq!(parse_sleigh_bool("0"), Ok(false));
+        assert!(parse_sleigh_bool("yes").is_err());
+        assert!(parse_sleigh_bool("2").is_err());
+        assert!(parse_sleigh_bool("").is_err());
+
+        const data_be_64: &str = include_str!("../Processors/DATA/data/languages/data-be-64.sla");
+        let canonical = data_be_64.replacen(r#"physical="false""#, r#"physical="true""#, 1);
+        let lenient = data_be_64
+            .replacen(r#"physical="false""#, r#"physical="True""#, 1)
+            .replacen(r#"flow="true""#, r#"flow="1""#, 1)
+            .replacen(r#"signbit="false""#, r#"signbit="FALSE""#, 1);
+        assert_eq!(
+            parse(&lenient).expect("Failed to parse lenient data-be-64 sla"),
+            parse(&canonical).expect("Failed to parse canonical data-be-64 sla")
+        );
+
+        let canonical = data_be_64.replacen(r#"flow="true""#, r#"flow="false""#, 1);
+        let lenient = data_be_64.replacen(r#"flow="true""#, r#"flow="0""#, 1);
+        assert_eq!(
+            parse(&lenient).expect("Failed to parse lenient data-be-64 sla"),
+            parse(&canonical).expect("Failed to parse canonical data-be-64 sla")
+        );
+    }
+
+    #[test]
+    fn test_symbol_by_id() {
+        const toy_be: &str = include_str!("../Processors/Toy/data/languages/toy_be.sla");
+        let slgh = parse(toy_be).expect("Failed to parse toy_be sla");
+        let table = slgh.symbol_table();
+
+        let id = Integer::from(0x50);
+        match table.symbol_by_id(&id) {
+            Some(SleighSymbolType::TripleSymbol(TripleSymbol::SubtableSymbol {
+                subtable, ..
+            })) => {
+                assert_eq!(subtable.header().name(), "Simm10");
+                assert_eq!(subtable.header().id(), &id);
+            }
+            other => panic!("Expected the Simm10 subtable, got {:?}", other),
+        }
+        match table.symbol_header_by_id(&id) {
+            Some(header @ SymbolHeaderType::SubTable { .. }) => {
+                assert_eq!(header.header().name(), "Simm10")
+            }
+            other => panic!("Expected the Simm10 subtable header, got {:?}", other),
+        }
+
+        // Every symbol can be found by its own id, including after the index is built
+        for header in table.symbol_headers() {
+            let id = header.header().id();
+            assert_eq!(table.symbol_header_by_id(id), Some(header));
+            assert!(table.symbol_by_id(id).is_some());
+        }
+        assert!(table.symbol_by_id(&Integer::from(0x10000)).is_none());
+        assert!(table.symbol_header_by_id(&Integer::from(-1)).is_none());
+    }
+
+    #[test]
+    fn test_space_token_section_symbols() {
+        let legacy = MINIMAL
+            .replacen(r#"symbolsize="1""#, r#"symbolsize="4""#, 1)
+            .replacen(
+                "<subtable_sym name=",
+                r#"<space_sym_head name="ram" id="0x1" scope="0x0"/>
+<token_sym_head name="instr" id="0x2" scope="0x0"/>
+<section_sym_head name="tail" id="0x3" scope="0x0"/>
+<subtable_sym name="#,
+                1,
+            )
+            .replacen(
+                "</symbol_table>",
+                r#"<space_sym name="ram" id="0x1" scope="0x0" space="ram"/>
+<token_sym name="instr" id="0x2" scope="0x0" size="4" bigendian="true"/>
+<section_sym name="tail" id="0x3" scope="0x0" index="1"/>
+</symbol_table>"#,
+                1,
+            );
+        let slgh =
+            parse(&legacy).expect("Failed to parse sla with space, token, and section symbols");
+        let table = slgh.symbol_table();
+
+        match table.symbol_by_id(&Integer::from(1)) {
+            Some(SleighSymbolType::SpaceSymbol(symbol)) => {
+                assert_eq!(symbol.header().name(), "ram");
+                assert_eq!(symbol.space(), "ram");
+            }
+            other => panic!("Expected a space symbol, got {:?}", other),
+        }
+        match table.symbol_by_id(&Integer::from(2)) {
+            Some(SleighSymbolType::TokenSymbol(symbol)) => {
+                assert_eq!(symbol.header().name(), "instr");
+                assert_eq!(symbol.size(), &Integer::from(4));
+                assert!(symbol.bigendian());
+            }
+            other => panic!("Expected a token symbol, got {:?}", other),
+        }
+        match table.symbol_by_id(&Integer::from(3)) {
+            Some(SleighSymbolType::SectionSymbol(symbol)) => {
+                assert_eq!(symbol.header().name(), "tail");
+                assert_eq!(symbol.index(), &Integer::from(1));
+            }
+            other => panic!("Expected a section symbol, got {:?}", other),
+        }
+        assert!(matches!(
+            table.symbol_header_by_id(&Integer::from(2)),
+            Some(SymbolHeaderType::Token { .. })
+        ));
+
+        let written = slgh.to_sla_string();
+        assert!(written.contains(r#"<space_sym_head name="ram" id="0x1" scope="0x0"/>"#));
+        assert!(written.contains(
+            r#"<token_sym name="instr" id="0x2" scope="0x0" size="4" bigendian="true"/>"#
+        ));
+        assert!(written.contains(r#"<section_sym name="tail" id="0x3" scope="0x0" index="1"/>"#));
+        assert_eq!(parse(&written).expect("Failed to parse written sla"), slgh);
+    }
+
+    #[test]
+    fn test_macro_label_symbols() {
+        let macros = MINIMAL
+            .replacen(r#"symbolsize="1""#, r#"symbolsize="3""#, 1)
+            .replacen(
+                "<subtable_sym name=",
+                r#"<macro_sym_head name="setflags" id="0x1" scope="0x0"/>
+<label_sym_head name="done" id="0x2" scope="0x0"/>
+<subtable_sym name="#,
+                1,
+            )
+            .replacen(
+                "</symbol_table>",
+                r#"<macro_sym name="setflags" id="0x1" scope="0x0" index="0">
+<oper id="0x10"/>
+<oper id="0x11"/>
+<construct_tpl>
+<null/></construct_tpl>
+</macro_sym>
+<label_sym name="done" id="0x2" scope="0x0" index="3" refcount="2" placed="true"/>
+</symbol_table>"#,
+                1,
+            );
+        let slgh = parse(&macros).expect("Failed to parse sla with macro and label symbols");
+        let table = slgh.symbol_table();
+
+        match table.symbol_by_id(&Integer::from(1)) {
+            Some(SleighSymbolType::MacroSymbol(symbol)) => {
+                assert_eq!(symbol.header().name(), "setflags");
+                assert_eq!(symbol.index(), &Integer::from(0));
+                let operands = symbol
+                    .operands()
+                    .iter()
+                    .map(|operand| operand.id().clone())
+                    .collect::<Vec<_>>();
+                assert_eq!(operands, [Integer::from(0x10), Integer::from(0x11)]);
+                assert!(symbol.templ().is_some());
+            }
+            other => panic!("Expected a macro symbol, got {:?}", other),
+        }
+        match table.symbol_by_id(&Integer::from(2)) {
+            Some(SleighSymbolType::LabelSymbol(symbol)) => {
+                assert_eq!(symbol.header().name(), "done");
+                assert_eq!(symbol.index(), &Integer::from(3));
+                assert_eq!(symbol.refcount(), &Integer::from(2));
+                assert!(symbol.placed());
+            }
+            other => panic!("Expected a label symbol, got {:?}", other),
+        }
+        assert!(matches!(
+            table.symbol_header_by_id(&Integer::from(1)),
+            Some(SymbolHeaderType::Macro { .. })
+        ));
+
+        let written = slgh.to_sla_string();
+        assert!(written.contains(r#"<macro_sym name="setflags" id="0x1" scope="0x0" index="0">"#));
+        assert!(written.contains(
+            r#"<label_sym name="done" id="0x2" scope="0x0" index="3" refcount="2" placed="true"/>"#
+        ));
+        assert_eq!(parse(&written).expect("Failed to parse written sla"), slgh);
+    }
+
+    #[test]
+    fn test_opcode_mnemonics() {
+        const MNEMONICS: [&str; 74] = [
+            "BLANK",
+            "COPY",
+            "LOAD",
+            "STORE",
+            "BRANCH",
+            "CBRANCH",
+            "BRANCHIND",
+            "CALL",
+            "CALLIND",
+            "CALLOTHER",
+            "RETURN",
+            "INT_EQUAL",
+            "INT_NOTEQUAL",
+            "INT_SLESS",
+            "INT_SLESSEQUAL",
+            "INT_LESS",
+            "INT_LESSEQUAL",
+            "INT_ZEXT",
+            "INT_SEXT",
+            "INT_ADD",
+            "INT_SUB",
+            "INT_CARRY",
+            "INT_SCARRY",
+            "INT_SBORROW",
+            "INT_2COMP",
+            "INT_NEGATE",
+            "INT_XOR",
+            "INT_AND",
+            "INT_OR",
+            "INT_LEFT",
+            "INT_RIGHT",
+            "INT_SRIGHT",
+            "INT_MULT",
+            "INT_DIV",
+            "INT_SDIV",
+            "INT_REM",
+            "INT_SREM",
+            "BOOL_NEGATE",
+            "BOOL_XOR",
+            "BOOL_AND",
+            "BOOL_OR",
+            "FLOAT_EQUAL",
+            "FLOAT_NOTEQUAL",
+            "FLOAT_LESS",
+            "FLOAT_LESSEQUAL",
+            "UNUSED1",
+            "FLOAT_NAN",
+            "FLOAT_ADD",
+            "FLOAT_DIV",
+            "FLOAT_MULT",
+            "FLOAT_SUB",
+            "FLOAT_NEG",
+            "FLOAT_ABS",
+            "FLOAT_SQRT",
+            "INT2FLOAT",
+            "FLOAT2FLOAT",
+            "TRUNC",
+            "CEIL",
+            "FLOOR",
+            "ROUND",
+            "BUILD",
+            "DELAY_SLOT",
+            "PIECE",
+            "SUBPIECE",
+            "CAST",
+            "LABEL",
+            "CROSSBUILD",
+            "SEGMENTOP",
+            "CPOOLREF",
+            "NEW",
+            "INSERT",
+            "EXTRACT",
+            "POPCOUNT",
+            "LZCOUNT",
+        ];
+        for mnemonic in MNEMONICS {
+            let code = OperationCode::from_mnemonic(mnemonic)
+                .unwrap_or_else(|| panic!("No operation code for {}", mnemonic));
+            assert_eq!(code.mnemonic(), mnemonic);
+            assert_eq!(OperationCode::from_mnemonic(code.mnemonic()), Some(code));
+        }
+        assert!(OperationCode::from_mnemonic("int_add").is_none());
+        assert!(OperationCode::from_mnemonic("").is_none());
+    }
+
+    #[test]
+    fn test_visitor() {
+        #[derive(Default)]
+        struct OpcodeTally {
+            opcodes: HashMap<&'static str, usize>,
+            constructors: usize,
+            decision_nodes: usize,
+            expressions: usize,
+        }
+
+        impl Visitor for OpcodeTally {
+            fn visit_constructor(&mut self, _constructor: &Constructor) {
+                self.constructors += 1;
+            }
+
+            fn visit_op_template(&mut self, op: &OperationTemplate) {
+                *self.opcodes.entry(op.code().mnemonic()).or_default() += 1;
+            }
+
+            fn visit_pattern_expression(&mut self, _expression: &PatternExpressionType) {
+                self.expressions += 1;
+            }
+
+            fn visit_decision_node(&mut self, _node: &DecisionNode) {
+                self.decision_nodes += 1;
+            }
+        }
+
+        const toy_be: &str = include_str!("../Processors/Toy/data/languages/toy_be.sla");
+        let slgh = parse(toy_be).expect("Failed to parse toy_be sla");
+        let mut tally = OpcodeTally::default();
+        slgh.accept(&mut tally);
+
+        let subtables = slgh
+            .symbol_table()
+            .symbols()
+            .iter()
+            .filter_map(|symbol| match symbol {
+                SleighSymbolType::TripleSymbol(TripleSymbol::SubtableSymbol {
+                    subtable, ..
+                }) => Some(subtable),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        let constructors = subtables
+            .iter()
+            .flat_map(|subtable| subtable.constructors())
+            .collect::<Vec<_>>();
+        let ops = constructors
+            .iter()
+            .flat_map(|constructor| {
+                constructor
+                    .templ()
+                    .into_iter()
+                    .chain(constructor.namedtempl())
+            })
+            .map(|templ| templ.vec().len())
+            .sum::<usize>();
+
+        assert_eq!(tally.constructors, constructors.len());
+        assert_eq!(tally.opcodes.values().sum::<usize>(), ops);
+        assert!(tally.opcodes["COPY"] > 0);
+        assert!(tally.opcodes["INT_ADD"] > 0);
+        // Every subtable has a root decision node, and the toy instruction table is split
+        assert!(tally.decision_nodes > subtables.len());
+        assert!(tally.expressions > 0);
+    }
+
+    #[test]
+    fn test_constructors() {
+        const SLA_6502: &str = include_str!("../Processors/6502/data/languages/6502.sla");
+        let slgh = parse(SLA_6502).expect("Failed to parse 6502 sla");
+
+        assert_eq!(
+            slgh.subtables().count(),
+            SLA_6502.matches("<subtable_sym ").count()
+        );
+        assert_eq!(slgh.constructors().count(), 79);
+        // The iterators borrow the tree, so walking them again yields the same constructors
+        assert_eq!(slgh.constructors().count(), slgh.constructors().count());
+        assert!(slgh
+            .constructors()
+            .zip(slgh.constructors())
+            .all(|(first, second)| std::ptr::eq(first, second)));
+        assert_eq!(
+            slgh.constructors().count(),
+            slgh.subtables()
+                .map(|subtable| subtable.constructors().len())
+                .sum::<usize>()
+        );
+        assert!(slgh
+            .subtables()
+            .any(|subtable| subtable.header().name() == "instruction"));
+    }
+
+    #[test]
+    fn test_integer_ext() {
+        let slgh = parse(MINIMAL).expect("Failed to parse minimal sla");
+        assert_eq!(slgh.uniqbase().as_i64(), Ok(0x100));
+        assert_eq!(slgh.uniqbase().as_u64(), Ok(0x100));
+        assert_eq!(slgh.uniqbase().as_usize(), Ok(0x100));
+
+        let input = MINIMAL.replacen(
+            r#"uniqbase="0x100""#,
+            r#"uniqbase="0x1000000000000000000000000""#,
+            1,
+        );
+        let slgh = parse(&input).expect("Failed to parse sla with a huge uniqbase");
+        let error = slgh
+            .uniqbase()
+            .as_u64()
+            .expect_err("Converted a huge uniqbase to u64");
+        assert_eq!(error.target(), "u64");
+        assert_eq!(error.value(), slgh.uniqbase());
+        assert_eq!(
+            error.to_string(),
+            "79228162514264337593543950336 does not fit in u64"
+        );
+        assert!(slgh.uniqbase().as_i64().is_err());
+        assert!(slgh.uniqbase().as_usize().is_err());
+
+        let negative = Integer::from(-1);
+        assert_eq!(negative.as_i64(), Ok(-1));
+        assert!(negative.as_u64().is_err());
+        assert!(negative.as_usize().is_err());
+    }
+
+    #[test]
+    fn test_parse_reader() {
+        let expected = parse(MINIMAL).expect("Failed to parse minimal sla");
+        assert_eq!(
+            parse_bytes(MINIMAL.as_bytes()).expect("Failed to parse bytes"),
+            expected
+        );
+        assert_eq!(
+            parse_reader(MINIMAL.as_bytes()).expect("Failed to parse from reader"),
+            expected
+        );
+
+        let mut invalid = MINIMAL.as_bytes().to_vec();
+        invalid[20] = 0xff;
+        match parse_bytes(&invalid) {
+            Err(SleighParseError::Utf8(error)) => assert_eq!(error.valid_up_to(), 20),
+            other => panic!("Expected a UTF-8 error, got {:?}", other),
+        }
+        assert!(matches!(
+            parse_reader(invalid.as_slice()),
+            Err(SleighParseError::Utf8(_))
+        ));
+
+        struct Failing;
+        impl std::io::Read for Failing {
+            fn read(&mut self, _buf: &mut [u8]) -> std::io::Result<usize> {
+                Err(std::io::Error::other("truncated archive"))
+            }
+        }
+        match parse_reader(Failing) {
+            Err(error @ SleighParseError::Io(_)) => {
+                assert_eq!(error.to_string(), "failed to read input: truncated archive");
+                assert!(std::error::Error::source(&error).is_some());
+            }
+            other => panic!("Expected an I/O error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_token_field_extract() {
+        let field = |bigendian, signbit, bytes: (i32, i32), bits: (i32, i32), shift: i32| {
+            TokenField::builder()
+                .bigendian(bigendian)
+                .signbit(signbit)
+                .bitstart(bits.0)
+                .bitend(bits.1)
+                .bytestart(bytes.0)
+                .byteend(bytes.1)
+                .shift(shift)
+                .build()
+        };
+        let token = [0x12, 0x34, 0xf0];
+
+        let big = field(true, false, (0, 1), (4, 11), 4);
+        assert_eq!(big.extract(&token), Some(Integer::from(0x23)));
+        let little = field(false, false, (0, 1), (4, 11), 4);
+        assert_eq!(little.extract(&token), Some(Integer::from(0x41)));
+
+        let signed = field(true, true, (2, 2), (0, 7), 0);
+        assert_eq!(signed.extract(&token), Some(Integer::from(-16)));
+        let unsigned = field(true, false, (2, 2), (0, 7), 0);
+        assert_eq!(unsigned.extract(&token), Some(Integer::from(0xf0)));
+        let signed_little = field(false, true, (0, 1), (0, 15), 0);
+        assert_eq!(
+            signed_little.extract(&[0xfe, 0xff]),
+            Some(Integer::from(-2))
+        );
+
+        // Bytes past the end of the buffer read as zero
+        assert_eq!(big.extract(&[0x12]), Some(Integer::from(0x20)));
+        assert_eq!(field(true, false, (1, 0), (0, 7), 0).extract(&token), None);
+        // A shift past the end of the value
+        assert_eq!(
+            field(true, false, (0, 1), (0, 7), 128).extract(&token),
+            None
+        );
+        // A sign bit past the end of the value
+        assert_eq!(field(true, true, (0, 1), (0, 200), 0).extract(&token), None);
+        // Byte offsets whose end overflows
+        let far = |bytestart: usize, byteend: usize| {
+            TokenField::builder()
+                .bigendian(true)
+                .signbit(false)
+                .bitstart(0)
+                .bitend(7)
+                .bytestart(Integer::from(bytestart))
+                .byteend(Integer::from(byteend))
+                .shift(0)
+                .build()
+        };
+        assert_eq!(far(usize::MAX, usize::MAX).extract(&token), None);
+        assert_eq!(far(0, usize::MAX).extract(&token), None);
+    }
+
+    #[test]
+    fn test_decision_resolve() {
+        let block = |mask: u32, val: u32| {
+            PatternBlock::builder()
+                .offset(0)
+                .nonzero(1)
+                .mask_vals(vec![PatternBlockWord::builder()
+                    .mask(mask)
+                    .val(val)
+                    .build()])
+                .build()
+        };
+        let pair = |id: i32, pattern| DecisionNodePair::builder().id(id).pattern(pattern).build();
+        let node = |context, start: i32, bitsize: i32, pairs, children| {
+            DecisionNode::builder()
+                .number(0)
+                .context(context)
+                .start(start)
+                .bitsize(bitsize)
+                .pairs(pairs)
+                .children(children)
+                .build()
+        };
+        let instruction = |mask, val| {
+            DisjointPatternType::Instruction(
+                InstructionPattern::builder()
+                    .mask_value(block(mask, val))
+                    .build(),
+            )
+        };
+
+        // The toy `add` register and immediate forms, split on the top instruction bit and
+        // then on bit 0 of the context
+        let tree = node(
+            false,
+            0,
+            1,
+            vec![],
+            vec![
+                node(false, 0, 0, vec![], vec![]),
+                node(
+                    true,
+                    0,
+                    1,
+                    vec![],
+                    vec![
+                        node(
+                            false,
+                            0,
+                            0,
+                            vec![
+                                pair(0, instruction(0xf8000000, 0xc0000000)),
+                                pair(1, instruction(0xf8000000, 0xc8000000)),
+                            ],
+                            vec![],
+                        ),
+                        node(
+                            false,
+                            0,
+                            0,
+                            vec![pair(
+                                2,
+                                DisjointPatternType::Context(
+                                    ContextPattern::builder()
+                                        .mask_value(block(0x80000000, 0x80000000))
+                                        .build(),
+                                ),
+                            )],
+                            vec![],
+                        ),
+                    ],
+                ),
+            ],
+        );
+
+        let leaf = &tree.children()[1].children()[0];
+        assert_eq!(
+            tree.resolve(&[0xc0, 0x12], &[0; 4]),
+            Some(leaf.pairs()[0].pattern())
+        );
+        assert_eq!(
+            tree.resolve(&[0xc8, 0x1e], &[0; 4]),
+            Some(leaf.pairs()[1].pattern())
+        );
+        let leaf = &tree.children()[1].children()[1];
+        assert_eq!(
+            tree.resolve(&[0xc0, 0x12], &[0x80, 0, 0, 0]),
+            Some(leaf.pairs()[0].pattern())
+        );
+        // No pattern matches, or the leaf is empty
+        assert_eq!(tree.resolve(&[0xd0, 0x00], &[0; 4]), None);
+        assert_eq!(tree.resolve(&[0x00, 0x00], &[0; 4]), None);
+        // Decision bits wider than the value they are read into
+        assert_eq!(
+            node(false, 0, 200, vec![], vec![]).resolve(&[0xc0, 0x12], &[0; 4]),
+            None
+        );
+
+        const toy_be: &str = include_str!("../Processors/Toy/data/languages/toy_be.sla");
+        let slgh = parse(toy_be).expect("Failed to parse toy_be sla");
+        let root = slgh
+            .subtables()
+            .find(|subtable| subtable.header().name() == "instruction")
+            .expect("No instruction subtable");
+        assert!(root.decisiontree().resolve(&[0xc0, 0x12], &[]).is_some());
+    }
+
+    #[test]
+    fn test_pattern_expression_evaluate() {
+        struct Fixed;
+        impl PatternContext for Fixed {
+            fn token_field(&self, _field: &TokenField) -> Option<Integer> {
+                Some(Integer::from(12))
+            }
+
+            fn context_field(&self, _field: &ContextField) -> Option<Integer> {
+                Some(Integer::from(3))
+            }
+
+            fn start(&self) -> Option<Integer> {
+                Some(Integer::from(0x100))
+            }
+        }
+
+        let constant = |val: i64| {
+            PatternExpressionType::PatternValue(PatternValueType::ConstantValue(
+                ConstantValue::builder().val(val).build(),
+            ))
+        };
+        let token = || {
+            PatternExpressionType::PatternValue(PatternValueType::TokenField(
+                TokenField::builder()
+                    .bigendian(true)
+                    .signbit(false)
+                    .bitstart(0)
+                    .bitend(7)
+                    .bytestart(0)
+                    .byteend(0)
+                    .shift(0)
+                    .build(),
+            ))
+        };
+        let context = || {
+            PatternExpressionType::PatternValue(PatternValueType::ContextField(
+                ContextField::builder()
+                    .signbit(false)
+                    .startbit(0)
+                    .endbit(7)
+                    .startbyte(0)
+                    .endbyte(0)
+                    .shift(0)
+                    .build(),
+            ))
+        };
+        let binary =
+            |make: fn(PatternExpressionType, PatternExpressionType) -> BinaryExpressionType,
+             left,
+             right| {
+                PatternExpressionType::BinaryExpression(Box::new(make(left, right)))
+            };
+        macro_rules! op {
+            ($variant:ident) => {
+                |left, right| BinaryExpressionType::$variant {
+                    _start: (),
+                    left,
+                    right,
+                    _end: (),
+                }
+            };
+        }
+
+        let cases: [(fn(_, _) -> _, i64); 9] = [
+            (op!(Plus), 15),
+            (op!(Sub), 9),
+            (op!(Mult), 36),
+            (op!(LeftShift), 96),
+            (op!(RightShift), 1),
+            (op!(And), 0),
+            (op!(Or), 15),
+            (op!(Xor), 15),
+            (op!(Div), 4),
+        ];
+        for (make, expected) in cases {
+            let expression = binary(make, token(), context());
+            assert_eq!(
+                expression.evaluate(&Fixed),
+                Some(Integer::from(expected)),
+                "{:?}",
+                expression.kind()
+            );
+        }
+
+        let minus = PatternExpressionType::UnaryExpression(Box::new(UnaryExpressionType::Minus {
+            _start: (),
+            inner: token(),
+            _end: (),
+        }));
+        assert_eq!(minus.evaluate(&Fixed), Some(Integer::from(-12)));
+        // (-token + 2) * ~context = (-10) * -4
+        let nested = binary(
+            op!(Mult),
+            binary(op!(Plus), minus, constant(2)),
+            PatternExpressionType::UnaryExpression(Box::new(UnaryExpressionType::Not {
+                _start: (),
+                inner: context(),
+                _end: (),
+            })),
+        );
+        assert_eq!(nested.evaluate(&Fixed), Some(Integer::from(40)));
+        // Shift amounts past Ghidra's 64-bit `intb` are rejected
+        assert_eq!(
+            binary(op!(LeftShift), token(), constant(0xffffffffff)).evaluate(&Fixed),
+            None
+        );
+        assert_eq!(
+            binary(op!(LeftShift), token(), constant(64)).evaluate(&Fixed),
+            None
+        );
+        assert_eq!(
+            binary(op!(LeftShift), token(), constant(63)).evaluate(&Fixed),
+            Some(Integer::from(12) << 63u64)
+        );
+
+        let start = PatternExpressionType::PatternValue(PatternValueType::StartInstructionValue {
+            _tag: (),
+        });
+        assert_eq!(
+            binary(op!(Plus), start, constant(2)).evaluate(&Fixed),
+            Some(Integer::from(0x102))
+        );
+        let end =
+            PatternExpressionType::PatternValue(PatternValueType::EndInstructionValue { _tag: () });
+        assert_eq!(end.evaluate(&Fixed), None);
+        assert_eq!(
+            binary(op!(Div), token(), constant(0)).evaluate(&Fixed),
+            None
+        );
+        assert_eq!(
+            binary(op!(LeftShift), token(), constant(-1)).evaluate(&Fixed),
+            None
+        );
+    }
+
+    #[test]
+    fn test_pattern_block_matches() {
+        const toy_be: &str = include_str!("../Processors/Toy/data/languages/toy_be.sla");
+        let slgh = parse(toy_be).expect("Failed to parse toy_be sla");
+        let root = slgh
+            .subtables()
+            .find(|subtable| subtable.header().name() == "instruction")
+            .expect("No instruction subtable");
+        let block = |instruction: &[u8]| match root.decisiontree().resolve(instruction, &[]) {
+            Some(DisjointPatternType::Instruction(pattern)) => pattern.mask_value(),
+            other => panic!("Expected an instruction pattern, got {:?}", other),
+        };
+
+        // Constrains bits of both bytes
+        let two = block(&[0x80, 0x00]);
+        assert_eq!(two.mask_vals()[0].mask(), &Integer::from(0xfff80000u32));
+        assert!(two.matches(&[0x80, 0x00]));
+        assert!(two.matches(&[0x80, 0x07, 0xff, 0xff, 0xff]));
+        assert!(!two.matches(&[0x80, 0x08]));
+        assert!(!two.matches(&[0x81, 0x00]));
+        // Too short for the pattern
+        assert!(!two.matches(&[0x80]));
+        assert!(!two.matches(&[]));
+
+        // The register form of `add` only constrains the first byte
+        let add = block(&[0xc0, 0x12]);
+        assert!(add.matches(&[0xc0, 0x12]));
+        assert!(add.matches(&[0xc0]));
+        assert!(!add.matches(&[0xc8, 0x1e]));
+        assert!(!add.matches(&[]));
+
+        // An offset whose end overflows is past the end of any buffer
+        let far = PatternBlock::builder()
+            .offset(Integer::from(usize::MAX))
+            .nonzero(1)
+            .mask_vals(vec![PatternBlockWord::builder()
+                .mask(0xff000000u32)
+                .val(0)
+                .build()])
+            .build();
+        assert!(!far.matches(&[0x00]));
+    }
+
+    #[test]
+    fn test_hex_integer() {
+        const toy_be: &str = include_str!("../Processors/Toy/data/languages/toy_be.sla");
+        let slgh = parse(toy_be).expect("Failed to parse toy_be sla");
+        let table = slgh.symbol_table();
+
+        let header = table
+            .symbol_header_by_id(&Integer::from(0x50))
+            .expect("No symbol 0x50")
+            .header();
+        let id: &HexInteger = header.id();
+        assert_eq!(id, &Integer::from(0x50));
+        assert_eq!(id.to_string(), "0x50");
+        assert!(format!("{:?}", header).contains("id: 0x50"));
+        // Decimal fields are still plain integers
+        assert_eq!(format!("{:?}", slgh.align()), "1");
+
+        assert_eq!(HexInteger(Integer::from(-0x1f)).to_string(), "-0x1f");
+        assert_eq!(Integer::from(HexInteger::from(Integer::from(7))), 7);
+        assert_eq!(HexInteger::default(), Integer::from(0));
+    }
+
+    #[test]
+    fn test_template_for_section() {
+        let input = MINIMAL.replacen(
+            "<construct_tpl>\n<null/></construct_tpl>\n",
+            r#"<construct_tpl>
+<null/></construct_tpl>
+<construct_tpl section="1" delay="1">
+<null/></construct_tpl>
+<construct_tpl section="0">
+<null/></construct_tpl>
+"#,
+            1,
+        );
+        let slgh = parse(&input).expect("Failed to parse sla with named sections");
+        let constructor = slgh.constructors().next().expect("No constructor");
+        assert_eq!(constructor.namedtempl().len(), 2);
+
+        let main = constructor
+            .template_for_section(None)
+            .expect("No main template");
+        assert!(main.section().is_none());
+        assert!(std::ptr::eq(
+            main,
+            constructor.templ().expect("No template")
+        ));
+        let first = constructor
+            .template_for_section(Some(1))
+            .expect("No section 1");
+        assert_eq!(first.section(), Some(&Integer::from(1)));
+        assert_eq!(first.delay(), Some(&Integer::from(1)));
+        let zero = constructor
+            .template_for_section(Some(0))
+            .expect("No section 0");
+        assert_eq!(zero.section(), Some(&Integer::from(0)));
+        assert!(constructor.template_for_section(Some(2)).is_none());
+    }
+
+    #[test]
+    fn test_bit_range_symbol() {
+        let input = MINIMAL
+            .replacen(
+                r#"<symbol_table scopesize="1" symbolsize="1">"#,
+                r#"<symbol_table scopesize="1" symbolsize="3">"#,
+                1,
+            )
+            .replacen(
+                "<subtable_sym name=\"instruction\"",
+                r#"<varnode_sym_head name="flags" id="0x1" scope="0x0"/>
+<bitrange_sym_head name="carry" id="0x2" scope="0x0"/>
+<subtable_sym name="instruction""#,
+                1,
+            )
+            .replacen(
+                "</symbol_table>",
+                r#"<varnode_sym name="flags" id="0x1" scope="0x0" space="ram" offset="0x10" size="4">
+</varnode_sym>
+<bitrange_sym name="carry" id="0x2" scope="0x0" varnode="0x1" bitoffset="3" numbits="1"/>
+</symbol_table>"#,
+                1,
+            );
+        let slgh = parse(&input).expect("Failed to parse sla with a bitrange symbol");
+        let table = slgh.symbol_table();
+
+        match table.symbol_by_id(&Integer::from(2)) {
+            Some(SleighSymbolType::BitRangeSymbol(symbol)) => {
+                assert_eq!(symbol.header().name(), "carry");
+                assert_eq!(symbol.varnode(), &Integer::from(1));
+                assert_eq!(symbol.bitoffset(), &Integer::from(3));
+                assert_eq!(symbol.numbits(), &Integer::from(1));
+                assert_eq!(
+                    table
+                        .symbol_by_id(symbol.varnode())
+                        .map(|varnode| varnode.header().name()),
+                    Some("flags")
+                );
+            }
+            other => panic!("Expected a bitrange symbol, got {:?}", other),
+        }
+        assert!(matches!(
+            table.symbol_header_by_id(&Integer::from(2)),
+            Some(SymbolHeaderType::BitRange { .. })
+        ));
+
+        let written = slgh.to_sla_string();
+        assert!(written.contains(
+            r#"<bitrange_sym name="carry" id="0x2" scope="0x0" varnode="0x1" bitoffset="3" numbits="1"/>"#
+        ));
+        assert_eq!(parse(&written).expect("Failed to parse written sla"), slgh);
+    }
+
+    #[test]
+    fn test_validate() {
+        let slgh = parse(MINIMAL).expect("Failed to parse minimal sla");
+        assert_eq!(slgh.validate(), vec![]);
+
+        const toy_be: &str = include_str!("../Processors/Toy/data/languages/toy_be.sla");
+        let slgh = parse(toy_be).expect("Failed to parse toy_be sla");
+        assert_eq!(slgh.validate(), vec![]);
+
+        let input = MINIMAL
+            .replacen(
+                r#"<symbol_table scopesize="1" symbolsize="1">"#,
+                r#"<symbol_table scopesize="1" symbolsize="3">"#,
+                1,
+            )
+            .replacen(
+                "</symbol_table>",
+                r#"<operand_sym name="dangling" id="0x1" scope="0x0" off="0" base="-1" minlen="0" index="0">
+<operand_exp index="0" table="0x7" ct="0x0"/>
+</operand_sym>
+</symbol_table>"#,
+                1,
+            )
+            .replacen(
+                r#"<subtable_sym_head name="instruction" id="0x0" scope="0x0"/>"#,
+                r#"<subtable_sym_head name="instruction" id="0x0" scope="0x0"/>
+<operand_sym_head name="dangling" id="0x1" scope="0x0"/>"#,
+                1,
+            );
+        let slgh = parse(&input).expect("Failed to parse sla with a dangling operand");
+        let issues = slgh.validate();
+        assert_eq!(issues.len(), 2);
+        assert_eq!(issues[0].id(), None);
+        assert_eq!(
+            issues[0].message(),
+            "symbolsize is 3 but 2 symbol headers are declared"
+        );
+        assert_eq!(issues[1].id(), Some(&HexInteger::from(Integer::from(7))));
+        assert_eq!(
+            issues[1].to_string(),
+            "operand 0 refers to table 0x7, which is not a subtable (id 0x7)"
+        );
+
+        let input = MINIMAL
+            .replacen(r#"parent="0x0"/>"#, r#"parent="0x3"/>"#, 1)
+            .replacen(r#"numct="1""#, r#"numct="2""#, 1);
+        let slgh = parse(&input).expect("Failed to parse sla with a dangling scope parent");
+        let issues = slgh.validate();
+        assert_eq!(issues.len(), 2);
+        assert_eq!(issues[0].id(), Some(&HexInteger::from(Integer::from(0))));
+        assert_eq!(issues[0].message(), "scope parent 0x3 does not exist");
+        assert_eq!(
+            issues[1].message(),
+            "subtable instruction declares 2 constructors but has 1"
+        );
+    }
+
+    #[test]
+    fn test_parse_with_spans() {
+        const toy_be: &str = include_str!("../Processors/Toy/data/languages/toy_be.sla");
+        let (slgh, spans) = parse_with_spans(toy_be).expect("Failed to parse toy_be sla");
+        assert_eq!(slgh, parse(toy_be).expect("Failed to parse toy_be sla"));
+        assert_eq!(spans.spaces().len(), slgh.spaces().spaces().len());
+        assert_eq!(spans.symbols().len(), slgh.symbol_table().symbols().len());
+        assert_eq!(spans.constructors().len(), slgh.constructors().count());
+
+        let constructor = slgh
+            .constructors()
+            .find(|constructor| *constructor.line() == (Integer::from(0), Integer::from(156)))
+            .expect("No constructor at line 0:156");
+        let span = spans
+            .constructor(&slgh, constructor)
+            .expect("No span for constructor");
+        let text = &toy_be[span.range()];
+        assert!(text.starts_with(r#"<constructor parent="0x0" first="1" length="2" line="0:156">"#));
+        assert!(text.ends_with("</constructor>"));
+
+        let register = &slgh.spaces().spaces()[3];
+        let span = spans.space(&slgh, register).expect("No span for space");
+        assert!(toy_be[span.range()].starts_with(r#"<space name="register""#));
+        assert!(toy_be[span.range()].ends_with("/>"));
+
+        for (symbol, span) in slgh.symbol_table().symbols().iter().zip(spans.symbols()) {
+            assert_eq!(spans.symbol(&slgh, symbol), Some(*span));
+            let name = format!(r#"name="{}""#, symbol.header().name());
+            assert!(toy_be[span.range()].contains(&name));
+        }
+    }
+
+    #[test]
+    fn test_display_summaries() {
+        let slgh = parse(MINIMAL).expect("Failed to parse minimal sla");
+        let ram = slgh.spaces().by_name("ram").expect("No ram space");
+        assert_eq!(ram.to_string(), "ram[size=4, wordsize=1, be]");
+
+        let input = MINIMAL.replacen(
+            r#"<space name="ram" index="2" bigendian="true" delay="1" size="4" physical="true"/>"#,
+            r#"<space name="ram" index="2" bigendian="false" delay="1" size="8" wordsize="2" physical="true"/>"#,
+            1,
+        );
+        let slgh = parse(&input).expect("Failed to parse sla with a word sized space");
+        let ram = slgh.spaces().by_name("ram").expect("No ram space");
+        assert_eq!(ram.to_string(), "ram[size=8, wordsize=2, le]");
+
+        let header = slgh.symbol_table().symbol_headers()[0].header();
+        assert_eq!(header.to_string(), "instruction#0x0");
+
+        let field = TokenField::builder()
+            .bigendian(true)
+            .signbit(false)
+            .bitstart(0)
+            .bitend(7)
+            .bytestart(0)
+            .byteend(0)
+            .shift(0)
+            .build();
+        assert_eq!(field.to_string(), "tok[7..0]");
+        assert_ne!(field.to_string(), format!("{:?}", field));
+    }
+
+    #[test]
+    fn test_display_template() {
+        const toy_be: &str = include_str!("../Processors/Toy/data/languages/toy_be.sla");
+        let slgh = parse(toy_be).expect("Failed to parse toy_be sla");
+        let constructor = slgh
+            .constructors()
+            .find(|constructor| *constructor.line() == (Integer::from(0), Integer::from(155)))
+            .expect("No constructor at line 0:155");
+        assert_eq!(constructor.display_template(), "imm {0}, {1}");
+
+        let nop = parse(MINIMAL).expect("Failed to parse minimal sla");
+        let nop = nop.constructors().next().expect("No constructor");
+        assert_eq!(nop.display_template(), "nop");
+
+        // Literal braces, as in the register lists of ARM's `ldm`, are escaped
+        let braces = parse(&MINIMAL.replacen(r#"piece="nop""#, r#"piece="{0}""#, 1))
+            .expect("Failed to parse sla with a brace piece");
+        let braces = braces.constructors().next().expect("No constructor");
+        assert_eq!(braces.display_template(), "{{0}}");
+    }
+
+    #[test]
+    fn test_xml_comments_and_declaration() {
+        let expected = parse(MINIMAL).expect("Failed to parse minimal sla");
+        let input = format!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<!-- generated - by hand -->\n{}",
+            MINIMAL
+        );
+        assert_eq!(
+            parse(&input).expect("Failed to parse sla with a declaration and comment"),
+            expected
+        );
+
+        let input = MINIMAL
+            .replacen("<sourcefiles>", "<!-- the files --><sourcefiles>", 1)
+            .replacen(
+                "<print piece=\"nop\"/>",
+                "<print piece=\"nop\"/>\n<!--\nmultiline <comment>\n-->",
+                1,
+            )
+            .replacen("</symbol_table>", "<?sleigh note?>\n</symbol_table>", 1);
+        assert_eq!(
+            parse(&input).expect("Failed to parse sla with embedded comments"),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_const_tpl_kind() {
+        let real = ConstantTemplateType::Real {
+            _start: (),
+            val: HexInteger::from(Integer::from(0x10)),
+            _end: (),
+        };
+        assert_eq!(real.kind(), ConstTplKind::Real);
+        assert_eq!(
+            real.as_real_val(),
+            Some(&HexInteger::from(Integer::from(0x10)))
+        );
+        assert_eq!(real.as_handle(), None);
+
+        let handle = ConstantTemplateType::Handle {
+            _start: (),
+            val: Integer::from(1),
+            selector: ConstantTemplateSelector::OffsetPlus { _offset_plus: () },
+            plus: Some(HexInteger::from(Integer::from(4))),
+            _end: (),
+        };
+        assert_eq!(handle.kind(), ConstTplKind::Handle);
+        assert_eq!(handle.as_real_val(), None);
+        let (index, selector, plus) = handle.as_handle().expect("Not a handle");
+        assert_eq!(index, &Integer::from(1));
+        assert!(matches!(
+            selector,
+            ConstantTemplateSelector::OffsetPlus { .. }
+        ));
+        assert_eq!(plus, Some(&HexInteger::from(Integer::from(4))));
+
+        let markers = [
+            (
+                ConstantTemplateType::Start { _start: () },
+                ConstTplKind::Start,
+            ),
+            (ConstantTemplateType::End { _end: () }, ConstTplKind::End),
+            (ConstantTemplateType::Next { _next: () }, ConstTplKind::Next),
+            (
+                ConstantTemplateType::Next2 { _next2: () },
+                ConstTplKind::Next2,
+            ),
+            (
+                ConstantTemplateType::CurSpace { _cur_space: () },
+                ConstTplKind::CurSpace,
+            ),
+            (
+                ConstantTemplateType::CurSpaceSize {
+                    _cur_space_size: (),
+                },
+                ConstTplKind::CurSpaceSize,
+            ),
+            (
+                ConstantTemplateType::SpaceId {
+                    _start: (),
+                    name: "ram".to_string(),
+                    _end: (),
+                },
+                ConstTplKind::SpaceId,
+            ),
+            (
+                ConstantTemplateType::JumpRelative {
+                    _start: (),
+                    val: HexInteger::from(Integer::from(2)),
+                    _end: (),
+                },
+                ConstTplKind::JumpRelative,
+            ),
+            (
+                ConstantTemplateType::FlowRef { _flow_ref: () },
+                ConstTplKind::FlowRef,
+            ),
+            (
+                ConstantTemplateType::FlowDest { _flow_dest: () },
+                ConstTplKind::FlowDest,
+            ),
+            (
+                ConstantTemplateType::FlowDestSize {
+                    _flow_dest_size: (),
+                },
+                ConstTplKind::FlowDestSize,
+            ),
+            (
+                ConstantTemplateType::Unknown {
+                    type_name: "future".to_string(),
+                    val: None,
+                    _end: (),
+                },
+                ConstTplKind::Unknown,
+            ),
+        ];
+        for (constant, kind) in markers {
+            assert_eq!(constant.kind(), kind);
+            assert_eq!(constant.as_real_val(), None);
+            assert_eq!(constant.as_handle(), None);
+        }
+    }
+
+    #[test]
+    fn test_null_or_value_as_option() {
+        let minimal = parse(MINIMAL).expect("Failed to parse minimal sla");
+        let nop = minimal.constructors().next().expect("No constructor");
+        let templ = nop.templ().expect("No template");
+        assert!(templ.result().as_option().is_none());
+
+        const toy_be: &str = include_str!("../Processors/Toy/data/languages/toy_be.sla");
+        let slgh = parse(toy_be).expect("Failed to parse toy_be sla");
+        let templates = slgh
+            .constructors()
+            .filter_map(|constructor| constructor.templ())
+            .collect::<Vec<_>>();
+        assert!(templates
+            .iter()
+            .any(|templ| templ.result().as_option().is_some()));
+        let ops = templates
+            .iter()
+            .flat_map(|templ| templ.vec())
+            .collect::<Vec<_>>();
+        let store = ops
+            .iter()
+            .find(|op| op.code().mnemonic() == "STORE")
+            .expect("No store operation");
+        assert!(store.output().as_option().is_none());
+        let copy = ops
+            .iter()
+            .find(|op| op.code().mnemonic() == "COPY")
+            .expect("No copy operation");
+        assert!(copy.output().as_option().is_some());
+
+        let null = VarNodeTableValueType::Null { _null: () };
+        assert_eq!(null.as_option(), None);
+        let value = VarNodeTableValueType::Value(VarNodeTableValue::builder().id(3).build());
+        assert_eq!(
+            value.as_option().map(|varnode| varnode.id()),
+            Some(&HexInteger::from(Integer::from(3)))
+        );
+    }
+
+    #[test]
+    fn test_constructor_line() {
+        let slgh = parse(&MINIMAL.replacen(r#"line="0:1""#, r#"line="12:3""#, 1))
+            .expect("Failed to parse sla with a source file and line");
+        let constructor = slgh.constructors().next().expect("No constructor");
+        assert_eq!(constructor.line(), &(Integer::from(12), Integer::from(3)));
+
+        let slgh = parse(&MINIMAL.replacen(r#"line="0:1""#, r#"line="12""#, 1))
+            .expect("Failed to parse sla with a line and no source file");
+        let constructor = slgh.constructors().next().expect("No constructor");
+        assert_eq!(constructor.line(), &(Integer::from(0), Integer::from(12)));
+        assert!(slgh.to_sla_string().contains(r#"line="0:12""#));
+
+        let malformed = parse(&MINIMAL.replacen(r#"line="0:1""#, r#"line="12:""#, 1));
+        assert!(matches!(malformed, Err(SleighParseError::Syntax(_))));
+    }
+
+    #[test]
+    fn test_version_4_header_unknown_attributes() {
+        let input = MINIMAL.replacen(
+            r#"<sleigh version="3" bigendian="true" align="1" uniqbase="0x100">"#,
+            r#"<sleigh version="4" bigendian="true" align="1" uniqbase="0x100" maxdelay="0x1" sourcelines="true" note="a &amp; b">"#,
+            1,
+        );
+        let slgh = parse(&input).expect("Failed to parse version 4 header");
+        assert_eq!(slgh.version(), Some(&Integer::from(4)));
+        assert_eq!(slgh.maxdelay(), Some(&HexInteger::from(Integer::from(1))));
+        let attributes = slgh
+            .unknown_attributes()
+            .iter()
+            .map(|attribute| (attribute.name(), attribute.value()))
+            .collect::<Vec<_>>();
+        assert_eq!(attributes, vec![("sourcelines", "true"), ("note", "a & b")]);
+
+        let written = slgh.to_sla_string();
+        assert!(written.contains(r#"sourcelines="true" note="a &amp; b">"#));
+        assert_eq!(parse(&written).expect("Failed to parse written sla"), slgh);
+
+        // Unknown attributes may sit between known ones, and known ones in any order
+        let input = MINIMAL.replacen(
+            r#"<sleigh version="3" bigendian="true" align="1" uniqbase="0x100">"#,
+            r#"<sleigh version="4" newattr="x" uniqbase="0x100" bigendian="true" other='y' align="1">"#,
+            1,
+        );
+        let interleaved = parse(&input).expect("Failed to parse interleaved attributes");
+        assert_eq!(interleaved.version(), Some(&Integer::from(4)));
+        assert!(interleaved.bigendian());
+        assert_eq!(interleaved.align(), &Integer::from(1));
+        assert_eq!(interleaved.uniqbase(), &Integer::from(0x100));
+        let attributes = interleaved
+            .unknown_attributes()
+            .iter()
+            .map(|attribute| (attribute.name(), attribute.value()))
+            .collect::<Vec<_>>();
+        assert_eq!(attributes, vec![("newattr", "x"), ("other", "y")]);
+        assert_eq!(
+            parse(&interleaved.to_sla_string()).expect("Failed to parse written sla"),
+            interleaved
+        );
+
+        // Required attributes are still required
+        let input = MINIMAL.replacen(r#" align="1""#, "", 1);
+        assert!(matches!(
+            parse(&input),
+            Err(SleighParseError::MissingCapture {
+                field: "Sleigh::align",
+                location: Some(_),
+            })
+        ));
+
+        let slgh = parse(MINIMAL).expect("Failed to parse minimal sla");
+        assert!(slgh.unknown_attributes().is_empty());
+    }
+
+    #[test]
+    fn test_empty_and_non_sleigh_input() {
+        for input in [
+            "",
+            "   \n\t",
+            "<?xml version=\"1.0\"?>\n<!-- nothing here -->\n",
+        ] {
+            assert!(
+                matches!(parse(input), Err(SleighParseError::EmptyInput)),
+                "{:?} was not reported as empty",
+                input
+            );
+        }
+        for input in [
+            "<notsleigh/>",
+            "  <spaces defaultspace=\"ram\"></spaces>",
+            "<sleighs version=\"3\">",
+            "<?xml version=\"1.0\"?><!-- a comment --><notsleigh/>",
+            "sleigh",
+        ] {
+            assert!(
+                matches!(parse(input), Err(SleighParseError::NotASleighFile)),
+                "{:?} was not reported as a non sleigh file",
+                input
+            );
+        }
+        assert_eq!(
+            SleighParseError::NotASleighFile.to_string(),
+            "input is not a sleigh specification, the root element is not <sleigh>"
+        );
+        assert!(matches!(
+            parse_bytes(b""),
+            Err(SleighParseError::EmptyInput)
+        ));
+
+        // A `<sleigh` root with a broken body is still a syntax error
+        assert!(matches!(
+            parse("<sleigh version=\"3\">"),
+            Err(SleighParseError::Syntax(_))
+        ));
+        assert!(parse(&format!("\n  <!-- leading -->\n{}", MINIMAL)).is_ok());
+    }
+
+    #[test]
+    fn test_into_symbols() {
+        const toy_be: &str = include_str!("../Processors/Toy/data/languages/toy_be.sla");
+        let slgh = parse(toy_be).expect("Failed to parse toy_be sla");
+        let names = slgh
+            .symbol_table()
+            .into_iter()
+            .map(|symbol| symbol.header().name().to_string())
+            .collect::<Vec<_>>();
+        let symbols = slgh.into_symbols();
+        assert_eq!(symbols.len(), names.len());
+        assert!(symbols
+            .iter()
+            .zip(&names)
+            .all(|(symbol, name)| symbol.header().name() == name));
+
+        let table = SymbolTable::builder()
+            .scopesize(0)
+            .symbolsize(symbols.len())
+            .symbols(symbols)
+            .build();
+        let mut subtables = Vec::new();
+        for symbol in table {
+            if let SleighSymbolType::TripleSymbol(TripleSymbol::SubtableSymbol {
+                subtable, ..
+            }) = symbol
+            {
+                subtables.push(subtable);
+            }
+        }
+        assert!(subtables
+            .iter()
+            .any(|subtable| subtable.header().name() == "instruction"));
+
+        let table = SymbolTable::builder().scopesize(0).symbolsize(0).build();
+        assert_eq!(table.into_iter().count(), 0);
+    }
+
+    #[test]
+    fn test_addr_space_attribute_order() {
+        let expected = parse(MINIMAL).expect("Failed to parse minimal sla");
+        let input = MINIMAL.replacen(
+            r#"<space name="ram" index="2" bigendian="true" delay="1" size="4" physical="true"/>"#,
+            r#"<space physical="true" size="4" delay="1" bigendian="true" index="2" name="ram"/>"#,
+            1,
+        );
+        let slgh = parse(&input).expect("Failed to parse sla with reversed space attributes");
+        assert_eq!(slgh, expected);
+
+        let input = MINIMAL.replacen(
+            r#"<space name="ram" index="2" bigendian="true" delay="1" size="4" physical="true"/>"#,
+            r#"<space wordsize="2" physical="false" deadcodedelay="0" size="8" delay="1" bigendian="false" index="2" name="ram"/>"#,
+            1,
+        );
+        let slgh = parse(&input).expect("Failed to parse sla with reordered space attributes");
+        let ram = slgh.spaces().by_name("ram").expect("No ram space");
+        assert_eq!(ram.size(), &Integer::from(8));
+        assert_eq!(ram.wordsize(), Some(&Integer::from(2)));
+        assert_eq!(ram.deadcodedelay(), Some(&Integer::from(0)));
+        assert!(!ram.bigendian());
+        assert!(!ram.physical());
+
+        let input = MINIMAL.replacen(r#" delay="1" size="4""#, r#" delay="1""#, 1);
+        match parse(&input) {
+            Err(SleighParseError::MissingCapture { field, .. }) => {
+                assert_eq!(field, "AddrSpace::size")
+            }
+            other => panic!("Expected a missing size, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_structurally_eq() {
+        let input = MINIMAL.replacen(
+            r#"<sourcefile name="test.sinc" index="0"/>"#,
+            r#"<sourcefile name="test.sinc" index="0"/>
+<sourcefile name="other.sinc" index="1"/>"#,
+            1,
+        );
+        let slgh = parse(&input).expect("Failed to parse sla with two source files");
+        let reordered = input.replacen(
+            r#"<sourcefile name="test.sinc" index="0"/>
+<sourcefile name="other.sinc" index="1"/>"#,
+            r#"<sourcefile name="other.sinc" index="1"/>
+<sourcefile name="test.sinc" index="0"/>"#,
+            1,
+        );
+        let reordered = parse(&reordered).expect("Failed to parse sla with reordered source files");
+        assert_ne!(slgh, reordered);
+        assert!(slgh.structurally_eq(&reordered));
+        assert!(reordered.structurally_eq(&slgh));
+        assert!(slgh.structurally_eq(&slgh));
+
+        let renamed = input.replacen("other.sinc", "renamed.sinc", 1);
+        let renamed = parse(&renamed).expect("Failed to parse sla with a renamed source file");
+        assert!(!slgh.structurally_eq(&renamed));
+
+        let duplicated = input.replacen(
+            r#"<sourcefile name="other.sinc" index="1"/>"#,
+            r#"<sourcefile name="test.sinc" index="0"/>"#,
+            1,
+        );
+        let duplicated =
+            parse(&duplicated).expect("Failed to parse sla with a duplicate source file");
+        assert!(!slgh.structurally_eq(&duplicated));
+
+        let minimal = parse(MINIMAL).expect("Failed to parse minimal sla");
+        assert!(!slgh.structurally_eq(&minimal));
+
+        // Named templates are identified by their section, so their order is insignificant
+        let sections = |first: &str, second: &str| {
+            parse(&MINIMAL.replacen(
+                "<construct_tpl>\n<null/></construct_tpl>\n",
+                &format!(
+                    "<construct_tpl>\n<null/></construct_tpl>\n{}\n<null/></construct_tpl>\n{}\n<null/></construct_tpl>\n",
+                    first, second
+                ),
+                1,
+            ))
+            .expect("Failed to parse sla with named templates")
+        };
+        let named = sections(
+            r#"<construct_tpl section="0">"#,
+            r#"<construct_tpl section="1">"#,
+        );
+        let reordered = sections(
+            r#"<construct_tpl section="1">"#,
+            r#"<construct_tpl section="0">"#,
+        );
+        assert_ne!(named, reordered);
+        assert!(named.structurally_eq(&reordered));
+        let delayed = sections(
+            r#"<construct_tpl section="1" delay="1">"#,
+            r#"<construct_tpl section="0">"#,
+        );
+        assert!(!named.structurally_eq(&delayed));
+    }
+
+    #[test]
+    fn test_parse_with_progress() {
+        // A progress bar a CLI would draw, reduced to the state it would render
+        #[derive(Default)]
+        struct ProgressBar {
+            length: usize,
+            position: usize,
+            finished: bool,
+            invocations: usize,
+        }
+
+        impl ProgressBar {
+            fn update(&mut self, progress: ParseProgress) {
+                self.invocations += 1;
+                match progress {
+                    ParseProgress::Started { .. } => self.length = 0,
+                    ParseProgress::Symbols { completed, total } => {
+                        self.length = total;
+                        self.position = completed;
+                    }
+                    ParseProgress::Finished => self.finished = true,
+                    _ => {}
+                }
+            }
+        }
+
+        const toy_be: &str = include_str!("../Processors/Toy/data/languages/toy_be.sla");
+        let mut bar = ProgressBar::default();
+        let slgh = parse_with_progress(toy_be, &mut |progress| bar.update(progress))
+            .expect("Failed to parse toy_be.sla");
+        let symbols = slgh.symbol_table().symbols().len();
+        assert!(symbols > 0);
+        assert_eq!(bar.invocations, symbols + 3);
+        assert_eq!(bar.length, symbols);
+        assert_eq!(bar.position, symbols);
+        assert!(bar.finished);
+
+        let mut steps = Vec::new();
+        assert!(parse_with_progress("", &mut |progress| steps.push(progress)).is_err());
+        assert_eq!(steps, vec![ParseProgress::Started { bytes: 0 }]);
+    }
+
+    #[test]
+    fn test_resolve_operands() {
+        const toy_be: &str = include_str!("../Processors/Toy/data/languages/toy_be.sla");
+        let slgh = parse(toy_be).expect("Failed to parse toy_be sla");
+        let constructor = slgh
+            .constructors()
+            .find(|constructor| *constructor.line() == (Integer::from(0), Integer::from(155)))
+            .expect("No constructor at line 0:155");
+        let operands = constructor.resolve_operands(slgh.symbol_table());
+        assert_eq!(operands.len(), 2);
+        let rd = operands[0].expect("Operand 0 did not resolve");
+        let imm10 = operands[1].expect("Operand 1 did not resolve");
+        assert_eq!(rd.header().name(), "rd");
+        assert_eq!(*rd.index(), 0);
+        assert_eq!(
+            rd.subsym().map(|subsym| subsym.0.clone()),
+            Some(Integer::from(0x41))
+        );
+        assert_eq!(imm10.header().name(), "Imm10");
+        assert_eq!(*imm10.index(), 1);
+        assert_eq!(
+            imm10.subsym().map(|subsym| subsym.0.clone()),
+            Some(Integer::from(0x56))
+        );
+
+        // The subtable symbol is not an operand symbol
+        let unresolved = Constructor::builder()
+            .parent(0)
+            .first(0)
+            .length(0)
+            .line((Integer::from(0), Integer::from(0)))
+            .operands(vec![ConstructorOperand::builder().id(0).build()])
+            .printpiece(vec![])
+            .contexts(vec![])
+            .templ(None)
+            .namedtempl(vec![])
+            .build();
+        assert_eq!(unresolved.resolve_operands(slgh.symbol_table()), vec![None]);
+    }
+
+    #[test]
+    fn test_missing_bigendian() {
+        let slgh = parse(MINIMAL).expect("Failed to parse minimal sla");
+        assert_eq!(slgh.bigendian_attribute(), Some(true));
+        assert!(slgh.bigendian());
+
+        let input = MINIMAL.replacen(r#" bigendian="true" align"#, r#" align"#, 1);
+        let slgh = parse(&input).expect("Failed to parse sla without bigendian");
+        assert_eq!(slgh.bigendian_attribute(), None);
+        assert!(slgh.bigendian());
+        assert_eq!(slgh.to_sla_string().matches("bigendian").count(), 2);
+        assert_eq!(
+            parse(&slgh.to_sla_string()).expect("Failed to reparse"),
+            slgh
+        );
+
+        let input = input.replace(r#"bigendian="true""#, r#"bigendian="false""#);
+        let slgh = parse(&input).expect("Failed to parse little endian sla without bigendian");
+        assert!(!slgh.bigendian());
+    }
+
+    #[test]
+    fn test_length_with_operands() {
+        const SLA_6502: &str = include_str!("../Processors/6502/data/languages/6502.sla");
+        let slgh = parse(SLA_6502).expect("Failed to parse 6502 sla");
+        let table = slgh.symbol_table();
+        // OP1: "#"imm16, whose operand starts one byte in and is two bytes long
+        let constructor = slgh
+            .constructors()
+            .find(|constructor| *constructor.line() == (Integer::from(0), Integer::from(89)))
+            .expect("No constructor at line 0:89");
+        assert_eq!(constructor.min_length(), 3);
+        assert_eq!(constructor.length_with_operands(table), 3);
+
+        // The same operand on a constructor whose own pattern is shorter
+        let short = Constructor::builder()
+            .parent(0x20)
+            .first(0)
+            .length(1)
+            .line((Integer::from(0), Integer::from(0)))
+            .operands(vec![ConstructorOperand::builder().id(0x25).build()])
+            .printpiece(vec![])
+            .contexts(vec![])
+            .templ(None)
+            .namedtempl(vec![])
+            .build();
+        assert_eq!(short.min_length(), 1);
+        assert_eq!(short.length_with_operands(table), 3);
+    }
+
+    #[test]
+    #[cfg(feature = "rayon")]
+    fn test_parse_many() {
+        const toy_be: &str = include_str!("../Processors/Toy/data/languages/toy_be.sla");
+        const SLA_6502: &str = include_str!("../Processors/6502/data/languages/6502.sla");
+        const SLA_8051: &str = include_str!("../Processors/8051/data/languages/8051.sla");
+        let inputs = [toy_be, SLA_6502, "<notsleigh/>", SLA_8051, MINIMAL];
+
+        let results = crate::parse_many(
+            inputs
+                .iter()
+                .map(|input| input.to_string())
+                .collect::<Vec<_>>(),
+        );
+        assert_eq!(results.len(), inputs.len());
+        for (input, result) in inputs.iter().zip(&results) {
+            match parse(input) {
+                Ok(expected) => assert_eq!(result.as_ref().ok(), Some(&expected)),
+                Err(_) => assert!(matches!(result, Err(SleighParseError::NotASleighFile))),
+            }
+        }
+    }
+
+    #[test]
+    fn test_context_fields() {
+        const ARM8_le: &str = include_str!("../Processors/ARM/data/languages/ARM8_le.sla");
+        let slgh = parse(ARM8_le).expect("Failed to parse ARM8_le sla");
+        let fields = slgh.context_fields();
+        assert_eq!(fields.len(), ARM8_le.matches("<context_sym ").count());
+        let tmode = fields
+            .iter()
+            .find(|field| field.header().name() == "TMode")
+            .expect("No TMode context field");
+        assert_eq!(tmode.bit_range(), (0, 0));
+        assert!(tmode.flow());
+        let lrset = fields
+            .iter()
+            .find(|field| field.header().name() == "LRset")
+            .expect("No LRset context field");
+        assert_eq!(lrset.bit_range(), (1, 1));
+        assert!(!lrset.flow());
+        assert!(fields
+            .iter()
+            .all(|field| field.varnode() == tmode.varnode()));
+        assert!(fields
+            .iter()
+            .all(|field| field.bit_range().0 <= field.bit_range().1));
+
+        let minimal = parse(MINIMAL).expect("Failed to parse minimal sla");
+        assert!(minimal.context_fields().is_empty());
+    }
+
+    #[test]
+    fn test_self_closing_and_paired_symbols() {
+        let with_varnode = |varnode: &str| {
+            MINIMAL
+                .replacen(r#"symbolsize="1""#, r#"symbolsize="2""#, 1)
+                .replacen(
+                    "<subtable_sym_head",
+                    r#"<varnode_sym_head name="r0" id="0x1" scope="0x0"/>
+<subtable_sym_head"#,
+                    1,
+                )
+                .replacen(
+                    "</symbol_table>",
+                    &format!("{}\n</symbol_table>", varnode),
+                    1,
+                )
+        };
+        let paired = parse(&with_varnode(
+            r#"<varnode_sym name="r0" id="0x1" scope="0x0" space="ram" offset="0x10" size="4">
+</varnode_sym>"#,
+        ))
+        .expect("Failed to parse paired varnode_sym");
+        let self_closing = parse(&with_varnode(
+            r#"<varnode_sym name="r0" id="0x1" scope="0x0" space="ram" offset="0x10" size="4"/>"#,
+        ))
+        .expect("Failed to parse self-closing varnode_sym");
+        assert_eq!(paired, self_closing);
+        let r0 = self_closing.register_by_name("r0").expect("No r0 register");
+        assert_eq!(
+            (r0.offset().0.clone(), r0.size()),
+            (Integer::from(0x10), &Integer::from(4))
+        );
+        // Written back out in the paired form Ghidra uses
+        assert!(self_closing.to_sla_string().contains("</varnode_sym>"));
+
+        // Elements Ghidra self-closes also parse in the paired form
+        let expected = parse(MINIMAL).expect("Failed to parse minimal sla");
+        let paired_head = MINIMAL.replacen(
+            r#"<subtable_sym_head name="instruction" id="0x0" scope="0x0"/>"#,
+            r#"<subtable_sym_head name="instruction" id="0x0" scope="0x0"></subtable_sym_head>"#,
+            1,
+        );
+        assert_eq!(
+            parse(&paired_head).expect("Failed to parse paired subtable_sym_head"),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_symbol_name_and_id() {
+        const SLA_8051: &str = include_str!("../Processors/8051/data/languages/8051.sla");
+        let slgh = parse(SLA_8051).expect("Failed to parse 8051 sla");
+        let table = slgh.symbol_table();
+        assert!(!table.symbols().is_empty());
+        for (symbol, header) in table.symbols().iter().zip(table.symbol_headers()) {
+            assert!(!symbol.name().is_empty());
+            assert_eq!(symbol.name(), header.header().name());
+            assert_eq!(symbol.id(), header.header().id());
+            assert_eq!(symbol.header(), header.header());
+        }
+        let instruction = table
+            .symbols()
+            .iter()
+            .find(|symbol| symbol.name() == "instruction")
+            .expect("No instruction table");
+        assert!(matches!(
+            instruction,
+            SleighSymbolType::TripleSymbol(TripleSymbol::SubtableSymbol { .. })
+        ));
+        assert!(table
+            .symbols()
+            .iter()
+            .any(|symbol| matches!(symbol, SleighSymbolType::UserOpSymbol { .. })));
+    }
+
+    #[test]
+    fn test_decimal_attribute_forms() {
+        let with_align =
+            |align: &str| MINIMAL.replacen(r#"align="1""#, &format!(r#"align="{}""#, align), 1);
+
+        let slgh = parse(&with_align("+5")).expect("Failed to parse align with a leading +");
+        assert_eq!(*slgh.align(), 5);
+
+        let large = "1234567890123456789012345678901234567890";
+        let slgh = parse(&with_align(large)).expect("Failed to parse a 40 digit align");
+        assert_eq!(slgh.align().to_string(), large);
+        assert!(slgh.align().as_i64().is_err());
+
+        let slgh = parse(&with_align("-3")).expect("Failed to parse a negative align");
+        assert_eq!(*slgh.align(), -3);
+
+        for invalid in ["1.0", "1e3", "1-2", "+-5"] {
+            match parse(&with_align(invalid)) {
+                Err(SleighParseError::InvalidInteger { field, raw, .. }) => {
+                    assert_eq!(field, "Sleigh::align");
+                    assert_eq!(raw, invalid);
+                }
+                other => panic!("Expected InvalidInteger for {:?}, got {:?}", invalid, other),
+            }
+        }
+
+        // Attributes parsed through the address space attribute map take the same forms
+        let slgh = parse(&MINIMAL.replacen(r#"size="4" physical"#, r#"size="+4" physical"#, 1))
+            .expect("Failed to parse space size with a leading +");
+        assert!(matches!(
+            parse(&MINIMAL.replacen(r#"size="4" physical"#, r#"size="+-4" physical"#, 1)),
+            Err(SleighParseError::InvalidInteger { .. })
+        ));
+        let unique = slgh.spaces().by_name("unique").expect("No unique space");
+        assert_eq!(*unique.size(), 4);
+    }
+
+    #[test]
+    fn test_parse_borrowed() {
+        // A single reference, since each use of a `const` may refer to a different copy
+        let sla_8051: &str = include_str!("../Processors/8051/data/languages/8051.sla");
+        let slgh = parse(sla_8051).expect("Failed to parse 8051 sla");
+        let borrowed = parse_borrowed(sla_8051).expect("Failed to parse 8051 sla borrowed");
+
+        assert_eq!(
+            borrowed.source_files(),
+            slgh.sourcefiles()
+                .source_files()
+                .iter()
+                .map(|file| file.name())
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            borrowed.symbols().len(),
+            slgh.symbol_table().symbols().len()
+        );
+        for (borrowed, owned) in borrowed.symbols().iter().zip(slgh.symbol_table().symbols()) {
+            assert_eq!(borrowed.name(), owned.name());
+            assert_eq!(borrowed.id(), owned.id());
+        }
+        let constructors = slgh.constructors().collect::<Vec<_>>();
+        assert_eq!(borrowed.constructors().len(), constructors.len());
+        for (borrowed, owned) in borrowed.constructors().iter().zip(constructors) {
+            let pieces = owned
+                .printpiece()
+                .iter()
+                .filter_map(|piece| match piece {
+                    PrintPieceType::Print(print) => Some(print.piece()),
+                    _ => None,
+                })
+                .collect::<Vec<_>>();
+            assert_eq!(borrowed.pieces(), pieces);
+        }
+        // Unescaped names point into the input
+        let input = sla_8051.as_bytes().as_ptr_range();
+        assert!(borrowed
+            .symbols()
+            .iter()
+            .all(|symbol| input.contains(&symbol.name().as_ptr())));
+
+        // Only escaped text is allocated
+        let escaped = MINIMAL.replacen(r#"piece="nop""#, r#"piece="&lt;nop&gt;""#, 1);
+        let escaped = parse_borrowed(&escaped).expect("Failed to parse escaped piece");
+        let piece = &escaped.constructors()[0].pieces()[0];
+        assert_eq!(piece, "<nop>");
+        assert!(matches!(piece, std::borrow::Cow::Owned(_)));
+
+        assert!(matches!(
+            parse_borrowed("<notsleigh/>"),
+            Err(SleighParseError::NotASleighFile)
+        ));
+        assert!(matches!(
+            parse_borrowed(&MINIMAL.replacen("</spaces>", "", 1)),
+            Err(SleighParseError::Syntax(_))
+        ));
+        // Values that are not indexed are still validated
+        let size = MINIMAL.replacen(r#"size="4""#, r#"size="four""#, 1);
+        assert!(matches!(
+            parse_borrowed(&size),
+            Err(SleighParseError::InvalidInteger { .. })
+        ));
+
+        // Invalid values are reported as `parse` reports them
+        for invalid in [
+            size,
+            MINIMAL.replacen(r#"align="1""#, r#"align="1.0""#, 1),
+            MINIMAL.replacen(r#"first="0""#, r#"first="0.5""#, 1),
+            MINIMAL.replacen(r#"context="false""#, r#"context="maybe""#, 1),
+            MINIMAL.replacen(r#"numct="1""#, r#"numct="1e3""#, 1),
+        ] {
+            assert_eq!(
+                parse_borrowed(&invalid)
+                    .expect_err("Parsed invalid value borrowed")
+                    .to_string(),
+                parse(&invalid)
+                    .expect_err("Parsed invalid value")
+                    .to_string()
+            );
+        }
+
+        // The kinds the index is collected from and checked by are still in the grammar
+        let language = crate::grammar::language();
+        for kind in crate::borrowed::NODE_KINDS {
+            assert_ne!(language.id_for_node_kind(kind, true), 0, "{}", kind);
+        }
+        for kind in crate::borrowed::checked_kinds() {
+            assert_ne!(language.id_for_node_kind(&kind, true), 0, "{}", kind);
+        }
+        // Every field whose value the grammar checks in a leaf transform is checked by the
+        // walk, apart from the fields of attribute groups and the pattern-checked line
+        let checked = crate::borrowed::INTEGER_FIELDS
+            .iter()
+            .chain(&crate::borrowed::BOOL_FIELDS)
+            .collect::<std::collections::HashSet<_>>();
+        let source = include_str!("lib.rs");
+        for call in [".leaf_integer(\"", ".leaf_bool(\""] {
+            for (start, _) in source.match_indices(call) {
+                let rest = &source[start + call.len()..];
+                let Some(field) = rest.find('"').map(|end| &rest[..end]) else {
+                    continue;
+                };
+                if !field.starts_with("Sleigh::")
+                    && !field.starts_with("AddrSpace::")
+                    && field != "Constructor::line"
+                {
+                    assert!(checked.contains(&field), "{}", field);
+                }
+            }
+        }
+
+        let owned = borrowed.clone().into_owned();
+        assert_eq!(owned, borrowed);
+        assert!(!input.contains(&owned.symbols()[0].name().as_ptr()));
+    }
+
+    #[test]
+    fn test_opcode() {
+        for (mnemonic, opcode) in [
+            ("BLANK", 0),
+            ("COPY", 1),
+            ("STORE", 3),
+            ("CBRANCH", 5),
+            ("RETURN", 10),
+            ("INT_ADD", 19),
+            ("INT_SUB", 20),
+            ("BOOL_NEGATE", 37),
+            ("FLOAT_NAN", 46),
+            ("ROUND", 59),
+            ("PIECE", 62),
+            ("SUBPIECE", 63),
+            ("CPOOLREF", 68),
+            ("LZCOUNT", 73),
+        ] {
+            let code = OperationCode::from_mnemonic(mnemonic).expect("Unknown mnemonic");
+            assert_eq!(code.opcode(), opcode, "{}", mnemonic);
+            assert_eq!(OperationCode::from_opcode(opcode), Some(code));
+        }
+        for opcode in 0..74 {
+            let code = OperationCode::from_opcode(opcode).expect("Missing opcode");
+            assert_eq!(code.opcode(), opcode);
+        }
+        assert_eq!(OperationCode::from_opcode(74), None);
+        assert_eq!(OperationCode::from_opcode(u32::MAX), None);
+    }
+
+    #[test]
+    fn test_handle_template_components() {
+        const toy_be: &str = include_str!("../Processors/Toy/data/languages/toy_be.sla");
+        let slgh = parse(toy_be).expect("Failed to parse toy_be sla");
+        let handles = slgh
+            .constructors()
+            .filter_map(|constructor| constructor.templ())
+            .filter_map(|templ| templ.result().as_option())
+            .collect::<Vec<_>>();
+        assert!(!handles.is_empty());
+        for handle in handles {
+            let accessors = [
+                handle.space(),
+                handle.size(),
+                handle.ptrspace(),
+                handle.ptroffset(),
+                handle.ptrsize(),
+                handle.temp_space(),
+                handle.temp_offset(),
+            ];
+            for (component, accessor) in handle.components().into_iter().zip(accessors) {
+                assert!(std::ptr::eq(component, accessor));
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_prefix() {
+        let expected = parse(MINIMAL).expect("Failed to parse minimal sla");
+
+        let (slgh, rest) = parse_prefix(MINIMAL).expect("Failed to parse minimal sla prefix");
+        assert_eq!(slgh, expected);
+        assert_eq!(rest, "");
+
+        let input = format!("{}\n\0\x01trailing <bytes>", MINIMAL);
+        assert!(parse(&input).is_err());
+        let (slgh, rest) = parse_prefix(&input).expect("Failed to parse sla with trailing bytes");
+        assert_eq!(slgh, expected);
+        assert_eq!(rest, "\n\0\x01trailing <bytes>");
+
+        // Concatenated specifications are read one at a time
+        let input = format!(
+            "{}{}",
+            MINIMAL,
+            MINIMAL.replacen("</sleigh>", "< / sleigh >", 1)
+        );
+        let (first, rest) = parse_prefix(&input).expect("Failed to parse first sla");
+        let (second, rest) = parse_prefix(rest).expect("Failed to parse second sla");
+        assert_eq!(first, expected);
+        assert_eq!(second, expected);
+        assert_eq!(rest, "");
+
+        // A closing tag in a comment does not end the specification
+        let input = MINIMAL.replacen("<sourcefiles>", "<!-- </sleigh> --><sourcefiles>", 1);
+        let (slgh, rest) = parse_prefix(&input).expect("Failed to parse sla with a comment");
+        assert_eq!(slgh, expected);
+        assert_eq!(rest, "");
+
+        assert!(matches!(
+            parse_prefix(&MINIMAL.replacen("</spaces>", "", 1)),
+            Err(SleighParseError::Syntax(_))
+        ));
+    }
+
+    #[test]
+    fn test_decision_stats() {
+        let pair = |id: i32| {
+            DecisionNodePair::builder()
+                .id(id)
+                .pattern(DisjointPatternType::Instruction(
+                    InstructionPattern::builder()
+                        .mask_value(
+                            PatternBlock::builder()
+                                .offset(0)
+                                .nonzero(0)
+                                .mask_vals(vec![])
+                                .build(),
+                        )
+                        .build(),
+                ))
+                .build()
+        };
+        let node = |pairs, children| {
+            DecisionNode::builder()
+                .number(0)
+                .context(false)
+                .start(0)
+                .bitsize(0)
+                .pairs(pairs)
+                .children(children)
+                .build()
+        };
+
+        let leaf = node(vec![pair(0), pair(1)], vec![]);
+        assert_eq!(
+            leaf.stats(),
+            DecisionStats {
+                max_depth: 1,
+                node_count: 1,
+                pair_count: 2
+            }
+        );
+
+        let tree = node(
+            vec![],
+            vec![
+                node(vec![pair(0)], vec![]),
+                node(
+                    vec![],
+                    vec![node(vec![pair(1), pair(2)], vec![]), node(vec![], vec![])],
+                ),
+            ],
+        );
+        assert_eq!(
+            tree.stats(),
+            DecisionStats {
+                max_depth: 3,
+                node_count: 5,
+                pair_count: 3
+            }
+        );
+
+        const toy_be: &str = include_str!("../Processors/Toy/data/languages/toy_be.sla");
+        let slgh = parse(toy_be).expect("Failed to parse toy_be sla");
+        let stats = slgh
+            .subtables()
+            .map(|subtable| subtable.decisiontree().stats())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            stats.iter().map(|stats| stats.node_count).sum::<usize>(),
+            toy_be.matches("<decision ").count()
+        );
+        assert_eq!(
+            stats.iter().map(|stats| stats.pair_count).sum::<usize>(),
+            toy_be.matches("<pair ").count()
+        );
+        assert!(stats
+            .iter()
+            .all(|stats| stats.max_depth >= 1 && stats.max_depth <= stats.node_count));
+        assert!(stats.iter().any(|stats| stats.max_depth > 1));
+    }
+
+    #[test]
+    fn test_uppercase_hex_prefix() {
+        let upper = MINIMAL
+            .replacen(
+                r#"uniqbase="0x100""#,
+                r#"uniqbase="0XDEAD" maxdelay="0X1""#,
+                1,
+            )
+            .replacen(
+                r#"<subtable_sym_head name="instruction" id="0x0""#,
+                r#"<subtable_sym_head name="instruction" id="0X0""#,
+                1,
+            )
+            .replacen(r#"mask="0xff000000""#, r#"mask="0XFF000000""#, 1);
+        let slgh = parse(&upper).expect("Failed to parse uppercase 0X prefixes");
+        assert_eq!(slgh.uniqbase(), &Integer::from(0xdead));
+        assert_eq!(
+            slgh.maxdelay().map(|maxdelay| maxdelay.0.clone()),
+            Some(Integer::from(1))
+        );
+
+        let borrowed = parse_borrowed(&upper).expect("Failed to parse uppercase 0X prefixes");
+        assert_eq!(borrowed.symbols()[0].id(), &Integer::from(0));
+
+        // Values are written back with the lowercase prefix
+        let written = slgh.to_sla_string();
+        assert!(written.contains(r#"uniqbase="0xdead""#));
+        assert!(!written.contains("0X"));
+        assert_eq!(parse(&written).expect("Failed to reparse"), slgh);
+    }
+
+    #[test]
+    fn test_constructors_by_subtable() {
+        const toy_be: &str = include_str!("../Processors/Toy/data/languages/toy_be.sla");
+        let slgh = parse(toy_be).expect("Failed to parse toy_be sla");
+        let groups = slgh.constructors_by_subtable();
+        assert_eq!(groups.len(), slgh.subtables().count());
+        assert_eq!(
+            groups.values().map(Vec::len).sum::<usize>(),
+            slgh.constructors().count()
+        );
+
+        let instruction = slgh
+            .subtables()
+            .find(|subtable| subtable.header().name() == "instruction")
+            .expect("No instruction subtable");
+        let constructors = &groups[instruction.header().id()];
+        assert_eq!(
+            Integer::from(constructors.len()),
+            *instruction.numct().expect("No numct")
+        );
+        assert!(constructors
+            .iter()
+            .all(|constructor| constructor.parent() == instruction.header().id()));
+    }
+
+    #[test]
+    fn test_pattern_expression_to_infix() {
+        let constant = |val: i64| {
+            PatternExpressionType::PatternValue(PatternValueType::ConstantValue(
+                ConstantValue::builder().val(val).build(),
+            ))
+        };
+        let field = || {
+            PatternExpressionType::PatternValue(PatternValueType::TokenField(
+                TokenField::builder()
+                    .bigendian(true)
+                    .signbit(false)
+                    .bitstart(0)
+                    .bitend(7)
+                    .bytestart(0)
+                    .byteend(0)
+                    .shift(0)
+                    .build(),
+            ))
+        };
+        let binary =
+            |op: fn(PatternExpressionType, PatternExpressionType) -> BinaryExpressionType,
+             left,
+             right| {
+                PatternExpressionType::BinaryExpression(Box::new(op(left, right)))
+            };
+        let plus = |left, right| BinaryExpressionType::Plus {
+            _start: (),
+            left,
+            right,
+            _end: (),
+        };
+        let sub = |left, right| BinaryExpressionType::Sub {
+            _start: (),
+            left,
+            right,
+            _end: (),
+        };
+        let mult = |left, right| BinaryExpressionType::Mult {
+            _start: (),
+            left,
+            right,
+            _end: (),
+        };
+        let shift = |left, right| BinaryExpressionType::LeftShift {
+            _start: (),
+            left,
+            right,
+            _end: (),
+        };
+
+        let expression = binary(shift, binary(plus, field(), constant(4)), constant(2));
+        assert_eq!(expression.to_infix(), "(tok[7..0] + 0x4) << 0x2");
+
+        let expression = binary(plus, field(), binary(mult, constant(4), constant(2)));
+        assert_eq!(expression.to_infix(), "tok[7..0] + 0x4 * 0x2");
+
+        let expression = binary(sub, binary(sub, field(), constant(1)), constant(2));
+        assert_eq!(expression.to_infix(), "tok[7..0] - 0x1 - 0x2");
+        let expression = binary(sub, field(), binary(sub, constant(1), constant(2)));
+        assert_eq!(expression.to_infix(), "tok[7..0] - (0x1 - 0x2)");
+
+        let not = PatternExpressionType::UnaryExpression(Box::new(UnaryExpressionType::Not {
+            _start: (),
+            inner: binary(plus, field(), constant(-1)),
+            _end: (),
+        }));
+        assert_eq!(not.to_infix(), "~(tok[7..0] + -0x1)");
+        let minus = PatternExpressionType::UnaryExpression(Box::new(UnaryExpressionType::Minus {
+            _start: (),
+            inner: constant(-1),
+            _end: (),
+        }));
+        assert_eq!(minus.to_infix(), "-(-0x1)");
+
+        let next =
+            PatternExpressionType::PatternValue(PatternValueType::EndInstructionValue { _tag: () });
+        assert_eq!(binary(plus, next, minus).to_infix(), "inst_next + -(-0x1)");
+    }
+
+    #[test]
+    fn test_endian() {
+        let slgh = parse(MINIMAL).expect("Failed to parse minimal sla");
+        assert_eq!(slgh.endian(), Endian::Big);
+        let ram = slgh.spaces().by_name("ram").expect("No ram space");
+        assert_eq!(ram.endian(), Endian::Big);
+        assert!(bool::from(ram.endian()));
+
+        const SLA_8051: &str = include_str!("../Processors/8051/data/languages/8051.sla");
+        let slgh = parse(SLA_8051).expect("Failed to parse 8051 sla");
+        assert_eq!(slgh.endian(), Endian::from(slgh.bigendian()));
+        let code = slgh.spaces().by_name("CODE").expect("No CODE space");
+        assert_eq!(code.endian(), Endian::from(code.bigendian()));
+        assert_eq!(Endian::from(false), Endian::Little);
+    }
+
+    #[test]
+    fn test_escaped_quotes_in_attributes() {
+        let quoted = MINIMAL
+            .replacen(
+                r#"<sourcefile name="test.sinc""#,
+                r#"<sourcefile name="&quot;test&quot;.sinc""#,
+                1,
+            )
+            .replacen(
+                r#"<print piece="nop"/>"#,
+                r#"<print piece="&quot;"/><print piece="n&quot;p" /><print piece="&quot;/>&quot;"/>"#,
+                1,
+            )
+            .replacen(
+                r#"<space name="ram""#,
+                r#"<space name="&quot;ram&quot;""#,
+                1,
+            )
+            .replacen(
+                r#"<spaces defaultspace="ram">"#,
+                r#"<spaces defaultspace="&quot;ram&quot;">"#,
+                1,
+            );
+        let slgh = parse(&quoted).expect("Failed to parse escaped quotes");
+        assert_eq!(slgh.sourcefiles().source_files()[0].name(), "\"test\".sinc");
+        let constructor = slgh.constructors().next().expect("No constructor");
+        assert_eq!(constructor.display_template(), "\"n\"p\"/>\"");
+        assert_eq!(slgh.spaces().defaultspace(), "\"ram\"");
+        assert!(slgh.spaces().by_name("\"ram\"").is_some());
+
+        let borrowed = parse_borrowed(&quoted).expect("Failed to parse escaped quotes");
+        assert_eq!(borrowed.source_files(), ["\"test\".sinc"]);
+        assert_eq!(
+            borrowed.constructors()[0].pieces(),
+            ["\"", "n\"p", "\"/>\""]
+        );
+
+        // The escapes are written back, so the output parses to the same tree
+        let written = slgh.to_sla_string();
+        assert!(written.contains(r#"piece="n&quot;p""#));
+        assert_eq!(parse(&written).expect("Failed to reparse"), slgh);
+    }
+
+    #[test]
+    fn test_constructor_by_id() {
+        const toy_be: &str = include_str!("../Processors/Toy/data/languages/toy_be.sla");
+        let slgh = parse(toy_be).expect("Failed to parse toy_be sla");
+        let instruction = slgh
+            .subtables()
+            .find(|subtable| subtable.header().name() == "instruction")
+            .expect("No instruction subtable");
+        let last = instruction.constructors().len() - 1;
+        assert!(std::ptr::eq(
+            instruction.constructor(last).expect("No last constructor"),
+            &instruction.constructors()[last]
+        ));
+        assert!(instruction.constructor(last + 1).is_none());
+
+        let constructor = slgh
+            .constructor_by_id(instruction.header().id(), 1)
+            .expect("No constructor 1 in instruction");
+        assert!(std::ptr::eq(constructor, &instruction.constructors()[1]));
+        assert_eq!(constructor.parent(), instruction.header().id());
+        assert!(slgh
+            .constructor_by_id(instruction.header().id(), last + 1)
+            .is_none());
+
+        // Ids that are not subtables resolve to nothing
+        let register = slgh.register_by_name("r0").expect("No r0 register");
+        assert!(slgh.constructor_by_id(register.header().id(), 0).is_none());
+    }
+
+    /// The edit replacing `old` at `start` in `text` with `new`, for
+    /// [`SleighTree::reparse`], and the edited text
+    fn replace_edit(text: &str, start: usize, old: &str, new: &str) -> (InputEdit, String) {
+        let point = |offset: usize| {
+            let before = &text[..offset];
+            let row = before.matches('\n').count();
+            let column = offset - before.rfind('\n').map_or(0, |newline| newline + 1);
+            Point { row, column }
+        };
+        let start_position = point(start);
+        let old_end_position = point(start + old.len());
+        let new_end_position = Point {
+            row: start_position.row,
+            column: start_position.column + new.len(),
+        };
+        let edit = InputEdit {
+            start_byte: start,
+            old_end_byte: start + old.len(),
+            new_end_byte: start + new.len(),
+            start_position,
+            old_end_position,
+            new_end_position,
+        };
+        let mut edited = text.to_string();
+        edited.replace_range(start..start + old.len(), new);
+        (edit, edited)
+    }
+
+    #[test]
+    fn test_incremental_reparse() {
+        const toy_be: &str = include_str!("../Processors/Toy/data/languages/toy_be.sla");
+        let mut tree = SleighTree::parse(toy_be).expect("Failed to parse toy_be sla");
+        assert_eq!(
+            tree.sleigh(),
+            &parse(toy_be).expect("Failed to parse toy_be sla")
+        );
+
+        let start = toy_be.find(r#"piece="imm""#).expect("No imm piece") + r#"piece=""#.len();
+        let (edit, edited) = replace_edit(toy_be, start, "imm", "immediate");
+
+        let full = parse(&edited).expect("Failed to parse edited toy_be sla");
+        let old_tree = tree.tree().clone();
+        tree.reparse(edit, &edited)
+            .expect("Failed to reparse edited toy_be sla");
+        assert_eq!(tree.sleigh(), &full);
+        assert!(tree
+            .sleigh()
+            .constructors()
+            .any(|constructor| constructor.display_template().starts_with("immediate ")));
+
+        // Only the edited piece differs from the previous tree
+        let mut old_tree = old_tree;
+        old_tree.edit(&edit);
+        assert!(old_tree
+            .changed_ranges(tree.tree())
+            .all(|range| range.end_byte - range.start_byte < 64));
+
+        // A failed reparse keeps the last good specification, and later edits still apply
+        let start = edited.find("<print ").expect("No print element");
+        let (edit, broken) = replace_edit(&edited, start, "<print ", "<prnt ");
+        assert!(matches!(
+            tree.reparse(edit, &broken),
+            Err(SleighParseError::Syntax(_))
+        ));
+        assert_eq!(tree.sleigh(), &full);
+        let (edit, fixed) = replace_edit(&broken, start, "<prnt ", "<print ");
+        tree.reparse(edit, &fixed)
+            .expect("Failed to reparse fixed toy_be sla");
+        assert_eq!(fixed, edited);
+        assert_eq!(tree.into_sleigh(), full);
+    }
+
+    #[test]
+    #[ignore = "slow, reports timings only"]
+    fn test_incremental_reparse_timing() {
+        use std::time::Instant;
+
+        const x86_64: &str = include_str!("../Processors/x86/data/languages/x86-64.sla");
+        let mut tree = SleighTree::parse(x86_64).expect("Failed to parse x86-64 sla");
+        let start = x86_64.find(r#"piece="MOV""#).expect("No MOV piece") + r#"piece=""#.len();
+        let (edit, edited) = replace_edit(x86_64, start, "MOV", "MOVE");
+
+        let begin = Instant::now();
+        let full = parse(&edited).expect("Failed to parse edited x86-64 sla");
+        let full_time = begin.elapsed();
+        let begin = Instant::now();
+        tree.reparse(edit, &edited)
+            .expect("Failed to reparse edited x86-64 sla");
+        let reparse_time = begin.elapsed();
+
+        assert_eq!(tree.sleigh(), &full);
+        eprintln!(
+            "x86-64 after a single piece edit: parse {:?}, reparse {:?}",
+            full_time, reparse_time
+        );
+    }
+
+    #[test]
+    fn test_addr_space_kinds() {
+        const SLA_8051: &str = include_str!("../Processors/8051/data/languages/8051.sla");
+        let slgh = parse(SLA_8051).expect("Failed to parse 8051 sla");
+        let spaces = slgh.spaces().spaces();
+        let unique = spaces
+            .iter()
+            .filter(|space| space.is_unique())
+            .collect::<Vec<_>>();
+        assert_eq!(unique.len(), 1);
+        assert_eq!(unique[0].space().name(), "unique");
+
+        for space in spaces {
+            let kinds = [
+                space.is_base(),
+                space.is_unique(),
+                space.is_other(),
+                space.is_overlay(),
+                space.is_space(),
+            ];
+            assert_eq!(kinds.iter().filter(|kind| **kind).count(), 1);
+            assert!(std::ptr::eq(
+                space.space(),
+                slgh.spaces()
+                    .by_name(space.space().name())
+                    .expect("Space not found by name")
+            ));
+        }
+        assert!(spaces
+            .iter()
+            .any(|space| space.is_space() && space.space().name() == "CODE"));
+        assert_eq!(
+            spaces.iter().filter(|space| space.is_other()).count(),
+            SLA_8051.matches("<space_other ").count()
+        );
+    }
+
+    #[test]
+    fn test_has_semantic_body() {
+        let null_result = parse(MINIMAL).expect("Failed to parse minimal sla");
+        let constructor = null_result.constructors().next().expect("No constructor");
+        assert!(constructor.has_semantic_body());
+        assert!(constructor
+            .templ()
+            .is_some_and(|templ| templ.result().as_option().is_none() && templ.vec().is_empty()));
+
+        let unimplemented = MINIMAL.replacen("<construct_tpl>\n<null/></construct_tpl>\n", "", 1);
+        let no_template =
+            parse(&unimplemented).expect("Failed to parse constructor without template");
+        let constructor = no_template.constructors().next().expect("No constructor");
+        assert!(constructor.templ().is_none());
+        assert!(!constructor.has_semantic_body());
+
+        const toy_be: &str = include_str!("../Processors/Toy/data/languages/toy_be.sla");
+        let slgh = parse(toy_be).expect("Failed to parse toy_be sla");
+        let exporting = slgh
+            .constructors()
+            .find(|constructor| {
+                constructor
+                    .templ()
+                    .is_some_and(|templ| templ.result().as_option().is_some())
+            })
+            .expect("No constructor exporting a handle");
+        assert!(exporting.has_semantic_body());
+        // Every template element is either a main section or a named section
+        let bodies = slgh
+            .constructors()
+            .filter(|constructor| constructor.has_semantic_body())
+            .count();
+        let named = slgh
+            .constructors()
+            .map(|constructor| constructor.namedtempl().len())
+            .sum::<usize>();
+        assert!(bodies < slgh.constructors().count());
+        assert_eq!(bodies + named, toy_be.matches("<construct_tpl").count());
+    }
+
+    #[test]
+    fn test_scope_tree() {
+        let nested = MINIMAL
+            .replacen(
+                r#"<scope id="0x0" parent="0x0"/>"#,
+                r#"<scope id="0x0" parent="0x0"/>
+<scope id="0x1" parent="0x0"/>
+<scope id="0x2" parent="0x1"/>
+<scope id="0x3" parent="0x1"/>"#,
+                1,
+            )
+            .replacen(r#"scopesize="1""#, r#"scopesize="4""#, 1)
+            .replace(
+                r#"name="instruction" id="0x0" scope="0x0""#,
+                r#"name="instruction" id="0x0" scope="0x2""#,
+            );
+        let slgh = parse(&nested).expect("Failed to parse nested scopes");
+        let tree = slgh.symbol_table().scope_tree();
+
+        let root = tree.root().expect("No global scope");
+        assert_eq!(root.id(), &Integer::from(0));
+        let ids = |id: i64| {
+            tree.children_of(&Integer::from(id))
+                .iter()
+                .map(|scope| scope.id().0.clone())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(ids(0), [Integer::from(1)]);
+        assert_eq!(ids(1), [Integer::from(2), Integer::from(3)]);
+        assert!(ids(2).is_empty());
+        assert!(ids(7).is_empty());
+
+        let in_scope = |id: i64| {
+            tree.symbols_in_scope(&Integer::from(id))
+                .map(|symbol| symbol.name().to_string())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(in_scope(2), ["instruction"]);
+        assert!(in_scope(0).is_empty());
+
+        const toy_be: &str = include_str!("../Processors/Toy/data/languages/toy_be.sla");
+        let slgh = parse(toy_be).expect("Failed to parse toy_be sla");
+        let table = slgh.symbol_table();
+        let tree = table.scope_tree();
+        let root = tree.root().expect("No global scope");
+        assert_eq!(tree.children_of(root.id()).len() + 1, table.scopes().len());
+        let scoped = std::iter::once(root)
+            .chain(tree.children_of(root.id()).iter().copied())
+            .map(|scope| tree.symbols_in_scope(scope.id()).count())
+            .sum::<usize>();
+        assert_eq!(scoped, table.symbols().len());
+    }
+
+    #[test]
+    fn test_operand_without_code() {
+        fn operand(slgh: &Sleigh, id: i64) -> &OperandSymbol {
+            match slgh.symbol_table().symbol_by_id(&Integer::from(id)) {
+                Some(SleighSymbolType::TripleSymbol(TripleSymbol::SpecificSymbol(
+                    SpecificSymbol::OperandSymbol(symbol),
+                ))) => symbol,
+                other => panic!("Symbol {:#x} is not an operand: {:?}", id, other),
+            }
+        }
+
+        const toy_be: &str = include_str!("../Processors/Toy/data/languages/toy_be.sla");
+        let slgh = parse(toy_be).expect("Failed to parse toy_be sla");
+        let simm = operand(&slgh, 0x4f);
+        assert_eq!(simm.code(), None);
+        assert_eq!(*simm.minlen(), 2);
+        assert_eq!(*simm.index(), 0);
+        assert_eq!(operand(&slgh, 0xb1).code(), Some(true));
+
+        let without_code = toy_be.replacen(
+            r#"id="0xb1" scope="0x2e" subsym="0x5d" off="0" base="-1" minlen="2" code="true" index="1""#,
+            r#"id="0xb1" scope="0x2e" subsym="0x5d" off="0" base="-1" minlen="2" index="1""#,
+            1,
+        );
+        assert_ne!(without_code, toy_be);
+        let slgh = parse(&without_code).expect("Failed to parse operand without code");
+        let rel = operand(&slgh, 0xb1);
+        assert_eq!(rel.code(), None);
+        assert_eq!(*rel.minlen(), 2);
+        assert_eq!(*rel.index(), 1);
+        assert!(!slgh
+            .to_sla_string()
+            .contains(r#"id="0xb1" scope="0x2e" subsym="0x5d" off="0" base="-1" minlen="2" code"#));
+    }
+
+    #[test]
+    fn test_userops() {
+        const SLA_X86: &str = include_str!("../Processors/x86/data/languages/x86.sla");
+        let slgh = parse(SLA_X86).expect("Failed to parse x86 sla");
+        let userops = slgh.userops();
+        assert_eq!(userops.len(), SLA_X86.matches("<userop ").count());
+        assert!(userops
+            .iter()
+            .enumerate()
+            .all(|(position, (_, index))| *index == position as i64));
+        assert!(userops.iter().any(|(name, _)| *name == "segment"));
+        assert!(parse(MINIMAL)
+            .expect("Failed to parse minimal sla")
+            .userops()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_clone() {
+        const toy_be: &str = include_str!("../Processors/Toy/data/languages/toy_be.sla");
+        let slgh = parse(toy_be).expect("Failed to parse toy_be sla");
+        let constructor = slgh.constructors().nth(1).expect("No second constructor");
+        let copy = constructor.clone();
+        assert_eq!(&copy, constructor);
+        assert!(!std::ptr::eq(&copy, constructor));
+
+        let copy = slgh.clone();
+        assert_eq!(copy, slgh);
+        // Lookups on the copy work whether or not the original built its index
+        let id = slgh.symbol_table().symbols()[3].header().id().0.clone();
+        assert_eq!(
+            copy.symbol_table().symbol_by_id(&id),
+            slgh.symbol_table().symbol_by_id(&id)
+        );
+        assert_eq!(
+            slgh.clone().symbol_table().symbol_by_id(&id),
+            slgh.symbol_table().symbol_by_id(&id)
+        );
+    }
+
+    #[test]
+    fn test_hash() {
+        use std::collections::HashSet;
+
+        const toy_be: &str = include_str!("../Processors/Toy/data/languages/toy_be.sla");
+        let slgh = parse(toy_be).expect("Failed to parse toy_be sla");
+        let copy = parse(toy_be).expect("Failed to parse toy_be sla");
+
+        let headers = slgh
+            .symbol_table()
+            .symbols()
+            .iter()
+            .chain(copy.symbol_table().symbols())
+            .map(SleighSymbolType::header)
+            .collect::<HashSet<_>>();
+        assert_eq!(headers.len(), slgh.symbol_table().symbols().len());
+
+        let spaces = slgh
+            .spaces()
+            .spaces()
+            .iter()
+            .chain(copy.spaces().spaces())
+            .map(|space| space.space().clone())
+            .collect::<HashSet<_>>();
+        assert_eq!(spaces.len(), slgh.spaces().spaces().len());
+        assert!(spaces.contains(slgh.spaces().by_name("ram").expect("No ram space")));
+
+        let pieces = slgh
+            .constructors()
+            .flat_map(Constructor::printpiece)
+            .filter_map(|piece| match piece {
+                PrintPieceType::Print(print) => Some(print),
+                PrintPieceType::Operand(_) => None,
+            })
+            .collect::<HashSet<_>>();
+        assert!(pieces.iter().any(|print| print.piece() == ","));
+        assert!(pieces.len() < toy_be.matches("<print ").count());
+
+        let ids = slgh
+            .symbol_table()
+            .symbols()
+            .iter()
+            .map(SleighSymbolType::id)
+            .collect::<HashSet<_>>();
+        assert_eq!(ids.len(), slgh.symbol_table().symbols().len());
+    }
+
+    #[test]
+    fn test_decision_to_dot() {
+        let pair = |id: i32| {
+            DecisionNodePair::builder()
+                .id(id)
+                .pattern(DisjointPatternType::Instruction(
+                    InstructionPattern::builder()
+                        .mask_value(
+                            PatternBlock::builder()
+                                .offset(0)
+                                .nonzero(0)
+                                .mask_vals(vec![])
+                                .build(),
+                        )
+                        .build(),
+                ))
+                .build()
+        };
+        let node = |start: i32, pairs, children| {
+            DecisionNode::builder()
+                .number(0)
+                .context(false)
+                .start(start)
+                .bitsize(1)
+                .pairs(pairs)
+                .children(children)
+                .build()
+        };
+        let tree = node(
+            4,
+            vec![],
+            vec![
+                node(0, vec![pair(0)], vec![]),
+                node(0, vec![pair(1), pair(2)], vec![]),
+            ],
+        );
+        let dot = tree.to_dot();
+        assert!(dot.starts_with("digraph decision {\n"));
+        assert!(dot.ends_with("}\n"));
+        assert!(dot.contains(r#"n0 [label="start=4 bitsize=1 context=false"];"#));
+        assert!(dot.contains(r#"n0 -> n1 [label="0"];"#));
+        assert!(dot.contains(r#"n0 -> n2 [label="1"];"#));
+        assert!(dot.contains(r#"n2 -> n2p1 [label="2 instruction"];"#));
+        assert_eq!(dot.matches(" -> ").count(), 2 + 3);
+
+        const toy_be: &str = include_str!("../Processors/Toy/data/languages/toy_be.sla");
+        let slgh = parse(toy_be).expect("Failed to parse toy_be sla");
+        for subtable in slgh.subtables() {
+            let tree = subtable.decisiontree();
+            let stats = tree.stats();
+            let dot = tree.to_dot();
+            assert_eq!(dot.matches(" [label=\"start=").count(), stats.node_count);
+            assert_eq!(
+                dot.matches(" -> ").count(),
+                stats.node_count - 1 + stats.pair_count
+            );
+        }
+    }
+
+    #[test]
+    fn test_minimal_sleigh() {
+        let minimal = Sleigh::minimal(true, "ram");
+        assert!(minimal.bigendian());
+        assert_eq!(minimal.spaces().defaultspace(), "ram");
+        let ram = minimal.spaces().by_name("ram").expect("No ram space");
+        assert!(ram.bigendian());
+        assert!(minimal.symbol_table().symbols().is_empty());
+        assert!(minimal.validate().is_empty());
+
+        let written = minimal.to_sla_string();
+        let reparsed = parse(&written).expect("Failed to reparse minimal sla");
+        assert_eq!(reparsed, minimal);
+        assert_eq!(reparsed.to_sla_string(), written);
+
+        let little = Sleigh::minimal(false, "code");
+        assert_eq!(little.endian(), Endian::Little);
+        assert_eq!(
+            parse(&little.to_sla_string()).expect("Failed to reparse minimal sla"),
+            little
+        );
+        assert!(little
+            .spaces()
+            .by_name("code")
+            .is_some_and(|code| !code.bigendian()));
+    }
+
+    #[test]
+    fn test_multiline_sleigh_attributes() {
+        let header = r#"<sleigh version="3" bigendian="true" align="1" uniqbase="0x100">"#;
+        let multiline = MINIMAL.replacen(
+            header,
+            "<sleigh\n    version=\"3\"\n    bigendian=\"true\"\n    align = \"1\"\r\n\tuniqbase=\"0x100\"\n    maxdelay=\"0x1\"\n>",
+            1,
+        );
+        assert_ne!(multiline, MINIMAL);
+        let slgh = parse(&multiline).expect("Failed to parse a multi-line sleigh element");
+        assert_eq!(slgh.version(), Some(&Integer::from(3)));
+        assert!(slgh.bigendian());
+        assert_eq!(*slgh.align(), 1);
+        assert_eq!(slgh.uniqbase(), &Integer::from(0x100));
+        assert!(slgh.maxdelay().is_some());
+
+        let minimal = parse(MINIMAL).expect("Failed to parse minimal sla");
+        assert_eq!(slgh.symbol_table(), minimal.symbol_table());
+    }
+
+    #[test]
+    fn test_operand_subtable() {
+        const toy_be: &str = include_str!("../Processors/Toy/data/languages/toy_be.sla");
+        let slgh = parse(toy_be).expect("Failed to parse toy_be sla");
+        let table = slgh.symbol_table();
+        let operand = |id: i64| match table.symbol_by_id(&Integer::from(id)) {
+            Some(SleighSymbolType::TripleSymbol(TripleSymbol::SpecificSymbol(
+                SpecificSymbol::OperandSymbol(symbol),
+            ))) => symbol,
+            other => panic!("Symbol {:#x} is not an operand: {:?}", id, other),
+        };
+
+        // Rel82 is decoded by the Rel82 subtable
+        let rel = operand(0xb1);
+        let subtable = rel.subtable(table).expect("Rel82 has no subtable");
+        assert_eq!(subtable.header().name(), "Rel82");
+        assert_eq!(subtable.header().id(), &Integer::from(0x5d));
+
+        let resolved = slgh
+            .constructors()
+            .find(|constructor| *constructor.line() == (Integer::from(0), Integer::from(155)))
+            .expect("No constructor at line 0:155")
+            .resolve_operands(table);
+        // rd refers to a register list
+        let rd = resolved[0].expect("Operand 0 did not resolve");
+        assert!(rd.subsym().is_some());
+        assert!(rd.subtable(table).is_none());
+
+        // simm0003 has no subsym at all
+        let simm = operand(0x4f);
+        assert!(simm.subsym().is_none());
+        assert!(simm.subtable(table).is_none());
+    }
+
+    #[test]
+    fn test_parse_file() {
+        let path = std::env::temp_dir().join(format!(
+            "tree-sitter-sleigh-test-parse-file-{}.sla",
+            std::process::id()
+        ));
+        std::fs::write(&path, MINIMAL).expect("Failed to write temporary sla");
+        let parsed = parse_file(&path);
+        let invalid = path.with_extension("bin");
+        std::fs::write(&invalid, [0xff, 0xfe]).expect("Failed to write temporary file");
+        let not_utf8 = parse_file(&invalid);
+        std::fs::remove_file(&path).expect("Failed to remove temporary sla");
+        std::fs::remove_file(&invalid).expect("Failed to remove temporary file");
+
+        assert_eq!(
+            parsed.expect("Failed to parse temporary sla"),
+            parse(MINIMAL).expect("Failed to parse minimal sla")
+        );
+        assert!(matches!(not_utf8, Err(SleighParseError::Utf8(_))));
+        match parse_file(&path) {
+            Err(SleighParseError::Io(error)) => {
+                assert_eq!(error.kind(), std::io::ErrorKind::NotFound)
+            }
+            other => panic!("Expected an IO error for a missing file, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_handle_template_evaluate() {
+        struct Instruction {
+            operands: Vec<HandleLocation>,
+        }
+
+        impl TemplateContext for Instruction {
+            fn operand(&self, index: &Integer) -> Option<HandleLocation> {
+                self.operands.get(usize::try_from(index).ok()?).cloned()
+            }
+
+            fn start(&self) -> Option<Integer> {
+                Some(Integer::from(0x1000))
+            }
+
+            fn end(&self) -> Option<Integer> {
+                Some(Integer::from(0x1002))
+            }
+        }
+
+        let location = |space: &str, offset: i64, size: i64| HandleLocation {
+            space: space.to_string(),
+            offset: Integer::from(offset),
+            size: Integer::from(size),
+        };
+
+        const toy_be: &str = include_str!("../Processors/Toy/data/languages/toy_be.sla");
+        let slgh = parse(toy_be).expect("Failed to parse toy_be sla");
+        let handles = slgh
+            .constructors()
+            .filter_map(|constructor| constructor.templ())
+            .filter_map(|templ| templ.result().as_option())
+            .collect::<Vec<_>>();
+        let none = Instruction { operands: vec![] };
+
+        // A register exported directly is static
+        let register = handles
+            .iter()
+            .find(|handle| {
+                matches!(handle.space(), ConstantTemplateType::SpaceId { name, .. } if name == "register")
+            })
+            .expect("No register handle");
+        let fixed = register
+            .evaluate(&none)
+            .expect("Register handle did not evaluate");
+        assert_eq!(fixed.space, "register");
+        assert_eq!(
+            Some(&fixed.offset),
+            register.ptroffset().as_real_val().map(|offset| &offset.0)
+        );
+        assert_eq!(
+            Some(&fixed.size),
+            register.size().as_real_val().map(|size| &size.0)
+        );
+
+        // `*[const]:4 operand` is only static when the operand is a constant
+        let constant = handles
+            .iter()
+            .find(|handle| {
+                matches!(
+                    handle.ptrspace(),
+                    ConstantTemplateType::Handle { val, .. } if *val == 0
+                )
+            })
+            .expect("No constant handle");
+        let immediate = Instruction {
+            operands: vec![location("const", 0x12, 2)],
+        };
+        assert_eq!(
+            constant.evaluate(&immediate),
+            Some(location("const", 0x12, 4))
+        );
+        let memory = Instruction {
+            operands: vec![location("ram", 0x12, 2)],
+        };
+        assert_eq!(constant.evaluate(&memory), None);
+        assert_eq!(constant.evaluate(&none), None);
+
+        // Relative forms read the instruction addresses
+        let real = |val: i64| ConstantTemplateType::Real {
+            _start: (),
+            val: HexInteger(Integer::from(val)),
+            _end: (),
+        };
+        let relative = |offset: ConstantTemplateType| {
+            HandleTemplate::builder()
+                .space(ConstantTemplateType::SpaceId {
+                    _start: (),
+                    name: "ram".to_string(),
+                    _end: (),
+                })
+                .size(real(4))
+                .ptrspace(real(0))
+                .ptroffset(offset)
+                .ptrsize(real(0))
+                .temp_space(real(0))
+                .temp_offset(real(0))
+                .build()
+        };
+        let start = relative(ConstantTemplateType::Start { _start: () });
+        assert_eq!(start.evaluate(&none), Some(location("ram", 0x1000, 4)));
+        let next = relative(ConstantTemplateType::Next { _next: () });
+        assert_eq!(next.evaluate(&none), Some(location("ram", 0x1002, 4)));
+        let next2 = relative(ConstantTemplateType::Next2 { _next2: () });
+        assert_eq!(next2.evaluate(&none), None);
+
+        // offset_plus adds to an address, and selects bytes of a constant
+        let offset_plus = |plus: Integer| ConstantTemplateType::Handle {
+            _start: (),
+            val: Integer::from(0),
+            selector: ConstantTemplateSelector::OffsetPlus { _offset_plus: () },
+            plus: Some(HexInteger(plus)),
+            _end: (),
+        };
+        let register = Instruction {
+            operands: vec![location("register", 0x10, 4)],
+        };
+        assert_eq!(
+            offset_plus(Integer::from(0x2)).evaluate(&register),
+            Some(Integer::from(0x12))
+        );
+        let immediate = Instruction {
+            operands: vec![location("const", 0x1234, 2)],
+        };
+        assert_eq!(
+            offset_plus(Integer::from(0x10000)).evaluate(&immediate),
+            Some(Integer::from(0x12))
+        );
+        // A byte count too large to shift by
+        assert_eq!(
+            offset_plus(Integer::from(1) << 78u64).evaluate(&immediate),
+            None
+        );
+    }
+
+    #[test]
+    fn test_symbol_collectors() {
+        const ARM7_le: &str = include_str!("../Processors/ARM/data/languages/ARM7_le.sla");
+
+        let sleigh = parse(ARM7_le).expect("Failed to parse ARM7_le sla");
+        assert_eq!(
+            sleigh.operand_symbols().count(),
+            ARM7_le.matches("<operand_sym ").count()
+        );
+        assert_eq!(
+            sleigh.value_symbols().count(),
+            ARM7_le.matches("<value_sym ").count()
+        );
+        assert_eq!(
+            sleigh.context_symbols().count(),
+            ARM7_le.matches("<context_sym ").count()
+        );
+        assert_eq!(
+            sleigh.subtable_symbols().count(),
+            ARM7_le.matches("<subtable_sym ").count()
+        );
+        assert!(sleigh.operand_symbols().count() > 0);
+        assert!(sleigh.value_symbols().count() > 0);
+        assert_eq!(
+            sleigh.context_symbols().collect::<Vec<_>>(),
+            sleigh.context_fields()
+        );
+    }
+
+    #[test]
+    fn test_overlay_space() {
+        let input = MINIMAL.replace(
+            "</spaces>",
+            "<space_overlay name=\"ram_ov\" index=\"3\" base=\"ram\" bigendian=\"true\" delay=\"1\" size=\"4\" physical=\"true\"/>\n</spaces>",
+        );
+        let sleigh = parse(&input).expect("Failed to parse overlay space");
+        let overlay = &sleigh.spaces().spaces()[2];
+        assert!(overlay.is_overlay());
+        assert_eq!(overlay.space().name(), "ram_ov");
+        assert_eq!(overlay.overlay().map(OverlaySpace::base), Some("ram"));
+        assert!(sleigh.spaces().spaces()[1].overlay().is_none());
+        assert_eq!(
+            parse(&sleigh.to_sla_string()).expect("Failed to reparse written sla"),
+            sleigh
+        );
+
+        let missing = MINIMAL.replace(
+            "</spaces>",
+            "<space_overlay name=\"ram_ov\" index=\"3\" bigendian=\"true\" delay=\"1\" size=\"4\" physical=\"true\"/>\n</spaces>",
+        );
+        assert!(matches!(
+            parse(&missing),
+            Err(SleighParseError::MissingCapture {
+                field: "OverlaySpace::base",
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_bit_range() {
+        let token = TokenField::builder()
+            .bigendian(true)
+            .signbit(true)
+            .bitstart(4)
+            .bitend(11)
+            .bytestart(0)
+            .byteend(1)
+            .shift(4)
+            .build();
+        let context = ContextField::builder()
+            .signbit(true)
+            .startbit(4)
+            .endbit(11)
+            .startbyte(0)
+            .endbyte(1)
+            .shift(4)
+            .build();
+        let expected = BitRange {
+            start: 4,
+            end: 11,
+            byte_start: 0,
+            byte_end: 1,
+            shift: 4,
+            signed: true,
+        };
+        assert_eq!(token.bit_range(), expected);
+        assert_eq!(context.bit_range(), expected);
+
+        let negative = ContextField::builder()
+            .signbit(false)
+            .startbit(-1)
+            .endbit(u64::MAX)
+            .startbyte(0)
+            .endbyte(0)
+            .shift(0)
+            .build();
+        assert_eq!(negative.bit_range().start, 0);
+        assert_eq!(negative.bit_range().end, u32::MAX);
+    }
+
+    #[test]
+    fn test_referenced_space_names() {
+        const SLA_8051: &str = include_str!("../Processors/8051/data/languages/8051.sla");
+        let sleigh = parse(SLA_8051).expect("Failed to parse 8051 sla");
+        let referenced = sleigh.referenced_space_names();
+        assert!(referenced.contains("register"));
+        assert!(referenced.contains("unique"));
+        assert!(referenced.contains("const"));
+        for name in referenced.iter().filter(|name| *name != "const") {
+            assert!(
+                sleigh.spaces().by_name(name).is_some(),
+                "{name} is not declared"
+            );
+        }
+
+        let minimal = parse(MINIMAL).expect("Failed to parse minimal sla");
+        assert!(minimal.referenced_space_names().is_empty());
+    }
+
+    #[test]
+    fn test_addr_space_omitted_attributes() {
+        let input = MINIMAL.replacen(
+            r#"<space name="ram" index="2" bigendian="true" delay="1" size="4" physical="true"/>"#,
+            r#"<space name="ram" index="2" size="4"/>"#,
+            1,
+        );
+        let sleigh = parse(&input).expect("Failed to parse space without optional attributes");
+        let ram = sleigh.spaces().by_name("ram").expect("No ram space");
+        assert_eq!(ram.delay(), &Integer::from(0));
+        assert_eq!(ram.deadcodedelay_or_default(), Integer::from(0));
+        assert!(!ram.bigendian());
+        assert!(!ram.physical());
+        assert_eq!(ram.wordsize(), None);
+        assert_eq!(
+            ram,
+            &AddrSpace::builder()
+                .name("ram".to_string())
+                .index(2)
+                .size(4)
+                .build()
+        );
+    }
+
+    #[test]
+    fn test_register_at() {
+        const SLA_X86: &str = include_str!("../Processors/x86/data/languages/x86.sla");
+        let sleigh = parse(SLA_X86).expect("Failed to parse x86 sla");
+        let name = |offset: u32, size: u32| {
+            sleigh
+                .register_at("register", &Integer::from(offset), &Integer::from(size))
+                .map(|varnode| varnode.header().name())
+        };
+        assert_eq!(name(0x0, 1), Some("AL"));
+        assert_eq!(name(0x0, 2), Some("AX"));
+        assert_eq!(name(0x0, 4), Some("EAX"));
+        assert_eq!(name(0x2, 1), Some("EAX"));
+        assert_eq!(name(0x4, 1), Some("CL"));
+        assert_eq!(name(0x2, 4), None);
+        assert!(sleigh
+            .register_at("ram", &Integer::from(0), &Integer::from(1))
+            .is_none());
+    }
+
+    #[test]
+    fn test_leaf_error_location() {
+        let input = MINIMAL.replacen(r#"delay="1""#, r#"delay="one""#, 1);
+        let line = input
+            .lines()
+            .position(|line| line.contains(r#"delay="one""#))
+            .expect("No line with an invalid delay")
+            + 1;
+        let column = input
+            .lines()
+            .nth(line - 1)
+            .and_then(|text| text.find("one"))
+            .expect("No invalid delay")
+            + 1;
+        let error = parse(&input).expect_err("Parsed an invalid delay");
+        let location = error.location().expect("No location for an invalid delay");
+        assert_eq!(location.line, line);
+        assert_eq!(location.column, column);
+        assert_eq!(&input[location.offset..location.offset + 3], "one");
+        assert!(error
+            .to_string()
+            .ends_with(&format!("at line {}, column {}", line, column)));
+
+        let input = MINIMAL.replacen(r#"bigendian="true" align"#, r#"bigendian="maybe" align"#, 1);
+        let location = parse(&input)
+            .expect_err("Parsed an invalid bigendian")
+            .location()
+            .expect("No location for an invalid bigendian");
+        assert_eq!(
+            (location.line, location.column),
+            (1, input.find("maybe").expect("No invalid bigendian") + 1)
+        );
+
+        let input = MINIMAL.replacen(r#"<space name="ram" index="2""#, r#"<space index="2""#, 1);
+        match parse(&input) {
+            Err(SleighParseError::MissingCapture {
+                field: "AddrSpace::name",
+                location: Some(location),
+            }) => assert_eq!(location.line, 7),
+            other => panic!("Expected a missing name, got {:?}", other),
+        }
+
+        assert!(parse("<sleigh")
+            .expect_err("Parsed an unterminated root")
+            .location()
+            .is_none());
+    }
+
+    #[test]
+    fn test_empty_print_pieces() {
+        let input = MINIMAL.replacen(
+            r#"<print piece="nop"/>"#,
+            r#"<print piece="mov"/>
+<print piece=" "/>
+<opprint id="0"/>
+<print piece=""/>
+<print piece=""/>
+<print piece=","/>
+<opprint id="1"/>
+<print piece=""/>"#,
+            1,
+        );
+        let sleigh = parse(&input).expect("Failed to parse sla with empty print pieces");
+        let constructor = sleigh.constructors().next().expect("No constructor");
+        assert_eq!(constructor.printpiece().len(), 8);
+        assert_eq!(
+            constructor
+                .printpiece()
+                .iter()
+                .filter(|piece| matches!(piece, PrintPieceType::Print(print) if print.piece().is_empty()))
+                .count(),
+            3
+        );
+        assert_eq!(constructor.display_template(), "mov {0},{1}");
+        assert_eq!(
+            parse(&sleigh.to_sla_string()).expect("Failed to reparse written sla"),
+            sleigh
+        );
+    }
+
+    #[test]
+    fn test_default_containers() {
+        assert!(SourceFiles::default().source_files().is_empty());
+        let spaces = Spaces::default();
+        assert_eq!(spaces.defaultspace(), "");
+        assert!(spaces.spaces().is_empty());
+        assert!(spaces.default_space().is_none());
+
+        let mut table = SymbolTable::default();
+        assert_eq!(table.scopesize(), &Integer::from(0));
+        assert_eq!(table.symbolsize(), &Integer::from(0));
+        table.push_scope(Scope::builder().id(0).parent(0).build());
+        assert_eq!(table.scopesize(), &Integer::from(1));
+
+        let header = SymbolHeader::builder()
+            .name("r0".to_string())
+            .id(0)
+            .scope(0)
+            .build();
+        assert!(table.symbol_by_id(&Integer::from(0)).is_none());
+        for (id, name) in [(0, "r0"), (1, "r1")] {
+            let header = SymbolHeader::builder()
+                .name(name.to_string())
+                .id(id)
+                .scope(0)
+                .build();
+            let varnode = VarNodeSymbol::builder()
+                .header(header.clone())
+                .space("register".to_string())
+                .offset(id * 4)
+                .size(4)
+                .build();
+            table.push_symbol(
+                SymbolHeaderType::VarNode {
+                    _start: (),
+                    header,
+                    _end: (),
+                },
+                SleighSymbolType::TripleSymbol(TripleSymbol::SpecificSymbol(
+                    SpecificSymbol::PatternlessSymbol(PatternlessSymbol::VarNodeSymbol(varnode)),
+                )),
+            );
+        }
+        assert_eq!(table.symbolsize(), &Integer::from(2));
+        assert_eq!(table.symbols().len(), 2);
+        assert_eq!(
+            table
+                .symbol_by_id(&Integer::from(1))
+                .map(|symbol| symbol.header().name()),
+            Some("r1")
+        );
+        assert_eq!(
+            table
+                .symbol_header_by_id(&Integer::from(0))
+                .map(|header| header.header()),
+            Some(&header)
+        );
+    }
+
+    #[test]
+    fn test_enumerate_paths() {
+        use std::collections::HashSet;
+
+        const toy_be: &str = include_str!("../Processors/Toy/data/languages/toy_be.sla");
+        let first = parse(toy_be).expect("Failed to parse toy_be sla");
+        let second = parse(toy_be).expect("Failed to parse toy_be sla");
+        let paths = first.enumerate_paths();
+        assert_eq!(paths, second.enumerate_paths());
+
+        let unique = paths.iter().map(|(path, _)| path).collect::<HashSet<_>>();
+        assert_eq!(unique.len(), paths.len());
+
+        struct Count(usize);
+        impl Visitor for Count {
+            fn visit_constructor(&mut self, _constructor: &Constructor) {
+                self.0 += 1;
+            }
+            fn visit_varnode_template(&mut self, _varnode: &VarNodeTemplate) {
+                self.0 += 1;
+            }
+        }
+        let mut count = Count(0);
+        first.accept(&mut count);
+        assert_eq!(
+            paths
+                .iter()
+                .filter(|(_, node)| matches!(
+                    node,
+                    NodeRef::Constructor(_) | NodeRef::VarNodeTemplate(_)
+                ))
+                .count(),
+            count.0
+        );
+
+        assert_eq!(paths[0].0, "spaces.spaces[0]");
+        let (path, node) = paths
+            .iter()
+            .find(|(_, node)| matches!(node, NodeRef::Constructor(_)))
+            .expect("No constructor path");
+        assert!(path.starts_with("symbol_table.symbols["));
+        assert!(path.ends_with("].constructors[0]"));
+        let constructor = first.constructors().next().expect("No constructor");
+        assert!(matches!(node, NodeRef::Constructor(found) if std::ptr::eq(*found, constructor)));
+    }
+
+    #[test]
+    fn test_interleaved_context_changes() {
+        let input = MINIMAL.replacen(
+            "<construct_tpl>",
+            r#"<commit id="0x1" num="0" mask="0x1" flow="false"/>
+<context_op i="0" shift="24" mask="0xff000000" >
+<intb val="1"/>
+</context_op>
+<commit id="0x2" num="0" mask="0x2" flow="true"/>
+<commit id="0x3" num="1" mask="0x4" flow="false"/>
+<context_op i="1" shift="0" mask="0xff" >
+<intb val="2"/>
+</context_op>
+<construct_tpl>"#,
+            1,
+        );
+        let sleigh = parse(&input).expect("Failed to parse interleaved context changes");
+        let constructor = sleigh.constructors().next().expect("No constructor");
+        assert_eq!(
+            constructor
+                .contexts()
+                .iter()
+                .map(|change| matches!(change, ContextChangeType::Commit(_)))
+                .collect::<Vec<_>>(),
+            [true, false, true, true, false]
+        );
+        assert_eq!(
+            constructor
+                .commits()
+                .map(|commit| commit.id().0.clone())
+                .collect::<Vec<_>>(),
+            [Integer::from(1), Integer::from(2), Integer::from(3)]
+        );
+        assert_eq!(
+            constructor
+                .context_ops()
+                .map(|operation| operation.i().clone())
+                .collect::<Vec<_>>(),
+            [Integer::from(0), Integer::from(1)]
+        );
+        assert_eq!(
+            parse(&sleigh.to_sla_string()).expect("Failed to reparse written sla"),
+            sleigh
+        );
+    }
+
+    #[test]
+    fn test_to_pcode_op() {
+        const toy_be: &str = include_str!("../Processors/Toy/data/languages/toy_be.sla");
+        let sleigh = parse(toy_be).expect("Failed to parse toy_be sla");
+        let ops = sleigh
+            .constructors()
+            .filter_map(Constructor::templ)
+            .flat_map(|templ| templ.vec())
+            .collect::<Vec<_>>();
+
+        let copy = ops
+            .iter()
+            .find(|op| matches!(op.code(), OperationCode::Copy { .. }))
+            .expect("No COPY template");
+        let pcode = copy.to_pcode_op();
+        assert!(matches!(pcode.opcode, OperationCode::Copy { .. }));
+        assert_eq!(pcode.output.as_ref(), copy.output().as_option());
+        assert!(pcode.output.is_some());
+        assert_eq!(pcode.inputs, copy.input());
+        assert_eq!(pcode.inputs.len(), 1);
+
+        let store = ops
+            .iter()
+            .find(|op| matches!(op.code(), OperationCode::Store { .. }))
+            .expect("No STORE template");
+        let pcode = store.to_pcode_op();
+        assert!(matches!(pcode.opcode, OperationCode::Store { .. }));
+        assert_eq!(pcode.output, None);
+        assert_eq!(pcode.inputs, store.input());
+        assert_eq!(pcode.inputs.len(), 3);
+    }
+
+    #[test]
+    fn test_single_quoted_attributes() {
+        let expected = parse(MINIMAL).expect("Failed to parse minimal sla");
+        let input = MINIMAL
+            .replacen(r#"version="3""#, "version='3'", 1)
+            .replacen(r#"name="test.sinc""#, "name='test.sinc'", 1)
+            .replacen(r#"name="ram" index="2""#, "name='ram' index='2'", 1)
+            .replacen(r#"line="0:1""#, "line='0:1'", 1)
+            .replacen(r#"piece="nop""#, "piece='nop'", 1)
+            .replacen(r#"mask="0xff000000""#, "mask='0xff000000'", 1);
+        assert_ne!(input, MINIMAL);
+        let sleigh = parse(&input).expect("Failed to parse single-quoted attributes");
+        assert_eq!(sleigh, expected);
+        assert_eq!(sleigh.version(), Some(&Integer::from(3)));
+        assert_eq!(sleigh.sourcefiles().source_files()[0].name(), "test.sinc");
+
+        let quoted = MINIMAL.replacen(r#"piece="nop""#, r#"piece='say "nop"'"#, 1);
+        let sleigh = parse(&quoted).expect("Failed to parse quotes in single-quoted attribute");
+        let constructor = sleigh.constructors().next().expect("No constructor");
+        assert_eq!(constructor.display_template(), r#"say "nop""#);
+
+        let borrowed =
+            parse_borrowed(&input).expect("Failed to parse single-quoted attributes borrowed");
+        assert_eq!(borrowed.source_files()[0], "test.sinc");
+        assert_eq!(borrowed.constructors()[0].pieces()[0], "nop");
+
+        let mismatched = MINIMAL.replacen(r#"version="3""#, r#"version="3'"#, 1);
+        assert!(parse(&mismatched).is_err());
+
+        const toy_be: &str = include_str!("../Processors/Toy/data/languages/toy_be.sla");
+        let single = toy_be.replacen(r#"<op_tpl code="COPY">"#, "<op_tpl code='COPY'>", 1);
+        assert_ne!(single, toy_be);
+        assert_eq!(
+            parse(&single).expect("Failed to parse single-quoted op_tpl"),
+            parse(toy_be).expect("Failed to parse toy_be sla")
+        );
+        let mismatched = toy_be.replacen(r#"<op_tpl code="COPY">"#, r#"<op_tpl code="COPY'>"#, 1);
+        assert!(parse(&mismatched).is_err());
+    }
+
+    #[test]
+    fn test_diff() {
+        const ARM7_le: &str = include_str!("../Processors/ARM/data/languages/ARM7_le.sla");
+        const ARM8_le: &str = include_str!("../Processors/ARM/data/languages/ARM8_le.sla");
+
+        let minimal = parse(MINIMAL).expect("Failed to parse minimal sla");
+        assert!(minimal.diff(&minimal.clone()).is_empty());
+
+        let changed = parse(
+            &MINIMAL
+                .replacen(r#"align="1""#, r#"align="2""#, 1)
+                .replacen(r#"uniqbase="0x100">"#, r#"uniqbase="0x100" note="x">"#, 1)
+                .replacen(r#"piece="nop""#, r#"piece="halt""#, 1),
+        )
+        .expect("Failed to parse changed minimal sla");
+        assert_eq!(
+            minimal.diff(&changed),
+            [
+                SleighDiff::Attribute {
+                    name: "align".to_string(),
+                    left: Some("1".to_string()),
+                    right: Some("2".to_string()),
+                },
+                SleighDiff::Attribute {
+                    name: "note".to_string(),
+                    left: None,
+                    right: Some("x".to_string()),
+                },
+                SleighDiff::SymbolChanged {
+                    name: "instruction".to_string(),
+                    id: HexInteger(Integer::from(0)),
+                },
+            ]
+        );
+
+        // Named templates are identified by their section, so their order is insignificant
+        let sections = |first: &str, second: &str| {
+            parse(&MINIMAL.replacen(
+                "<construct_tpl>\n<null/></construct_tpl>\n",
+                &format!(
+                    "<construct_tpl>\n<null/></construct_tpl>\n{}\n<null/></construct_tpl>\n{}\n<null/></construct_tpl>\n",
+                    first, second
+                ),
+                1,
+            ))
+            .expect("Failed to parse sla with named templates")
+        };
+        let named = sections(
+            r#"<construct_tpl section="0">"#,
+            r#"<construct_tpl section="1">"#,
+        );
+        let reordered = sections(
+            r#"<construct_tpl section="1">"#,
+            r#"<construct_tpl section="0">"#,
+        );
+        assert!(named.diff(&reordered).is_empty());
+        let delayed = sections(
+            r#"<construct_tpl section="1" delay="1">"#,
+            r#"<construct_tpl section="0">"#,
+        );
+        assert_eq!(
+            named.diff(&delayed),
+            [SleighDiff::SymbolChanged {
+                name: "instruction".to_string(),
+                id: HexInteger(Integer::from(0)),
+            }]
+        );
+
+        let arm7 = parse(ARM7_le).expect("Failed to parse ARM7_le sla");
+        let arm8 = parse(ARM8_le).expect("Failed to parse ARM8_le sla");
+        let diffs = arm7.diff(&arm8);
+        assert!(diffs
+            .iter()
+            .any(|diff| matches!(diff, SleighDiff::SymbolChanged { .. })));
+        let reverse = arm8.diff(&arm7);
+        let count = |diffs: &[SleighDiff], added: bool| {
+            diffs
+                .iter()
+                .filter(|diff| {
+                    if added {
+                        matches!(diff, SleighDiff::SymbolAdded { .. })
+                    } else {
+                        matches!(diff, SleighDiff::SymbolRemoved { .. })
+                    }
+                })
+                .count()
+        };
+        assert_eq!(count(&diffs, true), count(&reverse, false));
+        assert_eq!(count(&diffs, false), count(&reverse, true));
+    }
+
+    #[test]
+    fn test_integer_sign_and_bits() {
+        let slgh = parse(MINIMAL).expect("Failed to parse minimal sla");
+        let root = slgh.subtables().next().expect("No subtable");
+        let mask = match root.decisiontree().pairs()[0].pattern() {
+            DisjointPatternType::Instruction(pattern) => pattern.mask_value().mask_vals()[0].mask(),
+            other => panic!("Expected an instruction pattern, got {:?}", other),
+        };
+        assert_eq!(mask.significant_bits(), 32);
+        assert!(!mask.is_negative());
+        assert_eq!(slgh.uniqbase().significant_bits(), 9);
+        assert_eq!(Integer::from(0).significant_bits(), 0);
+        assert!(Integer::from(-1).is_negative());
+        assert_eq!(Integer::from(-0x80).significant_bits(), 8);
+    }
+
+    #[test]
+    fn test_numsections() {
+        let input = MINIMAL.replacen(
+            r#"uniqbase="0x100">"#,
+            r#"uniqbase="0x100" numsections="2">"#,
+            1,
+        );
+        let slgh = parse(&input).expect("Failed to parse sla with numsections");
+        assert_eq!(slgh.numsections(), Some(&Integer::from(2)));
+        assert!(slgh.unknown_attributes().is_empty());
+        let written = slgh.to_sla_string();
+        assert!(written.contains(r#"numsections="2""#));
+        assert_eq!(parse(&written).expect("Failed to parse written sla"), slgh);
+
+        let single = input.replacen(r#"numsections="2""#, "numsections='2'", 1);
+        let slgh = parse(&single).expect("Failed to parse single-quoted numsections");
+        assert_eq!(slgh.numsections(), Some(&Integer::from(2)));
+    }
+
+    #[test]
+    fn test_parse_header_only() {
+        const toy_be: &str = include_str!("../Processors/Toy/data/languages/toy_be.sla");
+        let slgh = parse(toy_be).expect("Failed to parse toy_be sla");
+        let header = parse_header_only(toy_be).expect("Failed to parse toy_be header");
+        assert_eq!(header.version(), slgh.version());
+        assert_eq!(header.bigendian(), slgh.bigendian());
+        assert_eq!(header.align(), slgh.align());
+        assert_eq!(header.uniqbase(), slgh.uniqbase());
+        assert_eq!(header.maxdelay(), slgh.maxdelay());
+        assert_eq!(header.uniqmask(), slgh.uniqmask());
+        assert_eq!(header.numsections(), slgh.numsections());
+        assert_eq!(header.unknown_attributes(), slgh.unknown_attributes());
+        assert_eq!(header.sourcefiles(), slgh.sourcefiles());
+        assert_eq!(header.spaces(), slgh.spaces());
+
+        // The symbol table is not read, so a malformed one is not an error
+        let start = MINIMAL.find("<symbol_table").expect("No symbol table");
+        let input = format!("{}<symbol_table>garbage</sleigh>", &MINIMAL[..start]);
+        assert!(parse(&input).is_err());
+        let header = parse_header_only(&input).expect("Failed to parse header");
+        assert_eq!(header.spaces().spaces().len(), 2);
+        assert_eq!(header.sourcefiles().source_files().len(), 1);
+
+        // Errors before the symbol table are reported where parse reports them
+        let input = MINIMAL.replacen(r#"size="4" physical"#, r#"size="four" physical"#, 1);
+        assert_eq!(
+            parse_header_only(&input)
+                .expect_err("Parsed the header of an invalid size")
+                .to_string(),
+            parse(&input)
+                .expect_err("Parsed an invalid size")
+                .to_string()
+        );
+        assert!(parse_header_only("<sleigh version=\"3\"").is_err());
+    }
+
+    #[test]
+    fn test_operand_value_resolve() {
+        const toy_be: &str = include_str!("../Processors/Toy/data/languages/toy_be.sla");
+        let slgh = parse(toy_be).expect("Failed to parse toy_be sla");
+        let table = slgh.symbol_table();
+        let simm = slgh
+            .operand_symbols()
+            .find(|operand| operand.header().name() == "simm0003")
+            .expect("No simm0003 operand");
+        let (subtable, constructor) = simm.localexp().resolve(table).expect("Unresolved operand");
+        assert_eq!(subtable.header().name(), "Simm4");
+        assert!(std::ptr::eq(constructor, &subtable.constructors()[0]));
+        assert_eq!(constructor.parent(), subtable.header().id());
+
+        let wrong_table = OperandValue::builder()
+            .index(0)
+            .table(simm.header().id().0.clone())
+            .constructor_id(0)
+            .build();
+        assert!(wrong_table.resolve(table).is_none());
+        let wrong_constructor = OperandValue::builder()
+            .index(0)
+            .table(subtable.header().id().0.clone())
+            .constructor_id(1)
+            .build();
+        assert!(wrong_constructor.resolve(table).is_none());
+    }
 }
@@ -0,0 +1,57 @@
+use crate::grammar::{
+    AddrSpace, AddrSpaceType, Scope, Sleigh, SleighAttributes, SourceFiles, Spaces, SymbolTable,
+};
+
+impl Sleigh {
+    /// The smallest specification this crate reads back: a unique space, a default space
+    /// named `default_space` with 4 byte addresses, and only the global scope, with no
+    /// source files or symbols. It is a starting point for tests and for round trips
+    /// through [`Sleigh::to_sla_string`], not a usable processor.
+    pub fn minimal(bigendian: bool, default_space: &str) -> Sleigh {
+        let space = |name: &str, index: i32, delay: i32| {
+            AddrSpace::builder()
+                .name(name.to_string())
+                .index(index)
+                .bigendian(bigendian)
+                .delay(delay)
+                .size(4)
+                .physical(true)
+                .build()
+        };
+        Sleigh::builder()
+            .attributes(
+                SleighAttributes::builder()
+                    .version(3)
+                    .bigendian(bigendian)
+                    .align(1)
+                    .uniqbase(0)
+                    .build(),
+            )
+            .sourcefiles(SourceFiles::builder().build())
+            .spaces(
+                Spaces::builder()
+                    .defaultspace(default_space.to_string())
+                    .spaces(vec![
+                        AddrSpaceType::Unique {
+                            _start: (),
+                            space: space("unique", 1, 0),
+                            _end: (),
+                        },
+                        AddrSpaceType::Space {
+                            _start: (),
+                            space: space(default_space, 2, 1),
+                            _end: (),
+                        },
+                    ])
+                    .build(),
+            )
+            .symbol_table(
+                SymbolTable::builder()
+                    .scopesize(1)
+                    .symbolsize(0)
+                    .scopes(vec![Scope::builder().id(0).parent(0).build()])
+                    .build(),
+            )
+            .build()
+    }
+}
@@ -0,0 +1,13 @@
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
+
+use crate::{grammar::Sleigh, parse, SleighParseError};
+
+/// Parse many `.sla` specifications in parallel with [`parse`]. The results are in the same
+/// order as `inputs`. Each parse runs entirely on one thread, and errors from leaf transforms
+/// are recorded per thread, so a failure in one input never affects another.
+pub fn parse_many<I>(inputs: I) -> Vec<Result<Sleigh, SleighParseError>>
+where
+    I: IntoParallelIterator<Item = String>,
+{
+    inputs.into_par_iter().map(|input| parse(&input)).collect()
+}
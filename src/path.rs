@@ -0,0 +1,36 @@
+use crate::grammar::{
+    AddrSpaceType, Constructor, ConstructorTemplate, ContextChangeType, DecisionNode,
+    HandleTemplate, OperationTemplate, PatternExpressionType, PatternValueType, Sleigh,
+    SleighSymbolType, SymbolHeaderType, VarNodeTemplate,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[non_exhaustive]
+/// A node of a specification, as listed by [`Sleigh::enumerate_paths`]. There is one variant
+/// per [`Visitor`](crate::Visitor) callback.
+pub enum NodeRef<'a> {
+    Space(&'a AddrSpaceType),
+    SymbolHeader(&'a SymbolHeaderType),
+    Symbol(&'a SleighSymbolType),
+    Constructor(&'a Constructor),
+    ContextChange(&'a ContextChangeType),
+    ConstructorTemplate(&'a ConstructorTemplate),
+    HandleTemplate(&'a HandleTemplate),
+    OpTemplate(&'a OperationTemplate),
+    VarNodeTemplate(&'a VarNodeTemplate),
+    PatternExpression(&'a PatternExpressionType),
+    PatternValue(&'a PatternValueType),
+    DecisionNode(&'a DecisionNode),
+}
+
+impl Sleigh {
+    /// Every node [`Sleigh::accept`] visits, in the same order, paired with a dotted path to
+    /// it from the root, e.g. `symbol_table.symbols[3].constructors[0].templ`. Paths are
+    /// unique within a specification and depend only on its structure, so the first path at
+    /// which two specifications' nodes differ is where they diverge.
+    pub fn enumerate_paths(&self) -> Vec<(String, NodeRef<'_>)> {
+        let mut paths = Vec::new();
+        self.walk(|path, node| paths.push((path.to_string(), node)));
+        paths
+    }
+}
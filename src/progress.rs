@@ -0,0 +1,34 @@
+use crate::{grammar::Sleigh, parse, SleighParseError};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+/// A step of [`parse_with_progress`]. The grammar parses the input in a single call, so
+/// symbol progress is reported by a walk over the parsed symbol table afterwards.
+pub enum ParseProgress {
+    /// Parsing started on an input of `bytes` bytes
+    Started { bytes: usize },
+    /// The grammar consumed all `bytes` bytes of the input
+    Parsed { bytes: usize },
+    /// `completed` of the `total` symbols in the symbol table have been read
+    Symbols { completed: usize, total: usize },
+    /// The specification was parsed successfully
+    Finished,
+}
+
+/// Parse a `.sla` specification with [`parse`], calling `progress` as it goes. A failed parse
+/// reports only [`ParseProgress::Started`]; a successful one reports every step, with one
+/// [`ParseProgress::Symbols`] per symbol.
+pub fn parse_with_progress(
+    input: &str,
+    progress: &mut dyn FnMut(ParseProgress),
+) -> Result<Sleigh, SleighParseError> {
+    progress(ParseProgress::Started { bytes: input.len() });
+    let sleigh = parse(input)?;
+    progress(ParseProgress::Parsed { bytes: input.len() });
+    let total = sleigh.symbol_table().symbols().len();
+    for completed in 1..=total {
+        progress(ParseProgress::Symbols { completed, total });
+    }
+    progress(ParseProgress::Finished);
+    Ok(sleigh)
+}
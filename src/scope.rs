@@ -0,0 +1,60 @@
+use std::collections::HashMap;
+
+use malachite::Integer;
+
+use crate::grammar::{Scope, SleighSymbolType, SymbolTable};
+
+#[derive(Debug, Clone)]
+/// The scopes of a symbol table arranged by their parents, as returned by
+/// [`SymbolTable::scope_tree`]
+pub struct ScopeTree<'a> {
+    table: &'a SymbolTable,
+    root: Option<&'a Scope>,
+    children: HashMap<&'a Integer, Vec<&'a Scope>>,
+}
+
+impl<'a> ScopeTree<'a> {
+    /// The global scope, which is its own parent, if the table has one
+    pub fn root(&self) -> Option<&'a Scope> {
+        self.root
+    }
+
+    /// The scopes whose parent is `id`, in table order. The global scope is not a child of
+    /// itself.
+    pub fn children_of(&self, id: &Integer) -> &[&'a Scope] {
+        self.children.get(id).map_or(&[], Vec::as_slice)
+    }
+
+    /// The symbols declared directly in the scope `id`, in table order. Symbols of nested
+    /// scopes are not included.
+    pub fn symbols_in_scope<'b>(
+        &'b self,
+        id: &'b Integer,
+    ) -> impl Iterator<Item = &'a SleighSymbolType> + 'b {
+        self.table
+            .symbols()
+            .iter()
+            .filter(move |symbol| symbol.header().scope().0 == *id)
+    }
+}
+
+impl SymbolTable {
+    /// The scopes arranged by their parents, for walking symbol visibility from the global
+    /// scope down
+    pub fn scope_tree(&self) -> ScopeTree<'_> {
+        let mut root = None;
+        let mut children = HashMap::<&Integer, Vec<&Scope>>::new();
+        for scope in self.scopes() {
+            if scope.id() == scope.parent() {
+                root.get_or_insert(scope);
+            } else {
+                children.entry(&scope.parent().0).or_default().push(scope);
+            }
+        }
+        ScopeTree {
+            table: self,
+            root,
+            children,
+        }
+    }
+}
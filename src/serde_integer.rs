@@ -0,0 +1,76 @@
+//! Serde adapters that write [`Integer`]s as decimal strings, so values of any width survive
+//! formats like JSON whose numbers are limited to 64 bits
+
+use malachite::Integer;
+use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::HexInteger;
+
+pub(crate) fn serialize<S: Serializer>(value: &Integer, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(value)
+}
+
+pub(crate) fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Integer, D::Error> {
+    let decimal = String::deserialize(deserializer)?;
+    decimal
+        .parse()
+        .map_err(|_| D::Error::custom(format!("invalid integer {:?}", decimal)))
+}
+
+struct Decimal<'a>(&'a Integer);
+
+impl Serialize for Decimal<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize(self.0, serializer)
+    }
+}
+
+#[derive(Deserialize)]
+struct OwnedDecimal(#[serde(deserialize_with = "deserialize")] Integer);
+
+pub(crate) mod option {
+    use super::*;
+
+    pub(crate) fn serialize<S: Serializer>(
+        value: &Option<Integer>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        value.as_ref().map(Decimal).serialize(serializer)
+    }
+
+    pub(crate) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<Integer>, D::Error> {
+        Ok(Option::<OwnedDecimal>::deserialize(deserializer)?.map(|OwnedDecimal(value)| value))
+    }
+}
+
+pub(crate) mod pair {
+    use super::*;
+
+    pub(crate) fn serialize<S: Serializer>(
+        value: &(Integer, Integer),
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        (Decimal(&value.0), Decimal(&value.1)).serialize(serializer)
+    }
+
+    pub(crate) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<(Integer, Integer), D::Error> {
+        let (OwnedDecimal(first), OwnedDecimal(second)) = Deserialize::deserialize(deserializer)?;
+        Ok((first, second))
+    }
+}
+
+impl Serialize for HexInteger {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize(&self.0, serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for HexInteger {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserialize(deserializer).map(HexInteger)
+    }
+}
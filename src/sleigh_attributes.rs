@@ -0,0 +1,70 @@
+use malachite::Integer;
+use typed_builder::TypedBuilder;
+
+use crate::HexInteger;
+
+#[derive(TypedBuilder, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// The attributes of the `<sleigh>` root element. They are parsed as a group, so they may
+/// appear in any order, and attributes this crate does not know about may appear between
+/// the known ones. They are read through the getters of [`Sleigh`](crate::grammar::Sleigh).
+///
+/// Only `align` and `uniqbase` are required.
+pub struct SleighAttributes {
+    #[builder(default, setter(transform = |v: impl Into<Integer>| {
+        Some(v.into())
+    }))]
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_integer::option"))]
+    pub(crate) version: Option<Integer>,
+    /// `bigendian` is always written by Ghidra, but minimal specs may omit it and rely on
+    /// the endianness of the default space
+    #[builder(default, setter(transform = |v: bool| Some(v)))]
+    pub(crate) bigendian: Option<bool>,
+    #[builder(setter(transform = |v: impl Into<Integer>| {
+        v.into()
+    }))]
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_integer"))]
+    pub(crate) align: Integer,
+    #[builder(setter(transform = |v: impl Into<Integer>| {
+        HexInteger(v.into())
+    }))]
+    pub(crate) uniqbase: HexInteger,
+    /// `maxdelay` is used, but is only usually set to 0x1 (1 delay slot)
+    #[builder(default, setter(transform = |v: impl Into<Integer>| {
+        Some(HexInteger(v.into()))
+    }))]
+    pub(crate) maxdelay: Option<HexInteger>,
+    #[builder(default, setter(transform = |v: impl Into<Integer>| {
+        Some(HexInteger(v.into()))
+    }))]
+    pub(crate) uniqmask: Option<HexInteger>,
+    /// `numsections` is written in decimal, like `version` and `align`
+    #[builder(default, setter(transform = |v: impl Into<Integer>| {
+        Some(v.into())
+    }))]
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_integer::option"))]
+    pub(crate) numsections: Option<Integer>,
+    /// Root attributes this crate does not know about, in the order they appear
+    #[builder(default)]
+    pub(crate) unknown_attributes: Vec<UnknownAttribute>,
+}
+
+#[derive(TypedBuilder, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// An attribute of the `<sleigh>` root this crate does not know about
+pub struct UnknownAttribute {
+    pub(crate) name: String,
+    pub(crate) value: String,
+}
+
+impl UnknownAttribute {
+    /// The attribute name
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The attribute value, unescaped
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+}
@@ -0,0 +1,115 @@
+use std::ops::Range;
+
+use rust_sitter::tree_sitter::{Node, Parser};
+
+use crate::{
+    error::extract,
+    grammar::{self, AddrSpaceType, Constructor, Sleigh, SleighSymbolType},
+    SleighParseError,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+/// A range of byte offsets into the text a specification was parsed from. `start` is
+/// inclusive and `end` is exclusive.
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Span {
+    /// The span as a range, for slicing the source text
+    pub fn range(&self) -> Range<usize> {
+        self.start..self.end
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+/// The source spans of the major nodes of a parsed specification, as returned by
+/// [`parse_with_spans`]. Spans are stored in the same order as the nodes of the parsed tree,
+/// and looked up by the nodes themselves.
+pub struct SpanMap {
+    spaces: Vec<Span>,
+    symbols: Vec<Span>,
+    constructors: Vec<Span>,
+}
+
+impl SpanMap {
+    /// The span of `space`, which must belong to the specification the map was built with
+    pub fn space(&self, sleigh: &Sleigh, space: &AddrSpaceType) -> Option<Span> {
+        position(sleigh.spaces().spaces().iter(), space)
+            .and_then(|position| self.spaces.get(position))
+            .copied()
+    }
+
+    /// The span of `symbol`, which must belong to the specification the map was built with
+    pub fn symbol(&self, sleigh: &Sleigh, symbol: &SleighSymbolType) -> Option<Span> {
+        position(sleigh.symbol_table().symbols().iter(), symbol)
+            .and_then(|position| self.symbols.get(position))
+            .copied()
+    }
+
+    /// The span of `constructor`, which must belong to the specification the map was built
+    /// with
+    pub fn constructor(&self, sleigh: &Sleigh, constructor: &Constructor) -> Option<Span> {
+        position(sleigh.constructors(), constructor)
+            .and_then(|position| self.constructors.get(position))
+            .copied()
+    }
+
+    /// The spans of the address spaces, in [`Spaces::spaces`](grammar::Spaces::spaces) order
+    pub fn spaces(&self) -> &[Span] {
+        &self.spaces
+    }
+
+    /// The spans of the symbols, in
+    /// [`SymbolTable::symbols`](grammar::SymbolTable::symbols) order
+    pub fn symbols(&self) -> &[Span] {
+        &self.symbols
+    }
+
+    /// The spans of the constructors, in [`Sleigh::constructors`] order
+    pub fn constructors(&self) -> &[Span] {
+        &self.constructors
+    }
+}
+
+/// The position of the node `target` in `nodes`, by identity rather than equality so equal
+/// nodes at different positions each get their own span
+fn position<'a, T: 'a>(nodes: impl IntoIterator<Item = &'a T>, target: &T) -> Option<usize> {
+    nodes
+        .into_iter()
+        .position(|node| std::ptr::eq(node, target))
+}
+
+/// Parse a `.sla` specification as [`parse`](crate::parse) does, and also record the byte
+/// span of each address space, symbol, and constructor in `input`
+pub fn parse_with_spans(input: &str) -> Result<(Sleigh, SpanMap), SleighParseError> {
+    let mut parser = Parser::new();
+    parser
+        .set_language(&grammar::language())
+        .expect("Incompatible tree-sitter language");
+    let tree = parser
+        .parse(input, None)
+        .expect("Failed to parse with no timeout or cancellation");
+    let sleigh = extract(&tree, input)?;
+    let mut spans = SpanMap::default();
+    collect(tree.root_node(), &mut spans);
+    Ok((sleigh, spans))
+}
+
+fn collect(node: Node, spans: &mut SpanMap) {
+    let span = Span {
+        start: node.start_byte(),
+        end: node.end_byte(),
+    };
+    match node.kind() {
+        "AddrSpaceType" => spans.spaces.push(span),
+        "SleighSymbolType" => spans.symbols.push(span),
+        "Constructor" => spans.constructors.push(span),
+        _ => {}
+    }
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        collect(child, spans);
+    }
+}
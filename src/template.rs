@@ -0,0 +1,235 @@
+use malachite::Integer;
+
+use crate::{
+    grammar::{
+        ConstantTemplateSelector, ConstantTemplateType, HandleTemplate, OperationCode,
+        OperationTemplate, VarNodeTemplate,
+    },
+    HexInteger,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+/// Flat tag identifying the kind of a [`ConstantTemplateType`] without matching through its
+/// marker fields
+pub enum ConstTplKind {
+    Real,
+    Handle,
+    Start,
+    End,
+    Next,
+    Next2,
+    CurSpace,
+    CurSpaceSize,
+    SpaceId,
+    JumpRelative,
+    FlowRef,
+    FlowDest,
+    FlowDestSize,
+    Unknown,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// A concrete varnode: `size` bytes at `offset` in the address space named `space`. Constants
+/// are in the `const` space, with the value as the offset.
+pub struct HandleLocation {
+    pub space: String,
+    pub offset: Integer,
+    pub size: Integer,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// A p-code operation of a template, with the `<null/>` output of an operation that writes
+/// nothing flattened to `None`, as returned by [`OperationTemplate::to_pcode_op`]
+pub struct PcodeOp {
+    pub opcode: OperationCode,
+    pub output: Option<VarNodeTemplate>,
+    pub inputs: Vec<VarNodeTemplate>,
+}
+
+impl OperationTemplate {
+    /// The operation as a [`PcodeOp`]
+    pub fn to_pcode_op(&self) -> PcodeOp {
+        PcodeOp {
+            opcode: self.code().clone(),
+            output: self.output().as_option().cloned(),
+            inputs: self.input().to_vec(),
+        }
+    }
+}
+
+/// The state of an instruction being built, which [`ConstantTemplateType`] and
+/// [`HandleTemplate`] are evaluated against. Operands must be supplied; the remaining values
+/// evaluate to `None` unless overridden.
+pub trait TemplateContext {
+    /// The location operand `index` of the constructor resolved to
+    fn operand(&self, index: &Integer) -> Option<HandleLocation>;
+
+    /// The address of the instruction being built (`inst_start`)
+    fn start(&self) -> Option<Integer> {
+        None
+    }
+
+    /// The address of the next instruction (`inst_next`)
+    fn end(&self) -> Option<Integer> {
+        None
+    }
+
+    /// The address of the instruction after the next one (`inst_next2`)
+    fn next2(&self) -> Option<Integer> {
+        None
+    }
+
+    /// The name of the space the instruction is in
+    fn cur_space(&self) -> Option<String> {
+        None
+    }
+
+    /// The size in bytes of addresses in [`TemplateContext::cur_space`]
+    fn cur_space_size(&self) -> Option<Integer> {
+        None
+    }
+
+    /// The reference address of a flow override
+    fn flow_ref(&self) -> Option<Integer> {
+        None
+    }
+
+    /// The destination address of a flow override
+    fn flow_dest(&self) -> Option<Integer> {
+        None
+    }
+
+    /// The size of [`TemplateContext::flow_dest`]
+    fn flow_dest_size(&self) -> Option<Integer> {
+        None
+    }
+}
+
+impl ConstantTemplateType {
+    /// The flat kind of this constant template
+    pub fn kind(&self) -> ConstTplKind {
+        match self {
+            ConstantTemplateType::Real { .. } => ConstTplKind::Real,
+            ConstantTemplateType::Handle { .. } => ConstTplKind::Handle,
+            ConstantTemplateType::Start { .. } => ConstTplKind::Start,
+            ConstantTemplateType::End { .. } => ConstTplKind::End,
+            ConstantTemplateType::Next { .. } => ConstTplKind::Next,
+            ConstantTemplateType::Next2 { .. } => ConstTplKind::Next2,
+            ConstantTemplateType::CurSpace { .. } => ConstTplKind::CurSpace,
+            ConstantTemplateType::CurSpaceSize { .. } => ConstTplKind::CurSpaceSize,
+            ConstantTemplateType::SpaceId { .. } => ConstTplKind::SpaceId,
+            ConstantTemplateType::JumpRelative { .. } => ConstTplKind::JumpRelative,
+            ConstantTemplateType::FlowRef { .. } => ConstTplKind::FlowRef,
+            ConstantTemplateType::FlowDest { .. } => ConstTplKind::FlowDest,
+            ConstantTemplateType::FlowDestSize { .. } => ConstTplKind::FlowDestSize,
+            ConstantTemplateType::Unknown { .. } => ConstTplKind::Unknown,
+        }
+    }
+
+    /// The literal value of a `real` constant
+    pub fn as_real_val(&self) -> Option<&HexInteger> {
+        match self {
+            ConstantTemplateType::Real { val, .. } => Some(val),
+            _ => None,
+        }
+    }
+
+    /// The operand index, the selected part of the operand, and the optional `plus` offset
+    /// of a `handle` constant
+    pub fn as_handle(&self) -> Option<(&Integer, &ConstantTemplateSelector, Option<&HexInteger>)> {
+        match self {
+            ConstantTemplateType::Handle {
+                val,
+                selector,
+                plus,
+                ..
+            } => Some((val, selector, plus.as_ref())),
+            _ => None,
+        }
+    }
+
+    /// The value of this constant in `ctx`, as Ghidra fixes it when building an instruction.
+    /// Returns `None` for constants that name a space, which [`Self::evaluate_space`]
+    /// resolves, and for values `ctx` does not supply.
+    pub fn evaluate(&self, ctx: &dyn TemplateContext) -> Option<Integer> {
+        match self {
+            ConstantTemplateType::Real { val, .. }
+            | ConstantTemplateType::JumpRelative { val, .. } => Some(val.0.clone()),
+            ConstantTemplateType::Handle {
+                val,
+                selector,
+                plus,
+                ..
+            } => {
+                let operand = ctx.operand(val)?;
+                match selector {
+                    ConstantTemplateSelector::Space { .. } => None,
+                    ConstantTemplateSelector::Offset { .. } => Some(operand.offset),
+                    ConstantTemplateSelector::Size { .. } => Some(operand.size),
+                    ConstantTemplateSelector::OffsetPlus { .. } => {
+                        let plus = plus
+                            .as_ref()
+                            .map_or_else(Integer::default, |plus| plus.0.clone());
+                        if operand.space == "const" {
+                            // The upper half selects bytes of a constant, shifting them down
+                            let bytes = u64::try_from(&(plus >> 16u32)).ok()?;
+                            Some(operand.offset >> bytes.checked_mul(8)?)
+                        } else {
+                            Some(operand.offset + (plus & Integer::from(0xffff)))
+                        }
+                    }
+                }
+            }
+            ConstantTemplateType::Start { .. } => ctx.start(),
+            ConstantTemplateType::End { .. } | ConstantTemplateType::Next { .. } => ctx.end(),
+            ConstantTemplateType::Next2 { .. } => ctx.next2(),
+            ConstantTemplateType::CurSpaceSize { .. } => ctx.cur_space_size(),
+            ConstantTemplateType::FlowRef { .. } => ctx.flow_ref(),
+            ConstantTemplateType::FlowDest { .. } => ctx.flow_dest(),
+            ConstantTemplateType::FlowDestSize { .. } => ctx.flow_dest_size(),
+            ConstantTemplateType::CurSpace { .. }
+            | ConstantTemplateType::SpaceId { .. }
+            | ConstantTemplateType::Unknown { .. } => None,
+        }
+    }
+
+    /// The name of the space this constant refers to in `ctx`, for `spaceid`, `curspace`,
+    /// and the space of a `handle`. Returns `None` for other constants.
+    pub fn evaluate_space(&self, ctx: &dyn TemplateContext) -> Option<String> {
+        match self {
+            ConstantTemplateType::SpaceId { name, .. } => Some(name.clone()),
+            ConstantTemplateType::CurSpace { .. } => ctx.cur_space(),
+            ConstantTemplateType::Handle {
+                val,
+                selector: ConstantTemplateSelector::Space { .. },
+                ..
+            } => Some(ctx.operand(val)?.space),
+            _ => None,
+        }
+    }
+}
+
+impl HandleTemplate {
+    /// The varnode this handle refers to in `ctx`, as Ghidra fixes it when building an
+    /// instruction.
+    ///
+    /// A handle with a `real` pointer space is static, and its location is read directly
+    /// from `space`, `size`, and `ptroffset`. A handle whose pointer space resolves to
+    /// `const` could have been dynamic but was not, and is read the same way. Other
+    /// dynamic handles are read through a pointer at run time, so they have no location
+    /// here and evaluate to `None`, as does a handle with a value `ctx` does not supply.
+    /// Offsets are not scaled by the word size of the space.
+    pub fn evaluate(&self, ctx: &dyn TemplateContext) -> Option<HandleLocation> {
+        if !matches!(self.ptrspace(), ConstantTemplateType::Real { .. })
+            && self.ptrspace().evaluate_space(ctx)? != "const"
+        {
+            return None;
+        }
+        Some(HandleLocation {
+            space: self.space().evaluate_space(ctx)?,
+            offset: self.ptroffset().evaluate(ctx)?,
+            size: self.size().evaluate(ctx)?,
+        })
+    }
+}
@@ -0,0 +1,158 @@
+use std::fmt;
+
+use malachite::Integer;
+
+use crate::{
+    grammar::{
+        OperandValue, PatternValueType, Sleigh, SleighSymbolType, SpecificSymbol, SymbolTable,
+        TripleSymbol,
+    },
+    HexInteger, Visitor,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// A broken invariant found by [`Sleigh::validate`]
+pub struct ValidationIssue {
+    id: Option<HexInteger>,
+    message: String,
+}
+
+impl ValidationIssue {
+    fn new(id: Option<&Integer>, message: impl Into<String>) -> Self {
+        Self {
+            id: id.cloned().map(HexInteger),
+            message: message.into(),
+        }
+    }
+
+    /// The id of the offending symbol, scope, or subtable, or `None` if the issue concerns
+    /// the symbol table as a whole
+    pub fn id(&self) -> Option<&HexInteger> {
+        self.id.as_ref()
+    }
+
+    /// A human readable description of the issue
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl fmt::Display for ValidationIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.id {
+            Some(id) => write!(f, "{} (id {})", self.message, id),
+            None => f.write_str(&self.message),
+        }
+    }
+}
+
+impl Sleigh {
+    /// Check invariants the parser does not enforce: declared counts match the parsed
+    /// lists, scope parents exist, and operand references resolve to subtables. An empty
+    /// list means no issues were found.
+    pub fn validate(&self) -> Vec<ValidationIssue> {
+        let table = self.symbol_table();
+        let mut issues = Vec::new();
+
+        if *table.scopesize() != table.scopes().len() {
+            issues.push(ValidationIssue::new(
+                None,
+                format!(
+                    "scopesize is {} but {} scopes are declared",
+                    table.scopesize(),
+                    table.scopes().len()
+                ),
+            ));
+        }
+        if *table.symbolsize() != table.symbol_headers().len() {
+            issues.push(ValidationIssue::new(
+                None,
+                format!(
+                    "symbolsize is {} but {} symbol headers are declared",
+                    table.symbolsize(),
+                    table.symbol_headers().len()
+                ),
+            ));
+        }
+
+        for scope in table.scopes() {
+            if !table
+                .scopes()
+                .iter()
+                .any(|other| other.id() == scope.parent())
+            {
+                issues.push(ValidationIssue::new(
+                    Some(scope.id()),
+                    format!("scope parent {} does not exist", scope.parent()),
+                ));
+            }
+        }
+
+        for subtable in self.subtables() {
+            if let Some(numct) = subtable.numct() {
+                if *numct != subtable.constructors().len() {
+                    issues.push(ValidationIssue::new(
+                        Some(subtable.header().id()),
+                        format!(
+                            "subtable {} declares {} constructors but has {}",
+                            subtable.header().name(),
+                            numct,
+                            subtable.constructors().len()
+                        ),
+                    ));
+                }
+            }
+        }
+
+        let mut operands = OperandTables {
+            table,
+            issues: &mut issues,
+        };
+        self.accept(&mut operands);
+
+        issues
+    }
+}
+
+/// Reports every [`OperandValue`] whose table id is not a subtable symbol
+struct OperandTables<'a> {
+    table: &'a SymbolTable,
+    issues: &'a mut Vec<ValidationIssue>,
+}
+
+impl OperandTables<'_> {
+    fn check(&mut self, operand: &OperandValue) {
+        if !matches!(
+            self.table.symbol_by_id(operand.table()),
+            Some(SleighSymbolType::TripleSymbol(
+                TripleSymbol::SubtableSymbol { .. }
+            ))
+        ) {
+            self.issues.push(ValidationIssue::new(
+                Some(operand.table()),
+                format!(
+                    "operand {} refers to table {}, which is not a subtable",
+                    operand.index(),
+                    operand.table()
+                ),
+            ));
+        }
+    }
+}
+
+impl Visitor for OperandTables<'_> {
+    fn visit_symbol(&mut self, symbol: &SleighSymbolType) {
+        if let SleighSymbolType::TripleSymbol(TripleSymbol::SpecificSymbol(
+            SpecificSymbol::OperandSymbol(operand),
+        )) = symbol
+        {
+            self.check(operand.localexp());
+        }
+    }
+
+    fn visit_pattern_value(&mut self, value: &PatternValueType) {
+        if let PatternValueType::OperandValue(operand) = value {
+            self.check(operand);
+        }
+    }
+}
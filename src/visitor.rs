@@ -0,0 +1,244 @@
+use std::fmt::{self, Write};
+
+use crate::{
+    disassemble::patval,
+    grammar::{
+        AddrSpaceType, Constructor, ConstructorTemplate, ContextChangeType, DecisionNode,
+        FamilySymbol, HandleTemplate, OperationTemplate, PatternExpressionType, PatternValueType,
+        Sleigh, SleighSymbolType, SpecificSymbol, SymbolHeaderType, TripleSymbol,
+        UnaryExpressionType, VarNodeTemplate,
+    },
+    NodeRef,
+};
+
+/// Callbacks for a depth-first walk of a [`Sleigh`] specification started by
+/// [`Sleigh::accept`]. Every method does nothing by default, so implementors only override
+/// the nodes they care about. Each node is visited before its children.
+pub trait Visitor {
+    /// Called for each address space
+    fn visit_space(&mut self, _space: &AddrSpaceType) {}
+
+    /// Called for each symbol header in the symbol table
+    fn visit_symbol_header(&mut self, _header: &SymbolHeaderType) {}
+
+    /// Called for each symbol in the symbol table
+    fn visit_symbol(&mut self, _symbol: &SleighSymbolType) {}
+
+    /// Called for each constructor of each subtable
+    fn visit_constructor(&mut self, _constructor: &Constructor) {}
+
+    /// Called for each context change of a constructor
+    fn visit_context_change(&mut self, _change: &ContextChangeType) {}
+
+    /// Called for each p-code template, main or named section, of a constructor or macro
+    fn visit_constructor_template(&mut self, _templ: &ConstructorTemplate) {}
+
+    /// Called for the result handle of a p-code template, if it has one
+    fn visit_handle_template(&mut self, _handle: &HandleTemplate) {}
+
+    /// Called for each p-code operation of a template
+    fn visit_op_template(&mut self, _op: &OperationTemplate) {}
+
+    /// Called for the output and each input of a p-code operation
+    fn visit_varnode_template(&mut self, _varnode: &VarNodeTemplate) {}
+
+    /// Called for each pattern expression node, including every operand of binary and
+    /// unary expressions
+    fn visit_pattern_expression(&mut self, _expression: &PatternExpressionType) {}
+
+    /// Called for each pattern value, both the leaves of pattern expressions and the fields
+    /// indexing value, name, context, and varnode list symbols
+    fn visit_pattern_value(&mut self, _value: &PatternValueType) {}
+
+    /// Called for each decision tree node, including every nested child
+    fn visit_decision_node(&mut self, _node: &DecisionNode) {}
+}
+
+impl Sleigh {
+    /// Walk the specification depth first, calling `v` for every node it visits: the address
+    /// spaces, then the symbol headers, then the symbols and everything they contain
+    pub fn accept(&self, v: &mut impl Visitor) {
+        self.walk(|_, node| match node {
+            NodeRef::Space(space) => v.visit_space(space),
+            NodeRef::SymbolHeader(header) => v.visit_symbol_header(header),
+            NodeRef::Symbol(symbol) => v.visit_symbol(symbol),
+            NodeRef::Constructor(constructor) => v.visit_constructor(constructor),
+            NodeRef::ContextChange(change) => v.visit_context_change(change),
+            NodeRef::ConstructorTemplate(templ) => v.visit_constructor_template(templ),
+            NodeRef::HandleTemplate(handle) => v.visit_handle_template(handle),
+            NodeRef::OpTemplate(op) => v.visit_op_template(op),
+            NodeRef::VarNodeTemplate(varnode) => v.visit_varnode_template(varnode),
+            NodeRef::PatternExpression(expression) => v.visit_pattern_expression(expression),
+            NodeRef::PatternValue(value) => v.visit_pattern_value(value),
+            NodeRef::DecisionNode(node) => v.visit_decision_node(node),
+        });
+    }
+
+    /// Walk the specification as [`Sleigh::accept`] does, calling `f` with the dotted path
+    /// from the root to each node, e.g. `symbol_table.symbols[3].constructors[0].templ`, and
+    /// the node itself
+    pub(crate) fn walk<'a>(&'a self, f: impl FnMut(&str, NodeRef<'a>)) {
+        let mut walk = Walk {
+            path: String::new(),
+            f,
+        };
+        for (index, space) in self.spaces().spaces().iter().enumerate() {
+            walk.at(format_args!("spaces.spaces[{index}]"), |walk| {
+                walk.node(NodeRef::Space(space))
+            });
+        }
+        let table = self.symbol_table();
+        for (index, header) in table.symbol_headers().iter().enumerate() {
+            walk.at(
+                format_args!("symbol_table.symbol_headers[{index}]"),
+                |walk| walk.node(NodeRef::SymbolHeader(header)),
+            );
+        }
+        for (index, symbol) in table.symbols().iter().enumerate() {
+            walk.at(format_args!("symbol_table.symbols[{index}]"), |walk| {
+                walk.symbol(symbol)
+            });
+        }
+    }
+}
+
+struct Walk<F> {
+    /// The path to the node being walked
+    path: String,
+    f: F,
+}
+
+impl<'a, F: FnMut(&str, NodeRef<'a>)> Walk<F> {
+    /// Run `walk` with `segment` appended to the current path
+    fn at(&mut self, segment: fmt::Arguments, walk: impl FnOnce(&mut Self)) {
+        let len = self.path.len();
+        // Writing to a `String` cannot fail
+        let _ = self.path.write_fmt(segment);
+        walk(self);
+        self.path.truncate(len);
+    }
+
+    fn node(&mut self, node: NodeRef<'a>) {
+        (self.f)(&self.path, node);
+    }
+
+    fn symbol(&mut self, symbol: &'a SleighSymbolType) {
+        self.node(NodeRef::Symbol(symbol));
+        match symbol {
+            SleighSymbolType::MacroSymbol(symbol) => {
+                if let Some(templ) = symbol.templ() {
+                    self.at(format_args!(".templ"), |walk| {
+                        walk.constructor_template(templ)
+                    });
+                }
+            }
+            SleighSymbolType::TripleSymbol(TripleSymbol::SubtableSymbol { subtable, .. }) => {
+                for (index, constructor) in subtable.constructors().iter().enumerate() {
+                    self.at(format_args!(".constructors[{index}]"), |walk| {
+                        walk.constructor(constructor)
+                    });
+                }
+                self.at(format_args!(".decisiontree"), |walk| {
+                    walk.decision_node(subtable.decisiontree())
+                });
+            }
+            SleighSymbolType::TripleSymbol(TripleSymbol::FamilySymbol(
+                FamilySymbol::ValueSymbol(value),
+            )) => {
+                self.at(format_args!(".patval"), |walk| {
+                    walk.node(NodeRef::PatternValue(patval(value)))
+                });
+            }
+            SleighSymbolType::TripleSymbol(TripleSymbol::SpecificSymbol(
+                SpecificSymbol::OperandSymbol(operand),
+            )) => {
+                if let Some(defexp) = operand.defexp() {
+                    self.at(format_args!(".defexp"), |walk| {
+                        walk.pattern_expression(defexp)
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn constructor(&mut self, constructor: &'a Constructor) {
+        self.node(NodeRef::Constructor(constructor));
+        for (index, change) in constructor.contexts().iter().enumerate() {
+            self.at(format_args!(".contexts[{index}]"), |walk| {
+                walk.node(NodeRef::ContextChange(change));
+                if let ContextChangeType::Operation(operation) = change {
+                    walk.at(format_args!(".patexp"), |walk| {
+                        walk.pattern_expression(operation.patexp())
+                    });
+                }
+            });
+        }
+        if let Some(templ) = constructor.templ() {
+            self.at(format_args!(".templ"), |walk| {
+                walk.constructor_template(templ)
+            });
+        }
+        for (index, templ) in constructor.namedtempl().iter().enumerate() {
+            self.at(format_args!(".namedtempl[{index}]"), |walk| {
+                walk.constructor_template(templ)
+            });
+        }
+    }
+
+    fn constructor_template(&mut self, templ: &'a ConstructorTemplate) {
+        self.node(NodeRef::ConstructorTemplate(templ));
+        if let Some(handle) = templ.result().as_option() {
+            self.at(format_args!(".result"), |walk| {
+                walk.node(NodeRef::HandleTemplate(handle))
+            });
+        }
+        for (index, op) in templ.vec().iter().enumerate() {
+            self.at(format_args!(".vec[{index}]"), |walk| {
+                walk.node(NodeRef::OpTemplate(op));
+                if let Some(output) = op.output().as_option() {
+                    walk.at(format_args!(".output"), |walk| {
+                        walk.node(NodeRef::VarNodeTemplate(output))
+                    });
+                }
+                for (index, input) in op.input().iter().enumerate() {
+                    walk.at(format_args!(".input[{index}]"), |walk| {
+                        walk.node(NodeRef::VarNodeTemplate(input))
+                    });
+                }
+            });
+        }
+    }
+
+    fn pattern_expression(&mut self, expression: &'a PatternExpressionType) {
+        self.node(NodeRef::PatternExpression(expression));
+        match expression {
+            PatternExpressionType::PatternValue(value) => self.at(format_args!(".value"), |walk| {
+                walk.node(NodeRef::PatternValue(value))
+            }),
+            PatternExpressionType::BinaryExpression(binary) => {
+                let (left, right) = binary.operands();
+                self.at(format_args!(".left"), |walk| walk.pattern_expression(left));
+                self.at(format_args!(".right"), |walk| {
+                    walk.pattern_expression(right)
+                });
+            }
+            PatternExpressionType::UnaryExpression(unary) => match unary.as_ref() {
+                UnaryExpressionType::Minus { inner, .. }
+                | UnaryExpressionType::Not { inner, .. } => self
+                    .at(format_args!(".inner"), |walk| {
+                        walk.pattern_expression(inner)
+                    }),
+            },
+        }
+    }
+
+    fn decision_node(&mut self, node: &'a DecisionNode) {
+        self.node(NodeRef::DecisionNode(node));
+        for (index, child) in node.children().iter().enumerate() {
+            self.at(format_args!(".children[{index}]"), |walk| {
+                walk.decision_node(child)
+            });
+        }
+    }
+}
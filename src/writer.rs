@@ -0,0 +1,913 @@
+use std::{
+    fmt,
+    io::{self, Write},
+};
+
+use malachite::Integer;
+
+use crate::grammar::{
+    AddrSpace, AddrSpaceType, BinaryExpressionType, BitRangeSymbol, CombinePattern, Commit,
+    ConstantTemplateSelector, ConstantTemplateType, ConstantValue, Constructor,
+    ConstructorTemplate, ConstructorTemplateResult, ContextChangeType, ContextField,
+    ContextPattern, ContextSymbol, DecisionNode, DisjointPatternType, EndSymbol, EpsilonSymbol,
+    FamilySymbol, FlowDestSymbol, FlowRefSymbol, HandleTemplate, InstructionPattern, LabelSymbol,
+    MacroSymbol, NameSymbol, Next2Symbol, OperandSymbol, OperandValue, Operation,
+    OperationTemplate, OperationTemplateOutput, PatternBlock, PatternExpressionType,
+    PatternValueType, PatternlessSymbol, PrintPieceType, Scope, SectionSymbol, Sleigh,
+    SleighSymbolType, SourceFile, SourceFiles, SpaceSymbol, Spaces, SpecificSymbol, StartSymbol,
+    SubtableSymbol, SymbolHeader, SymbolHeaderType, SymbolTable, TokenField, TokenSymbol,
+    TripleSymbol, UnaryExpressionType, UserOpSymbol, ValueMapSymbol, ValueSymbol, ValueSymbolType,
+    VarNodeListSymbol, VarNodeSymbol, VarNodeTableValueType, VarNodeTemplate,
+};
+
+impl Sleigh {
+    /// Serialize the specification as `.sla` XML directly into `w`, in the same layout
+    /// Ghidra writes. Nothing is buffered beyond what `w` itself buffers, so wrap files
+    /// and sockets in a [`std::io::BufWriter`].
+    pub fn write_sla(&self, w: &mut impl Write) -> io::Result<()> {
+        SleighWriter::new(w).item(self)
+    }
+
+    /// Serialize the specification as `.sla` XML, in the same layout Ghidra writes
+    pub fn to_sla_string(&self) -> String {
+        let mut sla = Vec::new();
+        self.write_sla(&mut sla)
+            .expect("Writing to a Vec cannot fail");
+        String::from_utf8(sla).expect("Written sla is not valid UTF-8")
+    }
+}
+
+impl fmt::Display for Sleigh {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_sla_string())
+    }
+}
+
+/// Streams grammar items out as `.sla` XML
+pub(crate) struct SleighWriter<'w, W: Write> {
+    w: &'w mut W,
+}
+
+/// A grammar item that can be written back out as `.sla` XML
+pub(crate) trait WriteSla {
+    fn write<W: Write>(&self, w: &mut SleighWriter<'_, W>) -> io::Result<()>;
+}
+
+impl<'w, W: Write> SleighWriter<'w, W> {
+    pub(crate) fn new(w: &'w mut W) -> Self {
+        Self { w }
+    }
+
+    pub(crate) fn item(&mut self, item: &impl WriteSla) -> io::Result<()> {
+        item.write(self)
+    }
+
+    fn items<'a, T: WriteSla + 'a>(
+        &mut self,
+        items: impl IntoIterator<Item = &'a T>,
+    ) -> io::Result<()> {
+        items.into_iter().try_for_each(|item| item.write(self))
+    }
+
+    fn raw(&mut self, raw: &str) -> io::Result<()> {
+        self.w.write_all(raw.as_bytes())
+    }
+
+    fn dec(&mut self, name: &str, value: &Integer) -> io::Result<()> {
+        write!(self.w, " {}=\"{}\"", name, value)
+    }
+
+    fn hex(&mut self, name: &str, value: &Integer) -> io::Result<()> {
+        if *value < 0 {
+            write!(self.w, " {}=\"-0x{:x}\"", name, -value)
+        } else {
+            write!(self.w, " {}=\"0x{:x}\"", name, value)
+        }
+    }
+
+    fn bool(&mut self, name: &str, value: bool) -> io::Result<()> {
+        write!(self.w, " {}=\"{}\"", name, value)
+    }
+
+    fn str(&mut self, name: &str, value: &str) -> io::Result<()> {
+        write!(self.w, " {}=\"", name)?;
+        self.escaped(value)?;
+        self.raw("\"")
+    }
+
+    fn escaped(&mut self, value: &str) -> io::Result<()> {
+        let mut rest = value;
+        while let Some(index) = rest.find(['&', '<', '>', '"', '\'']) {
+            self.raw(&rest[..index])?;
+            self.raw(match &rest[index..index + 1] {
+                "&" => "&amp;",
+                "<" => "&lt;",
+                ">" => "&gt;",
+                "\"" => "&quot;",
+                _ => "&apos;",
+            })?;
+            rest = &rest[index + 1..];
+        }
+        self.raw(rest)
+    }
+}
+
+impl WriteSla for Sleigh {
+    fn write<W: Write>(&self, w: &mut SleighWriter<'_, W>) -> io::Result<()> {
+        w.raw("<sleigh")?;
+        if let Some(version) = self.version() {
+            w.dec("version", version)?;
+        }
+        if let Some(bigendian) = self.bigendian_attribute() {
+            w.bool("bigendian", bigendian)?;
+        }
+        w.dec("align", self.align())?;
+        w.hex("uniqbase", self.uniqbase())?;
+        if let Some(maxdelay) = self.maxdelay() {
+            w.hex("maxdelay", maxdelay)?;
+        }
+        if let Some(uniqmask) = self.uniqmask() {
+            w.hex("uniqmask", uniqmask)?;
+        }
+        if let Some(numsections) = self.numsections() {
+            w.dec("numsections", numsections)?;
+        }
+        for attribute in self.unknown_attributes() {
+            w.str(attribute.name(), attribute.value())?;
+        }
+        w.raw(">\n")?;
+        w.item(self.sourcefiles())?;
+        w.item(self.spaces())?;
+        w.item(self.symbol_table())?;
+        w.raw("</sleigh>\n")
+    }
+}
+
+impl WriteSla for SourceFiles {
+    fn write<W: Write>(&self, w: &mut SleighWriter<'_, W>) -> io::Result<()> {
+        w.raw("<sourcefiles>\n")?;
+        w.items(self.source_files())?;
+        w.raw("</sourcefiles>\n")
+    }
+}
+
+impl WriteSla for SourceFile {
+    fn write<W: Write>(&self, w: &mut SleighWriter<'_, W>) -> io::Result<()> {
+        w.raw("<sourcefile")?;
+        w.str("name", self.name())?;
+        w.dec("index", self.index())?;
+        w.raw("/>\n")
+    }
+}
+
+impl WriteSla for Spaces {
+    fn write<W: Write>(&self, w: &mut SleighWriter<'_, W>) -> io::Result<()> {
+        w.raw("<spaces")?;
+        w.str("defaultspace", self.defaultspace())?;
+        w.raw(">\n")?;
+        w.items(self.spaces())?;
+        w.raw("</spaces>\n")
+    }
+}
+
+impl WriteSla for AddrSpaceType {
+    fn write<W: Write>(&self, w: &mut SleighWriter<'_, W>) -> io::Result<()> {
+        let tag = match self {
+            AddrSpaceType::Base { .. } => "<space_base",
+            AddrSpaceType::Unique { .. } => "<space_unique",
+            AddrSpaceType::Other { .. } => "<space_other",
+            AddrSpaceType::Overlay { .. } => "<space_overlay",
+            AddrSpaceType::Space { .. } => "<space",
+        };
+        w.raw(tag)?;
+        w.item(self.space())?;
+        if let Some(overlay) = self.overlay() {
+            w.str("base", overlay.base())?;
+        }
+        w.raw("/>\n")
+    }
+}
+
+impl WriteSla for AddrSpace {
+    fn write<W: Write>(&self, w: &mut SleighWriter<'_, W>) -> io::Result<()> {
+        w.str("name", self.name())?;
+        w.dec("index", self.index())?;
+        w.bool("bigendian", self.bigendian())?;
+        w.dec("delay", self.delay())?;
+        if let Some(deadcodedelay) = self.deadcodedelay() {
+            w.dec("deadcodedelay", deadcodedelay)?;
+        }
+        w.dec("size", self.size())?;
+        if let Some(wordsize) = self.wordsize() {
+            w.dec("wordsize", wordsize)?;
+        }
+        w.bool("physical", self.physical())
+    }
+}
+
+impl WriteSla for SymbolTable {
+    fn write<W: Write>(&self, w: &mut SleighWriter<'_, W>) -> io::Result<()> {
+        w.raw("<symbol_table")?;
+        w.dec("scopesize", self.scopesize())?;
+        w.dec("symbolsize", self.symbolsize())?;
+        w.raw(">\n")?;
+        w.items(self.scopes())?;
+        w.items(self.symbol_headers())?;
+        w.items(self.symbols())?;
+        w.raw("</symbol_table>\n")
+    }
+}
+
+impl WriteSla for Scope {
+    fn write<W: Write>(&self, w: &mut SleighWriter<'_, W>) -> io::Result<()> {
+        w.raw("<scope")?;
+        w.hex("id", self.id())?;
+        w.hex("parent", self.parent())?;
+        w.raw("/>\n")
+    }
+}
+
+impl WriteSla for SymbolHeader {
+    fn write<W: Write>(&self, w: &mut SleighWriter<'_, W>) -> io::Result<()> {
+        w.str("name", self.name())?;
+        w.hex("id", self.id())?;
+        w.hex("scope", self.scope())
+    }
+}
+
+impl WriteSla for SymbolHeaderType {
+    fn write<W: Write>(&self, w: &mut SleighWriter<'_, W>) -> io::Result<()> {
+        let (tag, header) = match self {
+            SymbolHeaderType::Space { header, .. } => ("<space_sym_head", header),
+            SymbolHeaderType::Token { header, .. } => ("<token_sym_head", header),
+            SymbolHeaderType::Section { header, .. } => ("<section_sym_head", header),
+            SymbolHeaderType::Macro { header, .. } => ("<macro_sym_head", header),
+            SymbolHeaderType::Label { header, .. } => ("<label_sym_head", header),
+            SymbolHeaderType::BitRange { header, .. } => ("<bitrange_sym_head", header),
+            SymbolHeaderType::UserOp { header, .. } => ("<userop_head", header),
+            SymbolHeaderType::Epsilon { header, .. } => ("<epsilon_sym_head", header),
+            SymbolHeaderType::Value { header, .. } => ("<value_sym_head", header),
+            SymbolHeaderType::ValueMap { header, .. } => ("<valuemap_sym_head", header),
+            SymbolHeaderType::Name { header, .. } => ("<name_sym_head", header),
+            SymbolHeaderType::VarNode { header, .. } => ("<varnode_sym_head", header),
+            SymbolHeaderType::Context { header, .. } => ("<context_sym_head", header),
+            SymbolHeaderType::VarNodeList { header, .. } => ("<varlist_sym_head", header),
+            SymbolHeaderType::Operand { header, .. } => ("<operand_sym_head", header),
+            SymbolHeaderType::Start { header, .. } => ("<start_sym_head", header),
+            SymbolHeaderType::End { header, .. } => ("<end_sym_head", header),
+            SymbolHeaderType::Next2 { header, .. } => ("<next2_sym_head", header),
+            SymbolHeaderType::FlowDest { header, .. } => ("<flowdest_sym_head", header),
+            SymbolHeaderType::FlowRef { header, .. } => ("<flowref_sym_head", header),
+            SymbolHeaderType::SubTable { header, .. } => ("<subtable_sym_head", header),
+        };
+        w.raw(tag)?;
+        w.item(header)?;
+        w.raw("/>\n")
+    }
+}
+
+impl WriteSla for SleighSymbolType {
+    fn write<W: Write>(&self, w: &mut SleighWriter<'_, W>) -> io::Result<()> {
+        match self {
+            SleighSymbolType::SpaceSymbol(symbol) => w.item(symbol),
+            SleighSymbolType::TokenSymbol(symbol) => w.item(symbol),
+            SleighSymbolType::SectionSymbol(symbol) => w.item(symbol),
+            SleighSymbolType::MacroSymbol(symbol) => w.item(symbol),
+            SleighSymbolType::LabelSymbol(symbol) => w.item(symbol),
+            SleighSymbolType::UserOpSymbol { user_op, .. } => w.item(user_op),
+            SleighSymbolType::TripleSymbol(triple) => w.item(triple),
+            SleighSymbolType::BitRangeSymbol(symbol) => w.item(symbol),
+        }
+    }
+}
+
+impl WriteSla for SpaceSymbol {
+    fn write<W: Write>(&self, w: &mut SleighWriter<'_, W>) -> io::Result<()> {
+        w.raw("<space_sym")?;
+        w.item(self.header())?;
+        w.str("space", self.space())?;
+        w.raw("/>\n")
+    }
+}
+
+impl WriteSla for TokenSymbol {
+    fn write<W: Write>(&self, w: &mut SleighWriter<'_, W>) -> io::Result<()> {
+        w.raw("<token_sym")?;
+        w.item(self.header())?;
+        w.dec("size", self.size())?;
+        w.bool("bigendian", self.bigendian())?;
+        w.raw("/>\n")
+    }
+}
+
+impl WriteSla for SectionSymbol {
+    fn write<W: Write>(&self, w: &mut SleighWriter<'_, W>) -> io::Result<()> {
+        w.raw("<section_sym")?;
+        w.item(self.header())?;
+        w.dec("index", self.index())?;
+        w.raw("/>\n")
+    }
+}
+
+impl WriteSla for MacroSymbol {
+    fn write<W: Write>(&self, w: &mut SleighWriter<'_, W>) -> io::Result<()> {
+        w.raw("<macro_sym")?;
+        w.item(self.header())?;
+        w.dec("index", self.index())?;
+        w.raw(">\n")?;
+        for operand in self.operands() {
+            w.raw("<oper")?;
+            w.hex("id", operand.id())?;
+            w.raw("/>\n")?;
+        }
+        if let Some(templ) = self.templ() {
+            w.item(templ)?;
+        }
+        w.raw("</macro_sym>\n")
+    }
+}
+
+impl WriteSla for LabelSymbol {
+    fn write<W: Write>(&self, w: &mut SleighWriter<'_, W>) -> io::Result<()> {
+        w.raw("<label_sym")?;
+        w.item(self.header())?;
+        w.dec("index", self.index())?;
+        w.dec("refcount", self.refcount())?;
+        w.bool("placed", self.placed())?;
+        w.raw("/>\n")
+    }
+}
+
+impl WriteSla for BitRangeSymbol {
+    fn write<W: Write>(&self, w: &mut SleighWriter<'_, W>) -> io::Result<()> {
+        w.raw("<bitrange_sym")?;
+        w.item(self.header())?;
+        w.hex("varnode", self.varnode())?;
+        w.dec("bitoffset", self.bitoffset())?;
+        w.dec("numbits", self.numbits())?;
+        w.raw("/>\n")
+    }
+}
+
+impl WriteSla for UserOpSymbol {
+    fn write<W: Write>(&self, w: &mut SleighWriter<'_, W>) -> io::Result<()> {
+        w.raw("<userop")?;
+        w.item(self.header())?;
+        w.dec("index", self.index())?;
+        w.raw("/>\n")
+    }
+}
+
+impl WriteSla for TripleSymbol {
+    fn write<W: Write>(&self, w: &mut SleighWriter<'_, W>) -> io::Result<()> {
+        match self {
+            TripleSymbol::FamilySymbol(FamilySymbol::ValueSymbol(value)) => match value {
+                ValueSymbolType::ValueMapSymbol(symbol) => w.item(symbol),
+                ValueSymbolType::NameSymbol(symbol) => w.item(symbol),
+                ValueSymbolType::ContextSymbol(symbol) => w.item(symbol),
+                ValueSymbolType::VarNodeListSymbol(symbol) => w.item(symbol),
+                ValueSymbolType::ValueSymbol(symbol) => w.item(symbol),
+            },
+            TripleSymbol::SpecificSymbol(specific) => match specific {
+                SpecificSymbol::PatternlessSymbol(PatternlessSymbol::EpsilonSymbol(symbol)) => {
+                    w.item(symbol)
+                }
+                SpecificSymbol::PatternlessSymbol(PatternlessSymbol::VarNodeSymbol(symbol)) => {
+                    w.item(symbol)
+                }
+                SpecificSymbol::OperandSymbol(symbol) => w.item(symbol),
+                SpecificSymbol::StartSymbol(symbol) => w.item(symbol),
+                SpecificSymbol::EndSymbol(symbol) => w.item(symbol),
+                SpecificSymbol::Next2Symbol(symbol) => w.item(symbol),
+                SpecificSymbol::FlowDestSymbol(symbol) => w.item(symbol),
+                SpecificSymbol::FlowRefSymbol(symbol) => w.item(symbol),
+            },
+            TripleSymbol::SubtableSymbol { subtable, .. } => w.item(subtable),
+        }
+    }
+}
+
+impl WriteSla for ValueSymbol {
+    fn write<W: Write>(&self, w: &mut SleighWriter<'_, W>) -> io::Result<()> {
+        w.raw("<value_sym")?;
+        w.item(self.header())?;
+        w.raw(">\n")?;
+        w.item(self.patval())?;
+        w.raw("</value_sym>\n")
+    }
+}
+
+impl WriteSla for ValueMapSymbol {
+    fn write<W: Write>(&self, w: &mut SleighWriter<'_, W>) -> io::Result<()> {
+        w.raw("<valuemap_sym")?;
+        w.item(self.header())?;
+        w.raw(">\n")?;
+        w.item(self.patval())?;
+        for value in self.valuetable() {
+            w.raw("<valuetab")?;
+            w.dec("val", value.val())?;
+            w.raw("/>\n")?;
+        }
+        w.raw("</valuemap_sym>\n")
+    }
+}
+
+impl WriteSla for NameSymbol {
+    fn write<W: Write>(&self, w: &mut SleighWriter<'_, W>) -> io::Result<()> {
+        w.raw("<name_sym")?;
+        w.item(self.header())?;
+        w.raw(">\n")?;
+        w.item(self.patval())?;
+        for name in self.nametable() {
+            w.raw("<nametab")?;
+            if let Some(name) = name.name() {
+                w.str("name", name)?;
+            }
+            w.raw("/>\n")?;
+        }
+        w.raw("</name_sym>\n")
+    }
+}
+
+impl WriteSla for ContextSymbol {
+    fn write<W: Write>(&self, w: &mut SleighWriter<'_, W>) -> io::Result<()> {
+        w.raw("<context_sym")?;
+        w.item(self.header())?;
+        w.hex("varnode", self.varnode())?;
+        w.dec("low", self.low())?;
+        w.dec("high", self.high())?;
+        w.bool("flow", self.flow())?;
+        w.raw(">\n")?;
+        w.item(self.patval())?;
+        w.raw("</context_sym>\n")
+    }
+}
+
+impl WriteSla for VarNodeListSymbol {
+    fn write<W: Write>(&self, w: &mut SleighWriter<'_, W>) -> io::Result<()> {
+        w.raw("<varlist_sym")?;
+        w.item(self.header())?;
+        w.raw(">\n")?;
+        w.item(self.patval())?;
+        for varnode in self.varnode_table() {
+            match varnode {
+                VarNodeTableValueType::Null { .. } => w.raw("<null/>\n")?,
+                VarNodeTableValueType::Value(value) => {
+                    w.raw("<var")?;
+                    w.hex("id", value.id())?;
+                    w.raw("/>\n")?;
+                }
+            }
+        }
+        w.raw("</varlist_sym>\n")
+    }
+}
+
+impl WriteSla for EpsilonSymbol {
+    fn write<W: Write>(&self, w: &mut SleighWriter<'_, W>) -> io::Result<()> {
+        w.raw("<epsilon_sym")?;
+        w.item(self.header())?;
+        w.raw("/>\n")
+    }
+}
+
+impl WriteSla for VarNodeSymbol {
+    fn write<W: Write>(&self, w: &mut SleighWriter<'_, W>) -> io::Result<()> {
+        w.raw("<varnode_sym")?;
+        w.item(self.header())?;
+        w.str("space", self.space())?;
+        w.hex("offset", self.offset())?;
+        w.dec("size", self.size())?;
+        w.raw(">\n</varnode_sym>\n")
+    }
+}
+
+impl WriteSla for OperandSymbol {
+    fn write<W: Write>(&self, w: &mut SleighWriter<'_, W>) -> io::Result<()> {
+        w.raw("<operand_sym")?;
+        w.item(self.header())?;
+        if let Some(subsym) = self.subsym() {
+            w.hex("subsym", subsym)?;
+        }
+        w.dec("off", self.off())?;
+        w.dec("base", self.base())?;
+        w.dec("minlen", self.minlen())?;
+        if let Some(code) = self.code() {
+            w.bool("code", code)?;
+        }
+        w.dec("index", self.index())?;
+        w.raw(">\n")?;
+        w.item(self.localexp())?;
+        if let Some(defexp) = self.defexp() {
+            w.item(defexp)?;
+        }
+        w.raw("</operand_sym>\n")
+    }
+}
+
+impl WriteSla for StartSymbol {
+    fn write<W: Write>(&self, w: &mut SleighWriter<'_, W>) -> io::Result<()> {
+        w.raw("<start_sym")?;
+        w.item(self.header())?;
+        w.raw("/>\n")
+    }
+}
+
+impl WriteSla for EndSymbol {
+    fn write<W: Write>(&self, w: &mut SleighWriter<'_, W>) -> io::Result<()> {
+        w.raw("<end_sym")?;
+        w.item(self.header())?;
+        w.raw("/>\n")
+    }
+}
+
+impl WriteSla for Next2Symbol {
+    fn write<W: Write>(&self, w: &mut SleighWriter<'_, W>) -> io::Result<()> {
+        w.raw("<next2_sym")?;
+        w.item(self.header())?;
+        w.raw("/>\n")
+    }
+}
+
+impl WriteSla for FlowDestSymbol {
+    fn write<W: Write>(&self, w: &mut SleighWriter<'_, W>) -> io::Result<()> {
+        w.raw("<flowdest_sym")?;
+        w.item(self.header())?;
+        w.raw("/>\n")
+    }
+}
+
+impl WriteSla for FlowRefSymbol {
+    fn write<W: Write>(&self, w: &mut SleighWriter<'_, W>) -> io::Result<()> {
+        w.raw("<flowref_sym")?;
+        w.item(self.header())?;
+        w.raw("/>\n")
+    }
+}
+
+impl WriteSla for SubtableSymbol {
+    fn write<W: Write>(&self, w: &mut SleighWriter<'_, W>) -> io::Result<()> {
+        w.raw("<subtable_sym")?;
+        w.item(self.header())?;
+        if let Some(numct) = self.numct() {
+            w.dec("numct", numct)?;
+        }
+        w.raw(">\n")?;
+        w.items(self.constructors())?;
+        w.item(self.decisiontree())?;
+        w.raw("</subtable_sym>\n")
+    }
+}
+
+impl WriteSla for Constructor {
+    fn write<W: Write>(&self, w: &mut SleighWriter<'_, W>) -> io::Result<()> {
+        w.raw("<constructor")?;
+        w.hex("parent", self.parent())?;
+        w.dec("first", self.first())?;
+        w.dec("length", self.length())?;
+        let (file, line) = self.line();
+        writeln!(w.w, " line=\"{}:{}\">", file, line)?;
+        for operand in self.operands() {
+            w.raw("<oper")?;
+            w.hex("id", operand.id())?;
+            w.raw("/>\n")?;
+        }
+        for piece in self.printpiece() {
+            match piece {
+                PrintPieceType::Operand(operand) => {
+                    w.raw("<opprint")?;
+                    w.dec("id", operand.id())?;
+                }
+                PrintPieceType::Print(print) => {
+                    w.raw("<print")?;
+                    w.str("piece", print.piece())?;
+                }
+            }
+            w.raw("/>\n")?;
+        }
+        w.items(self.contexts())?;
+        if let Some(templ) = self.templ() {
+            w.item(templ)?;
+        }
+        w.items(self.namedtempl())?;
+        w.raw("</constructor>\n")
+    }
+}
+
+impl WriteSla for ContextChangeType {
+    fn write<W: Write>(&self, w: &mut SleighWriter<'_, W>) -> io::Result<()> {
+        match self {
+            ContextChangeType::Operation(operation) => w.item(operation),
+            ContextChangeType::Commit(commit) => w.item(commit),
+        }
+    }
+}
+
+impl WriteSla for Operation {
+    fn write<W: Write>(&self, w: &mut SleighWriter<'_, W>) -> io::Result<()> {
+        w.raw("<context_op")?;
+        w.dec("i", self.i())?;
+        w.dec("shift", self.shift())?;
+        w.hex("mask", self.mask())?;
+        w.raw(" >\n")?;
+        w.item(self.patexp())?;
+        w.raw("</context_op>\n")
+    }
+}
+
+impl WriteSla for Commit {
+    fn write<W: Write>(&self, w: &mut SleighWriter<'_, W>) -> io::Result<()> {
+        w.raw("<commit")?;
+        w.hex("id", self.id())?;
+        w.dec("num", self.num())?;
+        w.hex("mask", self.mask())?;
+        w.bool("flow", self.flow())?;
+        w.raw("/>\n")
+    }
+}
+
+impl WriteSla for ConstructorTemplate {
+    fn write<W: Write>(&self, w: &mut SleighWriter<'_, W>) -> io::Result<()> {
+        w.raw("<construct_tpl")?;
+        if let Some(section) = self.section() {
+            w.dec("section", section)?;
+        }
+        if let Some(delay) = self.delay() {
+            w.dec("delay", delay)?;
+        }
+        if let Some(numlabels) = self.numlabels() {
+            w.dec("labels", numlabels)?;
+        }
+        w.raw(">\n")?;
+        match self.result() {
+            ConstructorTemplateResult::Null { .. } => w.raw("<null/>")?,
+            ConstructorTemplateResult::Result(handle) => w.item(handle)?,
+        }
+        w.items(self.vec())?;
+        w.raw("</construct_tpl>\n")
+    }
+}
+
+impl WriteSla for OperationTemplate {
+    fn write<W: Write>(&self, w: &mut SleighWriter<'_, W>) -> io::Result<()> {
+        w.raw("<op_tpl code=\"")?;
+        w.raw(self.code().mnemonic())?;
+        w.raw("\">")?;
+        match self.output() {
+            OperationTemplateOutput::Null { .. } => w.raw("<null/>\n")?,
+            OperationTemplateOutput::Output(output) => w.item(output)?,
+        }
+        w.items(self.input())?;
+        w.raw("</op_tpl>\n")
+    }
+}
+
+impl WriteSla for VarNodeTemplate {
+    fn write<W: Write>(&self, w: &mut SleighWriter<'_, W>) -> io::Result<()> {
+        w.raw("<varnode_tpl>")?;
+        w.items([self.space(), self.offset(), self.size()])?;
+        w.raw("</varnode_tpl>\n")
+    }
+}
+
+impl WriteSla for HandleTemplate {
+    fn write<W: Write>(&self, w: &mut SleighWriter<'_, W>) -> io::Result<()> {
+        w.raw("<handle_tpl>")?;
+        w.items([
+            self.space(),
+            self.size(),
+            self.ptrspace(),
+            self.ptroffset(),
+            self.ptrsize(),
+            self.temp_space(),
+            self.temp_offset(),
+        ])?;
+        w.raw("</handle_tpl>\n")
+    }
+}
+
+impl WriteSla for ConstantTemplateType {
+    fn write<W: Write>(&self, w: &mut SleighWriter<'_, W>) -> io::Result<()> {
+        match self {
+            ConstantTemplateType::Real { val, .. } => {
+                w.raw("<const_tpl type=\"real\"")?;
+                w.hex("val", val)?;
+            }
+            ConstantTemplateType::Handle {
+                val,
+                selector,
+                plus,
+                ..
+            } => {
+                w.raw("<const_tpl type=\"handle\"")?;
+                w.dec("val", val)?;
+                w.raw(match selector {
+                    ConstantTemplateSelector::Space { .. } => " s=\"space\"",
+                    ConstantTemplateSelector::Offset { .. } => " s=\"offset\"",
+                    ConstantTemplateSelector::Size { .. } => " s=\"size\"",
+                    ConstantTemplateSelector::OffsetPlus { .. } => " s=\"offset_plus\"",
+                })?;
+                if let Some(plus) = plus {
+                    w.hex("plus", plus)?;
+                }
+            }
+            ConstantTemplateType::Start { .. } => w.raw("<const_tpl type=\"start\"")?,
+            ConstantTemplateType::End { .. } => w.raw("<const_tpl type=\"end\"")?,
+            ConstantTemplateType::Next { .. } => w.raw("<const_tpl type=\"next\"")?,
+            ConstantTemplateType::Next2 { .. } => w.raw("<const_tpl type=\"next2\"")?,
+            ConstantTemplateType::CurSpace { .. } => w.raw("<const_tpl type=\"curspace\"")?,
+            ConstantTemplateType::CurSpaceSize { .. } => {
+                w.raw("<const_tpl type=\"curspace_size\"")?
+            }
+            ConstantTemplateType::SpaceId { name, .. } => {
+                w.raw("<const_tpl type=\"spaceid\"")?;
+                w.str("name", name)?;
+            }
+            ConstantTemplateType::JumpRelative { val, .. } => {
+                w.raw("<const_tpl type=\"relative\"")?;
+                w.hex("val", val)?;
+            }
+            ConstantTemplateType::FlowRef { .. } => w.raw("<const_tpl type=\"flowref\"")?,
+            ConstantTemplateType::FlowDest { .. } => w.raw("<const_tpl type=\"flowdest\"")?,
+            ConstantTemplateType::FlowDestSize { .. } => {
+                w.raw("<const_tpl type=\"flowdest_size\"")?
+            }
+            ConstantTemplateType::Unknown { type_name, val, .. } => {
+                w.raw("<const_tpl")?;
+                w.str("type", type_name)?;
+                if let Some(val) = val {
+                    w.hex("val", val)?;
+                }
+            }
+        }
+        w.raw("/>")
+    }
+}
+
+impl WriteSla for DecisionNode {
+    fn write<W: Write>(&self, w: &mut SleighWriter<'_, W>) -> io::Result<()> {
+        w.raw("<decision")?;
+        w.dec("number", self.number())?;
+        w.bool("context", self.context())?;
+        w.dec("start", self.start())?;
+        w.dec("size", self.bitsize())?;
+        w.raw(">\n")?;
+        for pair in self.pairs() {
+            w.raw("<pair")?;
+            w.dec("id", pair.id())?;
+            w.raw(">\n")?;
+            w.item(pair.pattern())?;
+            w.raw("</pair>\n")?;
+        }
+        w.items(self.children())?;
+        w.raw("</decision>\n")
+    }
+}
+
+impl WriteSla for DisjointPatternType {
+    fn write<W: Write>(&self, w: &mut SleighWriter<'_, W>) -> io::Result<()> {
+        match self {
+            DisjointPatternType::Instruction(pattern) => w.item(pattern),
+            DisjointPatternType::Context(pattern) => w.item(pattern),
+            DisjointPatternType::Combine(pattern) => w.item(pattern),
+        }
+    }
+}
+
+impl WriteSla for InstructionPattern {
+    fn write<W: Write>(&self, w: &mut SleighWriter<'_, W>) -> io::Result<()> {
+        w.raw("<instruct_pat>\n")?;
+        w.item(self.mask_value())?;
+        w.raw("</instruct_pat>\n")
+    }
+}
+
+impl WriteSla for ContextPattern {
+    fn write<W: Write>(&self, w: &mut SleighWriter<'_, W>) -> io::Result<()> {
+        w.raw("<context_pat>\n")?;
+        w.item(self.mask_value())?;
+        w.raw("</context_pat>\n")
+    }
+}
+
+impl WriteSla for CombinePattern {
+    fn write<W: Write>(&self, w: &mut SleighWriter<'_, W>) -> io::Result<()> {
+        w.raw("<combine_pat>\n")?;
+        w.item(self.context())?;
+        w.item(self.instr())?;
+        w.raw("</combine_pat>\n")
+    }
+}
+
+impl WriteSla for PatternBlock {
+    fn write<W: Write>(&self, w: &mut SleighWriter<'_, W>) -> io::Result<()> {
+        w.raw("<pat_block")?;
+        w.dec("offset", self.offset())?;
+        w.dec("nonzero", self.nonzero())?;
+        w.raw(">\n")?;
+        for word in self.mask_vals() {
+            w.raw("  <mask_word")?;
+            w.hex("mask", word.mask())?;
+            w.hex("val", word.val())?;
+            w.raw("/>\n")?;
+        }
+        w.raw("</pat_block>\n")
+    }
+}
+
+impl WriteSla for PatternExpressionType {
+    fn write<W: Write>(&self, w: &mut SleighWriter<'_, W>) -> io::Result<()> {
+        match self {
+            PatternExpressionType::PatternValue(value) => w.item(value),
+            PatternExpressionType::BinaryExpression(binary) => {
+                let (tag, left, right) = match binary.as_ref() {
+                    BinaryExpressionType::Plus { left, right, .. } => ("plus_exp", left, right),
+                    BinaryExpressionType::Sub { left, right, .. } => ("sub_exp", left, right),
+                    BinaryExpressionType::Mult { left, right, .. } => ("mult_exp", left, right),
+                    BinaryExpressionType::LeftShift { left, right, .. } => {
+                        ("lshift_exp", left, right)
+                    }
+                    BinaryExpressionType::RightShift { left, right, .. } => {
+                        ("rshift_exp", left, right)
+                    }
+                    BinaryExpressionType::And { left, right, .. } => ("and_exp", left, right),
+                    BinaryExpressionType::Or { left, right, .. } => ("or_exp", left, right),
+                    BinaryExpressionType::Xor { left, right, .. } => ("xor_exp", left, right),
+                    BinaryExpressionType::Div { left, right, .. } => ("div_exp", left, right),
+                };
+                writeln!(w.w, "<{}>", tag)?;
+                w.item(left)?;
+                w.item(right)?;
+                writeln!(w.w, "</{}>", tag)
+            }
+            PatternExpressionType::UnaryExpression(unary) => {
+                let (tag, inner) = match unary.as_ref() {
+                    UnaryExpressionType::Minus { inner, .. } => ("minus_exp", inner),
+                    UnaryExpressionType::Not { inner, .. } => ("not_exp", inner),
+                };
+                writeln!(w.w, "<{}>", tag)?;
+                w.item(inner)?;
+                writeln!(w.w, "</{}>", tag)
+            }
+        }
+    }
+}
+
+impl WriteSla for PatternValueType {
+    fn write<W: Write>(&self, w: &mut SleighWriter<'_, W>) -> io::Result<()> {
+        match self {
+            PatternValueType::TokenField(field) => w.item(field),
+            PatternValueType::ContextField(field) => w.item(field),
+            PatternValueType::ConstantValue(constant) => w.item(constant),
+            PatternValueType::OperandValue(operand) => w.item(operand),
+            PatternValueType::StartInstructionValue { .. } => w.raw("<start_exp/>"),
+            PatternValueType::EndInstructionValue { .. } => w.raw("<end_exp/>"),
+            PatternValueType::Next2InstructionValue { .. } => w.raw("<next2_exp/>"),
+        }
+    }
+}
+
+impl WriteSla for TokenField {
+    fn write<W: Write>(&self, w: &mut SleighWriter<'_, W>) -> io::Result<()> {
+        w.raw("<tokenfield")?;
+        w.bool("bigendian", self.bigendian())?;
+        w.bool("signbit", self.signbit())?;
+        w.dec("bitstart", self.bitstart())?;
+        w.dec("bitend", self.bitend())?;
+        w.dec("bytestart", self.bytestart())?;
+        w.dec("byteend", self.byteend())?;
+        w.dec("shift", self.shift())?;
+        w.raw("/>\n")
+    }
+}
+
+impl WriteSla for ContextField {
+    fn write<W: Write>(&self, w: &mut SleighWriter<'_, W>) -> io::Result<()> {
+        w.raw("<contextfield")?;
+        w.bool("signbit", self.signbit())?;
+        w.dec("startbit", self.startbit())?;
+        w.dec("endbit", self.endbit())?;
+        w.dec("startbyte", self.startbyte())?;
+        w.dec("endbyte", self.endbyte())?;
+        w.dec("shift", self.shift())?;
+        w.raw("/>\n")
+    }
+}
+
+impl WriteSla for ConstantValue {
+    fn write<W: Write>(&self, w: &mut SleighWriter<'_, W>) -> io::Result<()> {
+        w.raw("<intb")?;
+        w.dec("val", self.val())?;
+        w.raw("/>\n")
+    }
+}
+
+impl WriteSla for OperandValue {
+    fn write<W: Write>(&self, w: &mut SleighWriter<'_, W>) -> io::Result<()> {
+        w.raw("<operand_exp")?;
+        w.dec("index", self.index())?;
+        w.hex("table", self.table())?;
+        w.hex("ct", self.constructor_id())?;
+        w.raw("/>\n")
+    }
+}
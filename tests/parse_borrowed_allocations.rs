@@ -0,0 +1,100 @@
+//! Compares the allocations made and kept by `parse_borrowed` with those made and kept by
+//! `parse` on the same specification. This needs a counting global allocator, so it runs in its own test binary.
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+};
+
+use tree_sitter_sleigh::{parse, parse_borrowed};
+
+/// Counts allocations and deallocations made on the current thread while counting is
+/// enabled, so the counts are not disturbed by the test harness
+struct CountingAllocator;
+
+#[derive(Debug, Clone, Copy, Default)]
+struct AllocationCount {
+    allocations: usize,
+    deallocations: usize,
+}
+
+thread_local! {
+    static ALLOCATIONS: Cell<Option<AllocationCount>> = const { Cell::new(None) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let _ = ALLOCATIONS.try_with(|count| {
+            if let Some(mut current) = count.get() {
+                current.allocations += 1;
+                count.set(Some(current));
+            }
+        });
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        let _ = ALLOCATIONS.try_with(|count| {
+            if let Some(mut current) = count.get() {
+                current.deallocations += 1;
+                count.set(Some(current));
+            }
+        });
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// Run `f`, returning its result, its number of allocations, and the number of those still
+/// live when it returned
+fn count_allocations<T>(f: impl FnOnce() -> T) -> (T, usize, usize) {
+    ALLOCATIONS.with(|count| count.set(Some(AllocationCount::default())));
+    let result = f();
+    let count = ALLOCATIONS.with(|count| count.take()).unwrap_or_default();
+    (
+        result,
+        count.allocations,
+        count.allocations.saturating_sub(count.deallocations),
+    )
+}
+
+#[test]
+fn test_parse_borrowed_allocations() {
+    let sla_8051: &str = include_str!("../Processors/8051/data/languages/8051.sla");
+    // Initialize the grammar's lazily compiled patterns, which stay allocated
+    parse(sla_8051).expect("Failed to parse 8051 sla");
+    parse_borrowed(sla_8051).expect("Failed to parse 8051 sla borrowed");
+    let (borrowed, borrowed_total, borrowed_live) =
+        count_allocations(|| parse_borrowed(sla_8051).expect("Failed to parse 8051 sla borrowed"));
+    let (owned, owned_total, owned_live) =
+        count_allocations(|| parse(sla_8051).expect("Failed to parse 8051 sla"));
+    assert_eq!(
+        borrowed.symbols().len(),
+        owned.symbol_table().symbols().len()
+    );
+    // `parse` allocates at least a string per name and piece, both while building the tree
+    // and in the tree it keeps
+    let strings = borrowed.source_files().len()
+        + borrowed.symbols().len()
+        + borrowed
+            .constructors()
+            .iter()
+            .map(|constructor| constructor.pieces().len())
+            .sum::<usize>();
+    assert!(
+        borrowed_total + strings <= owned_total,
+        "{} + {} > {}",
+        borrowed_total,
+        strings,
+        owned_total
+    );
+    assert!(
+        borrowed_live + strings <= owned_live,
+        "{} + {} > {}",
+        borrowed_live,
+        strings,
+        owned_live
+    );
+}